@@ -8,8 +8,24 @@ fn main() {
     println!("cargo:rerun-if-env-changed=BBR_CHIAVDF_DIR");
     println!("cargo:rerun-if-env-changed=BBR_FORCE_WINDOWS_FALLBACK");
     println!("cargo:rerun-if-env-changed=BBR_FORCE_MACOS_ARM_FALLBACK");
+    println!("cargo:rerun-if-env-changed=BBR_FORCE_PORTABLE_FALLBACK");
+    // Set when the portable (slow) prover is linked instead of the fast engine.
+    println!("cargo:rustc-check-cfg=cfg(chiavdf_fallback)");
+    // Set when the pure-Rust prover (`rust-fallback` feature) replaces the native one.
+    println!("cargo:rustc-check-cfg=cfg(chiavdf_rust_fallback)");
     println!("cargo:rerun-if-env-changed=BBR_CLANG_CL");
 
+    if env::var_os("CARGO_FEATURE_RUST_FALLBACK").is_some() {
+        // Nothing native to build or link: `src/rust_fallback` stands in for
+        // the wrapper and implements these entry points.
+        println!("cargo:rustc-cfg=chiavdf_rust_fallback");
+        return;
+    }
+    if env::var_os("CARGO_FEATURE_DYNAMIC").is_some() {
+        link_dynamic(&env::var("CARGO_CFG_TARGET_OS").unwrap_or_default());
+        return;
+    }
+
     let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"));
     let repo_root = manifest_dir
         .parent()
@@ -84,6 +100,7 @@ or set BBR_CHIAVDF_DIR to a chiavdf checkout.",
                 "cargo:warning=BBR_FORCE_WINDOWS_FALLBACK=1 set; using Windows fallback implementation."
             );
             build_windows_fallback(&manifest_dir, &chiavdf_dir, &chiavdf_src);
+            emit_fallback_cfg();
         } else {
             build_windows_fast_path(&chiavdf_dir, &chiavdf_src);
         }
@@ -94,7 +111,17 @@ or set BBR_CHIAVDF_DIR to a chiavdf checkout.",
         println!(
             "cargo:warning=BBR_FORCE_MACOS_ARM_FALLBACK=1 set; using macOS ARM fallback implementation."
         );
-        build_macos_arm_fallback(&manifest_dir, &chiavdf_src);
+        build_unix_fallback(&manifest_dir, &chiavdf_src, &target_os);
+        emit_fallback_cfg();
+        return;
+    }
+    if env_flag("BBR_FORCE_PORTABLE_FALLBACK") {
+        // Used to cross-validate the fast engine against the portable prover.
+        println!(
+            "cargo:warning=BBR_FORCE_PORTABLE_FALLBACK=1 set; using the portable fallback implementation."
+        );
+        build_unix_fallback(&manifest_dir, &chiavdf_src, &target_os);
+        emit_fallback_cfg();
         return;
     }
     // GMP (and gmpxx) may be in a non-default location (e.g. Homebrew on macOS).
@@ -434,9 +461,10 @@ fn windows_mpir_dir(chiavdf_dir: &PathBuf) -> PathBuf {
     mpir_dir
 }
 
-/// Build the portable "slow" fallback on macOS ARM (Apple Silicon). The full
-/// chiavdf fast engine uses x86 intrinsics/assembly and is not available there.
-fn build_macos_arm_fallback(manifest_dir: &PathBuf, chiavdf_src: &PathBuf) {
+/// Build the portable "slow" fallback on macOS ARM (Apple Silicon), or on any Unix
+/// target with `BBR_FORCE_PORTABLE_FALLBACK=1`. The full chiavdf fast engine uses
+/// x86 intrinsics/assembly and is not available on Apple Silicon.
+fn build_unix_fallback(manifest_dir: &PathBuf, chiavdf_src: &PathBuf, target_os: &str) {
     let fallback_cpp = manifest_dir.join("native").join("chiavdf_fast_fallback.cpp");
     let lzcnt_c = chiavdf_src.join("refcode").join("lzcnt.c");
     println!("cargo:rerun-if-changed={}", fallback_cpp.display());
@@ -475,7 +503,11 @@ fn build_macos_arm_fallback(manifest_dir: &PathBuf, chiavdf_src: &PathBuf) {
     println!("cargo:rustc-link-lib=gmpxx");
     println!("cargo:rustc-link-lib=gmp");
     println!("cargo:rustc-link-lib=pthread");
-    println!("cargo:rustc-link-lib=c++");
+    if target_os == "macos" {
+        println!("cargo:rustc-link-lib=c++");
+    } else {
+        println!("cargo:rustc-link-lib=stdc++");
+    }
 }
 
 /// Detect GMP include path so the compiler can find `<gmp.h>` and `<gmpxx.h>`.
@@ -561,6 +593,46 @@ fn detect_boost_include() -> Option<String> {
     None
 }
 
+/// Link a prebuilt shared `libchiavdf_fastc` (the `dynamic` feature) instead of
+/// building the chiavdf submodule.
+///
+/// The library comes from `BBR_CHIAVDF_LIB_DIR`, or the linker's default search
+/// path when unset. It must export the fast wrapper's C API and bring its own GMP
+/// and C++ runtime dependencies. As with the submodule build, only the core prover
+/// API is used.
+fn link_dynamic(target_os: &str) {
+    println!("cargo:rerun-if-env-changed=BBR_CHIAVDF_LIB_DIR");
+    let file_name = match target_os {
+        "windows" => "chiavdf_fastc.dll",
+        "macos" => "libchiavdf_fastc.dylib",
+        _ => "libchiavdf_fastc.so",
+    };
+    match env::var_os("BBR_CHIAVDF_LIB_DIR").map(PathBuf::from) {
+        Some(lib_dir) => {
+            let library = lib_dir.join(file_name);
+            println!("cargo:rerun-if-changed={}", library.display());
+            if !library.exists() {
+                panic!(
+                    "feature dynamic: {} not found. Set BBR_CHIAVDF_LIB_DIR to the directory \
+containing the shared chiavdf fast library.",
+                    library.display()
+                );
+            }
+            println!("cargo:rustc-link-search=native={}", lib_dir.display());
+        }
+        None => println!(
+            "cargo:warning=feature dynamic: BBR_CHIAVDF_LIB_DIR not set; linking {file_name} from the default library path."
+        ),
+    }
+    println!("cargo:rustc-link-lib=dylib=chiavdf_fastc");
+}
+
+/// Marks the build as linking the portable prover in
+/// `native/chiavdf_fast_fallback.cpp`.
+fn emit_fallback_cfg() {
+    println!("cargo:rustc-cfg=chiavdf_fallback");
+}
+
 fn env_flag(name: &str) -> bool {
     match env::var(name) {
         Ok(value) => {
@@ -8,6 +8,11 @@
 #include <mutex>
 #include <vector>
 
+#if !defined(_WIN32)
+#include <csetjmp>
+#include <csignal>
+#endif
+
 // This is a Windows-friendly fallback implementation of the "chiavdf fast" C API.
 //
 // The Linux implementation relies on the full fast chiavdf engine (including
@@ -59,8 +64,94 @@ struct LastStreamingStats {
 
 thread_local LastStreamingStats last_streaming_stats;
 
+// DeserializeForm only recovers (a, b) and derives c from D, so bytes of a form
+// of another discriminant can deserialize without error; x is one of D's only
+// when a > 0 and b^2 - 4ac == D.
+bool is_form_of(const form& x, const integer& D) {
+    return x.a > integer(0) && x.b * x.b - integer(4) * x.a * x.c == D;
+}
+
+integer discriminant_for(std::vector<uint8_t>& challenge, size_t size_bits) {
+    return CreateDiscriminant(challenge, static_cast<int>(size_bits));
+}
+
 ChiavdfByteArray empty_result() { return ChiavdfByteArray{nullptr, 0}; }
 
+#if !defined(_WIN32)
+// Crashes inside a guarded call (an assertion's abort, a bad form tripping
+// SIGFPE/SIGSEGV) jump back to `guard_crashes`, which fails that one call instead
+// of taking every worker down with the process.
+// Best effort: the crashed call's allocations leak and its destructors don't
+// run. Signals outside a guarded call go to the previously installed handler.
+constexpr int GUARDED_SIGNALS[] = {SIGABRT, SIGBUS, SIGFPE, SIGILL, SIGSEGV};
+struct sigaction previous_actions[std::size(GUARDED_SIGNALS)];
+std::once_flag crash_handlers_once;
+
+// Where a crash on this thread jumps to; null outside guarded calls.
+thread_local sigjmp_buf* crash_jump = nullptr;
+
+void crash_handler(int signo, siginfo_t* info, void* context) {
+    sigjmp_buf* jump = crash_jump;
+    if (jump != nullptr) {
+        crash_jump = nullptr;
+        siglongjmp(*jump, 1);
+    }
+    for (size_t idx = 0; idx < std::size(GUARDED_SIGNALS); ++idx) {
+        if (GUARDED_SIGNALS[idx] != signo) {
+            continue;
+        }
+        const struct sigaction& previous = previous_actions[idx];
+        if ((previous.sa_flags & SA_SIGINFO) != 0) {
+            previous.sa_sigaction(signo, info, context);
+        } else if (previous.sa_handler != SIG_DFL && previous.sa_handler != SIG_IGN) {
+            previous.sa_handler(signo);
+        } else {
+            // Faults re-trigger on return and abort() raises again, now with
+            // the default action.
+            sigaction(signo, &previous, nullptr);
+        }
+        return;
+    }
+}
+
+void install_crash_handlers() {
+    struct sigaction action {};
+    action.sa_sigaction = crash_handler;
+    // The alternate stack keeps stack overflows reportable by the runtime's
+    // own handler.
+    action.sa_flags = SA_SIGINFO | SA_ONSTACK;
+    sigemptyset(&action.sa_mask);
+    for (size_t idx = 0; idx < std::size(GUARDED_SIGNALS); ++idx) {
+        sigaction(GUARDED_SIGNALS[idx], &action, &previous_actions[idx]);
+    }
+}
+
+#endif
+
+// Runs `call`, turning a crash inside it into a failed call (see `crash_handler`).
+// No-op on Windows, where the fallback has no signal guard.
+template <typename Call>
+ChiavdfByteArray guard_crashes(Call&& call) {
+#if defined(_WIN32)
+    return call();
+#else
+    std::call_once(crash_handlers_once, install_crash_handlers);
+    sigjmp_buf jump;
+    sigjmp_buf* const outer = crash_jump;
+    if (sigsetjmp(jump, 1) != 0) {
+        crash_jump = outer;
+        return empty_result();
+    }
+    crash_jump = &jump;
+    // Keep the compiler from moving the call's work outside the guarded window.
+    std::atomic_signal_fence(std::memory_order_seq_cst);
+    ChiavdfByteArray result = call();
+    std::atomic_signal_fence(std::memory_order_seq_cst);
+    crash_jump = outer;
+    return result;
+#endif
+}
+
 uint64_t saturating_add_u64(uint64_t lhs, uint64_t rhs) {
     if (lhs > std::numeric_limits<uint64_t>::max() - rhs) {
         return std::numeric_limits<uint64_t>::max();
@@ -99,7 +190,7 @@ void init_chiavdf_runtime() {
     fesetround(FE_TOWARDZERO);
 }
 
-ChiavdfByteArray prove_one_weso_slow(
+ChiavdfByteArray prove_one_weso_slow_unguarded(
     const uint8_t* challenge_hash,
     size_t challenge_size,
     const uint8_t* x_s,
@@ -118,15 +209,21 @@ ChiavdfByteArray prove_one_weso_slow(
     if (num_iterations == 0 || discriminant_size_bits == 0) {
         return empty_result();
     }
+    if (check_y_ref && (y_ref_s == nullptr || y_ref_s_size == 0)) {
+        return empty_result();
+    }
 
     try {
         std::call_once(init_once, init_chiavdf_runtime);
 
         std::vector<uint8_t> challenge_hash_bytes(challenge_hash, challenge_hash + challenge_size);
-        integer D = CreateDiscriminant(challenge_hash_bytes, static_cast<int>(discriminant_size_bits));
+        integer D = discriminant_for(challenge_hash_bytes, discriminant_size_bits);
         integer L = root(-D, 4);
 
         form x = DeserializeForm(D, x_s, x_s_size);
+        if (!is_form_of(x, D)) {
+            return empty_result();
+        }
 
         PulmarkReducer reducer;
         form y = form::from_abd(x.a, x.b, D);
@@ -164,7 +261,6 @@ ChiavdfByteArray prove_one_weso_slow(
 
             nudupl_form(y, y, D, L);
             reducer.reduce(y);
-
             if (progress_cb != nullptr && progress_interval != 0) {
                 const uint64_t done = i + 1;
                 if (done == num_iterations || (done % progress_interval) == 0) {
@@ -190,9 +286,6 @@ ChiavdfByteArray prove_one_weso_slow(
         }
 
         if (check_y_ref) {
-            if (y_ref_s == nullptr || y_ref_s_size == 0) {
-                return empty_result();
-            }
             if (y_serialized.size() != y_ref_s_size) {
                 return empty_result();
             }
@@ -210,6 +303,36 @@ ChiavdfByteArray prove_one_weso_slow(
         return empty_result();
     }
 }
+
+ChiavdfByteArray prove_one_weso_slow(
+    const uint8_t* challenge_hash,
+    size_t challenge_size,
+    const uint8_t* x_s,
+    size_t x_s_size,
+    const uint8_t* y_ref_s,
+    size_t y_ref_s_size,
+    bool check_y_ref,
+    size_t discriminant_size_bits,
+    uint64_t num_iterations,
+    uint64_t progress_interval,
+    ChiavdfProgressCallback progress_cb,
+    void* progress_user_data) {
+    return guard_crashes([&] {
+        return prove_one_weso_slow_unguarded(
+            challenge_hash,
+            challenge_size,
+            x_s,
+            x_s_size,
+            y_ref_s,
+            y_ref_s_size,
+            check_y_ref,
+            discriminant_size_bits,
+            num_iterations,
+            progress_interval,
+            progress_cb,
+            progress_user_data);
+    });
+}
 } // namespace
 
 extern "C" void chiavdf_set_bucket_memory_budget_bytes(uint64_t bytes) {
@@ -454,6 +577,7 @@ extern "C" ChiavdfByteArray* chiavdf_prove_one_weso_fast_streaming_getblock_opt_
                 use_progress ? static_cast<void*>(&progress_ctx) : nullptr);
 
             if (out_arrays[idx].data == nullptr || out_arrays[idx].length == 0) {
+                // `prove_one_weso_slow` recorded why.
                 free_byte_array_batch_internal(out_arrays, job_count);
                 return nullptr;
             }
@@ -1,13 +1,23 @@
 //! Public API for the chiavdf fast C wrapper.
 
+use std::cell::{Cell, RefCell};
 use std::ffi::c_void;
 use std::panic::{AssertUnwindSafe, catch_unwind};
-use std::time::Duration;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use thiserror::Error;
 
+use crate::chia::ClassgroupElement;
+use crate::classgroup;
+use crate::cost::check_iterations;
 use crate::ffi;
 
+/// Async wrappers around the proving functions (`tokio` feature).
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
 /// One VDF proof job input for the batch (“Trick 2”) API.
 #[derive(Debug, Clone, Copy)]
 pub struct ChiavdfBatchJob<'a> {
@@ -17,14 +27,158 @@ pub struct ChiavdfBatchJob<'a> {
     pub num_iterations: u64,
 }
 
+/// Iterations between progress updates when only a [`run_with_progress_info`]
+/// callback wants them.
+const PROGRESS_INFO_INTERVAL: u64 = 1 << 16;
+
+/// What a prover reports its progress to: the caller's callback and the
+/// [`run_with_progress_info`] callback of the proving thread. It also holds the
+/// prover while the thread's [`ProverSession`] is paused.
 struct ProgressCtx {
-    cb: *mut (dyn FnMut(u64) + Send),
+    cb: Option<*mut (dyn FnMut(u64) + Send)>,
+    info: Option<*mut (dyn FnMut(ProgressInfo) + Send)>,
+    session: Option<ProverSession>,
+    /// Iterations between the updates the callbacks get.
+    interval: u64,
+    /// Iterations between the prover's calls, finer than `interval` under a
+    /// session so pauses take effect soon.
+    check_interval: u64,
+    next_update: u64,
+    throttle: ProgressThrottle,
+    finalize_reported: bool,
+}
+
+impl ProgressCtx {
+    /// Context for a proof squaring up to `total_iters`, or `None` when nobody
+    /// wants its progress.
+    fn new(
+        progress: Option<Progress<'_>>,
+        total_iters: u64,
+    ) -> Result<Option<Self>, ChiavdfFastError> {
+        let info = PROGRESS_INFO.get();
+        let session = SESSION.with_borrow(Clone::clone);
+        let (interval, cb) = match progress {
+            Some((0, _)) => {
+                return Err(ChiavdfFastError::InvalidInput(
+                    "progress_interval must be > 0",
+                ));
+            }
+            Some((interval, cb)) => (interval, Some(cb as *mut (dyn FnMut(u64) + Send))),
+            None if info.is_some() => (PROGRESS_INFO_INTERVAL, None),
+            None if session.is_some() => (PAUSE_CHECK_ITERS, None),
+            None => return Ok(None),
+        };
+        let check_interval = if session.is_some() {
+            interval.min(PAUSE_CHECK_ITERS)
+        } else {
+            interval
+        };
+        Ok(Some(Self {
+            cb,
+            info,
+            session,
+            interval,
+            check_interval,
+            next_update: interval,
+            throttle: ProgressThrottle::new(total_iters),
+            finalize_reported: false,
+        }))
+    }
+
+    fn user_data(&mut self) -> *mut c_void {
+        std::ptr::from_mut(self).cast::<c_void>()
+    }
+
+    fn report(&mut self, iters_done: u64) {
+        if let Some(session) = &self.session {
+            session.wait_while_paused();
+        }
+        let total_iters = self.throttle.total_iters;
+        // The prover's last call before it folds the witness, whether or not the
+        // total is a multiple of the interval.
+        let squaring_done = iters_done.saturating_add(self.check_interval) > total_iters;
+        let due = self.check_interval == self.interval
+            || iters_done >= self.next_update
+            || iters_done >= total_iters;
+        if due {
+            self.next_update = (iters_done / self.interval)
+                .saturating_add(1)
+                .saturating_mul(self.interval);
+        }
+        if due && self.throttle.admit(iters_done) {
+            if let Some(cb) = self.cb {
+                // SAFETY: The callback outlives the prover call this context is for.
+                let cb = unsafe { &mut *cb };
+                let _ = catch_unwind(AssertUnwindSafe(|| cb(iters_done)));
+            }
+            if !self.finalize_reported {
+                self.report_info(iters_done, ProgressPhase::Squaring);
+            }
+        }
+        if squaring_done && !self.finalize_reported {
+            self.finalize_reported = true;
+            self.report_info(total_iters, ProgressPhase::Finalize);
+        }
+    }
+
+    fn report_info(&mut self, iters_done: u64, phase: ProgressPhase) {
+        if let Some(info) = self.info {
+            // SAFETY: `run_with_progress_info` keeps the callback alive while it
+            // is installed, which covers the prover call.
+            let info = unsafe { &mut *info };
+            let _ = catch_unwind(AssertUnwindSafe(|| {
+                info(ProgressInfo { iters_done, phase })
+            }));
+        }
+    }
+}
+
+thread_local! {
+    /// Callback of the innermost [`run_with_progress_info`] on the current thread.
+    static PROGRESS_INFO: Cell<Option<*mut (dyn FnMut(ProgressInfo) + Send)>> =
+        const { Cell::new(None) };
+    /// Session of the innermost [`ProverSession::run`] on the current thread.
+    static SESSION: RefCell<Option<ProverSession>> = const { RefCell::new(None) };
+    /// [`ProveOptions::progress_min_interval`] of the current thread.
+    static PROGRESS_MIN_INTERVAL: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+    /// Deadline of the proofs on the current thread (see [`DeadlineGuard`]).
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Drops progress updates that come too soon after the previous one.
+struct ProgressThrottle {
+    min_interval: Duration,
+    total_iters: u64,
+    last_delivered: Instant,
+}
+
+impl ProgressThrottle {
+    fn new(total_iters: u64) -> Self {
+        Self {
+            min_interval: PROGRESS_MIN_INTERVAL.get(),
+            total_iters,
+            last_delivered: Instant::now(),
+        }
+    }
+
+    /// Whether the update for `iters_done` is due; the final one always is.
+    fn admit(&mut self, iters_done: u64) -> bool {
+        if self.min_interval.is_zero() || iters_done >= self.total_iters {
+            return true;
+        }
+        let now = Instant::now();
+        if now.duration_since(self.last_delivered) < self.min_interval {
+            return false;
+        }
+        self.last_delivered = now;
+        true
+    }
 }
 
 unsafe extern "C" fn progress_trampoline(iters_done: u64, user_data: *mut c_void) {
+    // SAFETY: The provers pass a `ProgressCtx` that outlives the native call.
     let ctx = unsafe { &mut *(user_data as *mut ProgressCtx) };
-    let cb = unsafe { &mut *ctx.cb };
-    let _ = catch_unwind(AssertUnwindSafe(|| (cb)(iters_done)));
+    ctx.report(iters_done);
 }
 
 /// Errors returned by [`prove_one_weso_fast`].
@@ -34,13 +188,320 @@ pub enum ChiavdfFastError {
     #[error("invalid input: {0}")]
     InvalidInput(&'static str),
 
-    /// The native library failed to produce a proof.
-    #[error("chiavdf fast prove failed")]
-    NativeFailure,
+    /// The native library failed to produce a proof: it threw, crashed inside a
+    /// guarded call, or returned nothing usable. The C API reports no reason, so
+    /// this carries only what the Rust side could tell.
+    #[error("chiavdf fast prove failed: {0}")]
+    NativeFailure(String),
+
+    /// The expected output `y_ref` has the wrong size to be a form of the discriminant.
+    #[error("bad y_ref: {0}")]
+    InvalidYRef(String),
+
+    /// An input is not a valid form for the discriminant (or an output could not
+    /// be serialized).
+    #[error("invalid form: {0}")]
+    InvalidForm(String),
 
     /// The native library returned a buffer with an unexpected length.
     #[error("unexpected result length: {0}")]
     UnexpectedLength(usize),
+
+    /// The proof was refused because its deadline had passed (see
+    /// [`ProveOptions::deadline`]).
+    #[error("proof deadline exceeded")]
+    DeadlineExceeded,
+}
+
+impl ChiavdfFastError {
+    /// Whether the same call may succeed when retried.
+    ///
+    /// Native failures are unexplained and may be transient; inputs rejected
+    /// before proving fail the same way every time.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ChiavdfFastError::NativeFailure(_))
+    }
+}
+
+/// The failure of a native call that returned nothing: the C API reports no
+/// reason.
+fn native_error() -> ChiavdfFastError {
+    ChiavdfFastError::NativeFailure("no detail reported by the linked chiavdf".to_string())
+}
+
+/// Whether the deadline of the proofs on the current thread has passed.
+fn deadline_passed() -> bool {
+    DEADLINE
+        .get()
+        .is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+/// Installs a deadline for the proofs on the current thread, which each proof
+/// checks before it starts.
+struct DeadlineGuard {
+    previous: Option<Option<Instant>>,
+}
+
+impl DeadlineGuard {
+    fn install(deadline: Option<Instant>) -> Self {
+        let Some(deadline) = deadline else {
+            return Self { previous: None };
+        };
+        // A deadline inside another one can only come sooner.
+        let previous = DEADLINE.get();
+        DEADLINE.set(Some(previous.map_or(deadline, |outer| outer.min(deadline))));
+        Self {
+            previous: Some(previous),
+        }
+    }
+}
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous {
+            DEADLINE.set(previous);
+        }
+    }
+}
+
+/// Poll interval while a session is paused.
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Most iterations between the pause checks of a proof run under a
+/// [`ProverSession`].
+const PAUSE_CHECK_ITERS: u64 = 1 << 12;
+
+/// Handle for a long-running proof that can be paused and resumed from other
+/// threads.
+///
+/// Clones share state. Run the proof with [`ProverSession::run`] on the proving
+/// thread; while paused, the prover sleeps in its progress callback, which the
+/// session has it call at least every 4096 squarings, and keeps its
+/// intermediate state, so resuming continues where it stopped. A proof that is
+/// already folding its witness finishes first.
+#[derive(Debug, Clone, Default)]
+pub struct ProverSession {
+    paused: Arc<AtomicBool>,
+}
+
+impl ProverSession {
+    /// Create a session that is not paused.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Suspend the proof running under this session.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Release);
+    }
+
+    /// Continue a paused proof.
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Release);
+    }
+
+    /// Whether the session is currently paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
+    /// Run `prove` on the current thread under this session.
+    ///
+    /// Any proving function of this crate called inside `prove` honours
+    /// [`ProverSession::pause`].
+    pub fn run<T>(&self, prove: impl FnOnce() -> T) -> T {
+        self.wait_while_paused();
+        let previous = SESSION.replace(Some(self.clone()));
+        let guard = SessionGuard { previous };
+        let result = prove();
+        drop(guard);
+        result
+    }
+
+    /// Block while paused.
+    fn wait_while_paused(&self) {
+        while self.is_paused() {
+            std::thread::sleep(PAUSE_POLL_INTERVAL);
+        }
+    }
+}
+
+/// Restores the [`ProverSession`] installed before it.
+struct SessionGuard {
+    previous: Option<ProverSession>,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        SESSION.set(self.previous.take());
+    }
+}
+
+/// Phase of a running proof, as reported in [`ProgressInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressPhase {
+    /// Computing the squarings (`iters_done` advances).
+    Squaring,
+    /// Squaring is done; the checkpoints are being folded into the witness.
+    Finalize,
+}
+
+/// Progress of a running proof, reported by [`run_with_progress_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressInfo {
+    /// Squaring iterations completed, counted like the `*_with_progress`
+    /// callbacks (see [`ProverBuilder::progress`] for batches).
+    pub iters_done: u64,
+    /// What the prover is doing.
+    pub phase: ProgressPhase,
+}
+
+/// Restores the [`run_with_progress_info`] callback installed before it.
+struct ProgressInfoGuard {
+    previous: Option<*mut (dyn FnMut(ProgressInfo) + Send)>,
+}
+
+impl Drop for ProgressInfoGuard {
+    fn drop(&mut self) {
+        PROGRESS_INFO.set(self.previous);
+    }
+}
+
+/// Run `prove` on the current thread, reporting [`ProgressInfo`] to
+/// `on_progress`.
+///
+/// Proving functions of this crate called inside `prove` report at their
+/// `progress_interval` (the `*_with_progress` variants; every 65536 iterations
+/// otherwise), subject to [`ProveOptions::progress_min_interval`], and once
+/// when squaring ends and the witness is folded, so callers can tell a proof
+/// that is finalizing from one that is stuck at 100%. The callback runs on the
+/// proving thread. An inner call replaces the outer callback until it returns.
+pub fn run_with_progress_info<T, F>(mut on_progress: F, prove: impl FnOnce() -> T) -> T
+where
+    F: FnMut(ProgressInfo) + Send + 'static,
+{
+    let cb: &mut (dyn FnMut(ProgressInfo) + Send) = &mut on_progress;
+    let guard = ProgressInfoGuard {
+        previous: PROGRESS_INFO.replace(Some(cb as *mut (dyn FnMut(ProgressInfo) + Send))),
+    };
+    let result = prove();
+    drop(guard);
+    result
+}
+
+/// Per-call settings for the native prover.
+///
+/// Run proofs with [`ProveOptions::run`]; the settings apply to every proving
+/// function of this crate called inside it on the current thread.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProveOptions {
+    /// CPUs the proving thread, and the native threads it starts, are pinned to
+    /// (Linux CPU indices).
+    ///
+    /// Empty leaves them unpinned. The thread's previous affinity is restored
+    /// when [`run`](Self::run) returns. Ignored outside Linux.
+    pub cpu_set: Vec<usize>,
+    /// Shortest time between two callbacks of the `*_with_progress` provers.
+    ///
+    /// Updates that would come sooner are dropped (the final one is always
+    /// delivered), so callers can pass a fine `progress_interval` without the
+    /// update rate depending on CPU speed and job size. Zero delivers every
+    /// update.
+    pub progress_min_interval: Duration,
+    /// Instant after which proofs fail with
+    /// [`ChiavdfFastError::DeadlineExceeded`] instead of starting a result
+    /// nobody can use any more.
+    ///
+    /// It is checked as each proof starts, and between the segments of an
+    /// n-Wesolowski proof; the prover can't be stopped mid-computation, so a
+    /// proof already squaring at the deadline still finishes.
+    pub deadline: Option<Instant>,
+}
+
+impl ProveOptions {
+    /// Options that pin the native computation threads to `cpu_set`.
+    pub fn pinned(cpu_set: impl Into<Vec<usize>>) -> Self {
+        Self {
+            cpu_set: cpu_set.into(),
+            ..Self::default()
+        }
+    }
+
+    /// These options, with progress callbacks at least `min_interval` apart.
+    pub fn with_progress_min_interval(mut self, min_interval: Duration) -> Self {
+        self.progress_min_interval = min_interval;
+        self
+    }
+
+    /// These options, refusing proofs that start after `deadline`.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Run `prove` on the current thread with these options installed.
+    ///
+    /// Options don't nest: an inner `run` replaces the outer settings and clears
+    /// them when it returns.
+    pub fn run<T>(&self, prove: impl FnOnce() -> T) -> T {
+        let cpu_set = CpuSetGuard::install(&self.cpu_set);
+        let throttle = ProgressThrottleGuard::install(self.progress_min_interval);
+        let deadline = DeadlineGuard::install(self.deadline);
+        let result = prove();
+        drop(deadline);
+        drop(throttle);
+        drop(cpu_set);
+        result
+    }
+}
+
+/// Installs [`ProveOptions::progress_min_interval`] for [`ProgressThrottle`].
+struct ProgressThrottleGuard;
+
+impl ProgressThrottleGuard {
+    fn install(min_interval: Duration) -> Self {
+        PROGRESS_MIN_INTERVAL.set(min_interval);
+        Self
+    }
+}
+
+impl Drop for ProgressThrottleGuard {
+    fn drop(&mut self) {
+        PROGRESS_MIN_INTERVAL.set(Duration::ZERO);
+    }
+}
+
+/// Pins the current thread to [`ProveOptions::cpu_set`]; threads started while
+/// it is pinned inherit the mask.
+struct CpuSetGuard {
+    previous: Option<Vec<usize>>,
+}
+
+impl CpuSetGuard {
+    fn install(cpu_set: &[usize]) -> Self {
+        if cpu_set.is_empty() {
+            return Self { previous: None };
+        }
+        // Pinning is best effort: a failure leaves the thread where it was.
+        let previous = match bbr_client_affinity::current_thread_affinity() {
+            Ok(previous) if !previous.is_empty() => previous,
+            _ => return Self { previous: None },
+        };
+        if bbr_client_affinity::set_current_thread_affinity(cpu_set).is_err() {
+            return Self { previous: None };
+        }
+        Self {
+            previous: Some(previous),
+        }
+    }
+}
+
+impl Drop for CpuSetGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = &self.previous {
+            let _ = bbr_client_affinity::set_current_thread_affinity(previous);
+        }
+    }
 }
 
 /// Parameters selected by the streaming prover.
@@ -69,21 +530,93 @@ pub struct StreamingStats {
     pub bucket_updates: u64,
 }
 
-fn take_result(array: ffi::ChiavdfByteArray) -> Result<Vec<u8>, ChiavdfFastError> {
-    if array.data.is_null() || array.length == 0 {
-        return Err(ChiavdfFastError::NativeFailure);
+/// Output of a streaming proof together with the parameters and timing counters
+/// of that same call.
+///
+/// Returned by the `*_reported` provers, which read the native per-thread state
+/// right after the proof on the thread that computed it, so the report cannot
+/// pick up another proof's values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProveReport<T> {
+    /// The prover's output, as returned by the non-reporting variant.
+    pub output: T,
+    /// `(k,l)` parameters the proof used.
+    pub parameters: StreamingParameters,
+    /// Timing counters, when enabled with [`set_enable_streaming_stats`].
+    pub stats: Option<StreamingStats>,
+}
+
+/// Attach the current thread's streaming parameters and stats to `output`.
+///
+/// Must be called on the thread that ran the proof, before it starts another.
+fn report<T>(output: T) -> Result<ProveReport<T>, ChiavdfFastError> {
+    let parameters = last_streaming_parameters().ok_or_else(|| {
+        ChiavdfFastError::NativeFailure("no streaming parameters reported".to_string())
+    })?;
+    Ok(ProveReport {
+        output,
+        parameters,
+        stats: last_streaming_stats(),
+    })
+}
+
+/// A prover's result: the output form `y` and the witness proving it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofOutput {
+    /// Serialized output form after `num_iterations` squarings.
+    pub y: Vec<u8>,
+    /// The proof form, followed by the segments of an n-Wesolowski proof (see
+    /// [`prove_n_weso_fast`]); this is Chia's `VDFProof` witness.
+    pub witness: Vec<u8>,
+}
+
+impl ProofOutput {
+    /// Bytes of a compact proof's `y || witness`, as written by
+    /// [`ProverBuilder::prove_into`] (per job by [`ProverBuilder::prove_batch_into`]).
+    pub const COMPACT_SIZE: usize = 2 * crate::chia::CLASSGROUP_ELEMENT_SIZE;
+
+    /// Split a `y || witness` buffer from a prover with `witness_type`
+    /// intermediate segments (`0` for compact proofs), checking its length.
+    pub fn from_bytes(bytes: &[u8], witness_type: u8) -> Result<Self, ChiavdfFastError> {
+        // `2 * form + n * (8 + 2 * form)` bytes for n segments of `form`-byte forms.
+        let segments = usize::from(witness_type);
+        let forms = bytes.len().saturating_sub(8 * segments);
+        if forms == 0 || !forms.is_multiple_of(2 * (segments + 1)) {
+            return Err(ChiavdfFastError::UnexpectedLength(bytes.len()));
+        }
+        let (y, witness) = bytes.split_at(forms / (2 * (segments + 1)));
+        Ok(Self {
+            y: y.to_vec(),
+            witness: witness.to_vec(),
+        })
     }
 
-    // SAFETY: The native library returns a heap-allocated buffer of `length`
-    // bytes. We copy it out before freeing it.
-    let out = unsafe { std::slice::from_raw_parts(array.data, array.length).to_vec() };
-    unsafe { ffi::chiavdf_free_byte_array(array) };
+    /// The `y || witness` layout the native provers return and chiavdf's
+    /// verifiers take.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.y.as_slice(), self.witness.as_slice()].concat()
+    }
+}
 
-    if out.len() < 2 || out.len() % 2 != 0 {
-        return Err(ChiavdfFastError::UnexpectedLength(out.len()));
+fn take_result(array: ffi::ChiavdfByteArray) -> Result<ProofOutput, ChiavdfFastError> {
+    if array.data.is_null() {
+        return Err(native_error());
+    }
+    if array.length == 0 {
+        // SAFETY: Non-null buffers from the native library are freed exactly once,
+        // even when empty.
+        unsafe { ffi::chiavdf_free_byte_array(array) };
+        return Err(ChiavdfFastError::NativeFailure(
+            "native library returned an empty buffer".to_string(),
+        ));
     }
 
-    Ok(out)
+    // SAFETY: The native library returns a heap-allocated buffer of `length`
+    // bytes. We split it into owned copies before freeing it.
+    let out =
+        unsafe { ProofOutput::from_bytes(std::slice::from_raw_parts(array.data, array.length), 0) };
+    unsafe { ffi::chiavdf_free_byte_array(array) };
+    out
 }
 
 /// Set the memory budget (in bytes) used by the streaming prover parameter tuner.
@@ -107,7 +640,8 @@ pub fn set_enable_streaming_stats(enable: bool) {
 
 /// Return the most recent `(k,l)` parameters selected for a streaming proof on the current thread.
 ///
-/// Intended for debugging/benchmarking.
+/// Intended for debugging/benchmarking. Callers that may move work between
+/// threads should use a `*_reported` prover, which returns a [`ProveReport`].
 pub fn last_streaming_parameters() -> Option<StreamingParameters> {
     let mut k: u32 = 0;
     let mut l: u32 = 0;
@@ -128,7 +662,9 @@ pub fn last_streaming_parameters() -> Option<StreamingParameters> {
 /// Return timing counters for the most recent streaming proof on the current thread.
 ///
 /// Returns `None` if timing collection is disabled or no streaming proof has been
-/// computed successfully on this thread since enabling it.
+/// computed successfully on this thread since enabling it. Callers that may move
+/// work between threads should use a `*_reported` prover, which returns a
+/// [`ProveReport`].
 pub fn last_streaming_stats() -> Option<StreamingStats> {
     let mut checkpoint_total_ns: u64 = 0;
     let mut checkpoint_event_total_ns: u64 = 0;
@@ -156,6 +692,39 @@ pub fn last_streaming_stats() -> Option<StreamingStats> {
     })
 }
 
+/// Create the discriminant chiavdf derives from `challenge_hash`.
+///
+/// Returns the magnitude of the (always negative) discriminant as big-endian
+/// bytes, zero-padded to `size_bits / 8` bytes; the same value chiavdf's
+/// Python `create_discriminant` returns as a decimal string, without the sign.
+/// Computed in Rust, so it is available whichever prover is linked.
+pub fn create_discriminant(
+    challenge_hash: &[u8],
+    size_bits: usize,
+) -> Result<Vec<u8>, ChiavdfFastError> {
+    if challenge_hash.is_empty() {
+        return Err(ChiavdfFastError::InvalidInput(
+            "challenge_hash must not be empty",
+        ));
+    }
+    if size_bits == 0 || !size_bits.is_multiple_of(8) {
+        return Err(ChiavdfFastError::InvalidInput(
+            "size_bits must be a positive multiple of 8",
+        ));
+    }
+
+    let magnitude = classgroup::create_discriminant(challenge_hash, size_bits)
+        .magnitude()
+        .to_bytes_be();
+    let mut out = vec![0u8; size_bits / 8];
+    let start = out
+        .len()
+        .checked_sub(magnitude.len())
+        .ok_or(ChiavdfFastError::UnexpectedLength(magnitude.len()))?;
+    out[start..].copy_from_slice(&magnitude);
+    Ok(out)
+}
+
 struct BatchResultGuard {
     ptr: *mut ffi::ChiavdfByteArray,
     count: usize,
@@ -169,15 +738,26 @@ impl Drop for BatchResultGuard {
     }
 }
 
+/// Results of a batch prover call, one per job in job order.
+type BatchResults = Vec<Result<ProofOutput, ChiavdfFastError>>;
+
+/// Copy a batch result out of native memory. The outer error is for a batch that
+/// failed as a whole; an unusable entry only fails its own job.
 fn take_result_batch(
     ptr: *mut ffi::ChiavdfByteArray,
     count: usize,
-) -> Result<Vec<Vec<u8>>, ChiavdfFastError> {
-    if ptr.is_null() || count == 0 {
-        return Err(ChiavdfFastError::NativeFailure);
+) -> Result<BatchResults, ChiavdfFastError> {
+    if ptr.is_null() {
+        return Err(native_error());
     }
 
+    // Frees the batch on every return path, including an empty one.
     let guard = BatchResultGuard { ptr, count };
+    if count == 0 {
+        return Err(ChiavdfFastError::NativeFailure(
+            "native library returned an empty batch".to_string(),
+        ));
+    }
 
     // SAFETY: `ptr` points to an array of `count` `ChiavdfByteArray` entries.
     let arrays = unsafe { std::slice::from_raw_parts(guard.ptr, guard.count) };
@@ -185,30 +765,53 @@ fn take_result_batch(
     let mut out = Vec::with_capacity(count);
     for array in arrays {
         if array.data.is_null() || array.length == 0 {
-            return Err(ChiavdfFastError::NativeFailure);
+            out.push(Err(ChiavdfFastError::NativeFailure(
+                "native library returned an empty batch entry".to_string(),
+            )));
+            continue;
         }
         // SAFETY: The native library returns a heap-allocated buffer of `length`
-        // bytes. We copy it out before freeing the batch.
-        let bytes = unsafe { std::slice::from_raw_parts(array.data, array.length).to_vec() };
-        if bytes.len() < 2 || bytes.len() % 2 != 0 {
-            return Err(ChiavdfFastError::UnexpectedLength(bytes.len()));
-        }
-        out.push(bytes);
+        // bytes. We split it into owned copies before freeing the batch.
+        let bytes = unsafe { std::slice::from_raw_parts(array.data, array.length) };
+        out.push(ProofOutput::from_bytes(bytes, 0));
     }
 
     drop(guard);
     Ok(out)
 }
 
+/// Progress callback handed to the native provers, with its interval in
+/// iterations.
+type Progress<'a> = (u64, &'a mut (dyn FnMut(u64) + Send + 'static));
+
 /// Compute a compact (witness_type=0) Wesolowski proof using the fast chiavdf engine.
 ///
-/// Returns a byte buffer `y || proof` (typically 200 bytes for 1024-bit discriminants).
+/// The output's `y` and `witness` are 100 bytes each for 1024-bit discriminants.
 pub fn prove_one_weso_fast(
     challenge_hash: &[u8],
     x_s: &[u8],
     discriminant_size_bits: usize,
     num_iterations: u64,
-) -> Result<Vec<u8>, ChiavdfFastError> {
+) -> Result<ProofOutput, ChiavdfFastError> {
+    prove_with(
+        challenge_hash,
+        x_s,
+        None,
+        false,
+        discriminant_size_bits,
+        num_iterations,
+        None,
+    )
+}
+
+/// Reject a single proof's inputs before they reach the native prover.
+fn check_prove_inputs(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    y_ref_s: Option<&[u8]>,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+) -> Result<(), ChiavdfFastError> {
     if challenge_hash.is_empty() {
         return Err(ChiavdfFastError::InvalidInput(
             "challenge_hash must not be empty",
@@ -217,45 +820,180 @@ pub fn prove_one_weso_fast(
     if x_s.is_empty() {
         return Err(ChiavdfFastError::InvalidInput("x_s must not be empty"));
     }
+    if y_ref_s.is_some_and(<[u8]>::is_empty) {
+        return Err(ChiavdfFastError::InvalidInput("y_ref_s must not be empty"));
+    }
     if discriminant_size_bits == 0 {
         return Err(ChiavdfFastError::InvalidInput(
             "discriminant_size_bits must be > 0",
         ));
     }
-    if num_iterations == 0 {
-        return Err(ChiavdfFastError::InvalidInput("num_iterations must be > 0"));
+    check_iterations(num_iterations)?;
+    if let Some(y_ref_s) = y_ref_s {
+        check_y_ref_size(y_ref_s, discriminant_size_bits)?;
     }
+    check_starting_form(challenge_hash, x_s, discriminant_size_bits)?;
+    check_thread_options()
+}
 
-    // SAFETY: We pass pointers + lengths for all byte slices, and we copy out
-    // the returned buffer before freeing it.
-    unsafe {
-        take_result(ffi::chiavdf_prove_one_weso_fast(
-            challenge_hash.as_ptr(),
-            challenge_hash.len(),
-            x_s.as_ptr(),
-            x_s.len(),
-            discriminant_size_bits,
-            num_iterations,
-        ))
+/// Reject an expected output that cannot be a form of the discriminant, which
+/// the native prover would only fail on after squaring.
+fn check_y_ref_size(y_ref_s: &[u8], discriminant_size_bits: usize) -> Result<(), ChiavdfFastError> {
+    let expected = classgroup::form_size(discriminant_size_bits);
+    if y_ref_s.len() == expected {
+        return Ok(());
     }
+    Err(ChiavdfFastError::InvalidYRef(format!(
+        "{} bytes, expected {expected}",
+        y_ref_s.len()
+    )))
 }
 
-/// Compute a compact (witness_type=0) Wesolowski proof using the fast chiavdf engine.
+/// Reject proofs whose deadline has already passed.
+fn check_thread_options() -> Result<(), ChiavdfFastError> {
+    if deadline_passed() {
+        return Err(ChiavdfFastError::DeadlineExceeded);
+    }
+    Ok(())
+}
+
+/// Validate a single proof's inputs and run the native prover they select:
+/// streaming when `y_ref_s` is known (with the `GetBlock()` optimization if
+/// `getblock_opt`), reporting to `progress` when given.
+fn prove_with(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    y_ref_s: Option<&[u8]>,
+    getblock_opt: bool,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress: Option<Progress<'_>>,
+) -> Result<ProofOutput, ChiavdfFastError> {
+    check_prove_inputs(
+        challenge_hash,
+        x_s,
+        y_ref_s,
+        discriminant_size_bits,
+        num_iterations,
+    )?;
+
+    let Some(mut ctx) = ProgressCtx::new(progress, num_iterations)? else {
+        // SAFETY: We pass pointers + lengths for all byte slices, and we copy out
+        // the returned buffer before freeing it.
+        let array = unsafe {
+            match y_ref_s {
+                None => ffi::chiavdf_prove_one_weso_fast(
+                    challenge_hash.as_ptr(),
+                    challenge_hash.len(),
+                    x_s.as_ptr(),
+                    x_s.len(),
+                    discriminant_size_bits,
+                    num_iterations,
+                ),
+                Some(y_ref_s) if getblock_opt => {
+                    ffi::chiavdf_prove_one_weso_fast_streaming_getblock_opt(
+                        challenge_hash.as_ptr(),
+                        challenge_hash.len(),
+                        x_s.as_ptr(),
+                        x_s.len(),
+                        y_ref_s.as_ptr(),
+                        y_ref_s.len(),
+                        discriminant_size_bits,
+                        num_iterations,
+                    )
+                }
+                Some(y_ref_s) => ffi::chiavdf_prove_one_weso_fast_streaming(
+                    challenge_hash.as_ptr(),
+                    challenge_hash.len(),
+                    x_s.as_ptr(),
+                    x_s.len(),
+                    y_ref_s.as_ptr(),
+                    y_ref_s.len(),
+                    discriminant_size_bits,
+                    num_iterations,
+                ),
+            }
+        };
+        return take_result(array);
+    };
+    let progress_interval = ctx.check_interval;
+    let user_data = ctx.user_data();
+
+    // SAFETY: We pass pointers + lengths for all byte slices, and we copy out
+    // the returned buffer before freeing it. The callback and context pointers
+    // live for the duration of this call.
+    let array = unsafe {
+        match y_ref_s {
+            None => ffi::chiavdf_prove_one_weso_fast_with_progress(
+                challenge_hash.as_ptr(),
+                challenge_hash.len(),
+                x_s.as_ptr(),
+                x_s.len(),
+                discriminant_size_bits,
+                num_iterations,
+                progress_interval,
+                Some(progress_trampoline),
+                user_data,
+            ),
+            Some(y_ref_s) if getblock_opt => {
+                ffi::chiavdf_prove_one_weso_fast_streaming_getblock_opt_with_progress(
+                    challenge_hash.as_ptr(),
+                    challenge_hash.len(),
+                    x_s.as_ptr(),
+                    x_s.len(),
+                    y_ref_s.as_ptr(),
+                    y_ref_s.len(),
+                    discriminant_size_bits,
+                    num_iterations,
+                    progress_interval,
+                    Some(progress_trampoline),
+                    user_data,
+                )
+            }
+            Some(y_ref_s) => ffi::chiavdf_prove_one_weso_fast_streaming_with_progress(
+                challenge_hash.as_ptr(),
+                challenge_hash.len(),
+                x_s.as_ptr(),
+                x_s.len(),
+                y_ref_s.as_ptr(),
+                y_ref_s.len(),
+                discriminant_size_bits,
+                num_iterations,
+                progress_interval,
+                Some(progress_trampoline),
+                user_data,
+            ),
+        }
+    };
+    take_result(array)
+}
+
+/// Compute an n-Wesolowski proof with `witness_type` intermediate segments.
 ///
-/// Invokes `progress` every `progress_interval` iterations completed.
+/// `witness_type = 0` is the compact proof of [`prove_one_weso_fast`]. Otherwise
+/// the iterations are split into `witness_type + 1` roughly equal segments and the
+/// result's witness is
 ///
-/// Returns a byte buffer `y || proof` (typically 200 bytes for 1024-bit discriminants).
-pub fn prove_one_weso_fast_with_progress<F>(
+/// ```text
+/// proof || segment_n || ... || segment_1
+/// segment_i = u64be iterations || y_i || proof_i
+/// ```
+///
+/// Segment 1 starts at `x` and each later segment at the previous `y_i`; `y` and
+/// the leading `proof` cover the remaining iterations, so `y` is the output of
+/// all `num_iterations`. [`ProofOutput::to_bytes`] is the blob chiavdf's
+/// `CheckProofOfTimeNWesolowski` verifies, and the witness is Chia's `VDFProof`
+/// witness (see [`crate::chia::vdf_info_and_proof`]).
+pub fn prove_n_weso_fast(
     challenge_hash: &[u8],
     x_s: &[u8],
     discriminant_size_bits: usize,
     num_iterations: u64,
-    progress_interval: u64,
-    mut progress: F,
-) -> Result<Vec<u8>, ChiavdfFastError>
-where
-    F: FnMut(u64) + Send + 'static,
-{
+    witness_type: u8,
+) -> Result<ProofOutput, ChiavdfFastError> {
+    if witness_type == 0 {
+        return prove_one_weso_fast(challenge_hash, x_s, discriminant_size_bits, num_iterations);
+    }
     if challenge_hash.is_empty() {
         return Err(ChiavdfFastError::InvalidInput(
             "challenge_hash must not be empty",
@@ -269,46 +1007,59 @@ where
             "discriminant_size_bits must be > 0",
         ));
     }
-    if num_iterations == 0 {
-        return Err(ChiavdfFastError::InvalidInput("num_iterations must be > 0"));
-    }
-    if progress_interval == 0 {
+    check_iterations(num_iterations)?;
+    check_starting_form(challenge_hash, x_s, discriminant_size_bits)?;
+    check_thread_options()?;
+    if num_iterations <= u64::from(witness_type) {
         return Err(ChiavdfFastError::InvalidInput(
-            "progress_interval must be > 0",
+            "num_iterations must exceed witness_type",
         ));
     }
 
-    let cb: &mut (dyn FnMut(u64) + Send) = &mut progress;
-    let mut ctx = ProgressCtx {
-        cb: cb as *mut (dyn FnMut(u64) + Send),
-    };
-
-    // SAFETY: We pass pointers + lengths for all byte slices, and we copy out
-    // the returned buffer before freeing it. The callback and context pointers
-    // live for the duration of this call.
-    unsafe {
-        take_result(ffi::chiavdf_prove_one_weso_fast_with_progress(
-            challenge_hash.as_ptr(),
-            challenge_hash.len(),
-            x_s.as_ptr(),
-            x_s.len(),
+    // Each segment is a compact proof from the previous segment's `y`.
+    let segment_iters = num_iterations / (u64::from(witness_type) + 1);
+    let mut x_cur = x_s.to_vec();
+    let mut segments = Vec::with_capacity(usize::from(witness_type));
+    for _ in 0..witness_type {
+        let part = prove_one_weso_fast(
+            challenge_hash,
+            &x_cur,
             discriminant_size_bits,
-            num_iterations,
-            progress_interval,
-            Some(progress_trampoline),
-            std::ptr::addr_of_mut!(ctx).cast::<c_void>(),
-        ))
+            segment_iters,
+        )?;
+        segments.push(
+            [
+                &segment_iters.to_be_bytes(),
+                part.y.as_slice(),
+                &part.witness,
+            ]
+            .concat(),
+        );
+        x_cur = part.y;
+    }
+    let mut out = prove_one_weso_fast(
+        challenge_hash,
+        &x_cur,
+        discriminant_size_bits,
+        num_iterations - segment_iters * u64::from(witness_type),
+    )?;
+    for segment in segments.iter().rev() {
+        out.witness.extend_from_slice(segment);
     }
+    Ok(out)
 }
 
-/// Compute a compact (witness_type=0) Wesolowski proof using the fast chiavdf engine,
-/// using the known expected output `y_ref` (Trick 1 streaming mode).
+/// Square `x` `num_iterations` times and return the serialized `y`, without
+/// computing a witness.
 ///
-/// Returns a byte buffer `y || proof` (typically 200 bytes for 1024-bit discriminants).
-pub fn prove_one_weso_fast_streaming(
+/// Yields the same `y` as [`prove_one_weso_fast`], for generating test vectors
+/// and checking outputs locally. Squares on the calling thread with this
+/// crate's own class group arithmetic rather than the linked prover, so it
+/// works with every prover and cross-checks it, but runs several times slower
+/// than native squaring.
+pub fn evaluate_weso(
     challenge_hash: &[u8],
     x_s: &[u8],
-    y_ref_s: &[u8],
     discriminant_size_bits: usize,
     num_iterations: u64,
 ) -> Result<Vec<u8>, ChiavdfFastError> {
@@ -320,48 +1071,36 @@ pub fn prove_one_weso_fast_streaming(
     if x_s.is_empty() {
         return Err(ChiavdfFastError::InvalidInput("x_s must not be empty"));
     }
-    if y_ref_s.is_empty() {
-        return Err(ChiavdfFastError::InvalidInput("y_ref_s must not be empty"));
-    }
-    if discriminant_size_bits == 0 {
+    if discriminant_size_bits == 0 || !discriminant_size_bits.is_multiple_of(8) {
         return Err(ChiavdfFastError::InvalidInput(
-            "discriminant_size_bits must be > 0",
+            "discriminant_size_bits must be a positive multiple of 8",
         ));
     }
-    if num_iterations == 0 {
-        return Err(ChiavdfFastError::InvalidInput("num_iterations must be > 0"));
-    }
-
-    // SAFETY: We pass pointers + lengths for all byte slices, and we copy out
-    // the returned buffer before freeing it.
-    unsafe {
-        take_result(ffi::chiavdf_prove_one_weso_fast_streaming(
-            challenge_hash.as_ptr(),
-            challenge_hash.len(),
-            x_s.as_ptr(),
-            x_s.len(),
-            y_ref_s.as_ptr(),
-            y_ref_s.len(),
-            discriminant_size_bits,
-            num_iterations,
-        ))
-    }
+    check_iterations(num_iterations)?;
+    check_thread_options()?;
+
+    let d = classgroup::create_discriminant(challenge_hash, discriminant_size_bits);
+    let x = classgroup::deserialize(x_s, &d).ok_or_else(|| {
+        ChiavdfFastError::InvalidForm("x_s is not a valid form for the discriminant".to_owned())
+    })?;
+    let y = x.square_times(num_iterations, &d);
+    classgroup::serialize(&y, discriminant_size_bits)
+        .ok_or_else(|| ChiavdfFastError::InvalidForm("y could not be serialized".to_owned()))
 }
 
-/// Same as [`prove_one_weso_fast_streaming`], but invokes `progress` every
-/// `progress_interval` iterations completed.
-pub fn prove_one_weso_fast_streaming_with_progress<F>(
+/// Check that `x_s` is a form of the discriminant derived from
+/// `challenge_hash`, i.e. that the provers would accept it as their starting
+/// element.
+///
+/// [`ClassgroupElement::is_valid_for_discriminant`]
+/// only checks the encoding; an element of another challenge's discriminant
+/// passes it but fails here with [`ChiavdfFastError::InvalidForm`]. The provers
+/// run the same check before squaring.
+pub fn validate_form(
     challenge_hash: &[u8],
     x_s: &[u8],
-    y_ref_s: &[u8],
     discriminant_size_bits: usize,
-    num_iterations: u64,
-    progress_interval: u64,
-    mut progress: F,
-) -> Result<Vec<u8>, ChiavdfFastError>
-where
-    F: FnMut(u64) + Send + 'static,
-{
+) -> Result<(), ChiavdfFastError> {
     if challenge_hash.is_empty() {
         return Err(ChiavdfFastError::InvalidInput(
             "challenge_hash must not be empty",
@@ -370,46 +1109,114 @@ where
     if x_s.is_empty() {
         return Err(ChiavdfFastError::InvalidInput("x_s must not be empty"));
     }
-    if y_ref_s.is_empty() {
-        return Err(ChiavdfFastError::InvalidInput("y_ref_s must not be empty"));
-    }
     if discriminant_size_bits == 0 {
         return Err(ChiavdfFastError::InvalidInput(
             "discriminant_size_bits must be > 0",
         ));
     }
-    if num_iterations == 0 {
-        return Err(ChiavdfFastError::InvalidInput("num_iterations must be > 0"));
+    check_starting_form(challenge_hash, x_s, discriminant_size_bits)
+}
+
+/// Reject a starting form of another discriminant. The generator and identity
+/// are forms of every discriminant, so only other forms pay for deriving it.
+fn check_starting_form(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    discriminant_size_bits: usize,
+) -> Result<(), ChiavdfFastError> {
+    if x_s == ClassgroupElement::generator().as_bytes()
+        || x_s == ClassgroupElement::identity().as_bytes()
+    {
+        return Ok(());
     }
-    if progress_interval == 0 {
+    if !discriminant_size_bits.is_multiple_of(8) {
         return Err(ChiavdfFastError::InvalidInput(
-            "progress_interval must be > 0",
+            "discriminant_size_bits must be a multiple of 8",
         ));
     }
+    let d = classgroup::create_discriminant(challenge_hash, discriminant_size_bits);
+    match classgroup::deserialize(x_s, &d) {
+        Some(_) => Ok(()),
+        None => Err(ChiavdfFastError::InvalidForm(
+            "x_s is not a valid form for the discriminant".to_owned(),
+        )),
+    }
+}
 
-    let cb: &mut (dyn FnMut(u64) + Send) = &mut progress;
-    let mut ctx = ProgressCtx {
-        cb: cb as *mut (dyn FnMut(u64) + Send),
-    };
+/// Compute a compact (witness_type=0) Wesolowski proof using the fast chiavdf engine.
+///
+/// Invokes `progress` every `progress_interval` iterations completed.
+///
+/// The output's `y` and `witness` are 100 bytes each for 1024-bit discriminants.
+#[deprecated(note = "use `ProverBuilder::progress` and `ProverBuilder::prove`")]
+pub fn prove_one_weso_fast_with_progress<F>(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress_interval: u64,
+    mut progress: F,
+) -> Result<ProofOutput, ChiavdfFastError>
+where
+    F: FnMut(u64) + Send + 'static,
+{
+    prove_with(
+        challenge_hash,
+        x_s,
+        None,
+        false,
+        discriminant_size_bits,
+        num_iterations,
+        Some((progress_interval, &mut progress)),
+    )
+}
 
-    // SAFETY: We pass pointers + lengths for all byte slices, and we copy out
-    // the returned buffer before freeing it. The callback and context pointers
-    // live for the duration of this call.
-    unsafe {
-        take_result(ffi::chiavdf_prove_one_weso_fast_streaming_with_progress(
-            challenge_hash.as_ptr(),
-            challenge_hash.len(),
-            x_s.as_ptr(),
-            x_s.len(),
-            y_ref_s.as_ptr(),
-            y_ref_s.len(),
-            discriminant_size_bits,
-            num_iterations,
-            progress_interval,
-            Some(progress_trampoline),
-            std::ptr::addr_of_mut!(ctx).cast::<c_void>(),
-        ))
-    }
+/// Compute a compact (witness_type=0) Wesolowski proof using the fast chiavdf engine,
+/// using the known expected output `y_ref` (Trick 1 streaming mode).
+///
+/// The output's `y` and `witness` are 100 bytes each for 1024-bit discriminants.
+pub fn prove_one_weso_fast_streaming(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    y_ref_s: &[u8],
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+) -> Result<ProofOutput, ChiavdfFastError> {
+    prove_with(
+        challenge_hash,
+        x_s,
+        Some(y_ref_s),
+        false,
+        discriminant_size_bits,
+        num_iterations,
+        None,
+    )
+}
+
+/// Same as [`prove_one_weso_fast_streaming`], but invokes `progress` every
+/// `progress_interval` iterations completed.
+#[deprecated(note = "use `ProverBuilder` with `getblock_opt(false)`")]
+pub fn prove_one_weso_fast_streaming_with_progress<F>(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    y_ref_s: &[u8],
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress_interval: u64,
+    mut progress: F,
+) -> Result<ProofOutput, ChiavdfFastError>
+where
+    F: FnMut(u64) + Send + 'static,
+{
+    prove_with(
+        challenge_hash,
+        x_s,
+        Some(y_ref_s),
+        false,
+        discriminant_size_bits,
+        num_iterations,
+        Some((progress_interval, &mut progress)),
+    )
 }
 
 /// Same as [`prove_one_weso_fast_streaming`], but uses an optimized `GetBlock()`
@@ -420,45 +1227,21 @@ pub fn prove_one_weso_fast_streaming_getblock_opt(
     y_ref_s: &[u8],
     discriminant_size_bits: usize,
     num_iterations: u64,
-) -> Result<Vec<u8>, ChiavdfFastError> {
-    if challenge_hash.is_empty() {
-        return Err(ChiavdfFastError::InvalidInput(
-            "challenge_hash must not be empty",
-        ));
-    }
-    if x_s.is_empty() {
-        return Err(ChiavdfFastError::InvalidInput("x_s must not be empty"));
-    }
-    if y_ref_s.is_empty() {
-        return Err(ChiavdfFastError::InvalidInput("y_ref_s must not be empty"));
-    }
-    if discriminant_size_bits == 0 {
-        return Err(ChiavdfFastError::InvalidInput(
-            "discriminant_size_bits must be > 0",
-        ));
-    }
-    if num_iterations == 0 {
-        return Err(ChiavdfFastError::InvalidInput("num_iterations must be > 0"));
-    }
-
-    // SAFETY: We pass pointers + lengths for all byte slices, and we copy out
-    // the returned buffer before freeing it.
-    unsafe {
-        take_result(ffi::chiavdf_prove_one_weso_fast_streaming_getblock_opt(
-            challenge_hash.as_ptr(),
-            challenge_hash.len(),
-            x_s.as_ptr(),
-            x_s.len(),
-            y_ref_s.as_ptr(),
-            y_ref_s.len(),
-            discriminant_size_bits,
-            num_iterations,
-        ))
-    }
+) -> Result<ProofOutput, ChiavdfFastError> {
+    prove_with(
+        challenge_hash,
+        x_s,
+        Some(y_ref_s),
+        true,
+        discriminant_size_bits,
+        num_iterations,
+        None,
+    )
 }
 
 /// Same as [`prove_one_weso_fast_streaming_getblock_opt`], but invokes `progress`
 /// every `progress_interval` iterations completed.
+#[deprecated(note = "use `ProverBuilder::progress` and `ProverBuilder::prove`")]
 pub fn prove_one_weso_fast_streaming_getblock_opt_with_progress<F>(
     challenge_hash: &[u8],
     x_s: &[u8],
@@ -467,10 +1250,111 @@ pub fn prove_one_weso_fast_streaming_getblock_opt_with_progress<F>(
     num_iterations: u64,
     progress_interval: u64,
     mut progress: F,
-) -> Result<Vec<u8>, ChiavdfFastError>
+) -> Result<ProofOutput, ChiavdfFastError>
+where
+    F: FnMut(u64) + Send + 'static,
+{
+    prove_with(
+        challenge_hash,
+        x_s,
+        Some(y_ref_s),
+        true,
+        discriminant_size_bits,
+        num_iterations,
+        Some((progress_interval, &mut progress)),
+    )
+}
+
+/// Same as [`prove_one_weso_fast_streaming_getblock_opt`], but returns the
+/// parameters and stats of this proof with its output.
+pub fn prove_one_weso_fast_streaming_getblock_opt_reported(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    y_ref_s: &[u8],
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+) -> Result<ProveReport<ProofOutput>, ChiavdfFastError> {
+    report(prove_one_weso_fast_streaming_getblock_opt(
+        challenge_hash,
+        x_s,
+        y_ref_s,
+        discriminant_size_bits,
+        num_iterations,
+    )?)
+}
+
+/// Same as [`prove_one_weso_fast_streaming_getblock_opt_with_progress`], but
+/// returns the parameters and stats of this proof with its output.
+#[deprecated(note = "use `ProverBuilder::progress` and `ProverBuilder::prove_reported`")]
+pub fn prove_one_weso_fast_streaming_getblock_opt_with_progress_reported<F>(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    y_ref_s: &[u8],
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress_interval: u64,
+    mut progress: F,
+) -> Result<ProveReport<ProofOutput>, ChiavdfFastError>
+where
+    F: FnMut(u64) + Send + 'static,
+{
+    report(prove_with(
+        challenge_hash,
+        x_s,
+        Some(y_ref_s),
+        true,
+        discriminant_size_bits,
+        num_iterations,
+        Some((progress_interval, &mut progress)),
+    )?)
+}
+
+/// Compute multiple compact (witness_type=0) Wesolowski proofs in one shared
+/// squaring run (Trick 2), using:
+/// - streaming bucket accumulation (Trick 1)
+/// - precomputed `GetBlock()` mapping (GetBlock opt)
+///
+/// Returns one [`ProofOutput`] per job, in job order, and fails if any job did
+/// ([`ProverBuilder::prove_batch`] returns the jobs' results separately).
+pub fn prove_one_weso_fast_streaming_getblock_opt_batch(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    discriminant_size_bits: usize,
+    jobs: &[ChiavdfBatchJob<'_>],
+) -> Result<Vec<ProofOutput>, ChiavdfFastError> {
+    prove_batch_with(challenge_hash, x_s, discriminant_size_bits, jobs, None)?
+        .into_iter()
+        .collect()
+}
+
+/// Same as [`prove_one_weso_fast_streaming_getblock_opt_batch`], but invokes
+/// `progress` every `progress_interval` squaring iterations completed.
+#[deprecated(note = "use `ProverBuilder::progress` and `ProverBuilder::prove_batch`")]
+pub fn prove_one_weso_fast_streaming_getblock_opt_batch_with_progress<F>(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    discriminant_size_bits: usize,
+    jobs: &[ChiavdfBatchJob<'_>],
+    progress_interval: u64,
+    mut progress: F,
+) -> Result<Vec<ProofOutput>, ChiavdfFastError>
 where
     F: FnMut(u64) + Send + 'static,
 {
+    let cb: &mut (dyn FnMut(u64) + Send + 'static) = &mut progress;
+    let progress = (progress_interval > 0).then_some((progress_interval, cb));
+    prove_batch_with(challenge_hash, x_s, discriminant_size_bits, jobs, progress)?
+        .into_iter()
+        .collect()
+}
+
+/// Reject a batch's inputs before they reach the native prover.
+fn check_batch_inputs(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    discriminant_size_bits: usize,
+    jobs: &[ChiavdfBatchJob<'_>],
+) -> Result<(), ChiavdfFastError> {
     if challenge_hash.is_empty() {
         return Err(ChiavdfFastError::InvalidInput(
             "challenge_hash must not be empty",
@@ -479,193 +1363,514 @@ where
     if x_s.is_empty() {
         return Err(ChiavdfFastError::InvalidInput("x_s must not be empty"));
     }
-    if y_ref_s.is_empty() {
-        return Err(ChiavdfFastError::InvalidInput("y_ref_s must not be empty"));
-    }
     if discriminant_size_bits == 0 {
         return Err(ChiavdfFastError::InvalidInput(
             "discriminant_size_bits must be > 0",
         ));
     }
-    if num_iterations == 0 {
-        return Err(ChiavdfFastError::InvalidInput("num_iterations must be > 0"));
+    if jobs.is_empty() {
+        return Err(ChiavdfFastError::InvalidInput("jobs must not be empty"));
     }
-    if progress_interval == 0 {
-        return Err(ChiavdfFastError::InvalidInput(
-            "progress_interval must be > 0",
-        ));
+    for job in jobs {
+        if job.y_ref_s.is_empty() {
+            return Err(ChiavdfFastError::InvalidInput(
+                "job y_ref_s must not be empty",
+            ));
+        }
+        check_iterations(job.num_iterations)?;
     }
+    for job in jobs {
+        check_y_ref_size(job.y_ref_s, discriminant_size_bits)?;
+    }
+    check_starting_form(challenge_hash, x_s, discriminant_size_bits)?;
+    check_thread_options()
+}
 
-    let cb: &mut (dyn FnMut(u64) + Send) = &mut progress;
-    let mut ctx = ProgressCtx {
-        cb: cb as *mut (dyn FnMut(u64) + Send),
-    };
+/// Iterations a batch's progress counts up to: chiavdf's jobs share their
+/// squarings, so up to the longest; the fallbacks prove them one after another.
+fn batch_progress_total(jobs: &[ChiavdfBatchJob<'_>]) -> u64 {
+    let iterations = jobs.iter().map(|job| job.num_iterations);
+    if cfg!(any(chiavdf_fallback, chiavdf_rust_fallback)) {
+        iterations.fold(0, u64::saturating_add)
+    } else {
+        iterations.max().unwrap_or(0)
+    }
+}
 
-    // SAFETY: We pass pointers + lengths for all byte slices, and we copy out
-    // the returned buffer before freeing it. The callback and context pointers
-    // live for the duration of this call.
-    unsafe {
-        take_result(
-            ffi::chiavdf_prove_one_weso_fast_streaming_getblock_opt_with_progress(
+fn prove_batch_with(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    discriminant_size_bits: usize,
+    jobs: &[ChiavdfBatchJob<'_>],
+    progress: Option<Progress<'_>>,
+) -> Result<BatchResults, ChiavdfFastError> {
+    check_batch_inputs(challenge_hash, x_s, discriminant_size_bits, jobs)?;
+    let ctx = ProgressCtx::new(progress, batch_progress_total(jobs))?;
+
+    let ffi_jobs: Vec<ffi::ChiavdfBatchJob> = jobs
+        .iter()
+        .map(|job| ffi::ChiavdfBatchJob {
+            y_ref_s: job.y_ref_s.as_ptr(),
+            y_ref_s_size: job.y_ref_s.len(),
+            num_iterations: job.num_iterations,
+        })
+        .collect();
+
+    let ptr = match ctx {
+        // SAFETY: Pointers + lengths are provided for all slices and the
+        // returned batch pointer is freed by `take_result_batch`.
+        None => unsafe {
+            ffi::chiavdf_prove_one_weso_fast_streaming_getblock_opt_batch(
                 challenge_hash.as_ptr(),
                 challenge_hash.len(),
                 x_s.as_ptr(),
                 x_s.len(),
-                y_ref_s.as_ptr(),
-                y_ref_s.len(),
                 discriminant_size_bits,
-                num_iterations,
-                progress_interval,
-                Some(progress_trampoline),
-                std::ptr::addr_of_mut!(ctx).cast::<c_void>(),
-            ),
-        )
+                ffi_jobs.as_ptr(),
+                ffi_jobs.len(),
+            )
+        },
+        Some(mut ctx) => {
+            // SAFETY: Same as above, with progress callback + context valid for the
+            // duration of the call.
+            unsafe {
+                ffi::chiavdf_prove_one_weso_fast_streaming_getblock_opt_batch_with_progress(
+                    challenge_hash.as_ptr(),
+                    challenge_hash.len(),
+                    x_s.as_ptr(),
+                    x_s.len(),
+                    discriminant_size_bits,
+                    ffi_jobs.as_ptr(),
+                    ffi_jobs.len(),
+                    ctx.check_interval,
+                    Some(progress_trampoline),
+                    ctx.user_data(),
+                )
+            }
+        }
+    };
+
+    take_result_batch(ptr, ffi_jobs.len())
+}
+
+/// Same as [`prove_one_weso_fast_streaming_getblock_opt_batch_with_progress`]
+/// (no progress when `progress_interval` is 0), but returns the parameters and
+/// stats of this batch with its outputs.
+#[deprecated(note = "use `ProverBuilder::progress` and `ProverBuilder::prove_batch_reported`")]
+pub fn prove_one_weso_fast_streaming_getblock_opt_batch_reported<F>(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    discriminant_size_bits: usize,
+    jobs: &[ChiavdfBatchJob<'_>],
+    progress_interval: u64,
+    mut progress: F,
+) -> Result<ProveReport<Vec<ProofOutput>>, ChiavdfFastError>
+where
+    F: FnMut(u64) + Send + 'static,
+{
+    let cb: &mut (dyn FnMut(u64) + Send + 'static) = &mut progress;
+    let progress = (progress_interval > 0).then_some((progress_interval, cb));
+    report(
+        prove_batch_with(challenge_hash, x_s, discriminant_size_bits, jobs, progress)?
+            .into_iter()
+            .collect::<Result<_, _>>()?,
+    )
+}
+
+/// Copy `proofs` back to back into `out`.
+fn copy_proofs_into(proofs: &[ProofOutput], out: &mut [u8]) -> Result<usize, ChiavdfFastError> {
+    let mut written = 0;
+    for proof in proofs {
+        let len = proof.y.len() + proof.witness.len();
+        let Some(dst) = out.get_mut(written..written + len) else {
+            return Err(ChiavdfFastError::UnexpectedLength(len));
+        };
+        let (y, witness) = dst.split_at_mut(proof.y.len());
+        y.copy_from_slice(&proof.y);
+        witness.copy_from_slice(&proof.witness);
+        written += len;
     }
+    Ok(written)
 }
 
-/// Compute multiple compact (witness_type=0) Wesolowski proofs in one shared
-/// squaring run (Trick 2), using:
-/// - streaming bucket accumulation (Trick 1)
-/// - precomputed `GetBlock()` mapping (GetBlock opt)
+/// Progress callback owned by a [`ProverBuilder`], with its interval in
+/// iterations.
+type BoxedProgress = (u64, Box<dyn FnMut(u64) + Send>);
+
+/// Proof configuration in one place, instead of picking among the
+/// `prove_one_weso_fast*` variants and their argument lists.
 ///
-/// Returns one `y || proof` buffer per job (same format as single-job APIs).
-pub fn prove_one_weso_fast_streaming_getblock_opt_batch(
+/// Only the challenge, starting form and discriminant size are required. By
+/// default a single proof is compact (no `y_ref`), without progress, using the
+/// `GetBlock()` optimization once streaming.
+pub struct ProverBuilder<'a> {
+    challenge_hash: &'a [u8],
+    x_s: &'a [u8],
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    y_ref_s: Option<&'a [u8]>,
+    memory_budget_bytes: Option<u64>,
+    progress: Option<BoxedProgress>,
+    streaming_stats: Option<bool>,
+    getblock_opt: bool,
+    deadline: Option<Instant>,
+}
+
+impl std::fmt::Debug for ProverBuilder<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProverBuilder")
+            .field("challenge_hash", &self.challenge_hash)
+            .field("x_s", &self.x_s)
+            .field("discriminant_size_bits", &self.discriminant_size_bits)
+            .field("num_iterations", &self.num_iterations)
+            .field("y_ref_s", &self.y_ref_s)
+            .field("memory_budget_bytes", &self.memory_budget_bytes)
+            .field(
+                "progress_interval",
+                &self.progress.as_ref().map(|(interval, _)| *interval),
+            )
+            .field("streaming_stats", &self.streaming_stats)
+            .field("getblock_opt", &self.getblock_opt)
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
+
+impl<'a> ProverBuilder<'a> {
+    /// Prove from `x_s` for `challenge_hash` with a `discriminant_size_bits`
+    /// discriminant.
+    pub fn new(challenge_hash: &'a [u8], x_s: &'a [u8], discriminant_size_bits: usize) -> Self {
+        Self {
+            challenge_hash,
+            x_s,
+            discriminant_size_bits,
+            num_iterations: 0,
+            y_ref_s: None,
+            memory_budget_bytes: None,
+            progress: None,
+            streaming_stats: None,
+            getblock_opt: true,
+            deadline: None,
+        }
+    }
+
+    /// Iterations of a single proof; batch jobs carry their own.
+    pub fn iterations(mut self, num_iterations: u64) -> Self {
+        self.num_iterations = num_iterations;
+        self
+    }
+
+    /// Stream against the known output `y_ref_s` (Trick 1) instead of computing
+    /// a plain compact proof. Batch jobs carry their own.
+    pub fn y_ref(mut self, y_ref_s: &'a [u8]) -> Self {
+        self.y_ref_s = Some(y_ref_s);
+        self
+    }
+
+    /// Set the streaming tuner's memory budget before proving; see
+    /// [`set_bucket_memory_budget_bytes`], which this applies process-wide.
+    pub fn memory_budget_bytes(mut self, bytes: u64) -> Self {
+        self.memory_budget_bytes = Some(bytes);
+        self
+    }
+
+    /// Invoke `progress` with the iterations done every `progress_interval`
+    /// iterations (subject to [`ProveOptions::with_progress_min_interval`]).
+    ///
+    /// A batch counts up to its longest job, whose squarings chiavdf shares with
+    /// the others, or to the jobs' sum with the fallback provers, which prove
+    /// them one after another.
+    pub fn progress<F>(mut self, progress_interval: u64, progress: F) -> Self
+    where
+        F: FnMut(u64) + Send + 'static,
+    {
+        self.progress = Some((progress_interval, Box::new(progress)));
+        self
+    }
+
+    /// Enable or disable streaming timing counters before proving; see
+    /// [`set_enable_streaming_stats`].
+    pub fn streaming_stats(mut self, enable: bool) -> Self {
+        self.streaming_stats = Some(enable);
+        self
+    }
+
+    /// Whether streaming proofs use the optimized `GetBlock()` (on by default).
+    /// Batches always do.
+    pub fn getblock_opt(mut self, enable: bool) -> Self {
+        self.getblock_opt = enable;
+        self
+    }
+
+    /// Refuse to start with [`ChiavdfFastError::DeadlineExceeded`] once
+    /// `deadline` has passed; see [`ProveOptions::deadline`].
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Compute the configured proof.
+    pub fn prove(mut self) -> Result<ProofOutput, ChiavdfFastError> {
+        let _deadline = self.configure()?;
+        let progress: Option<Progress<'_>> = self
+            .progress
+            .as_mut()
+            .map(|(interval, cb)| (*interval, cb.as_mut()));
+        prove_with(
+            self.challenge_hash,
+            self.x_s,
+            self.y_ref_s,
+            self.getblock_opt,
+            self.discriminant_size_bits,
+            self.num_iterations,
+            progress,
+        )
+    }
+
+    /// [`ProverBuilder::prove`], with the parameters and stats of this proof.
+    /// Only streaming proofs (with [`ProverBuilder::y_ref`]) report them.
+    pub fn prove_reported(self) -> Result<ProveReport<ProofOutput>, ChiavdfFastError> {
+        if self.y_ref_s.is_none() {
+            return Err(ChiavdfFastError::InvalidInput(
+                "reported proofs need y_ref_s",
+            ));
+        }
+        report(self.prove()?)
+    }
+
+    /// Compute `jobs` in one shared squaring run (see
+    /// [`prove_one_weso_fast_streaming_getblock_opt_batch`]), returning each
+    /// job's result in job order.
+    ///
+    /// The outer error is for a batch that failed as a whole (bad inputs, a
+    /// native failure); a job whose result the native library returned unusable
+    /// fails alone, so the other jobs' proofs can still be used.
+    pub fn prove_batch(
+        mut self,
+        jobs: &[ChiavdfBatchJob<'_>],
+    ) -> Result<Vec<Result<ProofOutput, ChiavdfFastError>>, ChiavdfFastError> {
+        if !self.getblock_opt {
+            return Err(ChiavdfFastError::InvalidInput(
+                "batch proofs always use getblock_opt",
+            ));
+        }
+        let _deadline = self.configure()?;
+        let progress: Option<Progress<'_>> = self
+            .progress
+            .as_mut()
+            .map(|(interval, cb)| (*interval, cb.as_mut()));
+        prove_batch_with(
+            self.challenge_hash,
+            self.x_s,
+            self.discriminant_size_bits,
+            jobs,
+            progress,
+        )
+    }
+
+    /// [`ProverBuilder::prove_batch`], with the parameters and stats of this
+    /// batch.
+    pub fn prove_batch_reported(
+        self,
+        jobs: &[ChiavdfBatchJob<'_>],
+    ) -> Result<ProveReport<Vec<Result<ProofOutput, ChiavdfFastError>>>, ChiavdfFastError> {
+        report(self.prove_batch(jobs)?)
+    }
+
+    /// [`ProverBuilder::prove`], writing `y || witness` into `out` instead of
+    /// returning owned buffers. Returns the bytes written, the first half being
+    /// `y`; `out` needs at least [`ProofOutput::COMPACT_SIZE`] bytes.
+    ///
+    /// The prover still allocates the result, which is copied into `out`: the
+    /// fast native library can't write into caller memory.
+    pub fn prove_into(self, out: &mut [u8]) -> Result<usize, ChiavdfFastError> {
+        if out.len() < ProofOutput::COMPACT_SIZE {
+            return Err(ChiavdfFastError::InvalidInput(
+                "output buffer is too small for the proof",
+            ));
+        }
+        let proof = self.prove()?;
+        copy_proofs_into(std::slice::from_ref(&proof), out)
+    }
+
+    /// [`ProverBuilder::prove_batch`], writing each job's `y || witness` back to
+    /// back into `out` in job order instead of returning owned buffers (see
+    /// [`ProverBuilder::prove_into`]). `out` needs at least
+    /// `jobs.len() * ProofOutput::COMPACT_SIZE` bytes.
+    pub fn prove_batch_into(
+        self,
+        jobs: &[ChiavdfBatchJob<'_>],
+        out: &mut [u8],
+    ) -> Result<usize, ChiavdfFastError> {
+        if out.len() < jobs.len().saturating_mul(ProofOutput::COMPACT_SIZE) {
+            return Err(ChiavdfFastError::InvalidInput(
+                "output buffer is too small for the batch",
+            ));
+        }
+        let proofs = self
+            .prove_batch(jobs)?
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+        copy_proofs_into(&proofs, out)
+    }
+
+    /// Apply the process-wide settings and install the deadline for the proof,
+    /// which lasts until the returned guard is dropped.
+    fn configure(&self) -> Result<DeadlineGuard, ChiavdfFastError> {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(ChiavdfFastError::DeadlineExceeded);
+        }
+        if let Some(bytes) = self.memory_budget_bytes {
+            set_bucket_memory_budget_bytes(bytes);
+        }
+        if let Some(enable) = self.streaming_stats {
+            set_enable_streaming_stats(enable);
+        }
+        Ok(DeadlineGuard::install(self.deadline))
+    }
+}
+
+/// Squaring state at the end of a batch run, used to continue a later batch on
+/// the same challenge without re-squaring from the start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchCheckpoint {
+    challenge_hash: Vec<u8>,
+    discriminant_size_bits: usize,
+    form: Vec<u8>,
+    iterations: u64,
+}
+
+impl BatchCheckpoint {
+    /// Serialized form reached after [`BatchCheckpoint::iterations`] squarings.
+    pub fn form(&self) -> &[u8] {
+        &self.form
+    }
+
+    /// Total iterations (from the original starting form) the checkpoint is at.
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+}
+
+/// Same as [`prove_one_weso_fast_streaming_getblock_opt_batch`], but also returns
+/// a [`BatchCheckpoint`] at the highest iteration count in `jobs`.
+pub fn prove_one_weso_fast_streaming_getblock_opt_batch_retaining(
     challenge_hash: &[u8],
     x_s: &[u8],
     discriminant_size_bits: usize,
     jobs: &[ChiavdfBatchJob<'_>],
-) -> Result<Vec<Vec<u8>>, ChiavdfFastError> {
-    prove_one_weso_fast_streaming_getblock_opt_batch_with_progress(
+) -> Result<(Vec<ProofOutput>, BatchCheckpoint), ChiavdfFastError> {
+    let results = prove_one_weso_fast_streaming_getblock_opt_batch(
         challenge_hash,
         x_s,
         discriminant_size_bits,
         jobs,
-        0,
-        |_| {},
-    )
+    )?;
+    let Some((last_idx, last_job)) = jobs
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, job)| job.num_iterations)
+    else {
+        return Err(ChiavdfFastError::InvalidInput("jobs must not be empty"));
+    };
+    let last = &results[last_idx];
+    let checkpoint = BatchCheckpoint {
+        challenge_hash: challenge_hash.to_vec(),
+        discriminant_size_bits,
+        form: last.y.clone(),
+        iterations: last_job.num_iterations,
+    };
+    Ok((results, checkpoint))
 }
 
-/// Same as [`prove_one_weso_fast_streaming_getblock_opt_batch`], but invokes
-/// `progress` every `progress_interval` squaring iterations completed.
-pub fn prove_one_weso_fast_streaming_getblock_opt_batch_with_progress<F>(
-    challenge_hash: &[u8],
-    x_s: &[u8],
-    discriminant_size_bits: usize,
-    jobs: &[ChiavdfBatchJob<'_>],
-    progress_interval: u64,
-    mut progress: F,
-) -> Result<Vec<Vec<u8>>, ChiavdfFastError>
-where
-    F: FnMut(u64) + Send + 'static,
-{
-    if challenge_hash.is_empty() {
+/// Continue squaring from `checkpoint` and prove `jobs` at higher iteration counts.
+///
+/// `jobs` keep their absolute `num_iterations` (which must all exceed
+/// `checkpoint.iterations()`) and expected outputs. Only the remaining
+/// iterations are squared, so each returned proof is a proof segment
+/// from `checkpoint.form()` rather than a compact proof from the original
+/// starting form; combine it with the earlier segment (e.g. as an
+/// n-Wesolowski proof) where the verifier accepts that.
+///
+/// Returns the segment results and a checkpoint at the new highest iteration.
+pub fn prove_one_weso_fast_streaming_getblock_opt_batch_from_checkpoint(
+    checkpoint: &BatchCheckpoint,
+    challenge_hash: &[u8],
+    jobs: &[ChiavdfBatchJob<'_>],
+) -> Result<(Vec<ProofOutput>, BatchCheckpoint), ChiavdfFastError> {
+    if challenge_hash != checkpoint.challenge_hash.as_slice() {
         return Err(ChiavdfFastError::InvalidInput(
-            "challenge_hash must not be empty",
+            "checkpoint was taken for a different challenge",
         ));
     }
-    if x_s.is_empty() {
-        return Err(ChiavdfFastError::InvalidInput("x_s must not be empty"));
-    }
-    if discriminant_size_bits == 0 {
+    if jobs
+        .iter()
+        .any(|job| job.num_iterations <= checkpoint.iterations)
+    {
         return Err(ChiavdfFastError::InvalidInput(
-            "discriminant_size_bits must be > 0",
+            "job num_iterations must exceed the checkpoint iterations",
         ));
     }
-    if jobs.is_empty() {
-        return Err(ChiavdfFastError::InvalidInput("jobs must not be empty"));
-    }
-    for job in jobs {
-        if job.y_ref_s.is_empty() {
-            return Err(ChiavdfFastError::InvalidInput(
-                "job y_ref_s must not be empty",
-            ));
-        }
-        if job.num_iterations == 0 {
-            return Err(ChiavdfFastError::InvalidInput(
-                "job num_iterations must be > 0",
-            ));
-        }
-    }
 
-    let ffi_jobs: Vec<ffi::ChiavdfBatchJob> = jobs
+    let relative: Vec<ChiavdfBatchJob<'_>> = jobs
         .iter()
-        .map(|job| ffi::ChiavdfBatchJob {
-            y_ref_s: job.y_ref_s.as_ptr(),
-            y_ref_s_size: job.y_ref_s.len(),
-            num_iterations: job.num_iterations,
+        .map(|job| ChiavdfBatchJob {
+            y_ref_s: job.y_ref_s,
+            num_iterations: job.num_iterations - checkpoint.iterations,
         })
         .collect();
-
-    let ptr = if progress_interval == 0 {
-        // SAFETY: Pointers + lengths are provided for all slices and the
-        // returned batch pointer is freed by `take_result_batch`.
-        unsafe {
-            ffi::chiavdf_prove_one_weso_fast_streaming_getblock_opt_batch(
-                challenge_hash.as_ptr(),
-                challenge_hash.len(),
-                x_s.as_ptr(),
-                x_s.len(),
-                discriminant_size_bits,
-                ffi_jobs.as_ptr(),
-                ffi_jobs.len(),
-            )
-        }
-    } else {
-        let cb: &mut (dyn FnMut(u64) + Send) = &mut progress;
-        let mut ctx = ProgressCtx {
-            cb: cb as *mut (dyn FnMut(u64) + Send),
-        };
-        // SAFETY: Same as above, with progress callback + context valid for the
-        // duration of the call.
-        unsafe {
-            ffi::chiavdf_prove_one_weso_fast_streaming_getblock_opt_batch_with_progress(
-                challenge_hash.as_ptr(),
-                challenge_hash.len(),
-                x_s.as_ptr(),
-                x_s.len(),
-                discriminant_size_bits,
-                ffi_jobs.as_ptr(),
-                ffi_jobs.len(),
-                progress_interval,
-                Some(progress_trampoline),
-                std::ptr::addr_of_mut!(ctx).cast::<c_void>(),
-            )
-        }
-    };
-
-    take_result_batch(ptr, ffi_jobs.len())
+    let (results, next) = prove_one_weso_fast_streaming_getblock_opt_batch_retaining(
+        challenge_hash,
+        &checkpoint.form,
+        checkpoint.discriminant_size_bits,
+        &relative,
+    )?;
+    Ok((
+        results,
+        BatchCheckpoint {
+            iterations: checkpoint.iterations + next.iterations,
+            ..next
+        },
+    ))
 }
 
 #[cfg(test)]
+// The argument-list provers stay covered until they are removed.
+#[allow(deprecated)]
 mod tests {
     use std::sync::Arc;
     use std::sync::atomic::{AtomicU64, Ordering};
 
+    use crate::chia::ClassgroupElement;
+
     use super::{
-        ChiavdfBatchJob, prove_one_weso_fast, prove_one_weso_fast_streaming,
-        prove_one_weso_fast_streaming_getblock_opt,
+        ChiavdfBatchJob, ChiavdfFastError, ProgressPhase, ProofOutput, ProveOptions, ProverBuilder,
+        ProverSession, create_discriminant, last_streaming_parameters, prove_one_weso_fast,
+        prove_one_weso_fast_streaming, prove_one_weso_fast_streaming_getblock_opt,
         prove_one_weso_fast_streaming_getblock_opt_batch,
+        prove_one_weso_fast_streaming_getblock_opt_batch_from_checkpoint,
+        prove_one_weso_fast_streaming_getblock_opt_batch_reported,
+        prove_one_weso_fast_streaming_getblock_opt_batch_retaining,
         prove_one_weso_fast_streaming_getblock_opt_batch_with_progress,
+        prove_one_weso_fast_streaming_getblock_opt_reported,
         prove_one_weso_fast_streaming_getblock_opt_with_progress,
         prove_one_weso_fast_streaming_with_progress, prove_one_weso_fast_with_progress,
+        run_with_progress_info,
     };
 
     const TEST_DISCRIMINANT_BITS: usize = 1024;
+    const LARGE_DISCRIMINANT_BITS: usize = 2048;
     const TEST_CHALLENGE: [u8; 32] = [
         0x62, 0x62, 0x72, 0x2d, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2d, 0x66, 0x66, 0x69, 0x2d,
         0x74, 0x65, 0x73, 0x74, 0x2d, 0x76, 0x31, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
         0x08, 0x09,
     ];
 
-    fn default_classgroup_element() -> [u8; 100] {
-        let mut element = [0u8; 100];
-        element[0] = 0x08;
-        element
-    }
-
-    fn split_y_and_witness(result: &[u8]) -> (&[u8], &[u8]) {
-        let half = result.len() / 2;
-        (&result[..half], &result[half..])
+    fn split_y_and_witness(result: &ProofOutput) -> (&[u8], &[u8]) {
+        (&result.y, &result.witness)
     }
 
     fn estimate_bucket_form_bytes(discriminant_size_bits: usize) -> u64 {
@@ -707,9 +1912,8 @@ mod tests {
                 let checkpoints = u128::from(num_iterations.div_ceil(kl));
                 let fold = u128::from(l) << (k + 1);
 
-                let cost = updates * UPDATE_WEIGHT
-                    + checkpoints * CHECKPOINT_WEIGHT
-                    + fold * FOLD_WEIGHT;
+                let cost =
+                    updates * UPDATE_WEIGHT + checkpoints * CHECKPOINT_WEIGHT + fold * FOLD_WEIGHT;
                 if best.is_none() || cost < best_cost {
                     best_cost = cost;
                     best = Some((k, l));
@@ -746,9 +1950,251 @@ mod tests {
         }
     }
 
+    #[test]
+    fn create_discriminant_is_deterministic() {
+        assert!(matches!(
+            create_discriminant(&[], TEST_DISCRIMINANT_BITS),
+            Err(ChiavdfFastError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            create_discriminant(&TEST_CHALLENGE, TEST_DISCRIMINANT_BITS + 1),
+            Err(ChiavdfFastError::InvalidInput(_))
+        ));
+
+        let d = create_discriminant(&TEST_CHALLENGE, TEST_DISCRIMINANT_BITS).expect("discriminant");
+        assert_eq!(d.len(), TEST_DISCRIMINANT_BITS / 8);
+        assert_ne!(
+            d[0] & 0x80,
+            0,
+            "discriminant should use all {TEST_DISCRIMINANT_BITS} bits"
+        );
+        assert_eq!(
+            create_discriminant(&TEST_CHALLENGE, TEST_DISCRIMINANT_BITS).expect("discriminant"),
+            d
+        );
+
+        let mut other = TEST_CHALLENGE;
+        other[0] ^= 1;
+        assert_ne!(
+            create_discriminant(&other, TEST_DISCRIMINANT_BITS).expect("discriminant"),
+            d
+        );
+    }
+
+    #[test]
+    fn deadline_refuses_late_proofs() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let later = std::time::Instant::now() + std::time::Duration::from_secs(3600);
+        let ok = ProverBuilder::new(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS)
+            .iterations(1_024)
+            .deadline(later)
+            .prove();
+        assert!(ok.is_ok());
+
+        let passed = std::time::Instant::now();
+        let late = ProverBuilder::new(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS)
+            .iterations(1_024)
+            .deadline(passed)
+            .prove();
+        assert!(matches!(late, Err(ChiavdfFastError::DeadlineExceeded)));
+        let late = ProveOptions::default()
+            .with_deadline(passed)
+            .run(|| prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 1_024));
+        assert!(matches!(late, Err(ChiavdfFastError::DeadlineExceeded)));
+    }
+
+    #[test]
+    fn progress_info_reports_squaring_then_finalize() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let prove = || {
+            prove_one_weso_fast_with_progress(
+                &TEST_CHALLENGE,
+                &x_s,
+                TEST_DISCRIMINANT_BITS,
+                4_096,
+                1_024,
+                |_| {},
+            )
+        };
+        let infos = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = infos.clone();
+        run_with_progress_info(move |info| recorded.lock().unwrap().push(info), prove)
+            .expect("proof");
+        // Nothing is reported once the callback is uninstalled.
+        prove().expect("proof");
+
+        let infos = infos.lock().unwrap();
+        let (squaring, finalize): (Vec<&super::ProgressInfo>, Vec<&super::ProgressInfo>) = infos
+            .iter()
+            .partition(|info| info.phase == ProgressPhase::Squaring);
+        assert_eq!(squaring.last().map(|info| info.iters_done), Some(4_096));
+        assert!(
+            squaring
+                .windows(2)
+                .all(|pair| pair[0].iters_done < pair[1].iters_done)
+        );
+        assert_eq!(
+            finalize
+                .iter()
+                .map(|info| info.iters_done)
+                .collect::<Vec<_>>(),
+            [4_096]
+        );
+        assert_eq!(
+            infos.last().map(|info| info.phase),
+            Some(ProgressPhase::Finalize)
+        );
+    }
+
+    #[test]
+    fn progress_min_interval_drops_all_but_the_final_update() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = updates.clone();
+        ProveOptions::default()
+            .with_progress_min_interval(std::time::Duration::from_secs(3_600))
+            .run(|| {
+                prove_one_weso_fast_with_progress(
+                    &TEST_CHALLENGE,
+                    &x_s,
+                    TEST_DISCRIMINANT_BITS,
+                    4_096,
+                    1,
+                    move |iters_done| recorded.lock().unwrap().push(iters_done),
+                )
+            })
+            .expect("proof");
+        assert_eq!(*updates.lock().unwrap(), vec![4_096]);
+    }
+
+    #[test]
+    fn pinned_proofs_match_unpinned_ones() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let reference = prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 1_024)
+            .expect("reference proof should succeed");
+
+        let allowed = bbr_client_affinity::current_thread_affinity().expect("affinity");
+        let cpu = allowed.first().copied().unwrap_or(0);
+        let (pinned, affinity) = ProveOptions::pinned([cpu]).run(|| {
+            (
+                prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 1_024),
+                bbr_client_affinity::current_thread_affinity().expect("affinity"),
+            )
+        });
+        assert_eq!(pinned.expect("pinned proof should succeed"), reference);
+        if cfg!(target_os = "linux") {
+            assert_eq!(affinity, [cpu]);
+        }
+        assert_eq!(
+            bbr_client_affinity::current_thread_affinity().expect("affinity"),
+            allowed
+        );
+
+        let unpinned = ProveOptions::default()
+            .run(|| prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 1_024));
+        assert_eq!(unpinned.expect("unpinned proof should succeed"), reference);
+    }
+
+    #[test]
+    fn prover_session_waits_for_resume() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let reference = prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 1_024)
+            .expect("reference proof should succeed");
+
+        let session = ProverSession::new();
+        session.pause();
+        let resumer = {
+            let session = session.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                session.resume();
+            })
+        };
+        let resumed = session
+            .run(|| prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 1_024))
+            .expect("resumed proof should succeed");
+        resumer.join().expect("resumer thread");
+        assert_eq!(resumed, reference);
+    }
+
+    #[test]
+    fn prover_session_pauses_a_running_proof() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let session = ProverSession::new();
+        let resumed_at = Arc::new(std::sync::Mutex::new(None));
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (session_for_cb, resumed_for_cb, recorded) =
+            (session.clone(), resumed_at.clone(), updates.clone());
+        let proof = session.run(|| {
+            prove_one_weso_fast_with_progress(
+                &TEST_CHALLENGE,
+                &x_s,
+                TEST_DISCRIMINANT_BITS,
+                4_096,
+                1_024,
+                move |iters_done| {
+                    recorded
+                        .lock()
+                        .unwrap()
+                        .push((iters_done, std::time::Instant::now()));
+                    if iters_done == 1_024 {
+                        session_for_cb.pause();
+                        let (session, resumed_at) =
+                            (session_for_cb.clone(), resumed_for_cb.clone());
+                        std::thread::spawn(move || {
+                            std::thread::sleep(std::time::Duration::from_millis(100));
+                            *resumed_at.lock().unwrap() = Some(std::time::Instant::now());
+                            session.resume();
+                        });
+                    }
+                },
+            )
+        });
+        proof.expect("paused proof should finish once resumed");
+
+        let resumed_at = resumed_at.lock().unwrap().expect("session was resumed");
+        let updates = updates.lock().unwrap();
+        assert_eq!(
+            updates.iter().map(|(iters, _)| *iters).collect::<Vec<_>>(),
+            [1_024, 2_048, 3_072, 4_096]
+        );
+        assert!(updates[1..].iter().all(|(_, at)| *at >= resumed_at));
+    }
+
+    #[test]
+    fn n_weso_segments_chain_to_the_compact_output() {
+        use super::prove_n_weso_fast;
+        use crate::chia::{CLASSGROUP_ELEMENT_SIZE, N_WESO_SEGMENT_SIZE, proof_witness_type};
+
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let compact = prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 3_000)
+            .expect("compact proof should succeed");
+        assert_eq!(
+            prove_n_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 3_000, 0)
+                .expect("witness_type 0 is the compact proof"),
+            compact
+        );
+        assert!(matches!(
+            prove_n_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 2, 2),
+            Err(ChiavdfFastError::InvalidInput(_))
+        ));
+        let out = prove_n_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 3_000, 2)
+            .expect("n-wesolowski proof should succeed");
+        assert_eq!(proof_witness_type(&out.to_bytes()).expect("layout"), 2);
+        assert_eq!(out.y.len(), CLASSGROUP_ELEMENT_SIZE);
+        assert_eq!(out.y, compact.y, "final y covers all iterations");
+
+        // Segments follow the final proof, last segment first.
+        let first = &out.witness[out.witness.len() - N_WESO_SEGMENT_SIZE..];
+        assert_eq!(&first[..8], &1_000u64.to_be_bytes());
+        let reference = prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 1_000)
+            .expect("segment reference should succeed");
+        assert_eq!(&first[8..], reference.to_bytes().as_slice());
+    }
+
     #[test]
     fn streaming_getblock_opt_matches_reference_y() {
-        let x_s = default_classgroup_element();
+        let x_s = ClassgroupElement::generator().to_bytes();
         let num_iterations = 1_024;
 
         let base = prove_one_weso_fast(
@@ -777,10 +2223,10 @@ mod tests {
 
     #[test]
     fn batch_getblock_opt_matches_reference_ys() {
-        let x_s = default_classgroup_element();
+        let x_s = ClassgroupElement::generator().to_bytes();
         let iterations = [640_u64, 1_280_u64];
 
-        let single_results: Vec<Vec<u8>> = iterations
+        let single_results: Vec<ProofOutput> = iterations
             .into_iter()
             .map(|num_iterations| {
                 prove_one_weso_fast(
@@ -821,9 +2267,188 @@ mod tests {
         }
     }
 
+    #[test]
+    fn reported_provers_return_their_own_parameters() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let iterations = [640_u64, 4_096_u64];
+        let y_refs: Vec<Vec<u8>> = iterations
+            .into_iter()
+            .map(|num_iterations| {
+                let base = prove_one_weso_fast(
+                    &TEST_CHALLENGE,
+                    &x_s,
+                    TEST_DISCRIMINANT_BITS,
+                    num_iterations,
+                )
+                .expect("single proof should succeed");
+                split_y_and_witness(&base).0.to_vec()
+            })
+            .collect();
+
+        let small = prove_one_weso_fast_streaming_getblock_opt_reported(
+            &TEST_CHALLENGE,
+            &x_s,
+            &y_refs[0],
+            TEST_DISCRIMINANT_BITS,
+            iterations[0],
+        )
+        .expect("reported proof should succeed");
+        assert_eq!(split_y_and_witness(&small.output).0, y_refs[0].as_slice());
+        assert!(small.parameters.k > 0 && small.parameters.l > 0);
+
+        // A proof on another thread leaves this thread's report untouched.
+        let other = std::thread::spawn({
+            let x_s = x_s.clone();
+            let y_ref = y_refs[1].clone();
+            move || {
+                prove_one_weso_fast_streaming_getblock_opt_reported(
+                    &TEST_CHALLENGE,
+                    &x_s,
+                    &y_ref,
+                    TEST_DISCRIMINANT_BITS,
+                    iterations[1],
+                )
+                .expect("reported proof should succeed")
+            }
+        })
+        .join()
+        .unwrap();
+        assert_eq!(split_y_and_witness(&other.output).0, y_refs[1].as_slice());
+        assert_eq!(last_streaming_parameters(), Some(small.parameters));
+
+        let jobs: Vec<ChiavdfBatchJob<'_>> = y_refs
+            .iter()
+            .zip(iterations)
+            .map(|(y_ref, num_iterations)| ChiavdfBatchJob {
+                y_ref_s: y_ref,
+                num_iterations,
+            })
+            .collect();
+        let batch = prove_one_weso_fast_streaming_getblock_opt_batch_reported(
+            &TEST_CHALLENGE,
+            &x_s,
+            TEST_DISCRIMINANT_BITS,
+            &jobs,
+            0,
+            |_| {},
+        )
+        .expect("reported batch should succeed");
+        assert_eq!(batch.output.len(), jobs.len());
+        assert!(batch.parameters.k > 0 && batch.parameters.l > 0);
+    }
+
+    #[test]
+    fn streaming_and_batch_match_reference_with_2048_bit_discriminant() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let iterations = [640_u64, 1_280_u64];
+
+        let single_results: Vec<ProofOutput> = iterations
+            .into_iter()
+            .map(|num_iterations| {
+                prove_one_weso_fast(
+                    &TEST_CHALLENGE,
+                    &x_s,
+                    LARGE_DISCRIMINANT_BITS,
+                    num_iterations,
+                )
+                .expect("2048-bit single proof should succeed")
+            })
+            .collect();
+
+        let small = prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 640)
+            .expect("1024-bit single proof should succeed");
+        assert_ne!(
+            split_y_and_witness(&small).0,
+            split_y_and_witness(&single_results[0]).0,
+            "discriminant size must reach the prover"
+        );
+
+        for (single, num_iterations) in single_results.iter().zip(iterations) {
+            let (y_ref, _) = split_y_and_witness(single);
+            let streaming = prove_one_weso_fast_streaming_getblock_opt(
+                &TEST_CHALLENGE,
+                &x_s,
+                y_ref,
+                LARGE_DISCRIMINANT_BITS,
+                num_iterations,
+            )
+            .expect("2048-bit streaming proof should succeed");
+            let (stream_y, stream_witness) = split_y_and_witness(&streaming);
+            assert_eq!(stream_y, y_ref, "T={num_iterations}");
+            assert!(!stream_witness.is_empty());
+        }
+
+        let jobs: Vec<ChiavdfBatchJob<'_>> = single_results
+            .iter()
+            .zip(iterations)
+            .map(|(single, num_iterations)| ChiavdfBatchJob {
+                y_ref_s: split_y_and_witness(single).0,
+                num_iterations,
+            })
+            .collect();
+        let batch = prove_one_weso_fast_streaming_getblock_opt_batch(
+            &TEST_CHALLENGE,
+            &x_s,
+            LARGE_DISCRIMINANT_BITS,
+            &jobs,
+        )
+        .expect("2048-bit batch proof should succeed");
+        assert_eq!(batch.len(), jobs.len());
+        for (out, job) in batch.iter().zip(jobs.iter()) {
+            let (y, witness) = split_y_and_witness(out);
+            assert_eq!(y, job.y_ref_s, "T={}", job.num_iterations);
+            assert!(!witness.is_empty());
+        }
+    }
+
+    #[test]
+    fn batch_checkpoint_continuation_matches_reference_y() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let reference: Vec<ProofOutput> = [640_u64, 1_280_u64]
+            .into_iter()
+            .map(|num_iterations| {
+                prove_one_weso_fast(
+                    &TEST_CHALLENGE,
+                    &x_s,
+                    TEST_DISCRIMINANT_BITS,
+                    num_iterations,
+                )
+                .expect("single proof should succeed")
+            })
+            .collect();
+        let y_640 = split_y_and_witness(&reference[0]).0;
+        let y_1280 = split_y_and_witness(&reference[1]).0;
+
+        let (first, checkpoint) = prove_one_weso_fast_streaming_getblock_opt_batch_retaining(
+            &TEST_CHALLENGE,
+            &x_s,
+            TEST_DISCRIMINANT_BITS,
+            &[ChiavdfBatchJob {
+                y_ref_s: y_640,
+                num_iterations: 640,
+            }],
+        )
+        .expect("batch proof should succeed");
+        assert_eq!(split_y_and_witness(&first[0]).0, y_640);
+        assert_eq!(checkpoint.iterations(), 640);
+        assert_eq!(checkpoint.form(), y_640);
+
+        let (second, next) = prove_one_weso_fast_streaming_getblock_opt_batch_from_checkpoint(
+            &checkpoint,
+            &TEST_CHALLENGE,
+            &[ChiavdfBatchJob {
+                y_ref_s: y_1280,
+                num_iterations: 1_280,
+            }],
+        )
+        .expect("continuation should succeed");
+        assert_eq!(split_y_and_witness(&second[0]).0, y_1280);
+        assert_eq!(next.iterations(), 1_280);
+    }
+
     #[test]
     fn progress_variants_and_streaming_modes_match_reference_y() {
-        let x_s = default_classgroup_element();
+        let x_s = ClassgroupElement::generator().to_bytes();
         let num_iterations = 1_024_u64;
 
         let base = prove_one_weso_fast(
@@ -932,4 +2557,198 @@ mod tests {
         assert_eq!(split_y_and_witness(&batch_with_progress[0]).0, y_ref);
         assert!(batch_progress_calls.load(Ordering::Relaxed) > 0);
     }
+
+    #[test]
+    fn prover_builder_matches_argument_list_provers() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let num_iterations = 1_024_u64;
+        let base = prove_one_weso_fast(
+            &TEST_CHALLENGE,
+            &x_s,
+            TEST_DISCRIMINANT_BITS,
+            num_iterations,
+        )
+        .expect("single proof should succeed");
+
+        let compact = ProverBuilder::new(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS)
+            .iterations(num_iterations)
+            .prove()
+            .expect("builder compact proof should succeed");
+        assert_eq!(compact, base);
+
+        let progress_last = Arc::new(AtomicU64::new(0));
+        let streaming = ProverBuilder::new(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS)
+            .iterations(num_iterations)
+            .y_ref(&base.y)
+            .progress(128, {
+                let last = Arc::clone(&progress_last);
+                move |iters_done| last.store(iters_done, Ordering::Relaxed)
+            })
+            .prove_reported()
+            .expect("builder streaming proof should succeed");
+        assert_eq!(streaming.output.y, base.y);
+        assert!(progress_last.load(Ordering::Relaxed) > 0);
+        let expected = prove_one_weso_fast_streaming_getblock_opt(
+            &TEST_CHALLENGE,
+            &x_s,
+            &base.y,
+            TEST_DISCRIMINANT_BITS,
+            num_iterations,
+        )
+        .expect("streaming proof should succeed");
+        assert_eq!(streaming.output, expected);
+
+        let jobs = [ChiavdfBatchJob {
+            y_ref_s: &base.y,
+            num_iterations,
+        }];
+        let batch = ProverBuilder::new(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS)
+            .prove_batch(&jobs)
+            .expect("builder batch should succeed");
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].as_ref().expect("builder batch job").y, base.y);
+
+        assert!(matches!(
+            ProverBuilder::new(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS)
+                .iterations(num_iterations)
+                .prove_reported(),
+            Err(ChiavdfFastError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            ProverBuilder::new(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS)
+                .getblock_opt(false)
+                .prove_batch(&jobs),
+            Err(ChiavdfFastError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn take_result_rejects_null_buffers() {
+        let null = super::ffi::ChiavdfByteArray {
+            data: std::ptr::null_mut(),
+            length: 0,
+        };
+        assert!(matches!(
+            super::take_result(null),
+            Err(ChiavdfFastError::NativeFailure(_))
+        ));
+        for count in [0, 1, 3] {
+            assert!(matches!(
+                super::take_result_batch(std::ptr::null_mut(), count),
+                Err(ChiavdfFastError::NativeFailure(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn y_ref_of_the_wrong_size_is_rejected_up_front() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let short = [0x08u8; 3];
+
+        let err = prove_one_weso_fast_streaming_getblock_opt(
+            &TEST_CHALLENGE,
+            &x_s,
+            &short,
+            TEST_DISCRIMINANT_BITS,
+            640,
+        )
+        .expect_err("a truncated y_ref cannot match");
+        assert!(
+            matches!(err, ChiavdfFastError::InvalidYRef(_)),
+            "unexpected error: {err}"
+        );
+        assert!(!err.is_retryable());
+
+        let jobs = [ChiavdfBatchJob {
+            y_ref_s: &short,
+            num_iterations: 640,
+        }];
+        let err = prove_one_weso_fast_streaming_getblock_opt_batch(
+            &TEST_CHALLENGE,
+            &x_s,
+            TEST_DISCRIMINANT_BITS,
+            &jobs,
+        )
+        .expect_err("a truncated y_ref cannot match");
+        assert!(
+            matches!(err, ChiavdfFastError::InvalidYRef(_)),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn batch_with_adversarial_jobs_fails_cleanly() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let reference = prove_one_weso_fast(&TEST_CHALLENGE, &x_s, TEST_DISCRIMINANT_BITS, 640)
+            .expect("single proof should succeed");
+        let (y_ref, _) = split_y_and_witness(&reference);
+        let garbage = [0xffu8; 100];
+        let short = [0x08u8; 3];
+
+        let cases: [&[ChiavdfBatchJob<'_>]; 4] = [
+            // Wrong expected output after a valid job: the batch result is freed
+            // as a whole, never returned partially filled.
+            &[
+                ChiavdfBatchJob {
+                    y_ref_s: y_ref,
+                    num_iterations: 640,
+                },
+                ChiavdfBatchJob {
+                    y_ref_s: &garbage,
+                    num_iterations: 1_280,
+                },
+            ],
+            // Truncated expected output.
+            &[ChiavdfBatchJob {
+                y_ref_s: &short,
+                num_iterations: 640,
+            }],
+            // Targets out of iteration order.
+            &[
+                ChiavdfBatchJob {
+                    y_ref_s: &garbage,
+                    num_iterations: 1_280,
+                },
+                ChiavdfBatchJob {
+                    y_ref_s: y_ref,
+                    num_iterations: 640,
+                },
+            ],
+            // The same target twice.
+            &[
+                ChiavdfBatchJob {
+                    y_ref_s: y_ref,
+                    num_iterations: 640,
+                },
+                ChiavdfBatchJob {
+                    y_ref_s: y_ref,
+                    num_iterations: 640,
+                },
+            ],
+        ];
+        for jobs in cases {
+            match prove_one_weso_fast_streaming_getblock_opt_batch(
+                &TEST_CHALLENGE,
+                &x_s,
+                TEST_DISCRIMINANT_BITS,
+                jobs,
+            ) {
+                Ok(outputs) => {
+                    assert_eq!(outputs.len(), jobs.len());
+                    for out in outputs {
+                        assert_eq!(out.y.len(), out.witness.len());
+                    }
+                }
+                Err(err) => assert!(
+                    matches!(
+                        err,
+                        ChiavdfFastError::NativeFailure(_)
+                            | ChiavdfFastError::InvalidYRef(_)
+                            | ChiavdfFastError::UnexpectedLength(_)
+                    ),
+                    "unexpected error: {err}"
+                ),
+            }
+        }
+    }
 }
@@ -0,0 +1,146 @@
+//! Async wrappers that run proofs on tokio's blocking pool (`tokio` feature).
+//!
+//! Each call returns a [`ProofTask`] to await plus a `watch` receiver holding the
+//! number of iterations done so far, so callers don't need their own
+//! `spawn_blocking` and progress plumbing.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use ::tokio::sync::watch;
+use ::tokio::task::JoinHandle;
+
+use super::{ChiavdfFastError, ProofOutput, ProverBuilder, ProverSession};
+
+/// A proof running on the blocking pool; resolves to its [`ProofOutput`].
+///
+/// The proof can't be stopped: dropping the task before it completes detaches
+/// it, resuming it if paused so its thread is released once it finishes.
+#[derive(Debug)]
+pub struct ProofTask {
+    handle: JoinHandle<Result<ProofOutput, ChiavdfFastError>>,
+    session: ProverSession,
+}
+
+impl ProofTask {
+    fn spawn(
+        prove: impl FnOnce() -> Result<ProofOutput, ChiavdfFastError> + Send + 'static,
+    ) -> Self {
+        let session = ProverSession::new();
+        let session_for_task = session.clone();
+        let handle = ::tokio::task::spawn_blocking(move || session_for_task.run(prove));
+        Self { handle, session }
+    }
+
+    /// Session controlling the proof (pause, resume).
+    pub fn session(&self) -> &ProverSession {
+        &self.session
+    }
+}
+
+impl Future for ProofTask {
+    type Output = Result<ProofOutput, ChiavdfFastError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // A panicked or aborted blocking task produced no proof.
+        Pin::new(&mut self.get_mut().handle).poll(cx).map(|res| {
+            res.unwrap_or_else(|err| {
+                Err(ChiavdfFastError::NativeFailure(format!(
+                    "proving task failed: {err}"
+                )))
+            })
+        })
+    }
+}
+
+impl Drop for ProofTask {
+    fn drop(&mut self) {
+        if !self.handle.is_finished() {
+            self.session.resume();
+        }
+    }
+}
+
+/// Async [`super::prove_one_weso_fast`].
+///
+/// The receiver is updated every `progress_interval` iterations; `0` disables
+/// progress updates. Must be called from within a tokio runtime.
+pub fn prove_one_weso_fast_async(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress_interval: u64,
+) -> (ProofTask, watch::Receiver<u64>) {
+    let (progress_tx, progress_rx) = watch::channel(0);
+    let challenge_hash = challenge_hash.to_vec();
+    let x_s = x_s.to_vec();
+    let task = ProofTask::spawn(move || {
+        let prover = ProverBuilder::new(&challenge_hash, &x_s, discriminant_size_bits)
+            .iterations(num_iterations);
+        with_progress(prover, progress_interval, progress_tx).prove()
+    });
+    (task, progress_rx)
+}
+
+/// Async [`super::prove_one_weso_fast_streaming_getblock_opt`].
+///
+/// Same progress and runtime rules as [`prove_one_weso_fast_async`].
+pub fn prove_one_weso_fast_streaming_getblock_opt_async(
+    challenge_hash: &[u8],
+    x_s: &[u8],
+    y_ref_s: &[u8],
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress_interval: u64,
+) -> (ProofTask, watch::Receiver<u64>) {
+    let (progress_tx, progress_rx) = watch::channel(0);
+    let challenge_hash = challenge_hash.to_vec();
+    let x_s = x_s.to_vec();
+    let y_ref_s = y_ref_s.to_vec();
+    let task = ProofTask::spawn(move || {
+        let prover = ProverBuilder::new(&challenge_hash, &x_s, discriminant_size_bits)
+            .iterations(num_iterations)
+            .y_ref(&y_ref_s);
+        with_progress(prover, progress_interval, progress_tx).prove()
+    });
+    (task, progress_rx)
+}
+
+/// Publish `prover`'s progress to `progress_tx` every `progress_interval`
+/// iterations; `0` leaves it without progress.
+fn with_progress(
+    prover: ProverBuilder<'_>,
+    progress_interval: u64,
+    progress_tx: watch::Sender<u64>,
+) -> ProverBuilder<'_> {
+    if progress_interval == 0 {
+        return prover;
+    }
+    prover.progress(progress_interval, move |iters_done| {
+        progress_tx.send_replace(iters_done);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::prove_one_weso_fast;
+    use crate::chia::ClassgroupElement;
+
+    use super::prove_one_weso_fast_async;
+
+    const TEST_CHALLENGE: [u8; 32] = [0x5a; 32];
+
+    #[::tokio::test]
+    async fn async_proof_matches_blocking_and_reports_progress() {
+        let x_s = ClassgroupElement::generator().to_bytes();
+        let expected =
+            prove_one_weso_fast(&TEST_CHALLENGE, &x_s, 1024, 1_024).expect("blocking proof");
+
+        let (task, progress) = prove_one_weso_fast_async(&TEST_CHALLENGE, &x_s, 1024, 1_024, 256);
+        let out = task.await.expect("async proof");
+        assert_eq!(out, expected);
+        assert!(*progress.borrow() > 0);
+    }
+}
@@ -0,0 +1,371 @@
+//! Chia protocol serialization of prover output.
+//!
+//! The prover returns a [`ProofOutput`] (`y` and its witness). Chia nodes expect
+//! the proof as a streamable `VDFProof` and the claimed output as part of a
+//! `VDFInfo`; these helpers produce the exact byte layout so integrators don't
+//! have to.
+
+use crate::api::{ChiavdfFastError, ProofOutput};
+
+/// Size of a serialized `ClassgroupElement` (1024-bit discriminants).
+pub const CLASSGROUP_ELEMENT_SIZE: usize = 100;
+
+/// Witness type of a compact (single-segment) Wesolowski proof.
+pub const WITNESS_TYPE_COMPACT: u8 = 0;
+
+/// Size of one n-Wesolowski witness segment: `u64be iterations || y_i || proof_i`.
+pub const N_WESO_SEGMENT_SIZE: usize = 8 + 2 * CLASSGROUP_ELEMENT_SIZE;
+
+/// Compressed-form flag bits (chiavdf `bqfc`): sign of `b`, sign of `t`, and the
+/// special encodings of the identity `(1, 1)` and generator `(2, 1)` forms.
+const FLAG_B_SIGN: u8 = 0x01;
+const FLAG_T_SIGN: u8 = 0x02;
+const FLAG_IS_IDENTITY: u8 = 0x04;
+const FLAG_IS_GENERATOR: u8 = 0x08;
+const KNOWN_FLAGS: u8 = FLAG_B_SIGN | FLAG_T_SIGN | FLAG_IS_IDENTITY | FLAG_IS_GENERATOR;
+
+/// A serialized (compressed) Chia `ClassgroupElement`, e.g. a proof input `x` or
+/// output `y`.
+///
+/// Construction checks the encoding's structure (flags, special-form padding,
+/// `g` size), so malformed `(a, b)` encodings are rejected before they reach the
+/// native prover; the native layer still does the full decompression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClassgroupElement([u8; CLASSGROUP_ELEMENT_SIZE]);
+
+impl ClassgroupElement {
+    /// The generator form `(2, 1)`: the starting element of bluebox proofs.
+    pub const fn generator() -> Self {
+        Self::special(FLAG_IS_GENERATOR)
+    }
+
+    /// The identity form `(1, 1)`.
+    pub const fn identity() -> Self {
+        Self::special(FLAG_IS_IDENTITY)
+    }
+
+    const fn special(flag: u8) -> Self {
+        let mut bytes = [0u8; CLASSGROUP_ELEMENT_SIZE];
+        bytes[0] = flag;
+        Self(bytes)
+    }
+
+    /// Parse a serialized element, rejecting structurally malformed encodings.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ChiavdfFastError> {
+        let bytes: [u8; CLASSGROUP_ELEMENT_SIZE] = bytes
+            .try_into()
+            .map_err(|_| ChiavdfFastError::InvalidInput("classgroup element must be 100 bytes"))?;
+        let flags = bytes[0];
+        if flags & !KNOWN_FLAGS != 0 {
+            return Err(ChiavdfFastError::InvalidInput(
+                "classgroup element has unknown flag bits",
+            ));
+        }
+        if flags & (FLAG_IS_IDENTITY | FLAG_IS_GENERATOR) != 0 {
+            let single_flag = flags == FLAG_IS_IDENTITY || flags == FLAG_IS_GENERATOR;
+            if !single_flag || bytes[1..].iter().any(|&b| b != 0) {
+                return Err(ChiavdfFastError::InvalidInput(
+                    "malformed identity/generator encoding",
+                ));
+            }
+        } else if !g_size_fits(bytes[1], CLASSGROUP_DISCRIMINANT_BITS) {
+            return Err(ChiavdfFastError::InvalidInput(
+                "classgroup element g size out of range",
+            ));
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Serialized bytes (always [`CLASSGROUP_ELEMENT_SIZE`] long).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+
+    /// Borrow the serialized bytes, as taken by the prove functions (`x_s`, `y_ref_s`).
+    pub fn as_bytes(&self) -> &[u8; CLASSGROUP_ELEMENT_SIZE] {
+        &self.0
+    }
+
+    /// Whether the encoding can describe a form of a discriminant with
+    /// `discriminant_size_bits` bits (the compressed `g` must fit).
+    pub fn is_valid_for_discriminant(&self, discriminant_size_bits: usize) -> bool {
+        if discriminant_size_bits == 0 {
+            return false;
+        }
+        let flags = self.0[0];
+        flags & (FLAG_IS_IDENTITY | FLAG_IS_GENERATOR) != 0
+            || g_size_fits(self.0[1], discriminant_size_bits)
+    }
+}
+
+impl Default for ClassgroupElement {
+    fn default() -> Self {
+        Self::generator()
+    }
+}
+
+impl AsRef<[u8]> for ClassgroupElement {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for ClassgroupElement {
+    type Error = ChiavdfFastError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+/// Discriminant size [`CLASSGROUP_ELEMENT_SIZE`] is laid out for.
+const CLASSGROUP_DISCRIMINANT_BITS: usize = 1024;
+
+/// Loose bound on the stored `g` size byte: `g` never needs more than half the
+/// discriminant's bits.
+fn g_size_fits(g_size: u8, discriminant_size_bits: usize) -> bool {
+    let d_bits = discriminant_size_bits.div_ceil(32) * 32;
+    usize::from(g_size) < d_bits / 16
+}
+
+/// Chia `VDFProof`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VdfProof {
+    /// Number of intermediate segments (0 for compact proofs).
+    pub witness_type: u8,
+    /// Serialized witness form(s).
+    pub witness: Vec<u8>,
+    /// Whether the proof starts from the identity element (true for bluebox proofs).
+    pub normalized_to_identity: bool,
+}
+
+impl VdfProof {
+    /// Streamable serialization: `u8 witness_type || u32be len || witness || u8 flag`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(1 + 4 + self.witness.len() + 1);
+        out.push(self.witness_type);
+        out.extend_from_slice(&(self.witness.len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.witness);
+        out.push(u8::from(self.normalized_to_identity));
+        out
+    }
+}
+
+/// Chia `VDFInfo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VdfInfo {
+    /// Challenge hash the discriminant is derived from.
+    pub challenge: [u8; 32],
+    /// VDF iteration count.
+    pub number_of_iterations: u64,
+    /// Serialized output `ClassgroupElement` (`y`).
+    pub output: [u8; CLASSGROUP_ELEMENT_SIZE],
+}
+
+impl VdfInfo {
+    /// Streamable serialization: `challenge || u64be iterations || output`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(32 + 8 + CLASSGROUP_ELEMENT_SIZE);
+        out.extend_from_slice(&self.challenge);
+        out.extend_from_slice(&self.number_of_iterations.to_be_bytes());
+        out.extend_from_slice(&self.output);
+        out
+    }
+}
+
+/// Witness type (number of intermediate segments) of a raw prover result (see
+/// [`ProofOutput::to_bytes`]): `0` for compact `y || proof`, `n` for an
+/// n-Wesolowski `y || proof || n segments` (see [`crate::api::prove_n_weso_fast`]).
+pub fn proof_witness_type(result: &[u8]) -> Result<u8, ChiavdfFastError> {
+    result
+        .len()
+        .checked_sub(2 * CLASSGROUP_ELEMENT_SIZE)
+        .filter(|segments| segments % N_WESO_SEGMENT_SIZE == 0)
+        .and_then(|segments| u8::try_from(segments / N_WESO_SEGMENT_SIZE).ok())
+        .ok_or(ChiavdfFastError::UnexpectedLength(result.len()))
+}
+
+/// Split a raw compact or n-Wesolowski prover result into `(y, witness)`.
+///
+/// The witness is everything after `y` (the proof, then any segments), as Chia's
+/// `VDFProof` carries it.
+pub fn split_proof_output(result: &[u8]) -> Result<(&[u8], &[u8]), ChiavdfFastError> {
+    proof_witness_type(result)?;
+    Ok(result.split_at(CLASSGROUP_ELEMENT_SIZE))
+}
+
+/// Build the Chia `VDFInfo` and compact `VDFProof` for a prover result.
+///
+/// `normalized_to_identity` should be `true` when the proof was computed from the
+/// default (identity) element, as bluebox compact proofs are. Fails for
+/// n-Wesolowski results; use [`vdf_info_and_proof`] for those.
+pub fn compact_vdf_info_and_proof(
+    challenge: &[u8],
+    number_of_iterations: u64,
+    output: &ProofOutput,
+    normalized_to_identity: bool,
+) -> Result<(VdfInfo, VdfProof), ChiavdfFastError> {
+    if output_witness_type(output)? != WITNESS_TYPE_COMPACT {
+        return Err(ChiavdfFastError::UnexpectedLength(
+            output.y.len() + output.witness.len(),
+        ));
+    }
+    vdf_info_and_proof(
+        challenge,
+        number_of_iterations,
+        output,
+        normalized_to_identity,
+    )
+}
+
+/// Build the Chia `VDFInfo` and `VDFProof` for a compact or n-Wesolowski prover
+/// result; `witness_type` is taken from the witness length.
+pub fn vdf_info_and_proof(
+    challenge: &[u8],
+    number_of_iterations: u64,
+    output: &ProofOutput,
+    normalized_to_identity: bool,
+) -> Result<(VdfInfo, VdfProof), ChiavdfFastError> {
+    let challenge: [u8; 32] = challenge
+        .try_into()
+        .map_err(|_| ChiavdfFastError::InvalidInput("challenge must be 32 bytes"))?;
+    let witness_type = output_witness_type(output)?;
+    let mut y = [0u8; CLASSGROUP_ELEMENT_SIZE];
+    y.copy_from_slice(&output.y);
+
+    Ok((
+        VdfInfo {
+            challenge,
+            number_of_iterations,
+            output: y,
+        },
+        VdfProof {
+            witness_type,
+            witness: output.witness.clone(),
+            normalized_to_identity,
+        },
+    ))
+}
+
+fn output_witness_type(output: &ProofOutput) -> Result<u8, ChiavdfFastError> {
+    let length = output.y.len() + output.witness.len();
+    if output.y.len() != CLASSGROUP_ELEMENT_SIZE {
+        return Err(ChiavdfFastError::UnexpectedLength(length));
+    }
+    output
+        .witness
+        .len()
+        .checked_sub(CLASSGROUP_ELEMENT_SIZE)
+        .filter(|segments| segments % N_WESO_SEGMENT_SIZE == 0)
+        .and_then(|segments| u8::try_from(segments / N_WESO_SEGMENT_SIZE).ok())
+        .ok_or(ChiavdfFastError::UnexpectedLength(length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CLASSGROUP_ELEMENT_SIZE, ClassgroupElement, N_WESO_SEGMENT_SIZE, ProofOutput,
+        compact_vdf_info_and_proof, proof_witness_type, split_proof_output, vdf_info_and_proof,
+    };
+
+    #[test]
+    fn serializes_compact_proof_in_streamable_layout() {
+        let mut result = vec![0u8; 2 * CLASSGROUP_ELEMENT_SIZE];
+        result[0] = 0x08;
+        result[CLASSGROUP_ELEMENT_SIZE] = 0x42;
+        let challenge = [7u8; 32];
+
+        let output = ProofOutput::from_bytes(&result, 0).expect("layout");
+        let (info, proof) = compact_vdf_info_and_proof(&challenge, 1000, &output, true)
+            .expect("valid prover output");
+
+        let info_bytes = info.to_bytes();
+        assert_eq!(info_bytes.len(), 32 + 8 + CLASSGROUP_ELEMENT_SIZE);
+        assert_eq!(&info_bytes[..32], &challenge);
+        assert_eq!(&info_bytes[32..40], &1000u64.to_be_bytes());
+        assert_eq!(info_bytes[40], 0x08);
+
+        let proof_bytes = proof.to_bytes();
+        assert_eq!(proof_bytes.len(), 1 + 4 + CLASSGROUP_ELEMENT_SIZE + 1);
+        assert_eq!(proof_bytes[0], 0);
+        assert_eq!(
+            &proof_bytes[1..5],
+            &(CLASSGROUP_ELEMENT_SIZE as u32).to_be_bytes()
+        );
+        assert_eq!(proof_bytes[5], 0x42);
+        assert_eq!(proof_bytes[proof_bytes.len() - 1], 1);
+    }
+
+    #[test]
+    fn serializes_n_wesolowski_proof_with_segments() {
+        let mut result = vec![0u8; 2 * CLASSGROUP_ELEMENT_SIZE + 2 * N_WESO_SEGMENT_SIZE];
+        result[0] = 0x08;
+        result[CLASSGROUP_ELEMENT_SIZE] = 0x42;
+        assert_eq!(proof_witness_type(&result).expect("layout"), 2);
+
+        let (y, witness) = split_proof_output(&result).expect("layout");
+        assert_eq!(y.len(), CLASSGROUP_ELEMENT_SIZE);
+        assert_eq!(witness.len(), result.len() - CLASSGROUP_ELEMENT_SIZE);
+
+        let output = ProofOutput::from_bytes(&result, 2).expect("layout");
+        assert_eq!(
+            (output.y.as_slice(), output.witness.as_slice()),
+            (y, witness)
+        );
+        assert_eq!(output.to_bytes(), result);
+        let (info, proof) =
+            vdf_info_and_proof(&[7u8; 32], 3000, &output, false).expect("valid prover output");
+        assert_eq!(info.output[0], 0x08);
+        assert_eq!(proof.witness_type, 2);
+        assert_eq!(proof.witness[0], 0x42);
+        assert!(compact_vdf_info_and_proof(&[7u8; 32], 3000, &output, false).is_err());
+
+        result.pop();
+        assert!(proof_witness_type(&result).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_output() {
+        assert!(split_proof_output(&[0u8; 199]).is_err());
+        assert!(ProofOutput::from_bytes(&[0u8; 199], 0).is_err());
+        assert!(ProofOutput::from_bytes(&[], 0).is_err());
+        let output = ProofOutput::from_bytes(&[0u8; 200], 0).expect("layout");
+        assert!(compact_vdf_info_and_proof(&[0u8; 31], 1, &output, false).is_err());
+        let short = ProofOutput::from_bytes(&[0u8; 104], 0).expect("layout");
+        assert!(compact_vdf_info_and_proof(&[0u8; 32], 1, &short, false).is_err());
+    }
+
+    #[test]
+    fn classgroup_element_round_trips_and_rejects_malformed() {
+        let generator = ClassgroupElement::generator();
+        assert_eq!(generator.as_bytes()[0], 0x08);
+        assert_eq!(
+            ClassgroupElement::from_bytes(&generator.to_bytes()).expect("generator"),
+            generator
+        );
+        assert_eq!(ClassgroupElement::default(), generator);
+        assert!(generator.is_valid_for_discriminant(1024));
+        assert!(!generator.is_valid_for_discriminant(0));
+
+        let mut compressed = [0u8; CLASSGROUP_ELEMENT_SIZE];
+        compressed[0] = 0x01;
+        compressed[1] = 20;
+        compressed[2] = 0x5a;
+        let element = ClassgroupElement::from_bytes(&compressed).expect("compressed form");
+        assert!(element.is_valid_for_discriminant(1024));
+        assert!(!element.is_valid_for_discriminant(256));
+
+        assert!(ClassgroupElement::from_bytes(&[0x08; 99]).is_err());
+        let mut bad = [0u8; CLASSGROUP_ELEMENT_SIZE];
+        bad[0] = 0x10;
+        assert!(ClassgroupElement::from_bytes(&bad).is_err());
+        bad[0] = 0x0c;
+        assert!(ClassgroupElement::from_bytes(&bad).is_err());
+        bad[0] = 0x08;
+        bad[50] = 1;
+        assert!(ClassgroupElement::from_bytes(&bad).is_err());
+        bad = [0u8; CLASSGROUP_ELEMENT_SIZE];
+        bad[1] = 64;
+        assert!(ClassgroupElement::from_bytes(&bad).is_err());
+    }
+}
@@ -0,0 +1,394 @@
+//! Binary quadratic forms of negative discriminant, and chiavdf's encodings:
+//! hash-to-prime discriminants and `bqfc` compressed forms.
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer as _;
+use num_traits::{One as _, Signed as _, ToPrimitive as _, Zero as _};
+use sha2::{Digest as _, Sha256};
+
+/// Compressed-form flag bits; see `crate::chia`.
+const FLAG_B_SIGN: u8 = 0x01;
+const FLAG_T_SIGN: u8 = 0x02;
+const FLAG_IS_IDENTITY: u8 = 0x04;
+const FLAG_IS_GENERATOR: u8 = 0x08;
+
+/// chiavdf's `BQFC_FORM_SIZE`: forms of smaller discriminants are zero-padded
+/// to the size of 1024-bit ones.
+const MIN_FORM_SIZE: usize = 100;
+
+/// Strong probable-prime bases; composites passing all of them don't turn up
+/// in hash-to-prime searches.
+const MILLER_RABIN_BASES: [u32; 20] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
+/// Candidates with a factor below this are rejected before Miller-Rabin.
+const TRIAL_DIVISION_LIMIT: u32 = 2_000;
+
+/// A form `(a, b, c)` with `b^2 - 4ac` equal to the discriminant it is used with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Form {
+    pub(crate) a: BigInt,
+    pub(crate) b: BigInt,
+    pub(crate) c: BigInt,
+}
+
+impl Form {
+    /// The form `(a, b)` of discriminant `d`, if `a > 0` and `c = (b^2 - d) / 4a`
+    /// is an integer.
+    pub(crate) fn from_ab(a: BigInt, b: BigInt, d: &BigInt) -> Option<Self> {
+        if !a.is_positive() {
+            return None;
+        }
+        let (c, rem) = (&b * &b - d).div_rem(&(&a << 2u32));
+        rem.is_zero().then_some(Self { a, b, c })
+    }
+
+    /// The identity `(1, 1)`.
+    pub(crate) fn identity(d: &BigInt) -> Self {
+        let one = BigInt::one();
+        let c = (&one - d) >> 2u32;
+        Self {
+            a: one.clone(),
+            b: one,
+            c,
+        }
+    }
+
+    fn normalize(&mut self) {
+        let minus_a = -&self.a;
+        if minus_a < self.b && self.b <= self.a {
+            return;
+        }
+        let r = (&self.a - &self.b).div_floor(&(&self.a << 1u32));
+        let ra = &r * &self.a;
+        self.c += &r * (&self.b + &ra);
+        self.b += ra << 1u32;
+    }
+
+    /// Replace the form by the reduced form of its class.
+    pub(crate) fn reduce(&mut self) {
+        self.normalize();
+        while self.a > self.c || (self.a == self.c && self.b.is_negative()) {
+            std::mem::swap(&mut self.a, &mut self.c);
+            self.b = -&self.b;
+            self.normalize();
+        }
+    }
+
+    /// The reduced product of `self` and `other` (Cohen, Algorithm 5.4.7).
+    pub(crate) fn compose(&self, other: &Self, d: &BigInt) -> Self {
+        let (f1, f2) = if self.a > other.a {
+            (other, self)
+        } else {
+            (self, other)
+        };
+        let s = (&f1.b + &f2.b) >> 1u32;
+        let n = &f2.b - &s;
+        // y1 * a2 + v * a1 = d0.
+        let (d0, y1) = gcd_cofactor(&f2.a, &f1.a);
+        // x2 * s - y2 * d0 = d1.
+        let (d1, x2) = gcd_cofactor(&s, &d0);
+        let y2 = (&x2 * &s - &d1) / &d0;
+        let v1 = &f1.a / &d1;
+        let v2 = &f2.a / &d1;
+        let r = (y1 * y2 * n - x2 * &f2.c).mod_floor(&v1);
+        Self::finish(&f2.b + ((v2.clone() * &r) << 1u32), v1 * v2, d)
+    }
+
+    /// The reduced square of `self`: [`Form::compose`] with itself.
+    pub(crate) fn square(&self, d: &BigInt) -> Self {
+        // x2 * b + y * a = d1.
+        let (d1, x2) = gcd_cofactor(&self.b, &self.a);
+        let v = &self.a / &d1;
+        let r = (-(x2 * &self.c)).mod_floor(&v);
+        Self::finish(&self.b + ((&v * &r) << 1u32), &v * &v, d)
+    }
+
+    fn finish(b: BigInt, a: BigInt, d: &BigInt) -> Self {
+        let c = (&b * &b - d) / (&a << 2u32);
+        let mut form = Self { a, b, c };
+        form.reduce();
+        form
+    }
+
+    /// `self^(2^n)`.
+    pub(crate) fn square_times(&self, n: u64, d: &BigInt) -> Self {
+        (0..n).fold(self.clone(), |form, _| form.square(d))
+    }
+
+    /// `self^e`.
+    #[cfg(test)]
+    pub(crate) fn pow(&self, e: &BigUint, d: &BigInt) -> Self {
+        let mut out = Self::identity(d);
+        for bit in (0..e.bits()).rev() {
+            out = out.square(d);
+            if e.bit(bit) {
+                out = out.compose(self, d);
+            }
+        }
+        out
+    }
+}
+
+/// `(g, x)` with `g = gcd(a, b) = x * a + y * b` for some `y`; cheaper than
+/// `Integer::extended_gcd`, which also tracks `y`.
+fn gcd_cofactor(a: &BigInt, b: &BigInt) -> (BigInt, BigInt) {
+    let (mut r0, mut r1) = (a.clone(), b.clone());
+    let (mut x0, mut x1) = (BigInt::one(), BigInt::zero());
+    while !r1.is_zero() {
+        let (q, r) = r0.div_rem(&r1);
+        r0 = std::mem::replace(&mut r1, r);
+        let x = &x0 - q * &x1;
+        x0 = std::mem::replace(&mut x1, x);
+    }
+    if r0.is_negative() {
+        (-r0, -x0)
+    } else {
+        (r0, x0)
+    }
+}
+
+/// chiavdf's `CreateDiscriminant`: the negated [`hash_prime`] of the challenge.
+pub(crate) fn create_discriminant(seed: &[u8], size_bits: usize) -> BigInt {
+    let prime = hash_prime(seed, size_bits, &[0, 1, 2, size_bits - 1]);
+    -BigInt::from(prime)
+}
+
+/// chiavdf's `HashPrime`: the first probable prime among big-endian numbers
+/// of `size_bits` bits (a multiple of 8) expanded from `seed` with SHA-256,
+/// each with the `bitmask` bits set.
+pub(crate) fn hash_prime(seed: &[u8], size_bits: usize, bitmask: &[usize]) -> BigUint {
+    let length = size_bits / 8;
+    let mut sprout = seed.to_vec();
+    let mut blob = Vec::with_capacity(length);
+    loop {
+        blob.clear();
+        while blob.len() < length {
+            // The seed counts up as a big-endian integer before every hash.
+            for byte in sprout.iter_mut().rev() {
+                *byte = byte.wrapping_add(1);
+                if *byte != 0 {
+                    break;
+                }
+            }
+            let hash = Sha256::digest(&sprout);
+            let take = hash.len().min(length - blob.len());
+            blob.extend_from_slice(&hash[..take]);
+        }
+        let mut p = BigUint::from_bytes_be(&blob);
+        for &bit in bitmask {
+            p.set_bit(bit as u64, true);
+        }
+        if is_probable_prime(&p) {
+            return p;
+        }
+    }
+}
+
+fn is_probable_prime(n: &BigUint) -> bool {
+    if let Some(small) = n.to_u32().filter(|&n| n < TRIAL_DIVISION_LIMIT) {
+        return small >= 2
+            && (2..small)
+                .take_while(|p| p * p <= small)
+                .all(|p| small % p != 0);
+    }
+    if (2..TRIAL_DIVISION_LIMIT).any(|p| (n % p).is_zero()) {
+        return false;
+    }
+    let one = BigUint::one();
+    let n_minus_one = n - &one;
+    let shift = n_minus_one.trailing_zeros().unwrap_or(0);
+    let odd = &n_minus_one >> shift;
+    MILLER_RABIN_BASES.iter().all(|&base| {
+        let mut x = BigUint::from(base).modpow(&odd, n);
+        if x == one || x == n_minus_one {
+            return true;
+        }
+        for _ in 1..shift {
+            x = &x * &x % n;
+            if x == n_minus_one {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Bytes of a serialized form for `d_bits`-bit discriminants.
+pub(crate) fn form_size(d_bits: usize) -> usize {
+    (d_bits.div_ceil(32) * 3 + 4).max(MIN_FORM_SIZE)
+}
+
+/// chiavdf's `SerializeForm` of a reduced form; `None` when a field does not
+/// fit its slot.
+pub(crate) fn serialize(form: &Form, d_bits: usize) -> Option<Vec<u8>> {
+    let d_bits = d_bits.next_multiple_of(32);
+    let mut out = vec![0u8; form_size(d_bits)];
+    if form.b.is_one() && form.a <= BigInt::from(2) {
+        out[0] = if form.a.is_one() {
+            FLAG_IS_IDENTITY
+        } else {
+            FLAG_IS_GENERATOR
+        };
+        return Some(out);
+    }
+
+    let compressed = compress(&form.a, &form.b);
+    let g_size = byte_len(&compressed.g) - 1;
+    if g_size >= d_bits / 32 {
+        return None;
+    }
+    out[0] = if compressed.b_sign { FLAG_B_SIGN } else { 0 }
+        | if compressed.t.is_negative() {
+            FLAG_T_SIGN
+        } else {
+            0
+        };
+    out[1] = u8::try_from(g_size).ok()?;
+    let mut offset = 2;
+    for (value, size) in [
+        (&compressed.a, d_bits / 16 - g_size),
+        (&compressed.t, d_bits / 32 - g_size),
+        (&compressed.g, g_size + 1),
+        (&compressed.b0, g_size + 1),
+    ] {
+        let bytes = value.magnitude().to_bytes_le();
+        if !value.is_zero() {
+            if bytes.len() > size {
+                return None;
+            }
+            out[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        }
+        offset += size;
+    }
+    Some(out)
+}
+
+/// chiavdf's `DeserializeForm` for discriminant `d`, additionally requiring the
+/// result to be a form of `d` and `bytes` to be its canonical encoding.
+///
+/// The identity and generator encodings are accepted at any length, so the
+/// 100-byte generator also starts proofs for discriminants over 1024 bits.
+pub(crate) fn deserialize(bytes: &[u8], d: &BigInt) -> Option<Form> {
+    let d_bits = usize::try_from(d.bits()).ok()?.next_multiple_of(32);
+    let (&flags, rest) = bytes.split_first()?;
+    if flags == FLAG_IS_IDENTITY || flags == FLAG_IS_GENERATOR {
+        let a = if flags == FLAG_IS_GENERATOR { 2 } else { 1 };
+        return rest
+            .iter()
+            .all(|&byte| byte == 0)
+            .then(|| Form::from_ab(BigInt::from(a), BigInt::one(), d))
+            .flatten();
+    }
+    let g_size = usize::from(*rest.first()?);
+    if bytes.len() != form_size(d_bits) || g_size >= d_bits / 32 {
+        return None;
+    }
+    let mut offset = 2;
+    let mut field = |size: usize| {
+        let value = BigInt::from_bytes_le(Sign::Plus, &bytes[offset..offset + size]);
+        offset += size;
+        value
+    };
+    let a = field(d_bits / 16 - g_size);
+    let mut t = field(d_bits / 32 - g_size);
+    let g = field(g_size + 1);
+    let b0 = field(g_size + 1);
+    if flags & FLAG_T_SIGN != 0 {
+        t = -t;
+    }
+    let (a, b) = decompress(a, t, g, b0, d)?;
+    let b = if flags & FLAG_B_SIGN != 0 { -b } else { b };
+    let form = Form::from_ab(a, b, d)?;
+    (serialize(&form, d_bits).as_deref() == Some(bytes)).then_some(form)
+}
+
+/// `bqfc` fields of a form `(a, b)`: `a = a' * g`, and `t = t' * g` the cofactor
+/// with `t * |b| = r (mod a)` for the first Euclidean remainder `r <= sqrt(a)`
+/// of `(a, |b|)`; `b0 = |b| / a'` restores the bits lost modulo `a'`.
+struct Compressed {
+    a: BigInt,
+    t: BigInt,
+    g: BigInt,
+    b0: BigInt,
+    b_sign: bool,
+}
+
+fn compress(a: &BigInt, b: &BigInt) -> Compressed {
+    if a == b {
+        return Compressed {
+            a: a.clone(),
+            t: BigInt::zero(),
+            g: BigInt::zero(),
+            b0: BigInt::zero(),
+            b_sign: false,
+        };
+    }
+    let b_abs = b.abs();
+    let bound = a.sqrt();
+    let (mut r2, mut r1) = (a.clone(), b_abs.clone());
+    // Cofactors with `r = -co * |b| (mod a)`.
+    let (mut co2, mut co1) = (BigInt::zero(), -BigInt::one());
+    while !r1.is_zero() && r1 > bound {
+        let (q, r) = r2.div_rem(&r1);
+        r2 = std::mem::replace(&mut r1, r);
+        let next = &co2 - &q * &co1;
+        co2 = std::mem::replace(&mut co1, next);
+    }
+    let t = -co1;
+    let g = a.gcd(&t);
+    if g.is_one() {
+        return Compressed {
+            a: a.clone(),
+            t,
+            g,
+            b0: BigInt::zero(),
+            b_sign: b.is_negative(),
+        };
+    }
+    let a_reduced = a / &g;
+    Compressed {
+        t: &t / &g,
+        b0: &b_abs / &a_reduced,
+        a: a_reduced,
+        g,
+        b_sign: b.is_negative(),
+    }
+}
+
+/// `(a, |b|)` from the [`Compressed`] fields, if they are consistent with `d`.
+fn decompress(
+    a_reduced: BigInt,
+    t_reduced: BigInt,
+    g: BigInt,
+    b0: BigInt,
+    d: &BigInt,
+) -> Option<(BigInt, BigInt)> {
+    if t_reduced.is_zero() {
+        return Some((a_reduced.clone(), a_reduced));
+    }
+    let a = &a_reduced * &g;
+    if a.is_zero() {
+        return None;
+    }
+    let t = &t_reduced * &g;
+    let square = (&t * &t * d).mod_floor(&a);
+    let r = square.sqrt();
+    if &r * &r != square {
+        return None;
+    }
+    let (r_reduced, rem) = r.div_rem(&g);
+    if !rem.is_zero() {
+        return None;
+    }
+    let t_inverse = t_reduced.mod_floor(&a_reduced).modinv(&a_reduced)?;
+    let b = (r_reduced * t_inverse).mod_floor(&a_reduced) + b0 * &a_reduced;
+    Some((a, b))
+}
+
+fn byte_len(n: &BigInt) -> usize {
+    usize::try_from(n.bits().div_ceil(8))
+        .unwrap_or(usize::MAX)
+        .max(1)
+}
@@ -0,0 +1,175 @@
+//! Iteration-count sanity limits and pre-flight cost estimation.
+//!
+//! A bogus job with an enormous iteration count would otherwise keep a worker
+//! busy for days before anyone notices. The prove functions reject counts above
+//! [`max_iterations`], and [`estimate_proof_cost`] lets callers predict memory use
+//! and rough duration before starting.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::api::ChiavdfFastError;
+
+/// Default upper bound on `num_iterations` accepted by the prove functions.
+///
+/// Well above any real bluebox job, but low enough to catch garbage input.
+pub const DEFAULT_MAX_ITERATIONS: u64 = 10_000_000_000;
+
+/// Squaring rate assumed by [`estimate_proof_cost`] for 1024-bit discriminants.
+const REFERENCE_ITERS_PER_SEC: u64 = 500_000;
+
+static MAX_ITERATIONS: AtomicU64 = AtomicU64::new(DEFAULT_MAX_ITERATIONS);
+
+/// Set the largest `num_iterations` the prove functions accept (process-wide).
+///
+/// If `max` is 0, the limit is reset to [`DEFAULT_MAX_ITERATIONS`].
+pub fn set_max_iterations(max: u64) {
+    let max = if max == 0 {
+        DEFAULT_MAX_ITERATIONS
+    } else {
+        max
+    };
+    MAX_ITERATIONS.store(max, Ordering::Relaxed);
+}
+
+/// Current upper bound on `num_iterations` accepted by the prove functions.
+pub fn max_iterations() -> u64 {
+    MAX_ITERATIONS.load(Ordering::Relaxed)
+}
+
+pub(crate) fn check_iterations(num_iterations: u64) -> Result<(), ChiavdfFastError> {
+    if num_iterations == 0 {
+        return Err(ChiavdfFastError::InvalidInput("num_iterations must be > 0"));
+    }
+    if num_iterations > max_iterations() {
+        return Err(ChiavdfFastError::InvalidInput(
+            "num_iterations exceeds the configured maximum",
+        ));
+    }
+    Ok(())
+}
+
+/// Coarse duration bucket for a proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProofTimeClass {
+    /// Under a minute.
+    Seconds,
+    /// Under an hour.
+    Minutes,
+    /// Under a day.
+    Hours,
+    /// A day or more.
+    Days,
+}
+
+impl ProofTimeClass {
+    fn from_duration(duration: Duration) -> Self {
+        match duration.as_secs() {
+            0..60 => Self::Seconds,
+            60..3_600 => Self::Minutes,
+            3_600..86_400 => Self::Hours,
+            _ => Self::Days,
+        }
+    }
+}
+
+/// Predicted resource use of a streaming proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofCostEstimate {
+    /// Bucket width parameter the tuner is expected to pick.
+    pub k: u32,
+    /// Number of rows the tuner is expected to pick.
+    pub l: u32,
+    /// Predicted bucket memory in bytes.
+    pub memory_bytes: u64,
+    /// Predicted wall time at a reference squaring rate.
+    pub estimated_duration: Duration,
+    /// Coarse bucket for `estimated_duration`.
+    pub time_class: ProofTimeClass,
+}
+
+fn bucket_form_bytes(discriminant_size_bits: usize) -> u64 {
+    let discr_bytes = (discriminant_size_bits as u64).div_ceil(8);
+    (discr_bytes * 16).max(2_048)
+}
+
+/// Estimate memory and duration of a streaming proof.
+///
+/// Mirrors the native `(k,l)` tuner's cost model under `memory_budget_bytes`
+/// (0 means unconstrained). The duration assumes a typical single-core
+/// squaring rate scaled by discriminant size, so treat it as an order of
+/// magnitude rather than a prediction for this machine.
+pub fn estimate_proof_cost(
+    num_iterations: u64,
+    discriminant_size_bits: usize,
+    memory_budget_bytes: u64,
+) -> ProofCostEstimate {
+    const UPDATE_WEIGHT: u128 = 16;
+    const FOLD_WEIGHT: u128 = 16;
+    const CHECKPOINT_WEIGHT: u128 = 1;
+
+    let num_iterations = num_iterations.max(1);
+    let bytes_per_form = bucket_form_bytes(discriminant_size_bits);
+    let budget = if memory_budget_bytes == 0 {
+        u128::MAX
+    } else {
+        u128::from(memory_budget_bytes.saturating_mul(80) / 100)
+    };
+
+    let mut best: Option<(u128, u32, u32)> = None;
+    for k in 4u32..=20u32 {
+        for l in 1u32..=64u32 {
+            let mem_required = (1u128 << k) * u128::from(l) * u128::from(bytes_per_form);
+            if mem_required > budget {
+                continue;
+            }
+            let updates = u128::from(num_iterations.div_ceil(u64::from(k)));
+            let checkpoints = u128::from(num_iterations.div_ceil(u64::from(k) * u64::from(l)));
+            let fold = u128::from(l) << (k + 1);
+            let cost =
+                updates * UPDATE_WEIGHT + checkpoints * CHECKPOINT_WEIGHT + fold * FOLD_WEIGHT;
+            if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                best = Some((cost, k, l));
+            }
+        }
+    }
+    // Below the smallest candidate the native tuner falls back to its minimum.
+    let (_, k, l) = best.unwrap_or((0, 4, 1));
+
+    let bits = discriminant_size_bits.max(1) as f64;
+    let scale = (bits / 1024.0).powi(2);
+    let secs = num_iterations as f64 * scale / REFERENCE_ITERS_PER_SEC as f64;
+    let estimated_duration = Duration::from_secs_f64(secs);
+
+    ProofCostEstimate {
+        k,
+        l,
+        memory_bytes: (1u64 << k) * u64::from(l) * bytes_per_form,
+        estimated_duration,
+        time_class: ProofTimeClass::from_duration(estimated_duration),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProofTimeClass, check_iterations, estimate_proof_cost, max_iterations};
+
+    #[test]
+    fn rejects_zero_and_absurd_iteration_counts() {
+        assert!(check_iterations(0).is_err());
+        assert!(check_iterations(1_000).is_ok());
+        assert!(check_iterations(max_iterations() + 1).is_err());
+    }
+
+    #[test]
+    fn estimate_respects_budget_and_scales_with_iterations() {
+        let small = estimate_proof_cost(1_000, 1024, 64 * 1024 * 1024);
+        assert_eq!(small.time_class, ProofTimeClass::Seconds);
+        assert!(small.memory_bytes <= 64 * 1024 * 1024);
+
+        let huge = estimate_proof_cost(100_000_000_000, 1024, 64 * 1024 * 1024);
+        assert_eq!(huge.time_class, ProofTimeClass::Days);
+        assert!(huge.memory_bytes <= 64 * 1024 * 1024);
+        assert!(huge.estimated_duration > small.estimated_duration);
+    }
+}
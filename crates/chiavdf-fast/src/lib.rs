@@ -2,18 +2,66 @@
 #![deny(unreachable_pub)]
 
 //! Minimal Rust wrapper around a fast chiavdf C API.
+//!
+//! Only the core prover API of the native library is used, so the optimized
+//! fast path (the `chiavdf` submodule or a `dynamic` library) and the portable
+//! and pure-Rust fallbacks behave the same: input checks, pausing, phase-aware
+//! progress, deadlines and thread pinning are all done on the Rust side.
 
 /// Public API for this crate.
 pub mod api;
 
+/// Chia protocol serialization helpers.
+pub mod chia;
+
+/// Iteration limits and proof cost estimation.
+pub mod cost;
+
+pub mod test_vectors;
+
+/// Pure-Rust class group arithmetic; the pure-Rust prover uses all of it.
+#[cfg_attr(not(chiavdf_rust_fallback), allow(dead_code))]
+mod classgroup;
+
+#[cfg_attr(chiavdf_rust_fallback, path = "rust_fallback/mod.rs")]
 mod ffi;
 
+/// Name of the linked prover implementation: `"fast"` for the optimized chiavdf
+/// engine, `"fallback"` for the portable prover, `"rust-fallback"` for the
+/// pure-Rust prover (`rust-fallback` feature).
+pub fn implementation_name() -> &'static str {
+    if cfg!(chiavdf_rust_fallback) {
+        "rust-fallback"
+    } else if cfg!(chiavdf_fallback) {
+        "fallback"
+    } else {
+        "fast"
+    }
+}
+
+#[allow(deprecated)]
 pub use api::{
-    ChiavdfBatchJob, ChiavdfFastError, StreamingParameters, StreamingStats, last_streaming_parameters,
-    last_streaming_stats, prove_one_weso_fast, prove_one_weso_fast_streaming,
+    BatchCheckpoint, ChiavdfBatchJob, ChiavdfFastError, ProgressInfo, ProgressPhase, ProofOutput,
+    ProveOptions, ProveReport, ProverBuilder, ProverSession, StreamingParameters, StreamingStats,
+    create_discriminant, evaluate_weso, last_streaming_parameters, last_streaming_stats,
+    prove_n_weso_fast, prove_one_weso_fast, prove_one_weso_fast_streaming,
     prove_one_weso_fast_streaming_getblock_opt, prove_one_weso_fast_streaming_getblock_opt_batch,
+    prove_one_weso_fast_streaming_getblock_opt_batch_from_checkpoint,
+    prove_one_weso_fast_streaming_getblock_opt_batch_reported,
+    prove_one_weso_fast_streaming_getblock_opt_batch_retaining,
     prove_one_weso_fast_streaming_getblock_opt_batch_with_progress,
+    prove_one_weso_fast_streaming_getblock_opt_reported,
     prove_one_weso_fast_streaming_getblock_opt_with_progress,
+    prove_one_weso_fast_streaming_getblock_opt_with_progress_reported,
     prove_one_weso_fast_streaming_with_progress, prove_one_weso_fast_with_progress,
-    set_bucket_memory_budget_bytes, set_enable_streaming_stats,
+    run_with_progress_info, set_bucket_memory_budget_bytes, set_enable_streaming_stats,
+    validate_form,
+};
+pub use chia::{
+    ClassgroupElement, VdfInfo, VdfProof, compact_vdf_info_and_proof, proof_witness_type,
+    split_proof_output, vdf_info_and_proof,
+};
+pub use cost::{
+    DEFAULT_MAX_ITERATIONS, ProofCostEstimate, ProofTimeClass, estimate_proof_cost, max_iterations,
+    set_max_iterations,
 };
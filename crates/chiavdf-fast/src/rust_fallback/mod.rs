@@ -0,0 +1,539 @@
+//! Pure-Rust stand-in for the chiavdf fast C wrapper (`rust-fallback` feature).
+//!
+//! Mounted as `crate::ffi` in place of the bindings, with the same items and
+//! signatures, so the API layer is unchanged. Proofs match chiavdf's byte for
+//! byte but compute on one thread with plain big-integer arithmetic, several
+//! times slower than the portable C++ prover.
+
+// Signatures mirror the C functions in ffi.rs.
+#![allow(clippy::too_many_arguments)]
+
+mod prover;
+
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use num_bigint::BigInt;
+
+use crate::classgroup;
+
+/// Byte buffer returned by the provers (a leaked `Box<[u8]>`).
+#[repr(C)]
+pub(crate) struct ChiavdfByteArray {
+    /// Pointer to heap-allocated bytes (owned by this module).
+    pub(crate) data: *mut u8,
+    /// Length of the buffer in bytes.
+    pub(crate) length: usize,
+}
+
+#[repr(C)]
+pub(crate) struct ChiavdfBatchJob {
+    pub(crate) y_ref_s: *const u8,
+    pub(crate) y_ref_s_size: usize,
+    pub(crate) num_iterations: u64,
+}
+
+pub(crate) type ProgressCallback = unsafe extern "C" fn(iters_done: u64, user_data: *mut c_void);
+
+thread_local! {
+    static LAST_PARAMETERS: Cell<Option<(u32, u32)>> = const { Cell::new(None) };
+}
+
+/// Run `call` as one native call, failing it on a panic like the C++ wrapper's
+/// crash guard.
+fn guarded<T>(call: impl FnOnce() -> Option<T>) -> Option<T> {
+    catch_unwind(AssertUnwindSafe(call)).ok().flatten()
+}
+
+/// `ptr[..len]`, or `None` when it is null or empty.
+///
+/// # Safety
+///
+/// A non-null `ptr` must be valid for reads of `len` bytes for `'a`.
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    // SAFETY: Guaranteed by the caller.
+    (!ptr.is_null() && len != 0).then(|| unsafe { std::slice::from_raw_parts(ptr, len) })
+}
+
+fn into_array(bytes: Vec<u8>) -> ChiavdfByteArray {
+    let length = bytes.len();
+    ChiavdfByteArray {
+        data: Box::into_raw(bytes.into_boxed_slice()).cast::<u8>(),
+        length,
+    }
+}
+
+fn empty_array() -> ChiavdfByteArray {
+    ChiavdfByteArray {
+        data: std::ptr::null_mut(),
+        length: 0,
+    }
+}
+
+fn discriminant(challenge: &[u8], size_bits: usize) -> Option<BigInt> {
+    if !size_bits.is_multiple_of(8) {
+        return None;
+    }
+    Some(classgroup::create_discriminant(challenge, size_bits))
+}
+
+/// Where a prover reports progress: `cb(completed_before + done)` every
+/// `interval` squarings and after the last one.
+struct Progress {
+    interval: u64,
+    cb: Option<ProgressCallback>,
+    user_data: *mut c_void,
+    completed_before: u64,
+}
+
+impl Progress {
+    fn report(&self, done: u64, num_iterations: u64) {
+        let Some(cb) = self.cb else {
+            return;
+        };
+        if self.interval != 0 && (done == num_iterations || done.is_multiple_of(self.interval)) {
+            // SAFETY: The caller passed `cb` together with its `user_data`.
+            unsafe { cb(self.completed_before.saturating_add(done), self.user_data) };
+        }
+    }
+}
+
+/// `y || proof` for `num_iterations` squarings of `x_s`, checking `y` against
+/// `y_ref` when given (chiavdf's `prove_one_weso_slow`).
+fn prove(
+    challenge: Option<&[u8]>,
+    x_s: Option<&[u8]>,
+    y_ref: Option<Option<&[u8]>>,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress: &Progress,
+) -> Option<Vec<u8>> {
+    let (Some(challenge), Some(x_s)) = (challenge, x_s) else {
+        return None;
+    };
+    if num_iterations == 0 || discriminant_size_bits == 0 || y_ref == Some(None) {
+        return None;
+    }
+
+    let d = discriminant(challenge, discriminant_size_bits)?;
+    let x = classgroup::deserialize(x_s, &d)?;
+    let (k, l) = prover::approximate_parameters(num_iterations);
+    LAST_PARAMETERS.set(Some((
+        u32::try_from(k).unwrap_or(u32::MAX),
+        u32::try_from(l).unwrap_or(u32::MAX),
+    )));
+
+    let mut intermediates = Vec::new();
+    let y = prover::square_from(&x, &d, num_iterations, &mut intermediates, k * l, |done| {
+        progress.report(done, num_iterations)
+    });
+
+    let y_serialized = classgroup::serialize(&y, discriminant_size_bits)?;
+    if let Some(Some(y_ref)) = y_ref
+        && y_serialized != y_ref
+    {
+        return None;
+    }
+
+    let b = prover::get_b(&x, &y, discriminant_size_bits)?;
+    let proof = prover::generate_wesolowski(&d, &b, &intermediates, num_iterations, k, l);
+    let mut out = y_serialized;
+    out.extend(classgroup::serialize(&proof, discriminant_size_bits)?);
+    Some(out)
+}
+
+pub(crate) unsafe fn chiavdf_set_bucket_memory_budget_bytes(_bytes: u64) {
+    // Intermediates are kept in a plain vector; there are no buckets to size.
+}
+
+pub(crate) unsafe fn chiavdf_get_last_streaming_parameters(
+    out_k: *mut u32,
+    out_l: *mut u32,
+    out_tuned: *mut bool,
+) -> bool {
+    let Some((k, l)) = LAST_PARAMETERS.get() else {
+        return false;
+    };
+    if out_k.is_null() || out_l.is_null() || out_tuned.is_null() {
+        return false;
+    }
+    // SAFETY: The caller passes pointers valid for writes.
+    unsafe {
+        *out_k = k;
+        *out_l = l;
+        *out_tuned = false;
+    }
+    true
+}
+
+pub(crate) unsafe fn chiavdf_set_enable_streaming_stats(_enable: bool) {
+    // There are no streaming stats to collect.
+}
+
+pub(crate) unsafe fn chiavdf_get_last_streaming_stats(
+    _out_checkpoint_total_ns: *mut u64,
+    _out_checkpoint_event_total_ns: *mut u64,
+    _out_finalize_total_ns: *mut u64,
+    _out_checkpoint_calls: *mut u64,
+    _out_bucket_updates: *mut u64,
+) -> bool {
+    false
+}
+
+pub(crate) unsafe fn chiavdf_prove_one_weso_fast(
+    challenge_hash: *const u8,
+    challenge_size: usize,
+    x_s: *const u8,
+    x_s_size: usize,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+) -> ChiavdfByteArray {
+    // SAFETY: Forwarded unchanged.
+    unsafe {
+        chiavdf_prove_one_weso_fast_with_progress(
+            challenge_hash,
+            challenge_size,
+            x_s,
+            x_s_size,
+            discriminant_size_bits,
+            num_iterations,
+            0,
+            None,
+            std::ptr::null_mut(),
+        )
+    }
+}
+
+pub(crate) unsafe fn chiavdf_prove_one_weso_fast_with_progress(
+    challenge_hash: *const u8,
+    challenge_size: usize,
+    x_s: *const u8,
+    x_s_size: usize,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress_interval: u64,
+    progress_cb: Option<ProgressCallback>,
+    progress_user_data: *mut c_void,
+) -> ChiavdfByteArray {
+    // SAFETY: The caller passes pointers valid for their sizes.
+    let (challenge, x_s) = unsafe { (bytes(challenge_hash, challenge_size), bytes(x_s, x_s_size)) };
+    let progress = Progress {
+        interval: progress_interval,
+        cb: progress_cb,
+        user_data: progress_user_data,
+        completed_before: 0,
+    };
+    guarded(|| {
+        prove(
+            challenge,
+            x_s,
+            None,
+            discriminant_size_bits,
+            num_iterations,
+            &progress,
+        )
+    })
+    .map_or_else(empty_array, into_array)
+}
+
+pub(crate) unsafe fn chiavdf_prove_one_weso_fast_streaming(
+    challenge_hash: *const u8,
+    challenge_size: usize,
+    x_s: *const u8,
+    x_s_size: usize,
+    y_ref_s: *const u8,
+    y_ref_s_size: usize,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+) -> ChiavdfByteArray {
+    // SAFETY: Forwarded unchanged.
+    unsafe {
+        chiavdf_prove_one_weso_fast_streaming_getblock_opt_with_progress(
+            challenge_hash,
+            challenge_size,
+            x_s,
+            x_s_size,
+            y_ref_s,
+            y_ref_s_size,
+            discriminant_size_bits,
+            num_iterations,
+            0,
+            None,
+            std::ptr::null_mut(),
+        )
+    }
+}
+
+pub(crate) unsafe fn chiavdf_prove_one_weso_fast_streaming_with_progress(
+    challenge_hash: *const u8,
+    challenge_size: usize,
+    x_s: *const u8,
+    x_s_size: usize,
+    y_ref_s: *const u8,
+    y_ref_s_size: usize,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress_interval: u64,
+    progress_cb: Option<ProgressCallback>,
+    progress_user_data: *mut c_void,
+) -> ChiavdfByteArray {
+    // SAFETY: Forwarded unchanged.
+    unsafe {
+        chiavdf_prove_one_weso_fast_streaming_getblock_opt_with_progress(
+            challenge_hash,
+            challenge_size,
+            x_s,
+            x_s_size,
+            y_ref_s,
+            y_ref_s_size,
+            discriminant_size_bits,
+            num_iterations,
+            progress_interval,
+            progress_cb,
+            progress_user_data,
+        )
+    }
+}
+
+pub(crate) unsafe fn chiavdf_prove_one_weso_fast_streaming_getblock_opt(
+    challenge_hash: *const u8,
+    challenge_size: usize,
+    x_s: *const u8,
+    x_s_size: usize,
+    y_ref_s: *const u8,
+    y_ref_s_size: usize,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+) -> ChiavdfByteArray {
+    // SAFETY: Forwarded unchanged.
+    unsafe {
+        chiavdf_prove_one_weso_fast_streaming_getblock_opt_with_progress(
+            challenge_hash,
+            challenge_size,
+            x_s,
+            x_s_size,
+            y_ref_s,
+            y_ref_s_size,
+            discriminant_size_bits,
+            num_iterations,
+            0,
+            None,
+            std::ptr::null_mut(),
+        )
+    }
+}
+
+pub(crate) unsafe fn chiavdf_prove_one_weso_fast_streaming_getblock_opt_with_progress(
+    challenge_hash: *const u8,
+    challenge_size: usize,
+    x_s: *const u8,
+    x_s_size: usize,
+    y_ref_s: *const u8,
+    y_ref_s_size: usize,
+    discriminant_size_bits: usize,
+    num_iterations: u64,
+    progress_interval: u64,
+    progress_cb: Option<ProgressCallback>,
+    progress_user_data: *mut c_void,
+) -> ChiavdfByteArray {
+    // SAFETY: The caller passes pointers valid for their sizes.
+    let (challenge, x_s, y_ref) = unsafe {
+        (
+            bytes(challenge_hash, challenge_size),
+            bytes(x_s, x_s_size),
+            bytes(y_ref_s, y_ref_s_size),
+        )
+    };
+    let progress = Progress {
+        interval: progress_interval,
+        cb: progress_cb,
+        user_data: progress_user_data,
+        completed_before: 0,
+    };
+    guarded(|| {
+        prove(
+            challenge,
+            x_s,
+            Some(y_ref),
+            discriminant_size_bits,
+            num_iterations,
+            &progress,
+        )
+    })
+    .map_or_else(empty_array, into_array)
+}
+
+pub(crate) unsafe fn chiavdf_prove_one_weso_fast_streaming_getblock_opt_batch(
+    challenge_hash: *const u8,
+    challenge_size: usize,
+    x_s: *const u8,
+    x_s_size: usize,
+    discriminant_size_bits: usize,
+    jobs: *const ChiavdfBatchJob,
+    job_count: usize,
+) -> *mut ChiavdfByteArray {
+    // SAFETY: Forwarded unchanged.
+    unsafe {
+        chiavdf_prove_one_weso_fast_streaming_getblock_opt_batch_with_progress(
+            challenge_hash,
+            challenge_size,
+            x_s,
+            x_s_size,
+            discriminant_size_bits,
+            jobs,
+            job_count,
+            0,
+            None,
+            std::ptr::null_mut(),
+        )
+    }
+}
+
+/// Proves the jobs one after another from the same `x_s`; progress counts the
+/// iterations of all jobs so far.
+pub(crate) unsafe fn chiavdf_prove_one_weso_fast_streaming_getblock_opt_batch_with_progress(
+    challenge_hash: *const u8,
+    challenge_size: usize,
+    x_s: *const u8,
+    x_s_size: usize,
+    discriminant_size_bits: usize,
+    jobs: *const ChiavdfBatchJob,
+    job_count: usize,
+    progress_interval: u64,
+    progress_cb: Option<ProgressCallback>,
+    progress_user_data: *mut c_void,
+) -> *mut ChiavdfByteArray {
+    // SAFETY: The caller passes pointers valid for their sizes, and `job_count`
+    // jobs whose `y_ref_s` are valid for `y_ref_s_size` bytes.
+    let (challenge, x_s, jobs) = unsafe {
+        (
+            bytes(challenge_hash, challenge_size),
+            bytes(x_s, x_s_size),
+            (!jobs.is_null() && job_count != 0)
+                .then(|| std::slice::from_raw_parts(jobs, job_count)),
+        )
+    };
+    let outputs = guarded(|| {
+        if challenge.is_none() || x_s.is_none() {
+            return None;
+        }
+        let jobs = jobs.filter(|_| discriminant_size_bits != 0)?;
+        let mut outputs = Vec::with_capacity(jobs.len());
+        let mut completed_before = 0u64;
+        for job in jobs {
+            // SAFETY: See above.
+            let y_ref = unsafe { bytes(job.y_ref_s, job.y_ref_s_size) };
+            if y_ref.is_none() || job.num_iterations == 0 {
+                return None;
+            }
+            let progress = Progress {
+                interval: progress_interval,
+                cb: progress_cb,
+                user_data: progress_user_data,
+                completed_before,
+            };
+            outputs.push(prove(
+                challenge,
+                x_s,
+                Some(y_ref),
+                discriminant_size_bits,
+                job.num_iterations,
+                &progress,
+            )?);
+            completed_before = completed_before.saturating_add(job.num_iterations);
+        }
+        Some(outputs)
+    });
+    let Some(outputs) = outputs else {
+        return std::ptr::null_mut();
+    };
+    let arrays: Box<[ChiavdfByteArray]> = outputs.into_iter().map(into_array).collect();
+    Box::into_raw(arrays).cast::<ChiavdfByteArray>()
+}
+
+pub(crate) unsafe fn chiavdf_free_byte_array_batch(arrays: *mut ChiavdfByteArray, count: usize) {
+    if arrays.is_null() {
+        return;
+    }
+    // SAFETY: `arrays` is a boxed slice of `count` arrays from the batch prover.
+    let arrays = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(arrays, count)) };
+    for array in arrays {
+        // SAFETY: Each array came from `into_array`.
+        unsafe { chiavdf_free_byte_array(array) };
+    }
+}
+
+pub(crate) unsafe fn chiavdf_free_byte_array(array: ChiavdfByteArray) {
+    if array.data.is_null() {
+        return;
+    }
+    // SAFETY: `array` came from `into_array`, which leaked a boxed slice.
+    drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(array.data, array.length)) });
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    use super::classgroup::{self, Form};
+    use super::prover;
+
+    const CHALLENGE: &[u8] = b"bbr-client-rust-fallback-tests!!";
+
+    fn generator(d: &BigInt) -> Form {
+        Form::from_ab(BigInt::from(2), BigInt::from(1), d).expect("generator")
+    }
+
+    #[test]
+    fn discriminant_has_the_requested_size() {
+        for bits in [512, 1024] {
+            let d = classgroup::create_discriminant(CHALLENGE, bits);
+            assert_eq!(d.bits(), bits as u64);
+            // -D = 7 (mod 8), so (2, 1) is a form of D.
+            assert_eq!(((-&d) % 8u32), BigInt::from(7));
+        }
+    }
+
+    #[test]
+    fn forms_round_trip_through_the_compressed_encoding() {
+        let d = classgroup::create_discriminant(CHALLENGE, 1024);
+        let mut form = generator(&d);
+        for _ in 0..200 {
+            form = form.square(&d);
+            let bytes = classgroup::serialize(&form, 1024).expect("serialize");
+            assert_eq!(bytes.len(), 100);
+            assert_eq!(classgroup::deserialize(&bytes, &d), Some(form.clone()));
+        }
+    }
+
+    #[test]
+    fn squaring_agrees_with_composition() {
+        let d = classgroup::create_discriminant(CHALLENGE, 512);
+        let x = generator(&d).square_times(37, &d);
+        let y = generator(&d).square_times(91, &d);
+        assert_eq!(x.square(&d), x.compose(&x, &d));
+        assert_eq!(x.compose(&y, &d), y.compose(&x, &d));
+        assert_eq!(
+            x.compose(&y, &d).square(&d),
+            x.square(&d).compose(&y.square(&d), &d)
+        );
+    }
+
+    #[test]
+    fn proofs_verify() {
+        let d = classgroup::create_discriminant(CHALLENGE, 512);
+        let x = generator(&d);
+        for num_iterations in [1, 64, 1_000, 4_097] {
+            let (k, l) = prover::approximate_parameters(num_iterations);
+            let mut intermediates = Vec::new();
+            let y = prover::square_from(&x, &d, num_iterations, &mut intermediates, k * l, |_| {});
+            let b = prover::get_b(&x, &y, 512).expect("serializable");
+            let proof = prover::generate_wesolowski(&d, &b, &intermediates, num_iterations, k, l);
+            assert!(
+                prover::verify(&x, &y, &proof, &d, num_iterations),
+                "T={num_iterations}"
+            );
+        }
+    }
+}
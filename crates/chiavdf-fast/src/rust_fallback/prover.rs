@@ -0,0 +1,106 @@
+//! Repeated squaring and Wesolowski proof generation (chiavdf's `prover_slow`).
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::ToPrimitive as _;
+
+use crate::classgroup::{self, Form};
+
+/// Fixed number of intermediates the parameters aim to keep in memory (chiavdf's
+/// `log_memory` of `ApproximateParameters`).
+const LOG_MEMORY: f64 = 23.253_496_66;
+
+/// chiavdf's `ApproximateParameters`: the bucket width `k` and round count `l`
+/// for `num_iterations`, balancing squarings in the proof against memory.
+pub(super) fn approximate_parameters(num_iterations: u64) -> (u64, u64) {
+    let log_t = (num_iterations as f64).log2();
+    let l = if log_t - LOG_MEMORY > 0.000_001 {
+        2f64.powf(log_t - LOG_MEMORY).ceil() as u64
+    } else {
+        1
+    };
+    let intermediate = num_iterations as f64 * std::f64::consts::LN_2 / (2.0 * l as f64);
+    let k = (intermediate.ln() - intermediate.ln().ln() + 0.25)
+        .round()
+        .max(1.0) as u64;
+    (k, l.max(1))
+}
+
+/// `x` squared `num_iterations` times, pushing `x^(2^(i * kl))` for every `i`
+/// onto `intermediates`; `on_step(done)` runs after every squaring.
+pub(super) fn square_from(
+    x: &Form,
+    d: &BigInt,
+    num_iterations: u64,
+    intermediates: &mut Vec<Form>,
+    kl: u64,
+    mut on_step: impl FnMut(u64),
+) -> Form {
+    let mut y = x.clone();
+    for i in 0..num_iterations {
+        if i % kl == 0 {
+            intermediates.push(y.clone());
+        }
+        y = y.square(d);
+        on_step(i + 1);
+    }
+    y
+}
+
+/// chiavdf's `GetB`: the Fiat-Shamir prime of the statement `x -> y`.
+pub(super) fn get_b(x: &Form, y: &Form, d_bits: usize) -> Option<BigUint> {
+    let mut seed = classgroup::serialize(x, d_bits)?;
+    seed.extend(classgroup::serialize(y, d_bits)?);
+    Some(classgroup::hash_prime(&seed, 264, &[263]))
+}
+
+/// chiavdf's `GetBlock`: the `k`-bit digit of `2^num_iterations / b` that
+/// intermediate `i` contributes to.
+fn get_block(i: u64, k: u64, num_iterations: u64, b: &BigUint) -> usize {
+    let exponent = BigUint::from(num_iterations - k * (i + 1));
+    let res = BigUint::from(2u32).modpow(&exponent, b);
+    ((res << k) / b).to_usize().unwrap_or(0)
+}
+
+/// chiavdf's `GenerateWesolowski`: the proof `x^(2^num_iterations / b)` from the
+/// intermediates of [`square_from`].
+pub(super) fn generate_wesolowski(
+    d: &BigInt,
+    b: &BigUint,
+    intermediates: &[Form],
+    num_iterations: u64,
+    k: u64,
+    l: u64,
+) -> Form {
+    let mut proof = Form::identity(d);
+    for j in (0..l).rev() {
+        proof = proof.square_times(k, d);
+        let mut ys = vec![Form::identity(d); 1 << k];
+        for (i, intermediate) in (0u64..).zip(intermediates) {
+            if num_iterations >= k * (i * l + j + 1) {
+                let block = get_block(i * l + j, k, num_iterations, b);
+                ys[block] = ys[block].compose(intermediate, d);
+            }
+        }
+        // prod ys[b]^b as a product of suffix products.
+        let mut suffix = Form::identity(d);
+        let mut round = Form::identity(d);
+        for y in ys.iter().skip(1).rev() {
+            suffix = suffix.compose(y, d);
+            round = round.compose(&suffix, d);
+        }
+        proof = proof.compose(&round, d);
+    }
+    proof
+}
+
+/// Check a proof the way chiavdf's verifier does: `proof^b * x^(2^T mod b) == y`.
+#[cfg(test)]
+pub(super) fn verify(x: &Form, y: &Form, proof: &Form, d: &BigInt, num_iterations: u64) -> bool {
+    let d_bits = usize::try_from(d.bits()).unwrap_or(0);
+    let Some(b) = get_b(x, y, d_bits) else {
+        return false;
+    };
+    let r = BigUint::from(2u32).modpow(&BigUint::from(num_iterations), &b);
+    let lhs = proof.pow(&b, d).compose(&x.pow(&r, d), d);
+    lhs == *y
+}
@@ -0,0 +1,194 @@
+//! Known-answer vectors for the provers.
+//!
+//! Each [`KnownAnswer`] is a proof computed once and committed, so correctness
+//! tests compare against fixed bytes instead of recomputing a reference at
+//! runtime, and a change in any prover's output between versions shows up as a
+//! mismatch. [`prover_variants`] runs one vector through every prover entry
+//! point and [`assert_parity`] checks all of them against it.
+//!
+//! Every vector proves from the generator form. The table was recorded with the
+//! pure-Rust prover (`rust-fallback` feature); after an intended output change,
+//! regenerate it with
+//! `BBR_KNOWN_ANSWERS_RECORD=1 cargo test -p bbr-client-chiavdf-fast --test known_answers -- --nocapture`
+//! and replace [`KNOWN_ANSWERS`] with the printed table.
+
+use crate::api::{
+    ChiavdfBatchJob, ChiavdfFastError, ProofOutput, ProverBuilder, prove_one_weso_fast,
+    prove_one_weso_fast_streaming, prove_one_weso_fast_streaming_getblock_opt,
+};
+use crate::chia::ClassgroupElement;
+
+/// One committed proof: `y = x^(2^num_iterations)` from the generator form `x`
+/// and its Wesolowski witness, both as hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownAnswer {
+    /// Challenge the discriminant is derived from.
+    pub challenge: [u8; 32],
+    /// Discriminant size in bits.
+    pub discriminant_size_bits: usize,
+    /// Number of squarings.
+    pub num_iterations: u64,
+    /// Serialized output form, hex.
+    pub y: &'static str,
+    /// Serialized compact witness, hex.
+    pub witness: &'static str,
+}
+
+impl KnownAnswer {
+    /// The serialized input form (always the generator).
+    pub fn x(&self) -> Vec<u8> {
+        ClassgroupElement::generator().to_bytes()
+    }
+
+    /// The expected proof.
+    pub fn expected(&self) -> ProofOutput {
+        ProofOutput {
+            y: from_hex(self.y),
+            witness: from_hex(self.witness),
+        }
+    }
+
+    /// Short description for failure messages.
+    pub fn label(&self) -> String {
+        format!(
+            "challenge={} bits={} T={}",
+            String::from_utf8_lossy(&self.challenge),
+            self.discriminant_size_bits,
+            self.num_iterations
+        )
+    }
+}
+
+/// The committed vectors: a few discriminant sizes and iteration counts, small
+/// enough to run through every prover variant on each test run.
+pub const KNOWN_ANSWERS: &[KnownAnswer] = &[
+    KnownAnswer {
+        challenge: *b"bbr-client-known-answer-vector-1",
+        discriminant_size_bits: 512,
+        num_iterations: 1000,
+        y: "020010b68567c465797a517fded4f56d5e074168dffaa5f351a5f9f313c755ed7e006faa9b41b6db3fcd97fdfc4bd7e335010702000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        witness: "0200c42641a45602ea43a7b7da666aafbdcbbf7fdd564705cd450fa82b481ea65e513fefdea60319546dbfb96709ad76aa080100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+    },
+    KnownAnswer {
+        challenge: *b"bbr-client-known-answer-vector-1",
+        discriminant_size_bits: 1024,
+        num_iterations: 64,
+        y: "0300099745191777c554350296b31123885ccfe465445d11c072549aca9d1f07b9155ee79d6ba38fb84f374382c539aedf8ce329070dce4566e9917ef2b5fe20bd049efc6511f9146f267461aa34a8ab637774761d328a3fe71d8f78f04cc71c98071003",
+        witness: "04000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+    },
+    KnownAnswer {
+        challenge: *b"bbr-client-known-answer-vector-1",
+        discriminant_size_bits: 1024,
+        num_iterations: 4096,
+        y: "0200edc58be8c040f110016643babc0cdfc88600710aa496ff6de90bb852665f5b7ca18e6bad996e718375605bd70cbc818bd3752fe06aa2c37ffccf2572dd1af4099ebfbf0bab75cf3359d685239778f739d8320969a49fef3cb7475ade371a13010100",
+        witness: "0000e7d25c6afdc51a69d98ad08f70fa2543358f8ae87484c80d6f40f698049838c7fb37573560abad95345036821e19167a26b85f41754934bf747aaa3c343e78427bf6d8eaff08108917efc64d786137ddd529a7a60f891b4bfc0c4356840359700100",
+    },
+    KnownAnswer {
+        challenge: *b"bbr-client-known-answer-vector-2",
+        discriminant_size_bits: 1024,
+        num_iterations: 2003,
+        y: "0200e0aa16369f16ca45f6e6a7475edd16295680597c987667fd2b3a0a94e5fe69234a431e9c05f1b0e37e95094d84a0fc7d4e6657e62bf5f92b21b8799c49848741dda83c8b542a7201322985460a7c344fe6cf628dc3a26f5fa5b3049fd3742f600100",
+        witness: "03001712912c530e8e640547aa496950fe498233eb80dc554d8b2006d3a915b7253f27cdc8711877c6599206ce5767fa403668c4441cdfb6cc86a8861ec1f396a915ba1737af7df38c4fbf6a5f186221617e325d27e82f65e9f6acf3f4a482ea281f0402",
+    },
+    KnownAnswer {
+        challenge: *b"bbr-client-known-answer-vector-2",
+        discriminant_size_bits: 2048,
+        num_iterations: 500,
+        y: "000065ae3d5a6fab7b4637912b47a06ec08e3368f4085446cbe8fcff4614a163f7f9dd9d7aaa8bc23a5ffa1b9e87a5291b73ae6e6d2fa1e85359171efa53188bf91c69fb02177db4bcedf216aab4c269b99d0e0c6047f4c5d6af7dfda3a0859422487e0394dbdd9bd0a006e0efa812a647c76bb04fc8f8a3ed03d7ddbc23a1267a45529f38967ca32b42882607d96d132e502f4f713e340159a89badc38d096c4e189bb3da08301bdd6047e0596a81c3507df5d8fbd8251a438d4098435ec9a1fc320100",
+        witness: "0000d0cbd5e3283f6c735e1d64158bbd01f4a2622f13b45096864837fbe3be622ff20d28b501e8075c4fe08c320e322b900678f8be37ac57990ac5332550fb3fdc45b9faf7ed601a3ede9836fb22ac41ef6d017715a53133ea5ee645e97259419c58f8f59995257f6779297fa20d4c6368250bbef92f6e5af70ce00c23a15f7fdc0d1d3726d8172a3e40198e213daf8c01e0d774683a34c5b4adda021bd8aa2406b38809bc412e33773a1ab9737ae03a17663f930c07cab63013524ed18e060d22090402",
+    },
+];
+
+/// Run `vector` through every prover entry point, returning each variant's name
+/// and result.
+///
+/// Covers the compact and streaming free functions, [`ProverBuilder::prove`],
+/// [`ProverBuilder::prove_batch`] and [`ProverBuilder::prove_into`]; the
+/// streaming variants get the expected `y` as their `y_ref`.
+pub fn prover_variants(
+    vector: &KnownAnswer,
+) -> Vec<(&'static str, Result<ProofOutput, ChiavdfFastError>)> {
+    let x = vector.x();
+    let y_ref = from_hex(vector.y);
+    let challenge = &vector.challenge;
+    let bits = vector.discriminant_size_bits;
+    let iterations = vector.num_iterations;
+    let builder = || ProverBuilder::new(challenge, &x, bits).iterations(iterations);
+    let into = |builder: ProverBuilder<'_>| {
+        // Forms grow with the discriminant; `COMPACT_SIZE` covers 1024 bits.
+        let mut out = vec![0u8; (2 * y_ref.len()).max(ProofOutput::COMPACT_SIZE)];
+        let written = builder.prove_into(&mut out)?;
+        ProofOutput::from_bytes(&out[..written], 0)
+    };
+
+    vec![
+        (
+            "prove_one_weso_fast",
+            prove_one_weso_fast(challenge, &x, bits, iterations),
+        ),
+        (
+            "prove_one_weso_fast_streaming",
+            prove_one_weso_fast_streaming(challenge, &x, &y_ref, bits, iterations),
+        ),
+        (
+            "prove_one_weso_fast_streaming_getblock_opt",
+            prove_one_weso_fast_streaming_getblock_opt(challenge, &x, &y_ref, bits, iterations),
+        ),
+        ("ProverBuilder::prove", builder().prove()),
+        (
+            "ProverBuilder::prove (streaming)",
+            builder().y_ref(&y_ref).getblock_opt(false).prove(),
+        ),
+        (
+            "ProverBuilder::prove_batch",
+            builder()
+                .prove_batch(&[ChiavdfBatchJob {
+                    y_ref_s: &y_ref,
+                    num_iterations: iterations,
+                }])
+                .and_then(|mut results| {
+                    results
+                        .pop()
+                        .unwrap_or(Err(ChiavdfFastError::UnexpectedLength(0)))
+                }),
+        ),
+        ("ProverBuilder::prove_into", into(builder())),
+        (
+            "ProverBuilder::prove_into (streaming)",
+            into(builder().y_ref(&y_ref)),
+        ),
+    ]
+}
+
+/// Every prover variant whose result differs from `vector`, with the reason.
+pub fn parity_mismatches(vector: &KnownAnswer) -> Vec<String> {
+    let expected = vector.expected();
+    prover_variants(vector)
+        .into_iter()
+        .filter_map(|(name, result)| match result {
+            Ok(proof) if proof == expected => None,
+            Ok(proof) if proof.y != expected.y => Some(format!("{name}: y differs")),
+            Ok(_) => Some(format!("{name}: witness differs")),
+            Err(err) => Some(format!("{name}: {err}")),
+        })
+        .collect()
+}
+
+/// Panic unless every prover variant reproduces `vector` byte for byte.
+pub fn assert_parity(vector: &KnownAnswer) {
+    let mismatches = parity_mismatches(vector);
+    assert!(
+        mismatches.is_empty(),
+        "{} ({}): {}",
+        vector.label(),
+        crate::implementation_name(),
+        mismatches.join("; ")
+    );
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("invalid hex in known answer"))
+        .collect()
+}
@@ -0,0 +1,130 @@
+//! Deliberately malformed forms must fail the one call with a structured error,
+//! never end the process, and leave the thread able to prove again.
+
+use bbr_client_chiavdf_fast::{
+    ChiavdfBatchJob, ChiavdfFastError, ClassgroupElement, prove_n_weso_fast, prove_one_weso_fast,
+    prove_one_weso_fast_streaming_getblock_opt, prove_one_weso_fast_streaming_getblock_opt_batch,
+};
+
+const CHALLENGE: [u8; 32] = *b"bbr-client-bad-forms-challenge!!";
+const DISCRIMINANT_BITS: usize = 1024;
+const ITERATIONS: u64 = 512;
+
+/// Inputs no honest caller produces: wrong sizes, saturated and zeroed fields,
+/// and byte patterns that decode to forms off the discriminant.
+fn bad_forms() -> Vec<(&'static str, Vec<u8>)> {
+    let generator = ClassgroupElement::generator().to_bytes();
+    let mut flipped = generator.clone();
+    flipped[0] ^= 0x01;
+    let mut noise = generator.clone();
+    for (idx, byte) in noise.iter_mut().enumerate() {
+        *byte = (idx as u8).wrapping_mul(151).wrapping_add(7);
+    }
+    vec![
+        ("single byte", vec![0x08]),
+        ("truncated", generator[..generator.len() - 1].to_vec()),
+        ("oversized", [generator.as_slice(), &[0xff]].concat()),
+        ("all zero", vec![0x00; generator.len()]),
+        ("all ones", vec![0xff; generator.len()]),
+        ("flipped flags", flipped),
+        ("noise", noise),
+    ]
+}
+
+fn check_rejected<T>(label: &str, result: Result<T, ChiavdfFastError>) -> Option<ChiavdfFastError> {
+    match result {
+        // Some patterns happen to decode to a valid form; that is fine too.
+        Ok(_) => None,
+        Err(ChiavdfFastError::NativeFailure(detail)) => {
+            assert!(!detail.is_empty(), "{label}: native failure without detail");
+            Some(ChiavdfFastError::NativeFailure(detail))
+        }
+        Err(
+            err @ (ChiavdfFastError::InvalidInput(_)
+            | ChiavdfFastError::InvalidForm(_)
+            | ChiavdfFastError::InvalidYRef(_)
+            | ChiavdfFastError::UnexpectedLength(_)),
+        ) => Some(err),
+        Err(err) => panic!("{label}: unexpected error: {err}"),
+    }
+}
+
+fn assert_thread_still_proves() {
+    let x_s = ClassgroupElement::generator().to_bytes();
+    let out = prove_one_weso_fast(&CHALLENGE, &x_s, DISCRIMINANT_BITS, ITERATIONS)
+        .expect("a valid proof after bad forms");
+    assert_eq!(out.y.len(), out.witness.len());
+}
+
+#[test]
+fn bad_x_fails_the_call_only() {
+    for (label, x_s) in bad_forms() {
+        check_rejected(
+            label,
+            prove_one_weso_fast(&CHALLENGE, &x_s, DISCRIMINANT_BITS, ITERATIONS),
+        );
+        check_rejected(
+            label,
+            prove_n_weso_fast(&CHALLENGE, &x_s, DISCRIMINANT_BITS, ITERATIONS, 2),
+        );
+    }
+    assert_thread_still_proves();
+}
+
+#[test]
+fn bad_y_ref_fails_the_call_only() {
+    let x_s = ClassgroupElement::generator().to_bytes();
+    for (label, y_ref) in bad_forms() {
+        let err = check_rejected(
+            label,
+            prove_one_weso_fast_streaming_getblock_opt(
+                &CHALLENGE,
+                &x_s,
+                &y_ref,
+                DISCRIMINANT_BITS,
+                ITERATIONS,
+            ),
+        );
+        assert!(err.is_some(), "{label}: a bad y_ref was accepted");
+    }
+    assert_thread_still_proves();
+}
+
+#[test]
+fn bad_forms_in_a_batch_fail_the_batch_only() {
+    for (label, form) in bad_forms() {
+        let jobs = [ChiavdfBatchJob {
+            y_ref_s: &form,
+            num_iterations: ITERATIONS,
+        }];
+        check_rejected(
+            label,
+            prove_one_weso_fast_streaming_getblock_opt_batch(
+                &CHALLENGE,
+                &form,
+                DISCRIMINANT_BITS,
+                &jobs,
+            ),
+        );
+    }
+    assert_thread_still_proves();
+}
+
+#[test]
+fn bad_forms_on_parallel_threads_leave_the_others_running() {
+    let handles: Vec<_> = bad_forms()
+        .into_iter()
+        .map(|(label, x_s)| {
+            std::thread::spawn(move || {
+                check_rejected(
+                    label,
+                    prove_one_weso_fast(&CHALLENGE, &x_s, DISCRIMINANT_BITS, ITERATIONS),
+                );
+                assert_thread_still_proves();
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().expect("prover thread");
+    }
+}
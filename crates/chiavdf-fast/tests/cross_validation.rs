@@ -0,0 +1,114 @@
+//! Cross-validation of the fast engine against the portable fallback prover.
+//!
+//! Only one implementation is linked per build, so the check runs in two passes
+//! sharing a vector file (see `scripts/crosscheck.sh`):
+//!
+//! 1. Build with `BBR_FORCE_PORTABLE_FALLBACK=1` (or the `rust-fallback`
+//!    feature) and run with `BBR_CROSSCHECK_RECORD=1 BBR_CROSSCHECK_VECTORS=<file>`
+//!    to record outputs.
+//! 2. Build normally and run with `BBR_CROSSCHECK_VECTORS=<file>` to compare.
+//!
+//! Without `BBR_CROSSCHECK_VECTORS` the test does nothing.
+
+use std::fmt::Write as _;
+
+use bbr_client_chiavdf_fast::{
+    ClassgroupElement, implementation_name, prove_one_weso_fast,
+    prove_one_weso_fast_streaming_getblock_opt,
+};
+
+const CHALLENGE: [u8; 32] = *b"bbr-client-crosscheck-challenge!";
+const DISCRIMINANT_BITS: [usize; 3] = [512, 1024, 2048];
+const ITERATIONS: [u64; 6] = [64, 1_000, 4_096, 10_007, 65_537, 100_003];
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{b:02x}");
+        out
+    })
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("invalid hex in vector file"))
+        .collect()
+}
+
+/// One `bits iterations y_hex witness_hex` line per grid point.
+fn record(path: &str) {
+    let x = ClassgroupElement::generator().to_bytes();
+    let mut out = format!("# implementation={}\n", implementation_name());
+    for bits in DISCRIMINANT_BITS {
+        for iterations in ITERATIONS {
+            let proof = prove_one_weso_fast(&CHALLENGE, &x, bits, iterations)
+                .unwrap_or_else(|err| panic!("prove bits={bits} T={iterations}: {err}"));
+            let _ = writeln!(
+                out,
+                "{bits} {iterations} {} {}",
+                to_hex(&proof.y),
+                to_hex(&proof.witness)
+            );
+        }
+    }
+    std::fs::write(path, out).expect("write vector file");
+}
+
+fn compare(path: &str) {
+    let raw = std::fs::read_to_string(path).expect("read vector file");
+    let x = ClassgroupElement::generator().to_bytes();
+    let mut mismatches = Vec::new();
+    let mut checked = 0usize;
+    for line in raw.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [bits, iterations, y_hex, witness_hex] = fields[..] else {
+            panic!("malformed vector line: {line}");
+        };
+        let bits: usize = bits.parse().expect("bits");
+        let iterations: u64 = iterations.parse().expect("iterations");
+        let (y_ref, witness_ref) = (from_hex(y_hex), from_hex(witness_hex));
+
+        let proof = prove_one_weso_fast(&CHALLENGE, &x, bits, iterations)
+            .unwrap_or_else(|err| panic!("prove bits={bits} T={iterations}: {err}"));
+        if proof.y != y_ref {
+            mismatches.push(format!("bits={bits} T={iterations}: y differs"));
+        } else if proof.witness != witness_ref {
+            mismatches.push(format!("bits={bits} T={iterations}: witness differs"));
+        }
+
+        let streaming =
+            prove_one_weso_fast_streaming_getblock_opt(&CHALLENGE, &x, &y_ref, bits, iterations);
+        match streaming {
+            Ok(out) if out.y == y_ref => {}
+            Ok(_) => mismatches.push(format!("bits={bits} T={iterations}: streaming y differs")),
+            Err(err) => mismatches.push(format!("bits={bits} T={iterations}: streaming: {err}")),
+        }
+        checked += 1;
+    }
+
+    assert!(checked > 0, "vector file {path} has no entries");
+    assert!(
+        mismatches.is_empty(),
+        "{} mismatch(es) across {checked} vectors from {path} ({} build):\n{}",
+        mismatches.len(),
+        implementation_name(),
+        mismatches.join("\n")
+    );
+}
+
+#[test]
+fn outputs_match_recorded_vectors() {
+    let Ok(path) = std::env::var("BBR_CROSSCHECK_VECTORS") else {
+        eprintln!("BBR_CROSSCHECK_VECTORS not set; skipping cross-validation");
+        return;
+    };
+    if std::env::var_os("BBR_CROSSCHECK_RECORD").is_some() {
+        record(&path);
+    } else {
+        compare(&path);
+    }
+}
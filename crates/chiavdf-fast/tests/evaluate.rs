@@ -0,0 +1,50 @@
+//! `evaluate_weso` must land on the same `y` as the provers.
+
+use bbr_client_chiavdf_fast::{
+    ChiavdfFastError, ClassgroupElement, evaluate_weso, prove_one_weso_fast,
+};
+
+const CHALLENGE: [u8; 32] = *b"bbr-client-evaluate-challenge!!!";
+const DISCRIMINANT_BITS: usize = 1024;
+
+#[test]
+fn evaluate_matches_the_proof_output() {
+    let x_s = ClassgroupElement::generator().to_bytes();
+    for iterations in [1, 255, 256, 1_000, 10_007] {
+        let y = evaluate_weso(&CHALLENGE, &x_s, DISCRIMINANT_BITS, iterations)
+            .unwrap_or_else(|err| panic!("evaluate T={iterations}: {err}"));
+        let proof = prove_one_weso_fast(&CHALLENGE, &x_s, DISCRIMINANT_BITS, iterations)
+            .unwrap_or_else(|err| panic!("prove T={iterations}: {err}"));
+        assert_eq!(y, proof.y, "T={iterations}");
+    }
+}
+
+#[test]
+fn evaluate_chains_like_repeated_squaring() {
+    let x_s = ClassgroupElement::generator().to_bytes();
+    let half = evaluate_weso(&CHALLENGE, &x_s, DISCRIMINANT_BITS, 500).expect("first half");
+    let chained = evaluate_weso(&CHALLENGE, &half, DISCRIMINANT_BITS, 500).expect("second half");
+    let whole = evaluate_weso(&CHALLENGE, &x_s, DISCRIMINANT_BITS, 1_000).expect("whole");
+    assert_eq!(chained, whole);
+}
+
+#[test]
+fn evaluate_rejects_bad_input() {
+    let x_s = ClassgroupElement::generator().to_bytes();
+    assert!(matches!(
+        evaluate_weso(&[], &x_s, DISCRIMINANT_BITS, 10),
+        Err(ChiavdfFastError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        evaluate_weso(&CHALLENGE, &[], DISCRIMINANT_BITS, 10),
+        Err(ChiavdfFastError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        evaluate_weso(&CHALLENGE, &x_s, 0, 10),
+        Err(ChiavdfFastError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        evaluate_weso(&CHALLENGE, &x_s, DISCRIMINANT_BITS, 0),
+        Err(ChiavdfFastError::InvalidInput(_))
+    ));
+}
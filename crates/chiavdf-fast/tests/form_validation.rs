@@ -0,0 +1,45 @@
+//! Proofs may start from any form of the challenge's discriminant, and forms of
+//! another discriminant are rejected instead of squared.
+
+use bbr_client_chiavdf_fast::{
+    ChiavdfFastError, ClassgroupElement, prove_one_weso_fast, validate_form,
+};
+
+const CHALLENGE: [u8; 32] = *b"bbr-client-form-validation-one!!";
+const OTHER_CHALLENGE: [u8; 32] = *b"bbr-client-form-validation-two!!";
+const DISCRIMINANT_BITS: usize = 1024;
+
+#[test]
+fn proof_from_an_intermediate_form_continues_the_chain() {
+    let generator = ClassgroupElement::generator().to_bytes();
+    let midway = prove_one_weso_fast(&CHALLENGE, &generator, DISCRIMINANT_BITS, 1_000)
+        .expect("first leg")
+        .y;
+    let continued = prove_one_weso_fast(&CHALLENGE, &midway, DISCRIMINANT_BITS, 2_000)
+        .expect("proof from the intermediate form");
+    let direct = prove_one_weso_fast(&CHALLENGE, &generator, DISCRIMINANT_BITS, 3_000)
+        .expect("direct proof");
+    assert_eq!(continued.y, direct.y);
+}
+
+#[test]
+fn form_of_another_discriminant_is_rejected() {
+    let generator = ClassgroupElement::generator().to_bytes();
+    let foreign = prove_one_weso_fast(&OTHER_CHALLENGE, &generator, DISCRIMINANT_BITS, 1_000)
+        .expect("proof for the other challenge")
+        .y;
+    match prove_one_weso_fast(&CHALLENGE, &foreign, DISCRIMINANT_BITS, 1_000) {
+        Err(ChiavdfFastError::InvalidForm(_)) => {}
+        other => panic!("expected InvalidForm, got {other:?}"),
+    }
+    validate_form(&CHALLENGE, &generator, DISCRIMINANT_BITS).expect("generator is valid");
+    validate_form(&OTHER_CHALLENGE, &foreign, DISCRIMINANT_BITS).expect("own discriminant");
+    match validate_form(&CHALLENGE, &foreign, DISCRIMINANT_BITS) {
+        Err(ChiavdfFastError::InvalidForm(_)) => {}
+        other => panic!("expected InvalidForm, got {other:?}"),
+    }
+    assert!(matches!(
+        validate_form(&CHALLENGE, &[], DISCRIMINANT_BITS),
+        Err(ChiavdfFastError::InvalidInput(_))
+    ));
+}
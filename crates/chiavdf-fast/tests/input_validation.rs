@@ -0,0 +1,178 @@
+//! Property tests for input validation at the FFI boundary.
+//!
+//! Arbitrary challenge / `x` / `y_ref` lengths and iteration counts must always
+//! produce either a well-formed `y`/proof pair or a structured error; the
+//! native side must never be handed an empty buffer or an out-of-range count.
+
+use bbr_client_chiavdf_fast::{
+    ChiavdfBatchJob, ChiavdfFastError, ProofOutput, max_iterations, prove_one_weso_fast,
+    prove_one_weso_fast_streaming, prove_one_weso_fast_streaming_getblock_opt,
+    prove_one_weso_fast_streaming_getblock_opt_batch,
+};
+use proptest::prelude::*;
+
+const DISCRIMINANT_BITS: usize = 1024;
+
+/// Keeps proofs that do reach the native prover cheap.
+const MAX_TEST_ITERATIONS: u64 = 2_048;
+
+fn bytes(max_len: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..=max_len)
+}
+
+/// Empty (to be rejected) or at least two bytes long.
+///
+/// The discriminant search counts the challenge up as a big-endian integer, so
+/// a one-byte challenge only yields 64 distinct candidates and the search
+/// often never finds a prime.
+fn challenge_bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![Just(Vec::new()), prop::collection::vec(any::<u8>(), 2..=64)]
+}
+
+/// Mostly a valid classgroup element (so some proofs succeed), sometimes garbage.
+fn x_bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        Just({
+            let mut element = vec![0u8; 100];
+            element[0] = 0x08;
+            element
+        }),
+        bytes(200),
+    ]
+}
+
+fn iterations() -> impl Strategy<Value = u64> {
+    prop_oneof![
+        Just(0u64),
+        1..=MAX_TEST_ITERATIONS,
+        (max_iterations() + 1)..=u64::MAX,
+    ]
+}
+
+fn expected_rejection(challenge: &[u8], x_s: &[u8], num_iterations: u64) -> bool {
+    challenge.is_empty()
+        || x_s.is_empty()
+        || num_iterations == 0
+        || num_iterations > max_iterations()
+}
+
+fn check_single(
+    result: Result<ProofOutput, ChiavdfFastError>,
+    must_reject: bool,
+) -> Result<(), TestCaseError> {
+    match result {
+        Ok(out) => {
+            prop_assert!(!must_reject, "invalid input was accepted");
+            prop_assert!(
+                !out.y.is_empty() && out.y.len() == out.witness.len(),
+                "bad lengths {}/{}",
+                out.y.len(),
+                out.witness.len()
+            );
+        }
+        Err(ChiavdfFastError::InvalidInput(_)) => {}
+        Err(
+            ChiavdfFastError::NativeFailure(_)
+            | ChiavdfFastError::InvalidYRef(_)
+            | ChiavdfFastError::InvalidForm(_)
+            | ChiavdfFastError::UnexpectedLength(_),
+        ) => {
+            prop_assert!(!must_reject, "invalid input reached the native prover");
+        }
+        Err(err) => prop_assert!(false, "unexpected error: {err}"),
+    }
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(48))]
+
+    #[test]
+    fn prove_validates_inputs(
+        challenge in challenge_bytes(),
+        x_s in x_bytes(),
+        num_iterations in iterations(),
+    ) {
+        let must_reject = expected_rejection(&challenge, &x_s, num_iterations);
+        let result = prove_one_weso_fast(&challenge, &x_s, DISCRIMINANT_BITS, num_iterations);
+        check_single(result, must_reject)?;
+    }
+
+    #[test]
+    fn streaming_validates_inputs(
+        challenge in challenge_bytes(),
+        x_s in x_bytes(),
+        y_ref in bytes(200),
+        num_iterations in iterations(),
+    ) {
+        let must_reject =
+            expected_rejection(&challenge, &x_s, num_iterations) || y_ref.is_empty();
+        let streaming = prove_one_weso_fast_streaming(
+            &challenge,
+            &x_s,
+            &y_ref,
+            DISCRIMINANT_BITS,
+            num_iterations,
+        );
+        check_single(streaming, must_reject)?;
+        let getblock = prove_one_weso_fast_streaming_getblock_opt(
+            &challenge,
+            &x_s,
+            &y_ref,
+            DISCRIMINANT_BITS,
+            num_iterations,
+        );
+        check_single(getblock, must_reject)?;
+    }
+
+    #[test]
+    fn batch_validates_inputs(
+        challenge in challenge_bytes(),
+        x_s in x_bytes(),
+        jobs in prop::collection::vec((bytes(200), iterations()), 0..4),
+    ) {
+        let batch_jobs: Vec<ChiavdfBatchJob<'_>> = jobs
+            .iter()
+            .map(|(y_ref, num_iterations)| ChiavdfBatchJob {
+                y_ref_s: y_ref,
+                num_iterations: *num_iterations,
+            })
+            .collect();
+        let must_reject = challenge.is_empty()
+            || x_s.is_empty()
+            || jobs.is_empty()
+            || jobs.iter().any(|(y_ref, num_iterations)| {
+                y_ref.is_empty() || *num_iterations == 0 || *num_iterations > max_iterations()
+            });
+
+        match prove_one_weso_fast_streaming_getblock_opt_batch(
+            &challenge,
+            &x_s,
+            DISCRIMINANT_BITS,
+            &batch_jobs,
+        ) {
+            Ok(outputs) => {
+                prop_assert!(!must_reject, "invalid batch was accepted");
+                prop_assert_eq!(outputs.len(), batch_jobs.len());
+                for out in outputs {
+                    prop_assert!(
+                        !out.y.is_empty() && out.y.len() == out.witness.len(),
+                        "bad lengths {}/{}",
+                        out.y.len(),
+                        out.witness.len()
+                    );
+                }
+            }
+            Err(ChiavdfFastError::InvalidInput(_)) => {}
+            Err(
+                ChiavdfFastError::NativeFailure(_)
+                | ChiavdfFastError::InvalidYRef(_)
+                | ChiavdfFastError::InvalidForm(_)
+                | ChiavdfFastError::UnexpectedLength(_),
+            ) => {
+                prop_assert!(!must_reject, "invalid batch reached the native prover");
+            }
+            Err(err) => prop_assert!(false, "unexpected error: {err}"),
+        }
+    }
+}
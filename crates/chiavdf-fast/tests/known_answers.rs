@@ -0,0 +1,53 @@
+//! Every prover variant must reproduce the committed known-answer vectors.
+//!
+//! With `BBR_KNOWN_ANSWERS_RECORD=1` the test instead prints a fresh
+//! `KNOWN_ANSWERS` table computed by the linked prover (see
+//! `bbr_client_chiavdf_fast::test_vectors`).
+
+use std::fmt::Write as _;
+
+use bbr_client_chiavdf_fast::prove_one_weso_fast;
+use bbr_client_chiavdf_fast::test_vectors::{KNOWN_ANSWERS, assert_parity};
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{b:02x}");
+        out
+    })
+}
+
+fn record() {
+    let mut out = String::from("\npub const KNOWN_ANSWERS: &[KnownAnswer] = &[\n");
+    for vector in KNOWN_ANSWERS {
+        let proof = prove_one_weso_fast(
+            &vector.challenge,
+            &vector.x(),
+            vector.discriminant_size_bits,
+            vector.num_iterations,
+        )
+        .unwrap_or_else(|err| panic!("{}: {err}", vector.label()));
+        let _ = write!(
+            out,
+            "    KnownAnswer {{\n        challenge: *b\"{}\",\n        discriminant_size_bits: {},\n        num_iterations: {},\n        y: \"{}\",\n        witness: \"{}\",\n    }},\n",
+            String::from_utf8_lossy(&vector.challenge),
+            vector.discriminant_size_bits,
+            vector.num_iterations,
+            to_hex(&proof.y),
+            to_hex(&proof.witness)
+        );
+    }
+    out.push_str("];");
+    println!("{out}");
+}
+
+#[test]
+fn every_prover_variant_matches_the_known_answers() {
+    if std::env::var_os("BBR_KNOWN_ANSWERS_RECORD").is_some() {
+        record();
+        return;
+    }
+    assert!(!KNOWN_ANSWERS.is_empty());
+    for vector in KNOWN_ANSWERS {
+        assert_parity(vector);
+    }
+}
@@ -0,0 +1,79 @@
+//! Proofs written into caller-owned buffers must match the allocating provers.
+
+use bbr_client_chiavdf_fast::{
+    ChiavdfBatchJob, ChiavdfFastError, ClassgroupElement, ProofOutput, ProverBuilder,
+};
+
+const CHALLENGE: [u8; 32] = *b"bbr-client-prove-into-challenge!";
+const DISCRIMINANT_BITS: usize = 1024;
+
+#[test]
+fn prove_into_matches_prove() {
+    let x_s = ClassgroupElement::generator().to_bytes();
+    let expected = ProverBuilder::new(&CHALLENGE, &x_s, DISCRIMINANT_BITS)
+        .iterations(5_000)
+        .prove()
+        .expect("compact proof");
+
+    let mut out = [0u8; ProofOutput::COMPACT_SIZE];
+    let written = ProverBuilder::new(&CHALLENGE, &x_s, DISCRIMINANT_BITS)
+        .iterations(5_000)
+        .prove_into(&mut out)
+        .expect("compact proof into a buffer");
+    assert_eq!(&out[..written], expected.to_bytes().as_slice());
+
+    let streamed = ProverBuilder::new(&CHALLENGE, &x_s, DISCRIMINANT_BITS)
+        .iterations(5_000)
+        .y_ref(&expected.y)
+        .prove_into(&mut out)
+        .expect("streaming proof into a buffer");
+    assert_eq!(
+        ProofOutput::from_bytes(&out[..streamed], 0)
+            .expect("split")
+            .y,
+        expected.y
+    );
+}
+
+#[test]
+fn prove_batch_into_writes_jobs_in_order() {
+    let x_s = ClassgroupElement::generator().to_bytes();
+    let iterations = [3_000u64, 7_000, 5_000];
+    let ys: Vec<Vec<u8>> = iterations
+        .iter()
+        .map(|&t| {
+            ProverBuilder::new(&CHALLENGE, &x_s, DISCRIMINANT_BITS)
+                .iterations(t)
+                .prove()
+                .expect("reference proof")
+                .y
+        })
+        .collect();
+    let jobs: Vec<ChiavdfBatchJob<'_>> = ys
+        .iter()
+        .zip(iterations)
+        .map(|(y, num_iterations)| ChiavdfBatchJob {
+            y_ref_s: y,
+            num_iterations,
+        })
+        .collect();
+    let expected = ProverBuilder::new(&CHALLENGE, &x_s, DISCRIMINANT_BITS)
+        .prove_batch(&jobs)
+        .expect("batch");
+
+    let mut out = vec![0u8; jobs.len() * ProofOutput::COMPACT_SIZE];
+    let written = ProverBuilder::new(&CHALLENGE, &x_s, DISCRIMINANT_BITS)
+        .prove_batch_into(&jobs, &mut out)
+        .expect("batch into a buffer");
+    assert_eq!(written, out.len());
+    for (chunk, proof) in out.chunks(ProofOutput::COMPACT_SIZE).zip(&expected) {
+        let proof = proof.as_ref().expect("batch job");
+        assert_eq!(chunk, proof.to_bytes().as_slice());
+    }
+
+    let mut short = vec![0u8; written - 1];
+    assert!(matches!(
+        ProverBuilder::new(&CHALLENGE, &x_s, DISCRIMINANT_BITS).prove_batch_into(&jobs, &mut short),
+        Err(ChiavdfFastError::InvalidInput(_))
+    ));
+}
@@ -17,6 +17,20 @@ pub fn set_current_thread_affinity(cpus: &[usize]) -> io::Result<()> {
     }
 }
 
+/// The CPUs the current thread may run on.
+///
+/// On non-Linux platforms this is currently always empty.
+pub fn current_thread_affinity() -> io::Result<Vec<usize>> {
+    #[cfg(target_os = "linux")]
+    {
+        current_thread_affinity_linux()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Ok(Vec::new())
+    }
+}
+
 #[cfg(target_os = "linux")]
 fn set_current_thread_affinity_linux(cpus: &[usize]) -> io::Result<()> {
     if cpus.is_empty() {
@@ -46,3 +60,29 @@ fn set_current_thread_affinity_linux(cpus: &[usize]) -> io::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(target_os = "linux")]
+fn current_thread_affinity_linux() -> io::Result<Vec<usize>> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+
+    let res =
+        unsafe { libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) };
+    if res != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let word_bits = std::mem::size_of::<libc::c_ulong>() * 8;
+    let words = std::mem::size_of::<libc::cpu_set_t>() / std::mem::size_of::<libc::c_ulong>();
+    let bits: *const libc::c_ulong = (&set as *const libc::cpu_set_t).cast::<libc::c_ulong>();
+
+    let mut cpus = Vec::new();
+    for idx in 0..words {
+        let word = unsafe { *bits.add(idx) };
+        for bit in 0..word_bits {
+            if word & ((1 as libc::c_ulong) << bit) != 0 {
+                cpus.push(idx * word_bits + bit);
+            }
+        }
+    }
+    Ok(cpus)
+}
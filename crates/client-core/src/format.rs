@@ -0,0 +1,199 @@
+//! Human-readable numbers, durations and submit reasons shared by the CLI, the
+//! GUI and engine events, so every frontend prints the same strings.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Separators used when printing numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberLocale {
+    /// Inserted between groups of three digits; `None` prints digits ungrouped.
+    pub thousands_separator: Option<char>,
+    /// Separates whole seconds from milliseconds in durations.
+    pub decimal_separator: char,
+}
+
+impl NumberLocale {
+    /// `1,234,567` and `1.500s`.
+    pub const ENGLISH: Self = Self {
+        thousands_separator: Some(','),
+        decimal_separator: '.',
+    };
+
+    /// `1.234.567` and `1,500s`.
+    pub const EUROPEAN: Self = Self {
+        thousands_separator: Some('.'),
+        decimal_separator: ',',
+    };
+
+    /// `1234567` and `1.500s`, for logs meant to be parsed.
+    pub const PLAIN: Self = Self {
+        thousands_separator: None,
+        decimal_separator: '.',
+    };
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+/// `n` with thousands grouped by commas.
+pub fn format_number(n: u64) -> String {
+    format_number_with(n, NumberLocale::default())
+}
+
+/// `n` with thousands grouped as `locale` says.
+pub fn format_number_with(n: u64, locale: NumberLocale) -> String {
+    let s = n.to_string();
+    let Some(separator) = locale.thousands_separator else {
+        return s;
+    };
+    let mut out = String::with_capacity(s.len() + s.len() / 3);
+    for (i, ch) in s.chars().enumerate() {
+        if i != 0 && (s.len() - i).is_multiple_of(3) {
+            out.push(separator);
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Compact duration: `250ms`, `1.500s`, `2m05s`, `1h02m03s`, `2d03h04m05s`.
+pub fn format_duration(d: Duration) -> String {
+    format_duration_with(d, NumberLocale::default())
+}
+
+/// [`format_duration`] with the decimal separator taken from `locale`.
+pub fn format_duration_with(d: Duration, locale: NumberLocale) -> String {
+    let ms = d.as_millis();
+    if ms < 1000 {
+        return format!("{ms}ms");
+    }
+
+    if ms < 60_000 {
+        let seconds = ms / 1000;
+        let millis = ms % 1000;
+        let separator = locale.decimal_separator;
+        return format!("{seconds}{separator}{millis:03}s");
+    }
+
+    if ms < 3_600_000 {
+        let minutes = ms / 60_000;
+        let seconds = (ms % 60_000) / 1000;
+        return format!("{minutes}m{seconds:02}s");
+    }
+
+    if ms < 86_400_000 {
+        let hours = ms / 3_600_000;
+        let minutes = (ms % 3_600_000) / 60_000;
+        let seconds = (ms % 60_000) / 1000;
+        return format!("{hours}h{minutes:02}m{seconds:02}s");
+    }
+
+    let days = ms / 86_400_000;
+    let hours = (ms % 86_400_000) / 3_600_000;
+    let minutes = (ms % 3_600_000) / 60_000;
+    let seconds = (ms % 60_000) / 1000;
+    format!("{days}d{hours:02}h{minutes:02}m{seconds:02}s")
+}
+
+/// Remaining time as `HH:MM:SS`; hours keep growing past 99.
+pub fn format_eta(total_seconds: u64) -> String {
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    format!("{hours:02}:{minutes:02}:{seconds:02}")
+}
+
+/// Backend submit reason (`already_compact`, `too-late`) as a capitalized phrase.
+pub fn humanize_submit_reason(reason: &str) -> String {
+    let s = reason.trim();
+    if s.is_empty() {
+        return "Unknown".to_string();
+    }
+
+    let lower = s.to_ascii_lowercase();
+    match lower.as_str() {
+        "accepted" => return "Accepted".to_string(),
+        "already_compact" => return "Already compact".to_string(),
+        _ => {}
+    }
+
+    let mut out = String::with_capacity(lower.len());
+    let mut capitalize_next = true;
+    for ch in lower.chars() {
+        if ch == '_' || ch == '-' {
+            out.push(' ');
+            capitalize_next = true;
+            continue;
+        }
+        if capitalize_next {
+            out.extend(ch.to_uppercase());
+            capitalize_next = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_grouped_by_locale() {
+        assert_eq!(format_number(0), "0");
+        assert_eq!(format_number(999), "999");
+        assert_eq!(format_number(1_000), "1,000");
+        assert_eq!(format_number(1_234_567), "1,234,567");
+        assert_eq!(
+            format_number_with(1_234_567, NumberLocale::EUROPEAN),
+            "1.234.567"
+        );
+        assert_eq!(
+            format_number_with(1_234_567, NumberLocale::PLAIN),
+            "1234567"
+        );
+        assert_eq!(format_number(u64::MAX), "18,446,744,073,709,551,615");
+    }
+
+    #[test]
+    fn durations_pick_the_largest_useful_unit() {
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_duration(Duration::from_millis(1_500)), "1.500s");
+        assert_eq!(
+            format_duration_with(Duration::from_millis(1_500), NumberLocale::EUROPEAN),
+            "1,500s"
+        );
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m05s");
+        assert_eq!(format_duration(Duration::from_secs(3_723)), "1h02m03s");
+        assert_eq!(format_duration(Duration::from_secs(183_845)), "2d03h04m05s");
+    }
+
+    #[test]
+    fn eta_is_clock_style() {
+        assert_eq!(format_eta(0), "00:00:00");
+        assert_eq!(format_eta(3_723), "01:02:03");
+        assert_eq!(format_eta(360_000), "100:00:00");
+    }
+
+    #[test]
+    fn submit_reasons_read_as_phrases() {
+        assert_eq!(humanize_submit_reason("accepted"), "Accepted");
+        assert_eq!(humanize_submit_reason("ALREADY_COMPACT"), "Already compact");
+        assert_eq!(humanize_submit_reason("too-late"), "Too Late");
+        assert_eq!(humanize_submit_reason("  "), "Unknown");
+    }
+
+    #[test]
+    fn locale_fields_default_individually() {
+        let locale: NumberLocale = serde_json::from_str(r#"{"decimal_separator":","}"#).unwrap();
+        assert_eq!(locale.thousands_separator, Some(','));
+        assert_eq!(locale.decimal_separator, ',');
+    }
+}
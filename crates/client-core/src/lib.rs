@@ -1 +1,7 @@
+pub mod format;
+pub mod network;
+pub mod redact;
+pub mod settings;
+pub mod signing;
 pub mod submitter;
+pub mod ui_state;
@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+
+use crate::network::NetworkSettings;
+use crate::redact::Redact;
+use crate::submitter::{write_config_json, xdg_config_home};
+use crate::ui_state::UiState;
+
+/// Saved worker settings (parallelism, work mode, memory budget).
+///
+/// Every field is optional: unset fields fall back to the frontend defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkerSettings {
+    #[serde(default)]
+    pub parallel: Option<u32>,
+    #[serde(default)]
+    pub use_groups: Option<bool>,
+    #[serde(default)]
+    pub mem_budget_bytes: Option<u64>,
+    /// Only lease jobs with at most this many iterations (for low-power machines).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<u64>,
+    /// Idle for the rest of the day after submitting this many proofs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_max_proofs: Option<u64>,
+    /// Idle for the rest of the day after this many hours of worker compute time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_max_cpu_hours: Option<f64>,
+    /// Proxy/TLS/timeout settings; see [`NetworkSettings`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkSettings>,
+    /// Which engine events the GUI forwards to its webview.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_verbosity: Option<EventVerbosity>,
+    /// GUI window and dashboard layout; see [`UiState`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui_state: Option<UiState>,
+}
+
+/// How much of the engine event stream the GUI forwards to its webview.
+///
+/// Worker progress is always available through polling; lower levels cut IPC
+/// traffic on machines running many workers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventVerbosity {
+    /// Everything, including worker progress once it moves by at least 1%.
+    Full,
+    /// Everything but worker progress.
+    #[default]
+    Normal,
+    /// Lifecycle events, job results, warnings and errors; per-worker job and
+    /// stage changes are picked up from the snapshot instead.
+    Quiet,
+}
+
+impl Redact for WorkerSettings {
+    fn redacted(&self) -> Self {
+        Self {
+            network: self.network.as_ref().map(Redact::redacted),
+            ..self.clone()
+        }
+    }
+}
+
+pub fn worker_settings_path() -> anyhow::Result<PathBuf> {
+    Ok(xdg_config_home()?.join("bbr-client").join("settings.json"))
+}
+
+pub fn load_worker_settings() -> anyhow::Result<Option<WorkerSettings>> {
+    let path = worker_settings_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = std::fs::read_to_string(&path)?;
+    let settings: WorkerSettings = serde_json::from_str(&raw)
+        .with_context(|| format!("invalid settings file {}", path.display()))?;
+    if let Some(network) = &settings.network {
+        network
+            .validate()
+            .with_context(|| format!("invalid settings file {}", path.display()))?;
+    }
+    Ok(Some(settings))
+}
+
+/// Save `settings`. When `settings.network` or `settings.ui_state` is unset, that section
+/// already on disk is kept, so frontends that don't edit it can't drop it.
+pub fn save_worker_settings(settings: &WorkerSettings) -> anyhow::Result<()> {
+    let mut settings = settings.clone();
+    if let Some(network) = &settings.network {
+        network.validate()?;
+    }
+    if settings.network.is_none() || settings.ui_state.is_none() {
+        let existing = load_worker_settings().ok().flatten().unwrap_or_default();
+        settings.network = settings.network.or(existing.network);
+        settings.ui_state = settings.ui_state.or(existing.ui_state);
+    }
+    write_config_json(&worker_settings_path()?, &settings)
+}
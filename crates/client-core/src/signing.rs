@@ -0,0 +1,170 @@
+//! Optional ed25519 key for signing job submissions, for backends that want to
+//! authenticate who submitted a proof.
+//!
+//! The key lives next to the other config files (see [`signing_key_path`]) as
+//! the base64 seed, readable only by the owner on Unix. Submissions carry the
+//! public key in [`SIGNATURE_KEY_HEADER`] and the signature of
+//! [`submission_message`] in [`SIGNATURE_HEADER`], both base64.
+
+use std::fmt;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+use base64::Engine as _;
+use base64::engine::general_purpose::{STANDARD as B64, STANDARD_NO_PAD as B64_NO_PAD};
+use ed25519_dalek::{SECRET_KEY_LENGTH, Signer as _, SigningKey};
+use sha2::{Digest as _, Sha256};
+
+use crate::submitter::xdg_config_home;
+
+/// Request header carrying the base64 ed25519 public key.
+pub const SIGNATURE_KEY_HEADER: &str = "x-wesoforge-public-key";
+
+/// Request header carrying the base64 signature of [`submission_message`].
+pub const SIGNATURE_HEADER: &str = "x-wesoforge-signature";
+
+/// Domain separator so a submission signature can't be replayed as anything else.
+const SUBMISSION_CONTEXT: &[u8] = b"wesoforge-submit-v1";
+
+/// Bytes signed for a submission: the context, job id, lease id and raw witness.
+///
+/// Binding the job and lease keeps a captured signature from being reused for
+/// another job or a later lease of the same one.
+pub fn submission_message(job_id: u64, lease_id: &str, witness: &[u8]) -> Vec<u8> {
+    let mut msg =
+        Vec::with_capacity(SUBMISSION_CONTEXT.len() + 10 + lease_id.len() + witness.len());
+    msg.extend_from_slice(SUBMISSION_CONTEXT);
+    msg.push(0);
+    msg.extend_from_slice(&job_id.to_be_bytes());
+    msg.extend_from_slice(lease_id.as_bytes());
+    msg.push(0);
+    msg.extend_from_slice(witness);
+    msg
+}
+
+/// Signs submissions with the locally stored key. `Debug` shows only the fingerprint.
+#[derive(Clone)]
+pub struct SubmissionSigner {
+    key: SigningKey,
+}
+
+impl SubmissionSigner {
+    /// Fresh random key (not saved; see [`generate_signing_key`]).
+    pub fn generate() -> anyhow::Result<Self> {
+        let mut seed = [0u8; SECRET_KEY_LENGTH];
+        getrandom::fill(&mut seed).map_err(|err| anyhow::anyhow!("no system randomness: {err}"))?;
+        Ok(Self {
+            key: SigningKey::from_bytes(&seed),
+        })
+    }
+
+    /// Base64 public key, as sent in [`SIGNATURE_KEY_HEADER`].
+    pub fn public_key_b64(&self) -> String {
+        B64.encode(self.key.verifying_key().as_bytes())
+    }
+
+    /// `SHA256:<base64>` digest of the public key, for comparing keys out of band.
+    pub fn fingerprint(&self) -> String {
+        let digest = Sha256::digest(self.key.verifying_key().as_bytes());
+        format!("SHA256:{}", B64_NO_PAD.encode(digest))
+    }
+
+    /// Base64 signature of [`submission_message`], as sent in [`SIGNATURE_HEADER`].
+    pub fn sign_submission(&self, job_id: u64, lease_id: &str, witness: &[u8]) -> String {
+        let signature = self
+            .key
+            .sign(&submission_message(job_id, lease_id, witness));
+        B64.encode(signature.to_bytes())
+    }
+}
+
+impl fmt::Debug for SubmissionSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubmissionSigner")
+            .field("fingerprint", &self.fingerprint())
+            .finish()
+    }
+}
+
+pub fn signing_key_path() -> anyhow::Result<PathBuf> {
+    Ok(xdg_config_home()?.join("bbr-client").join("signing_key"))
+}
+
+/// Load the stored key, or `None` when none has been generated.
+pub fn load_signing_key() -> anyhow::Result<Option<SubmissionSigner>> {
+    let path = signing_key_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = std::fs::read_to_string(&path)?;
+    let seed: [u8; SECRET_KEY_LENGTH] = B64
+        .decode(raw.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("invalid signing key file {}", path.display()))?;
+    Ok(Some(SubmissionSigner {
+        key: SigningKey::from_bytes(&seed),
+    }))
+}
+
+/// Generate and save a new key. Refuses to replace an existing key unless
+/// `overwrite` is set, since backends may have registered its public key.
+pub fn generate_signing_key(overwrite: bool) -> anyhow::Result<SubmissionSigner> {
+    let path = signing_key_path()?;
+    if path.exists() && !overwrite {
+        anyhow::bail!("a signing key already exists at {}", path.display());
+    }
+    let signer = SubmissionSigner::generate()?;
+    write_secret(&path, &B64.encode(signer.key.to_bytes()))?;
+    Ok(signer)
+}
+
+/// Write `contents` to `path` atomically, readable only by the owner on Unix.
+fn write_secret(path: &Path, contents: &str) -> anyhow::Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid config path: {}", path.display()))?;
+    std::fs::create_dir_all(dir)?;
+
+    let tmp = path.with_extension("tmp");
+    let _ = std::fs::remove_file(&tmp);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt as _;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signature, Verifier as _};
+
+    use super::*;
+
+    #[test]
+    fn submission_signature_verifies_against_public_key() {
+        let signer = SubmissionSigner::generate().expect("key");
+        let sig = signer.sign_submission(7, "lease-1", b"witness");
+
+        let sig = Signature::from_slice(&B64.decode(sig).expect("base64")).expect("signature");
+        let key = signer.key.verifying_key();
+        assert!(
+            key.verify(&submission_message(7, "lease-1", b"witness"), &sig)
+                .is_ok()
+        );
+        assert!(
+            key.verify(&submission_message(8, "lease-1", b"witness"), &sig)
+                .is_err()
+        );
+        assert!(signer.fingerprint().starts_with("SHA256:"));
+        assert!(!format!("{signer:?}").contains(&B64.encode(signer.key.to_bytes())));
+    }
+}
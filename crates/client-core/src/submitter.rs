@@ -1,9 +1,13 @@
+use std::fmt;
 use std::io::Write as _;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+use crate::redact::{REDACTED, Redact, redact_reward_address};
+
+/// `Debug` masks the reward address and name; see [`Redact`].
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct SubmitterConfig {
     #[serde(default)]
     pub reward_address: Option<String>,
@@ -25,7 +29,26 @@ impl SubmitterConfig {
     }
 }
 
-fn xdg_config_home() -> anyhow::Result<PathBuf> {
+impl Redact for SubmitterConfig {
+    fn redacted(&self) -> Self {
+        Self {
+            reward_address: self.reward_address.as_deref().map(redact_reward_address),
+            name: self.name.as_ref().map(|_| REDACTED.to_string()),
+        }
+    }
+}
+
+impl fmt::Debug for SubmitterConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let redacted = self.redacted();
+        f.debug_struct("SubmitterConfig")
+            .field("reward_address", &redacted.reward_address)
+            .field("name", &redacted.name)
+            .finish()
+    }
+}
+
+pub(crate) fn xdg_config_home() -> anyhow::Result<PathBuf> {
     if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
         let dir = PathBuf::from(dir);
         if dir.as_os_str().is_empty() {
@@ -80,22 +103,96 @@ pub fn load_submitter_config() -> anyhow::Result<Option<SubmitterConfig>> {
 }
 
 pub fn save_submitter_config(cfg: &SubmitterConfig) -> anyhow::Result<()> {
-    let path = submitter_config_path()?;
+    let mut cfg = cfg.clone();
+    cfg.normalize();
+    write_config_json(&submitter_config_path()?, &cfg)
+}
+
+pub(crate) fn write_config_json<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
     let dir = path
         .parent()
         .ok_or_else(|| anyhow::anyhow!("invalid config path: {}", path.display()))?;
     std::fs::create_dir_all(dir)?;
 
-    let mut cfg = cfg.clone();
-    cfg.normalize();
-
-    let json = serde_json::to_string_pretty(&cfg)?;
+    let json = serde_json::to_string_pretty(value)?;
     let tmp = path.with_extension("json.tmp");
     std::fs::write(&tmp, json)?;
     std::fs::rename(tmp, path)?;
     Ok(())
 }
 
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+/// Data + checksum characters of an encoded 32-byte puzzle hash.
+const REWARD_ADDRESS_DATA_LEN: usize = 58;
+
+fn bech32_polymod(hrp: &str, data: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+
+    let values = hrp
+        .bytes()
+        .map(|c| c >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.bytes().map(|c| c & 31))
+        .chain(data.iter().copied());
+
+    let mut chk: u32 = 1;
+    for v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ u32::from(v);
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+/// Validate a Chia reward address (`xch1…`, or `txch1…` for testnet).
+///
+/// Checks the prefix, length, character set and bech32m checksum, so typos are
+/// caught before the backend rejects the address.
+pub fn validate_reward_address(address: &str) -> anyhow::Result<()> {
+    let address = address.trim();
+    let lower = address.to_ascii_lowercase();
+    if address != lower && address != address.to_ascii_uppercase() {
+        anyhow::bail!("invalid address: mixed upper and lower case");
+    }
+
+    let Some((hrp, data)) = lower.rsplit_once('1') else {
+        anyhow::bail!("invalid address: expected an xch1… address");
+    };
+    if hrp != "xch" && hrp != "txch" {
+        anyhow::bail!("invalid address: expected an xch1… address");
+    }
+    if data.len() != REWARD_ADDRESS_DATA_LEN {
+        anyhow::bail!(
+            "invalid address: expected {} characters, got {}",
+            hrp.len() + 1 + REWARD_ADDRESS_DATA_LEN,
+            address.len()
+        );
+    }
+
+    let mut values = Vec::with_capacity(data.len());
+    for ch in data.bytes() {
+        let Some(v) = BECH32_CHARSET.iter().position(|&c| c == ch) else {
+            anyhow::bail!("invalid address: unexpected character {:?}", ch as char);
+        };
+        values.push(v as u8);
+    }
+    if bech32_polymod(hrp, &values) != BECH32M_CONST {
+        anyhow::bail!("invalid address: checksum mismatch (typo?)");
+    }
+    Ok(())
+}
+
 pub fn ensure_submitter_config(interactive: bool) -> anyhow::Result<Option<SubmitterConfig>> {
     match load_submitter_config() {
         Ok(Some(cfg)) => return Ok(Some(cfg)),
@@ -133,10 +230,13 @@ fn prompt_submitter_config() -> anyhow::Result<SubmitterConfig> {
 
     let reward_address = loop {
         let v = prompt_line("Reward address (xch…): ")?;
-        if v.is_empty() || v.starts_with("xch") {
+        if v.is_empty() {
             break v;
         }
-        println!("Invalid address: expected an xch… address (or leave empty).");
+        match validate_reward_address(&v) {
+            Ok(()) => break v,
+            Err(err) => println!("{err} (or leave empty)."),
+        }
     };
     let name = prompt_line("Name: ")?;
 
@@ -147,3 +247,40 @@ fn prompt_submitter_config() -> anyhow::Result<SubmitterConfig> {
     cfg.normalize();
     Ok(cfg)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes the puzzle hash 0x00 0x01 .. 0x1f.
+    const ADDRESS: &str = "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dkm";
+
+    #[test]
+    fn reward_address_validation() {
+        validate_reward_address(ADDRESS).unwrap();
+        validate_reward_address(&format!(" {} ", ADDRESS.to_ascii_uppercase())).unwrap();
+
+        let typo = ADDRESS.replacen("qqqs", "qqqz", 1);
+        let mixed_case = ADDRESS.replacen("qqqs", "Qqqs", 1);
+        for (bad, reason) in [
+            (typo.as_str(), "checksum"),
+            (mixed_case.as_str(), "mixed"),
+            (&ADDRESS[..ADDRESS.len() - 1], "characters"),
+            (
+                "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dka",
+                "checksum",
+            ),
+            (
+                "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dko",
+                "character",
+            ),
+            (
+                "bc1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dkm",
+                "xch1",
+            ),
+        ] {
+            let err = validate_reward_address(bad).unwrap_err().to_string();
+            assert!(err.contains(reason), "{bad}: {err}");
+        }
+    }
+}
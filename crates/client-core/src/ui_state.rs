@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{WorkerSettings, load_worker_settings, worker_settings_path};
+use crate::submitter::write_config_json;
+
+/// Smallest window edge worth restoring, in physical pixels.
+const MIN_WINDOW_EDGE: u32 = 200;
+/// Largest window edge worth restoring, in physical pixels.
+const MAX_WINDOW_EDGE: u32 = 16_384;
+
+/// The `ui_state` section of the settings file: GUI layout restored on the
+/// next launch.
+///
+/// Only the GUI reads and writes it; the CLI leaves it untouched.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiState {
+    /// Main window geometry when the GUI last closed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<WindowState>,
+    /// Selected dashboard tab.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dashboard_tab: Option<String>,
+    /// Selected time range per chart, keyed by chart id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub chart_ranges: BTreeMap<String, String>,
+}
+
+/// Outer position and inner size of a window, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Size and position are those from before the window was maximized.
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+impl WindowState {
+    /// Whether the size is one a window can sensibly be restored to; corrupt or
+    /// hand-edited values fall back to the default window.
+    pub fn is_restorable(&self) -> bool {
+        let edge = MIN_WINDOW_EDGE..=MAX_WINDOW_EDGE;
+        edge.contains(&self.width) && edge.contains(&self.height)
+    }
+}
+
+/// Load the `ui_state` section of the settings file (defaults when absent).
+pub fn load_ui_state() -> anyhow::Result<UiState> {
+    let ui_state = load_worker_settings()?
+        .and_then(|settings| settings.ui_state)
+        .unwrap_or_default();
+    Ok(ui_state)
+}
+
+/// Replace the `ui_state` section of the settings file, keeping every other
+/// section as it is on disk.
+pub fn save_ui_state(ui_state: &UiState) -> anyhow::Result<()> {
+    let mut settings: WorkerSettings = load_worker_settings()?.unwrap_or_default();
+    settings.ui_state = Some(ui_state.clone());
+    write_config_json(&worker_settings_path()?, &settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_without_ui_state_still_parse() {
+        let settings: WorkerSettings = serde_json::from_str(r#"{"parallel":4}"#).unwrap();
+        assert_eq!(settings.ui_state, None);
+
+        let settings: WorkerSettings =
+            serde_json::from_str(r#"{"ui_state":{"dashboard_tab":"workers"}}"#).unwrap();
+        let ui_state = settings.ui_state.unwrap();
+        assert_eq!(ui_state.dashboard_tab.as_deref(), Some("workers"));
+        assert_eq!(ui_state.window, None);
+        assert!(ui_state.chart_ranges.is_empty());
+    }
+
+    #[test]
+    fn implausible_window_sizes_are_not_restored() {
+        let window = WindowState {
+            x: -1_920,
+            y: 40,
+            width: 1_100,
+            height: 760,
+            maximized: false,
+        };
+        assert!(window.is_restorable());
+        for (width, height) in [(0, 760), (1_100, 10), (100_000, 760)] {
+            assert!(
+                !WindowState {
+                    width,
+                    height,
+                    ..window
+                }
+                .is_restorable()
+            );
+        }
+    }
+}
@@ -1,7 +1,10 @@
 //! Public API types for the in-process `bbr-client` engine.
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+use bbr_client_core::network::NetworkSettings;
+use bbr_client_core::signing::SubmissionSigner;
 use bbr_client_core::submitter::SubmitterConfig;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
@@ -11,19 +14,63 @@ use serde::{Deserialize, Serialize};
 pub enum PinMode {
     /// Do not pin worker compute threads.
     Off,
-    /// Pin worker compute threads to a shared-L3 (CCD/CCX) CPU set (Linux best-effort).
+    /// Pin worker compute threads, and the native prover threads they start, to a
+    /// shared-L3 (CCD/CCX) CPU set (Linux best-effort).
     L3,
 }
 
+/// cgroup v2 placement for compute threads (Linux only).
+///
+/// The engine process moves into `path` (which takes the memory limit) and
+/// compute threads into its threaded `compute` child (which takes the CPU
+/// weight). The parent cgroup must delegate the `cpu` and `memory` controllers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CgroupConfig {
+    /// cgroup path, relative to the cgroup v2 mount (`/sys/fs/cgroup`).
+    pub path: std::path::PathBuf,
+    /// `cpu.weight` of the compute threads (1..=10000; the kernel default is 100).
+    pub cpu_weight: Option<u32>,
+    /// `memory.max` for the engine process, in bytes.
+    pub memory_max_bytes: Option<u64>,
+}
+
 /// Configuration for the in-process engine.
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     /// Backend base URL (e.g. `http://127.0.0.1:8080`).
     pub backend_url: Url,
 
-    /// Number of workers to run concurrently.
+    /// Number of workers to run concurrently (the upper bound when `auto_parallel` is set).
     pub parallel: usize,
 
+    /// Pick the worker count automatically (`parallel: Auto`).
+    ///
+    /// At most `parallel` workers run, further limited by logical cores and by how
+    /// many `mem_budget_bytes` budgets fit in available memory. The limit is
+    /// re-checked periodically and only ever lowered, when the system comes under
+    /// memory pressure; busy workers finish their current job first.
+    pub auto_parallel: bool,
+
+    /// Keep at least this many bytes of system memory available.
+    ///
+    /// Available memory is re-checked periodically, also without `auto_parallel`;
+    /// when the running workers plus a `mem_budget_bytes` budget per idle worker
+    /// would leave less than this, the worker limit is lowered (never raised) and
+    /// a warning is emitted. `None` disables the check unless `auto_parallel` is
+    /// set, which then keeps a built-in reserve.
+    pub low_memory_threshold_bytes: Option<u64>,
+
+    /// Share cores and memory with other engines on this host (cooperative mode).
+    ///
+    /// Engines register in a peer directory under the state directory and
+    /// refresh their entry every few seconds. When the engines together ask for
+    /// more workers than there are logical cores, or for more memory than the
+    /// machine has (minus the low-memory reserve), each runs a share proportional
+    /// to what it asked for instead of assuming it owns the whole machine. Shares
+    /// grow back when peers exit; busy workers finish their current job first.
+    /// Only engines with this set take part.
+    pub cooperative: bool,
+
     /// Whether to fetch grouped work and compute batch proofs (Trick 2).
     ///
     /// When enabled, the engine leases work via `api/jobs/lease_batch` and uses
@@ -40,16 +87,21 @@ pub struct EngineConfig {
     /// Submitter metadata attached to job submissions.
     pub submitter: SubmitterConfig,
 
-    /// How long to sleep after an empty work fetch / error.
-    pub idle_sleep: Duration,
+    /// Sign submissions with this key (see [`bbr_client_core::signing`]).
+    ///
+    /// For backends that authenticate submitters; `None` sends unsigned submissions.
+    pub signer: Option<SubmissionSigner>,
 
-    /// Target number of progress updates per job.
+    /// Upper bound on the idle poll backoff, and the delay after a fetch error.
     ///
-    /// This is used to derive the chiavdf progress callback cadence
-    /// (`progress_interval`).
-    pub progress_steps: u64,
+    /// Empty work fetches back off exponentially from a short delay up to this
+    /// value; fetches resume immediately when work shows up or a worker frees up.
+    pub idle_sleep: Duration,
 
     /// How often the engine samples worker progress to emit progress events.
+    ///
+    /// Also the shortest time between two native progress callbacks, so the
+    /// update rate doesn't depend on CPU speed or job size.
     pub progress_tick: Duration,
 
     /// Maximum number of completed jobs retained in the snapshot.
@@ -57,20 +109,159 @@ pub struct EngineConfig {
 
     /// CPU pinning strategy.
     pub pin_mode: PinMode,
+
+    /// Place compute threads in a cgroup with CPU weight and memory limits
+    /// (Linux cgroup v2). Placement failures are reported as warnings.
+    pub cgroup: Option<CgroupConfig>,
+
+    /// Opt-in anonymous telemetry reporting (disabled when `None`).
+    pub telemetry: Option<TelemetryConfig>,
+
+    /// Deterministic scheduling seed (disabled when `None`).
+    ///
+    /// When set, leased work is queued in a stable order (by job/group id), work is
+    /// only handed out once every worker is idle, and workers are filled in a fixed
+    /// order derived from the seed. This trades throughput for reproducible job
+    /// assignment (integration tests, debugging scheduling issues).
+    pub schedule_seed: Option<u64>,
+
+    /// Drop leased work that cannot finish before its lease expires.
+    ///
+    /// The finish time is estimated from the squaring speed last measured on the
+    /// worker that would run it. Pending work that is clearly at risk is released
+    /// (its inflight entry is dropped) instead of being computed into a proof the
+    /// backend would reject as stale, and running proofs that clearly cannot finish
+    /// are abandoned; either way the worker picks up fresh work.
+    pub preempt_at_risk: bool,
+
+    /// Only compute jobs with at most this many iterations.
+    ///
+    /// Sent with lease requests so backends that support it filter server-side;
+    /// jobs above the limit that are leased anyway are dropped before they are
+    /// queued and handed back through the lease's `release_url` when the backend
+    /// offers one (groups keep their jobs within the limit). Dropped jobs are not
+    /// reported as finished. Lets low-power machines avoid multi-hour jobs they
+    /// can't finish within a lease.
+    pub max_iterations: Option<u64>,
+
+    /// Lease up to this many items ahead for busy workers that are about to finish.
+    ///
+    /// Hides the lease round-trip between jobs on high-latency links. Only workers
+    /// expected to finish soon count, and prefetching pauses while queued work is
+    /// getting old, so prefetched leases don't age waiting for a worker. `0`
+    /// disables prefetching (ignored with `schedule_seed`).
+    pub prefetch: usize,
+
+    /// Renew held leases this often, unless the backend sets its own cadence.
+    ///
+    /// Lease responses may carry `renew_interval` (seconds) and `renew_url`, which
+    /// take precedence; without a `renew_url`, renewals go to `api/jobs/renew_lease`.
+    /// Renewed expiries also extend how long workers keep computing. `None` only
+    /// renews leases whose backend asked for it.
+    pub lease_renew_interval: Option<Duration>,
+
+    /// Archive every submitted witness and backend response.
+    ///
+    /// Records are appended to a hash-chained JSONL file in the state directory
+    /// (see [`submission_archive_path`]) as local evidence of what was submitted
+    /// and when.
+    pub archive_submissions: bool,
+
+    /// Treat `output_mismatch` as an error.
+    ///
+    /// When the computed `y` differs from the backend's expected output, the proof
+    /// is not submitted; diagnostics (challenge, discriminant size, expected and
+    /// computed `y`) are emitted as an error event instead.
+    pub strict_output: bool,
+
+    /// Discriminant size (bits) for jobs that don't specify one.
+    ///
+    /// Jobs may carry `discriminant_size_bits`; the size is passed through to the
+    /// prover and used to validate the expected output. Only
+    /// [`Self::SUPPORTED_DISCRIMINANT_BITS`] are accepted.
+    pub discriminant_bits: usize,
+
+    /// Namespace for per-engine state files (inflight leases).
+    ///
+    /// `None` uses the default files. Set a distinct namespace for each engine when
+    /// running several engines in one process (e.g. one per backend) so they do not
+    /// overwrite each other's resume state. Must be a valid file name component.
+    pub state_namespace: Option<String>,
+
+    /// Run the engine's async internals on a dedicated runtime with this many threads.
+    ///
+    /// Keeps backend HTTP/TLS and bookkeeping on a small, fixed set of threads so
+    /// they don't compete with the caller's runtime; proofs always run on blocking
+    /// threads. `None` runs the engine on the caller's runtime.
+    pub engine_threads: Option<usize>,
+
+    /// Split large groups between two workers when other workers would sit idle.
+    ///
+    /// The lower- and higher-iteration halves of the group are proved by separate
+    /// workers (both squaring from the shared challenge), trading some duplicated
+    /// squaring for lower latency on the group's shorter proofs. A worker left idle
+    /// with nothing queued also takes the targets a running group hasn't reached
+    /// yet; each of those jobs is submitted by whichever worker finishes it first.
+    pub split_groups: bool,
+
+    /// Proxy, TLS and timeout settings for backend HTTP traffic.
+    ///
+    /// Expected to be validated already (see [`NetworkSettings::validate`]).
+    pub network: NetworkSettings,
+
+    /// Stop leasing work for the rest of the (local) day once this much was done.
+    ///
+    /// Leased work still finishes; fetches resume after local midnight. Proofs
+    /// submitted to this backend earlier today (from the job history) count
+    /// towards the cap, so restarting the engine doesn't reset it. `None` runs
+    /// unbounded.
+    pub daily_budget: Option<DailyBudget>,
+}
+
+/// Daily cap on the work an engine does (see [`EngineConfig::daily_budget`]).
+///
+/// Whichever limit is hit first ends the day; a budget without limits never does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DailyBudget {
+    /// Proofs submitted per day.
+    pub max_proofs: Option<u64>,
+    /// Worker compute time per day (a group proof counts once).
+    pub max_compute: Option<Duration>,
 }
 
 impl EngineConfig {
-    /// Default idle backoff used by the CLI worker.
+    /// Default idle backoff cap used by the CLI worker.
     pub const DEFAULT_IDLE_SLEEP: Duration = Duration::from_secs(10);
 
-    /// Default number of progress steps (matches the current CLI progress bars).
-    pub const DEFAULT_PROGRESS_STEPS: u64 = 20;
-
     /// Default progress sampling tick.
     pub const DEFAULT_PROGRESS_TICK: Duration = Duration::from_millis(200);
 
     /// Default size of the recent-jobs ring buffer.
     pub const DEFAULT_RECENT_JOBS_MAX: usize = 100;
+
+    /// Discriminant size of current Chia bluebox work.
+    pub const DEFAULT_DISCRIMINANT_BITS: usize = 1024;
+
+    /// Discriminant sizes the engine accepts in jobs and config.
+    pub const SUPPORTED_DISCRIMINANT_BITS: [usize; 2] = [1024, 2048];
+}
+
+/// Opt-in anonymous telemetry settings.
+///
+/// Reports only contain aggregate performance data (hardware class, speed,
+/// acceptance rate, client version); no reward address or submitter name is sent.
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    /// Endpoint receiving aggregate reports as JSON `POST` requests.
+    pub endpoint: Url,
+
+    /// How often to post a report.
+    pub interval: Duration,
+}
+
+impl TelemetryConfig {
+    /// Default reporting interval.
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(3600);
 }
 
 /// A lightweight summary of a leased proof job.
@@ -96,6 +287,8 @@ pub enum WorkerStage {
     Idle,
     /// Computing the proof witness.
     Computing,
+    /// Squaring is done; folding the streamed checkpoints into the witness.
+    Finalizing,
     /// Submitting the witness to the backend.
     Submitting,
 }
@@ -115,6 +308,10 @@ pub struct WorkerSnapshot {
     pub iters_total: u64,
     /// Estimated squaring speed in iterations/second.
     pub iters_per_sec: u64,
+    /// Paused with [`EngineHandle::pause_worker`]: finishes its current job, then
+    /// takes no new work until resumed.
+    #[serde(default)]
+    pub paused: bool,
 }
 
 /// Result of a completed job (submitted or failed).
@@ -136,6 +333,10 @@ pub struct JobOutcome {
     /// (e.g. `job_not_found`, lease conflicts).
     #[serde(default)]
     pub drop_inflight: bool,
+    /// The witness was dropped unsubmitted because its lease expired while it
+    /// waited to be submitted (e.g. during a backend outage).
+    #[serde(default)]
+    pub lease_expired: bool,
     /// Human-readable failure message, for compute/submit errors.
     pub error: Option<String>,
     /// Total compute time (milliseconds).
@@ -144,6 +345,146 @@ pub struct JobOutcome {
     pub submit_ms: u64,
     /// Total job time (milliseconds).
     pub total_ms: u64,
+    /// Time between leasing the job and a worker starting it (milliseconds).
+    #[serde(default)]
+    pub queue_wait_ms: u64,
+    /// Portion of `compute_ms` spent squaring (milliseconds).
+    ///
+    /// Only available when native streaming stats are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub squaring_ms: Option<u64>,
+    /// Portion of `compute_ms` spent in the streaming finalization phase (milliseconds).
+    ///
+    /// Only available when native streaming stats are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalize_ms: Option<u64>,
+    /// Number of submission attempts that failed before the final one.
+    #[serde(default)]
+    pub submit_retries: u32,
+    /// Diagnosis from recomputing a mismatched streaming proof with the
+    /// non-streaming prover (set only when the streaming output mismatched).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mismatch_check: Option<MismatchCheck>,
+    /// Environment snapshot, set when the backend rejected the submission
+    /// (anything but `accepted` or `already_compact`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<SubmissionDiagnostics>,
+    /// The witness was reused from another job this session with the same
+    /// challenge and iteration count instead of being computed again.
+    #[serde(default)]
+    pub reused_witness: bool,
+    /// Seconds left on the lease when the backend answered the submission, for
+    /// spotting jobs that routinely finish close to expiry. Set only for
+    /// submissions the backend answered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_headroom_secs: Option<u64>,
+}
+
+/// What a backend operator needs to debug a rejected submission reported by a
+/// user; kept with the job in the local history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubmissionDiagnostics {
+    /// Client version.
+    pub client_version: String,
+    /// Native prover implementation.
+    pub prover: String,
+    /// Streaming prover `k` (bucket width), when the proof was streamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streaming_k: Option<u32>,
+    /// Streaming prover `l` (rows), when the proof was streamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streaming_l: Option<u32>,
+    /// Total compute time (milliseconds).
+    pub compute_ms: u64,
+    /// Portion of `compute_ms` spent squaring, with native streaming stats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub squaring_ms: Option<u64>,
+    /// Portion of `compute_ms` spent finalizing, with native streaming stats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finalize_ms: Option<u64>,
+    /// Total submission time, retries included (milliseconds).
+    pub submit_ms: u64,
+    /// Submission attempts that failed before the rejected one.
+    pub submit_retries: u32,
+    /// HTTP status of the rejecting response.
+    pub http_status: u16,
+    /// Headers of the rejecting response (cookies omitted).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub response_headers: BTreeMap<String, String>,
+}
+
+/// Which prover path produced an output mismatch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchCheck {
+    /// The non-streaming prover matched the expected output, so the streaming path
+    /// was wrong; the non-streaming proof was submitted instead.
+    StreamingPath,
+    /// Both provers agree on an output that differs from the backend's expected
+    /// output (bad backend data).
+    BackendOutput,
+    /// The provers disagree with each other and with the expected output.
+    Inconsistent,
+    /// The non-streaming recomputation failed.
+    RecheckFailed,
+}
+
+/// A non-fatal (or, at shutdown, fatal) engine failure, by area.
+///
+/// `Display` gives the same message carried by [`EngineEvent::Error`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EngineError {
+    /// A backend request failed (work fetch, submission).
+    #[error("{message}")]
+    Backend {
+        /// Job the request was for, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        job_id: Option<u64>,
+        /// Error message.
+        message: String,
+    },
+    /// The backend rejected or lost a lease, or lease handling failed.
+    #[error("{message}")]
+    Lease {
+        /// Lease concerned, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lease_id: Option<String>,
+        /// Job concerned, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        job_id: Option<u64>,
+        /// Error message.
+        message: String,
+    },
+    /// A proof computation failed or produced a wrong output.
+    #[error("{message}")]
+    Compute {
+        /// Worker index (0-based).
+        worker_idx: usize,
+        /// Job concerned, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        job_id: Option<u64>,
+        /// Error message.
+        message: String,
+    },
+    /// Local state (inflight leases, history, archive) could not be read or written.
+    #[error("{message}")]
+    Persistence {
+        /// Error message.
+        message: String,
+    },
+    /// The engine could not be set up from its configuration.
+    #[error("{message}")]
+    Config {
+        /// Error message.
+        message: String,
+    },
+    /// An engine task failed unexpectedly.
+    #[error("{message}")]
+    Internal {
+        /// Error message.
+        message: String,
+    },
 }
 
 /// Engine event stream payload.
@@ -152,6 +493,11 @@ pub struct JobOutcome {
 pub enum EngineEvent {
     /// Engine started.
     Started,
+    /// Build and runtime environment, emitted once right after [`EngineEvent::Started`].
+    Environment {
+        /// Environment summary.
+        environment: EnvironmentInfo,
+    },
     /// Engine is stopping (graceful shutdown requested).
     StopRequested,
     /// Worker has been assigned a new job.
@@ -177,6 +523,10 @@ pub enum EngineEvent {
         /// all grouped proofs and is therefore typically higher than
         /// `iters_per_sec`.
         effective_iters_per_sec: u64,
+        /// Expected duration of the finalization phase that follows squaring
+        /// (milliseconds; 0 until the engine has measured one).
+        #[serde(default)]
+        finalize_ms_estimate: u64,
     },
     /// Worker stage transition.
     WorkerStage {
@@ -190,6 +540,18 @@ pub enum EngineEvent {
         /// Job outcome.
         outcome: JobOutcome,
     },
+    /// A worker task crashed and was restarted.
+    ///
+    /// The work it was running is requeued from the inflight store (or left for the
+    /// next run when resume state is disabled).
+    WorkerRestarted {
+        /// Worker index (0-based).
+        worker_idx: usize,
+        /// Restarts of this worker so far, including this one.
+        restarts: u32,
+        /// Why the task ended (e.g. the panic message).
+        reason: String,
+    },
     /// A warning from the engine.
     Warning {
         /// Warning message.
@@ -197,22 +559,363 @@ pub enum EngineEvent {
     },
     /// A non-fatal error from the engine.
     Error {
-        /// Error message.
+        /// Error message (`error` as text).
         message: String,
+        /// The error, with its area and context.
+        error: EngineError,
+    },
+    /// Final summary of the session, emitted once just before [`EngineEvent::Stopped`].
+    ShutdownReport {
+        /// Shutdown report.
+        report: ShutdownReport,
     },
     /// Engine stopped (no more workers running).
     Stopped,
 }
 
+impl From<EngineError> for EngineEvent {
+    fn from(error: EngineError) -> Self {
+        EngineEvent::Error {
+            message: error.to_string(),
+            error,
+        }
+    }
+}
+
+/// An [`EngineEvent`] with its position in the engine's event stream.
+///
+/// Sequence numbers start at 1 and increase by one per event, so a subscriber
+/// that sees a jump has missed events and should resynchronize from
+/// [`EngineHandle::snapshot`]. Serializes as the event with an extra `seq` field.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequencedEvent {
+    /// Sequence number of this event.
+    pub seq: u64,
+    /// The event.
+    #[serde(flatten)]
+    pub event: EngineEvent,
+}
+
+/// What an engine runs on and with, for support triage.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EnvironmentInfo {
+    /// Client version.
+    pub version: String,
+    /// Commit the client was built from (`WESOFORGE_GIT_HASH` at build time).
+    pub git_hash: Option<String>,
+    /// Target operating system.
+    pub os: String,
+    /// Target architecture.
+    pub arch: String,
+    /// Logical CPUs available to this process.
+    pub logical_cpus: usize,
+    /// Instruction set extensions used by the fast prover (see [`crate::CpuInfo`]).
+    pub cpu_features: Vec<String>,
+    /// Linked prover implementation (`fast`, `fallback` or `rust-fallback`).
+    pub native_implementation: String,
+    /// Configured worker count.
+    pub parallel: usize,
+    /// Whether the worker count is picked automatically.
+    pub auto_parallel: bool,
+    /// Streaming prover memory budget (bytes).
+    pub mem_budget_bytes: u64,
+    /// Backend base URL.
+    pub backend_url: String,
+    /// CPU pinning in effect: `off`, or `l3` when L3 pinning took effect.
+    pub pin_mode: String,
+}
+
+/// Work left behind and totals for an engine session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Leased jobs dropped at shutdown before any worker started them.
+    pub abandoned_leases: usize,
+    /// Jobs computed this session whose submission failed; their leases stay in
+    /// the inflight store so the next start retries them.
+    pub unsubmitted_jobs: usize,
+    /// Jobs computed this session whose lease expired before they could be
+    /// submitted; they were dropped.
+    #[serde(default)]
+    pub expired_jobs: usize,
+    /// Jobs left in the on-disk inflight store for resume on the next start.
+    pub resumable_jobs: usize,
+    /// Session totals.
+    pub session: SessionStats,
+}
+
+/// Totals accumulated over an engine session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionStats {
+    /// Session duration (milliseconds).
+    pub uptime_ms: u64,
+    /// Jobs finished (successfully or not).
+    pub jobs_finished: u64,
+    /// Jobs submitted to the backend.
+    pub jobs_submitted: u64,
+    /// Submitted jobs the backend accepted.
+    #[serde(default)]
+    pub jobs_accepted: u64,
+    /// Jobs that finished with an error.
+    pub jobs_failed: u64,
+    /// Iterations of submitted jobs.
+    pub iterations_submitted: u64,
+    /// Total compute time across all jobs (milliseconds).
+    pub compute_ms: u64,
+    /// Jobs that reused a witness computed for a duplicate job this session.
+    #[serde(default)]
+    pub duplicates_suppressed: u64,
+}
+
 /// Current engine state snapshot.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct StatusSnapshot {
     /// Whether the engine has been asked to stop.
     pub stop_requested: bool,
+    /// Whether leasing of new work is paused (in-flight work keeps running).
+    #[serde(default)]
+    pub paused: bool,
     /// Per-worker snapshots.
     pub workers: Vec<WorkerSnapshot>,
     /// Recently completed jobs (newest last).
     pub recent_jobs: Vec<JobOutcome>,
+    /// Set while the backend reports maintenance and work fetches are paused.
+    #[serde(default)]
+    pub backend_maintenance: Option<BackendMaintenance>,
+    /// Backend base URL this engine leases from, so snapshots from several
+    /// engines can be told apart.
+    #[serde(default)]
+    pub backend_url: String,
+    /// Totals for this engine's session so far.
+    #[serde(default)]
+    pub session: SessionStats,
+    /// Sequence number of the last event emitted before this snapshot was taken
+    /// (see [`SequencedEvent`]).
+    #[serde(default)]
+    pub last_event_seq: u64,
+    /// Progress towards the daily budget (`None` without one).
+    #[serde(default)]
+    pub budget: Option<BudgetStatus>,
+}
+
+/// Progress towards [`EngineConfig::daily_budget`] for the current local day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BudgetStatus {
+    /// Proofs submitted today.
+    pub proofs_today: u64,
+    /// Daily proof cap, if any.
+    pub max_proofs: Option<u64>,
+    /// Compute time spent today (milliseconds).
+    pub compute_ms_today: u64,
+    /// Daily compute cap (milliseconds), if any.
+    pub max_compute_ms: Option<u64>,
+    /// Unix timestamp (seconds) of the next local midnight, set while the budget
+    /// is used up and work fetches are paused.
+    pub resumes_at: Option<i64>,
+}
+
+/// Backend maintenance window, as observed by the engine.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackendMaintenance {
+    /// Unix timestamp (seconds) of the first maintenance response.
+    pub since: i64,
+    /// Unix timestamp (seconds) of the next work fetch attempt.
+    pub retry_at: i64,
+    /// Message from the backend, if any.
+    pub message: String,
+}
+
+/// Backend traffic and contact status.
+///
+/// Byte counts cover the JSON payloads of successful backend exchanges; "today"
+/// counters reset at local midnight.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkStatus {
+    /// Bytes sent to the backend today.
+    pub uploaded_bytes_today: u64,
+    /// Bytes received from the backend today.
+    pub downloaded_bytes_today: u64,
+    /// Bytes sent to the backend since the engine started.
+    pub uploaded_bytes_total: u64,
+    /// Bytes received from the backend since the engine started.
+    pub downloaded_bytes_total: u64,
+    /// Unix timestamp (seconds) of the last successful backend exchange.
+    pub last_contact_at: Option<i64>,
+    /// Unix timestamp (seconds) of the last successful submission.
+    pub last_submit_at: Option<i64>,
+    /// Request latency per backend endpoint (`lease`, `submit`), failed
+    /// requests included.
+    #[serde(default)]
+    pub latency: Vec<EndpointLatency>,
+}
+
+/// Request latency distribution for one backend endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EndpointLatency {
+    /// Endpoint name (`lease`, `submit` or `renew`).
+    pub endpoint: String,
+    /// Requests recorded since the engine started.
+    pub count: u64,
+    /// Histogram of all recorded requests, fastest bucket first.
+    pub buckets: Vec<LatencyBucket>,
+    /// Median latency over the last 100 requests (milliseconds).
+    pub p50_ms: u64,
+    /// 95th percentile latency over the last 100 requests (milliseconds).
+    pub p95_ms: u64,
+    /// Slowest request since the engine started (milliseconds).
+    pub max_ms: u64,
+}
+
+/// One latency histogram bucket.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LatencyBucket {
+    /// Upper bound in milliseconds (inclusive); `None` for the overflow bucket.
+    pub le_ms: Option<u64>,
+    /// Requests in this bucket.
+    pub count: u64,
+}
+
+/// A finished job recorded in the local job history.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JobHistoryEntry {
+    /// Unix timestamp (seconds) at which the job finished.
+    pub finished_at: i64,
+    /// Backend base URL the job was leased from.
+    pub backend_url: String,
+    /// Job outcome.
+    pub outcome: JobOutcome,
+}
+
+/// Load the local job history, optionally limited to jobs finished at or after
+/// `since` (unix timestamp, seconds).
+///
+/// The history file is rotated once it grows past 16 MiB, keeping one previous
+/// file, so only the most recent jobs are available.
+pub fn load_job_history(since: Option<i64>) -> anyhow::Result<Vec<JobHistoryEntry>> {
+    crate::history::load_job_history(since)
+}
+
+/// Path of the local job history file.
+pub fn job_history_path() -> anyhow::Result<std::path::PathBuf> {
+    crate::history::history_path()
+}
+
+/// Global progress of the compaction effort, from the backend's public stats
+/// endpoint. Fields the backend doesn't report are `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackendQueueStatus {
+    /// Blocks that still have uncompacted VDFs.
+    pub remaining_uncompact_blocks: Option<u64>,
+    /// Workers that recently leased or submitted work.
+    pub active_workers: Option<u64>,
+    /// Lowest block height that still has uncompacted VDFs.
+    #[serde(default)]
+    pub remaining_min_height: Option<u32>,
+    /// Highest block height that still has uncompacted VDFs.
+    #[serde(default)]
+    pub remaining_max_height: Option<u32>,
+    /// Unix timestamp (seconds) at which the status was fetched.
+    pub fetched_at: i64,
+}
+
+/// Fetch the backend's public queue statistics (`GET api/stats`).
+///
+/// Uses the same proxy/TLS/timeout settings as the engine. Callers polling this
+/// should cache the result; the endpoint is shared by every client.
+pub async fn fetch_backend_queue_status(
+    backend_url: &Url,
+    network: &NetworkSettings,
+) -> anyhow::Result<BackendQueueStatus> {
+    let http = crate::engine::build_http_client(network)?;
+    crate::backend::fetch_queue_status(&http, backend_url).await
+}
+
+/// A submitted witness and the backend's response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubmissionRecord {
+    /// Unix timestamp (seconds) at which the backend responded.
+    pub submitted_at: i64,
+    /// Backend base URL the witness was submitted to.
+    pub backend_url: String,
+    /// Backend job identifier.
+    pub job_id: u64,
+    /// Lease the job was held under.
+    pub lease_id: String,
+    /// Submitted witness (base64).
+    pub witness_b64: String,
+    /// Backend response reason (or rejection code).
+    pub reason: String,
+    /// Backend response detail.
+    pub detail: String,
+    /// Hash of the previous record (all zeros for the first record).
+    pub prev_hash: String,
+}
+
+/// Verify the hash chain of the local submission archive and return its records.
+///
+/// Fails on the first record whose hash or `prev_hash` link does not match.
+pub fn verify_submission_archive() -> anyhow::Result<Vec<SubmissionRecord>> {
+    crate::archive::verify_submission_archive()
+}
+
+/// Path of the local submission archive file.
+pub fn submission_archive_path() -> anyhow::Result<std::path::PathBuf> {
+    crate::archive::submission_archive_path()
+}
+
+/// Outcome of one troubleshooting check.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// Nothing to do.
+    Pass,
+    /// The client works, but something needs attention.
+    Warn,
+    /// The client cannot work until this is fixed.
+    Fail,
+}
+
+/// One troubleshooting check and, when it did not pass, a suggested fix.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DoctorCheck {
+    /// Stable identifier (`native`, `backend`, `config` or `state`).
+    pub id: String,
+    /// Human-readable check name.
+    pub title: String,
+    /// Check outcome.
+    pub status: CheckStatus,
+    /// What was checked and what was found.
+    pub detail: String,
+    /// Suggested fix, for checks that did not pass.
+    pub fix: Option<String>,
+}
+
+/// Result of [`run_doctor`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DoctorReport {
+    /// Checks in the order they were run.
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// Worst status across all checks.
+    pub fn status(&self) -> CheckStatus {
+        if self.checks.iter().any(|c| c.status == CheckStatus::Fail) {
+            CheckStatus::Fail
+        } else if self.checks.iter().any(|c| c.status == CheckStatus::Warn) {
+            CheckStatus::Warn
+        } else {
+            CheckStatus::Pass
+        }
+    }
+}
+
+/// Run the troubleshooting checks: a native prover self-test, backend
+/// reachability, settings validation and local state integrity.
+///
+/// The state check repairs damaged state files the same way engine startup
+/// does, so don't run it while an engine is using the same state namespace.
+pub async fn run_doctor(backend_url: &Url, state_namespace: Option<&str>) -> DoctorReport {
+    crate::doctor::run_doctor(backend_url, state_namespace).await
 }
 
 /// Handle to a running in-process engine instance.
@@ -221,27 +924,140 @@ pub struct EngineHandle {
     pub(crate) join: tokio::task::JoinHandle<anyhow::Result<()>>,
 }
 
+/// Control message for an engine started with [`Engine::run_blocking`].
+#[derive(Debug)]
+pub enum EngineControl {
+    /// Request a graceful shutdown (finish in-flight work, stop leasing new jobs).
+    Stop,
+    /// Forward engine events to the given channel until it is dropped.
+    Subscribe(std::sync::mpsc::Sender<EngineEvent>),
+}
+
+/// Synchronous entry point for embedders that don't run a Tokio runtime.
+pub struct Engine;
+
+impl Engine {
+    /// Run the engine to completion on a runtime owned by this call.
+    ///
+    /// Blocks the calling thread until the engine stops, either after
+    /// [`EngineControl::Stop`] or once `ctrl_rx`'s sender is dropped (treated as
+    /// a stop request).
+    pub fn run_blocking(
+        config: EngineConfig,
+        ctrl_rx: std::sync::mpsc::Receiver<EngineControl>,
+    ) -> anyhow::Result<()> {
+        crate::blocking::run_blocking(config, ctrl_rx)
+    }
+}
+
 /// Start a new in-process engine instance.
 pub fn start_engine(config: EngineConfig) -> EngineHandle {
     crate::engine::start_engine(config)
 }
 
+/// Start the engine with failure injection enabled (chaos testing only).
+#[cfg(feature = "fault-injection")]
+pub fn start_engine_with_faults(
+    config: EngineConfig,
+    faults: crate::fault::FaultInjector,
+) -> EngineHandle {
+    crate::engine::start_engine_with_faults(config, crate::fault::Faults::new(faults))
+}
+
 impl EngineHandle {
     /// Subscribe to the engine event stream.
     pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<EngineEvent> {
         self.inner.event_tx.subscribe()
     }
 
+    /// Subscribe to the engine event stream with sequence numbers attached.
+    ///
+    /// Like [`EngineHandle::subscribe`], a slow receiver loses events; the gap in
+    /// [`SequencedEvent::seq`] (or a `Lagged` error) tells it to refresh from
+    /// [`EngineHandle::snapshot`].
+    pub fn subscribe_sequenced(&self) -> tokio::sync::broadcast::Receiver<SequencedEvent> {
+        self.inner.event_tx.subscribe_sequenced()
+    }
+
+    /// Register a callback invoked with every finished job.
+    ///
+    /// Unlike [`EngineHandle::subscribe`], callbacks never miss events due to lag.
+    /// They run in event order on a dedicated task, off the engine loop; a slow
+    /// callback delays the ones after it, so hand heavy work off to a channel or
+    /// thread. Events emitted before the callback is registered are not replayed.
+    pub fn on_job_finished<F>(&self, callback: F)
+    where
+        F: Fn(JobOutcome) + Send + Sync + 'static,
+    {
+        self.inner.on_job_finished(std::sync::Arc::new(callback));
+    }
+
+    /// Register a callback invoked with every non-fatal engine error.
+    ///
+    /// Same delivery guarantees as [`EngineHandle::on_job_finished`].
+    pub fn on_error<F>(&self, callback: F)
+    where
+        F: Fn(EngineError) + Send + Sync + 'static,
+    {
+        self.inner.on_error(std::sync::Arc::new(callback));
+    }
+
     /// Get the latest engine snapshot.
     pub fn snapshot(&self) -> StatusSnapshot {
         self.inner.snapshot_rx.borrow().clone()
     }
 
+    /// Receiver that sees every snapshot the engine publishes, for consumers
+    /// living outside the task that owns this handle.
+    pub fn watch_snapshot(&self) -> tokio::sync::watch::Receiver<StatusSnapshot> {
+        self.inner.snapshot_rx.clone()
+    }
+
+    /// Get backend bandwidth usage and last-contact timestamps.
+    pub fn network_status(&self) -> NetworkStatus {
+        self.inner.network.status()
+    }
+
     /// Request a graceful shutdown (finish in-flight work, stop leasing new jobs).
     pub fn request_stop(&self) {
         self.inner.request_stop();
     }
 
+    /// Stop leasing new work; in-flight and already leased work keeps running.
+    pub fn pause(&self) {
+        self.inner.set_paused(true);
+    }
+
+    /// Resume leasing new work after [`EngineHandle::pause`].
+    pub fn resume(&self) {
+        self.inner.set_paused(false);
+    }
+
+    /// Whether the engine is currently paused.
+    pub fn is_paused(&self) -> bool {
+        self.inner.is_paused()
+    }
+
+    /// Keep one worker idle after its current job until [`EngineHandle::resume_worker`],
+    /// freeing its core without changing `parallel`.
+    ///
+    /// Returns `false` if `worker_idx` (0-based) is not a worker of this engine.
+    pub fn pause_worker(&self, worker_idx: usize) -> bool {
+        self.inner.set_worker_paused(worker_idx, true)
+    }
+
+    /// Let a worker paused with [`EngineHandle::pause_worker`] take work again.
+    ///
+    /// Returns `false` if `worker_idx` (0-based) is not a worker of this engine.
+    pub fn resume_worker(&self, worker_idx: usize) -> bool {
+        self.inner.set_worker_paused(worker_idx, false)
+    }
+
+    /// Whether `worker_idx` is paused with [`EngineHandle::pause_worker`].
+    pub fn is_worker_paused(&self, worker_idx: usize) -> bool {
+        self.inner.is_worker_paused(worker_idx)
+    }
+
     /// Wait for the engine to stop, returning the engine task result.
     pub async fn wait(self) -> anyhow::Result<()> {
         match self.join.await {
@@ -0,0 +1,193 @@
+use std::io::{BufRead as _, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+use crate::api::SubmissionRecord;
+use crate::inflight::xdg_state_home;
+
+/// `prev_hash` of the first record in a chain.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveLine {
+    #[serde(flatten)]
+    record: SubmissionRecord,
+    /// Hex SHA-256 of the JSON-serialized `record` (which includes `prev_hash`).
+    hash: String,
+}
+
+/// Append-only, hash-chained JSONL archive of submissions.
+///
+/// Each line commits to the previous one, so edits or deletions anywhere in the
+/// file are detected by [`verify_submission_archive`].
+pub(crate) struct SubmissionArchive {
+    path: PathBuf,
+    backend_url: String,
+    last_hash: Mutex<String>,
+}
+
+impl SubmissionArchive {
+    pub(crate) fn open(backend_url: &reqwest::Url) -> anyhow::Result<Self> {
+        let path = submission_archive_path()?;
+        let last_hash = last_hash(&path)?.unwrap_or_else(|| GENESIS_HASH.to_string());
+        Ok(Self {
+            path,
+            backend_url: backend_url.to_string(),
+            last_hash: Mutex::new(last_hash),
+        })
+    }
+
+    /// Append a record for `job_id`. Blocking; call from `spawn_blocking`.
+    pub(crate) fn append(
+        &self,
+        job_id: u64,
+        lease_id: &str,
+        witness_b64: String,
+        reason: &str,
+        detail: &str,
+        submitted_at: i64,
+    ) -> anyhow::Result<()> {
+        let mut last_hash = match self.last_hash.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let record = SubmissionRecord {
+            submitted_at,
+            backend_url: self.backend_url.clone(),
+            job_id,
+            lease_id: lease_id.to_string(),
+            witness_b64,
+            reason: reason.to_string(),
+            detail: detail.to_string(),
+            prev_hash: last_hash.clone(),
+        };
+        let hash = record_hash(&record)?;
+        let line = ArchiveLine { record, hash };
+
+        let dir = self
+            .path
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("invalid archive path: {}", self.path.display()))?;
+        std::fs::create_dir_all(dir)?;
+
+        let mut buf = serde_json::to_vec(&line)?;
+        buf.push(b'\n');
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(&buf)?;
+        file.sync_data()?;
+
+        *last_hash = line.hash;
+        Ok(())
+    }
+}
+
+fn record_hash(record: &SubmissionRecord) -> anyhow::Result<String> {
+    let digest = Sha256::digest(serde_json::to_vec(record)?);
+    Ok(digest.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+fn read_lines(path: &Path) -> anyhow::Result<Vec<ArchiveLine>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = std::fs::File::open(path)?;
+    let mut out = Vec::new();
+    for (idx, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = serde_json::from_str::<ArchiveLine>(line)
+            .map_err(|err| anyhow::anyhow!("line {}: {err}", idx + 1))?;
+        out.push(parsed);
+    }
+    Ok(out)
+}
+
+fn last_hash(path: &Path) -> anyhow::Result<Option<String>> {
+    Ok(read_lines(path)?.pop().map(|line| line.hash))
+}
+
+pub(crate) fn submission_archive_path() -> anyhow::Result<PathBuf> {
+    Ok(xdg_state_home()?
+        .join("bbr-client")
+        .join("submission-archive.jsonl"))
+}
+
+pub(crate) fn verify_submission_archive() -> anyhow::Result<Vec<SubmissionRecord>> {
+    verify_chain(&submission_archive_path()?)
+}
+
+fn verify_chain(path: &Path) -> anyhow::Result<Vec<SubmissionRecord>> {
+    let lines = read_lines(path)?;
+    let mut prev = GENESIS_HASH.to_string();
+    let mut out = Vec::with_capacity(lines.len());
+    for (idx, line) in lines.into_iter().enumerate() {
+        if line.record.prev_hash != prev {
+            anyhow::bail!("record {}: chain broken (prev_hash mismatch)", idx + 1);
+        }
+        if record_hash(&line.record)? != line.hash {
+            anyhow::bail!("record {}: hash mismatch (record modified)", idx + 1);
+        }
+        prev = line.hash;
+        out.push(line.record);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_break_the_chain() {
+        let dir = std::env::temp_dir().join(format!("bbr-archive-chain-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("submission-archive.jsonl");
+        let archive = SubmissionArchive {
+            path: path.clone(),
+            backend_url: "https://backend.example/".to_string(),
+            last_hash: Mutex::new(GENESIS_HASH.to_string()),
+        };
+        for job_id in 1..=3 {
+            archive
+                .append(
+                    job_id,
+                    "lease",
+                    "d2l0bmVzcw==".to_string(),
+                    "accepted",
+                    "",
+                    100,
+                )
+                .unwrap();
+        }
+
+        let records = verify_chain(&path).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].prev_hash, GENESIS_HASH);
+        // Reopening continues from the last record.
+        let last = archive.last_hash.lock().unwrap().clone();
+        assert_eq!(last_hash(&path).unwrap(), Some(last));
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        let edited = raw.replacen("\"accepted\"", "\"rejected\"", 1);
+        std::fs::write(&path, edited).unwrap();
+        let err = verify_chain(&path).unwrap_err();
+        assert!(err.to_string().contains("record 1: hash mismatch"), "{err}");
+
+        let mut lines: Vec<&str> = raw.lines().collect();
+        lines.remove(1);
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        let err = verify_chain(&path).unwrap_err();
+        assert!(err.to_string().contains("record 2: chain broken"), "{err}");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,9 +1,21 @@
-use base64::engine::general_purpose::STANDARD as B64;
+use std::collections::BTreeMap;
+use std::time::Instant;
+
 use base64::Engine as _;
-use reqwest::header;
+use base64::engine::general_purpose::STANDARD as B64;
 use reqwest::Url;
+use reqwest::header;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use bbr_client_chiavdf_fast::chia::{CLASSGROUP_ELEMENT_SIZE, ClassgroupElement};
+use bbr_client_core::signing::{SIGNATURE_HEADER, SIGNATURE_KEY_HEADER, SubmissionSigner};
+
+use crate::api::{BackendQueueStatus, EngineConfig};
+use crate::network::{
+    ENDPOINT_LEASE, ENDPOINT_RELEASE, ENDPOINT_RENEW, ENDPOINT_SUBMIT, NetworkStats,
+};
+
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum BackendError {
     #[error("invalid reward address")]
@@ -14,6 +26,11 @@ pub(crate) enum BackendError {
     LeaseConflict,
     #[error("job not found")]
     JobNotFound,
+    #[error("backend under maintenance")]
+    Maintenance {
+        retry_after: Option<std::time::Duration>,
+        message: String,
+    },
 }
 
 #[derive(Debug, Deserialize)]
@@ -59,9 +76,30 @@ async fn error_from_response(res: reqwest::Response) -> anyhow::Error {
         .and_then(|v| v.to_str().ok())
         .unwrap_or("")
         .to_string();
+    // Only the delay-seconds form of Retry-After is honored.
+    let retry_after = res
+        .headers()
+        .get(header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(std::time::Duration::from_secs);
     let body = res.text().await.unwrap_or_default();
+    let api_err = serde_json::from_str::<ApiErrorBody>(&body).ok();
+
+    let maintenance_code = api_err.as_ref().is_some_and(|e| e.code == "maintenance");
+    if status == reqwest::StatusCode::SERVICE_UNAVAILABLE || maintenance_code {
+        let message = api_err
+            .as_ref()
+            .map(|err| truncate_one_line(&err.message, 200))
+            .unwrap_or_default();
+        return BackendError::Maintenance {
+            retry_after,
+            message,
+        }
+        .into();
+    }
 
-    if let Ok(err) = serde_json::from_str::<ApiErrorBody>(&body) {
+    if let Some(err) = api_err {
         if status == reqwest::StatusCode::BAD_REQUEST && err.code == "invalid_reward_address" {
             return BackendError::InvalidRewardAddress.into();
         }
@@ -104,27 +142,174 @@ async fn error_from_response(res: reqwest::Response) -> anyhow::Error {
     }
 }
 
+/// POST `body` as JSON and decode a successful JSON response, recording payload sizes.
+///
+/// The request's latency is recorded under `endpoint` whether or not it succeeds.
+async fn post_json<B: Serialize, R: DeserializeOwned>(
+    http: &reqwest::Client,
+    net: &NetworkStats,
+    endpoint: &'static str,
+    url: Url,
+    body: &B,
+) -> anyhow::Result<R> {
+    post_json_with_headers(http, net, endpoint, url, header::HeaderMap::new(), body).await
+}
+
+/// [`post_json`] with extra request headers.
+async fn post_json_with_headers<B: Serialize, R: DeserializeOwned>(
+    http: &reqwest::Client,
+    net: &NetworkStats,
+    endpoint: &'static str,
+    url: Url,
+    headers: header::HeaderMap,
+    body: &B,
+) -> anyhow::Result<R> {
+    let body = serde_json::to_vec(body)?;
+    let uploaded = body.len();
+    let started_at = Instant::now();
+    let res = post_bytes(http, url, headers, body).await;
+    net.record_latency(endpoint, started_at.elapsed());
+
+    let bytes = res?;
+    net.record_exchange(uploaded, bytes.len());
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// POST a JSON body and return the body of a successful response.
+async fn post_bytes(
+    http: &reqwest::Client,
+    url: Url,
+    headers: header::HeaderMap,
+    body: Vec<u8>,
+) -> anyhow::Result<Vec<u8>> {
+    let res = send_post(http, url, headers, body).await?;
+    if !res.status().is_success() {
+        return Err(error_from_response(res).await);
+    }
+    Ok(res.bytes().await?.to_vec())
+}
+
+/// POST a JSON body, returning the response whatever its status.
+async fn send_post(
+    http: &reqwest::Client,
+    url: Url,
+    headers: header::HeaderMap,
+    body: Vec<u8>,
+) -> anyhow::Result<reqwest::Response> {
+    Ok(http
+        .post(url)
+        .headers(headers)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await?)
+}
+
+/// Status and headers of a backend reply, kept to diagnose rejected submissions.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ResponseMeta {
+    pub(crate) status: u16,
+    /// Header values by name; cookies are left out.
+    pub(crate) headers: BTreeMap<String, String>,
+}
+
+impl ResponseMeta {
+    fn of(res: &reqwest::Response) -> Self {
+        let headers = res
+            .headers()
+            .iter()
+            .filter(|(name, _)| *name != header::SET_COOKIE)
+            .map(|(name, value)| {
+                (
+                    name.as_str().to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+        Self {
+            status: res.status().as_u16(),
+            headers,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QueueStatsResponse {
+    #[serde(default, alias = "uncompact_blocks")]
+    remaining_uncompact_blocks: Option<u64>,
+    #[serde(default)]
+    active_workers: Option<u64>,
+    #[serde(default, alias = "min_uncompact_height")]
+    remaining_min_height: Option<u32>,
+    #[serde(default, alias = "max_uncompact_height")]
+    remaining_max_height: Option<u32>,
+}
+
+/// Public queue statistics; needs no lease or reward address.
+pub(crate) async fn fetch_queue_status(
+    http: &reqwest::Client,
+    backend: &Url,
+) -> anyhow::Result<BackendQueueStatus> {
+    let url = backend.join("api/stats")?;
+    let res = http.get(url).send().await?;
+    if !res.status().is_success() {
+        return Err(error_from_response(res).await);
+    }
+    let stats: QueueStatsResponse = serde_json::from_slice(&res.bytes().await?)?;
+    Ok(BackendQueueStatus {
+        remaining_uncompact_blocks: stats.remaining_uncompact_blocks,
+        active_workers: stats.active_workers,
+        remaining_min_height: stats.remaining_min_height,
+        remaining_max_height: stats.remaining_max_height,
+        fetched_at: chrono::Utc::now().timestamp(),
+    })
+}
+
 #[derive(Debug, Serialize)]
 struct WorkRequest {
     count: u32,
+    /// Server-side iteration filter; ignored by backends that don't support it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_iterations: Option<u64>,
+}
+
+/// Optional renewal schedule and release endpoint a backend may attach to a lease.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct LeaseTerms {
+    /// Seconds between renewals.
+    #[serde(default)]
+    pub(crate) renew_interval: Option<u64>,
+    /// Renewal endpoint, absolute or relative to the backend URL.
+    #[serde(default)]
+    pub(crate) renew_url: Option<String>,
+    /// Endpoint taking back leased jobs the client will not compute, absolute or
+    /// relative to the backend URL.
+    #[serde(default)]
+    pub(crate) release_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct BackendWorkBatch {
     pub(crate) lease_id: String,
     pub(crate) lease_expires_at: i64,
+    #[serde(flatten)]
+    pub(crate) terms: LeaseTerms,
     pub(crate) jobs: Vec<BackendJobDto>,
 }
 
 #[derive(Debug, Serialize)]
 struct LeaseBatchRequest {
     count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_iterations: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
 struct LeaseGroupsResponse {
     lease_id: String,
     lease_expires_at: i64,
+    #[serde(flatten)]
+    terms: LeaseTerms,
     groups: Vec<LeasedGroupDto>,
 }
 
@@ -149,6 +334,84 @@ pub(crate) struct BackendJobDto {
     pub(crate) challenge_b64: String,
     pub(crate) number_of_iterations: u64,
     pub(crate) output_b64: String,
+    /// Discriminant size; absent for backends that only hand out 1024-bit work.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) discriminant_size_bits: Option<usize>,
+    /// Serialized element the VDF starts from (`ICC_EOS_VDF` work may start
+    /// from a non-default form); absent for work starting from the generator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) input_b64: Option<String>,
+}
+
+impl BackendJobDto {
+    /// Discriminant size of this job, `default` if the backend didn't say.
+    pub(crate) fn discriminant_bits(&self, default: usize) -> usize {
+        self.discriminant_size_bits.unwrap_or(default)
+    }
+
+    /// Element the proof starts from: `input_b64`, or the generator without one.
+    pub(crate) fn initial_element(&self) -> Result<ClassgroupElement, String> {
+        let Some(input_b64) = self.input_b64.as_deref() else {
+            return Ok(ClassgroupElement::generator());
+        };
+        let input = B64
+            .decode(input_b64.as_bytes())
+            .map_err(|err| format!("input_b64 does not decode: {err}"))?;
+        ClassgroupElement::from_bytes(&input)
+            .map_err(|err| format!("input_b64 is malformed: {err}"))
+    }
+
+    /// Sanity-check a leased job before handing it to the native prover.
+    ///
+    /// Returns a short description of the first problem found.
+    pub(crate) fn validate(&self, discriminant_bits: usize) -> Result<(), String> {
+        if !EngineConfig::SUPPORTED_DISCRIMINANT_BITS.contains(&discriminant_bits) {
+            return Err(format!(
+                "discriminant_size_bits {discriminant_bits} not supported"
+            ));
+        }
+        if !(1..=4).contains(&self.field_vdf) {
+            return Err(format!("field_vdf {} not in 1..=4", self.field_vdf));
+        }
+        let max_iters = bbr_client_chiavdf_fast::max_iterations();
+        if self.number_of_iterations == 0 || self.number_of_iterations > max_iters {
+            return Err(format!(
+                "number_of_iterations {} not in 1..={max_iters}",
+                self.number_of_iterations
+            ));
+        }
+        let output = match B64.decode(self.output_b64.as_bytes()) {
+            Ok(output) if output.len() == CLASSGROUP_ELEMENT_SIZE => output,
+            Ok(output) => {
+                return Err(format!(
+                    "output_b64 decodes to {} bytes, expected {}",
+                    output.len(),
+                    CLASSGROUP_ELEMENT_SIZE
+                ));
+            }
+            Err(err) => return Err(format!("output_b64 does not decode: {err}")),
+        };
+        match ClassgroupElement::from_bytes(&output) {
+            Ok(element) if element.is_valid_for_discriminant(discriminant_bits) => {}
+            Ok(_) => {
+                return Err(format!(
+                    "output_b64 is not a {discriminant_bits}-bit classgroup element"
+                ));
+            }
+            Err(err) => return Err(format!("output_b64 is malformed: {err}")),
+        }
+        // Whether x is a form of this job's discriminant is left to the prover,
+        // which rejects it with `InvalidForm` before squaring.
+        if !self
+            .initial_element()?
+            .is_valid_for_discriminant(discriminant_bits)
+        {
+            return Err(format!(
+                "input_b64 is not a {discriminant_bits}-bit classgroup element"
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -165,46 +428,69 @@ struct SubmitRequest {
 pub(crate) struct SubmitResponse {
     pub(crate) reason: String,
     pub(crate) detail: String,
+    #[serde(skip)]
+    pub(crate) response: ResponseMeta,
+}
+
+/// A failed submission, with the backend's reply when it sent one.
+#[derive(Debug)]
+pub(crate) struct SubmitError {
+    pub(crate) error: anyhow::Error,
+    pub(crate) response: Option<ResponseMeta>,
+}
+
+impl From<anyhow::Error> for SubmitError {
+    fn from(error: anyhow::Error) -> Self {
+        Self {
+            error,
+            response: None,
+        }
+    }
 }
 
 pub(crate) async fn fetch_work(
     http: &reqwest::Client,
+    net: &NetworkStats,
     backend: &Url,
     count: u32,
+    max_iterations: Option<u64>,
 ) -> anyhow::Result<BackendWorkBatch> {
     let url = backend.join("api/jobs/lease_proofs")?;
-    let res = http
-        .post(url)
-        .json(&WorkRequest { count })
-        .send()
-        .await?;
-
-    if !res.status().is_success() {
-        return Err(error_from_response(res).await);
-    }
-    Ok(res.json().await?)
+    post_json(
+        http,
+        net,
+        ENDPOINT_LEASE,
+        url,
+        &WorkRequest {
+            count,
+            max_iterations,
+        },
+    )
+    .await
 }
 
 pub(crate) async fn fetch_batch_work(
     http: &reqwest::Client,
+    net: &NetworkStats,
     backend: &Url,
     count: u32,
-) -> anyhow::Result<Vec<BackendWorkGroup>> {
+    max_iterations: Option<u64>,
+) -> anyhow::Result<(Vec<BackendWorkGroup>, LeaseTerms)> {
     let count = count.clamp(1, 32);
     let url = backend.join("api/jobs/lease_batch")?;
-    let res = http
-        .post(url)
-        .json(&LeaseBatchRequest { count: Some(count) })
-        .send()
-        .await?;
-
-    if !res.status().is_success() {
-        return Err(error_from_response(res).await);
-    }
-
-    let batch: LeaseGroupsResponse = res.json().await?;
+    let batch: LeaseGroupsResponse = post_json(
+        http,
+        net,
+        ENDPOINT_LEASE,
+        url,
+        &LeaseBatchRequest {
+            count: Some(count),
+            max_iterations,
+        },
+    )
+    .await?;
     if batch.groups.is_empty() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), batch.terms));
     }
 
     let mut out = Vec::with_capacity(batch.groups.len());
@@ -221,32 +507,121 @@ pub(crate) async fn fetch_batch_work(
         });
     }
 
-    Ok(out)
+    Ok((out, batch.terms))
+}
+
+#[derive(Debug, Serialize)]
+struct RenewLeaseRequest<'a> {
+    lease_id: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct LeaseRenewed {
+    pub(crate) lease_expires_at: i64,
+    #[serde(flatten)]
+    pub(crate) terms: LeaseTerms,
+}
+
+/// Extend `lease_id` via its renewal endpoint `url`.
+pub(crate) async fn renew_lease(
+    http: &reqwest::Client,
+    net: &NetworkStats,
+    url: Url,
+    lease_id: &str,
+) -> anyhow::Result<LeaseRenewed> {
+    post_json(
+        http,
+        net,
+        ENDPOINT_RENEW,
+        url,
+        &RenewLeaseRequest { lease_id },
+    )
+    .await
+}
+
+#[derive(Debug, Serialize)]
+struct ReleaseJobsRequest<'a> {
+    lease_id: &'a str,
+    job_ids: &'a [u64],
+}
+
+/// Hand `job_ids` leased under `lease_id` back via the lease's release endpoint `url`.
+pub(crate) async fn release_jobs(
+    http: &reqwest::Client,
+    net: &NetworkStats,
+    url: Url,
+    lease_id: &str,
+    job_ids: &[u64],
+) -> anyhow::Result<()> {
+    let _: serde::de::IgnoredAny = post_json(
+        http,
+        net,
+        ENDPOINT_RELEASE,
+        url,
+        &ReleaseJobsRequest { lease_id, job_ids },
+    )
+    .await?;
+    Ok(())
 }
 
 pub(crate) async fn submit_job(
     http: &reqwest::Client,
+    net: &NetworkStats,
     backend: &Url,
     job_id: u64,
     lease_id: &str,
     witness: &[u8],
     reward_address: Option<&str>,
     name: Option<&str>,
-) -> anyhow::Result<SubmitResponse> {
-    let url = backend.join(&format!("api/jobs/{job_id}/submit"))?;
-    let res = http
-        .post(url)
-        .json(&SubmitRequest {
-            lease_id: lease_id.to_string(),
-            witness_b64: B64.encode(witness),
-            reward_address: reward_address.map(str::to_string),
-            name: name.map(str::to_string),
-        })
-        .send()
-        .await?;
+    signer: Option<&SubmissionSigner>,
+) -> Result<SubmitResponse, SubmitError> {
+    let url = backend
+        .join(&format!("api/jobs/{job_id}/submit"))
+        .map_err(anyhow::Error::from)?;
+    let mut headers = header::HeaderMap::new();
+    if let Some(signer) = signer {
+        headers.insert(
+            SIGNATURE_KEY_HEADER,
+            header::HeaderValue::from_str(&signer.public_key_b64()).map_err(anyhow::Error::from)?,
+        );
+        headers.insert(
+            SIGNATURE_HEADER,
+            header::HeaderValue::from_str(&signer.sign_submission(job_id, lease_id, witness))
+                .map_err(anyhow::Error::from)?,
+        );
+    }
+    let body = serde_json::to_vec(&SubmitRequest {
+        lease_id: lease_id.to_string(),
+        witness_b64: B64.encode(witness),
+        reward_address: reward_address.map(str::to_string),
+        name: name.map(str::to_string),
+    })
+    .map_err(anyhow::Error::from)?;
 
+    // Same as `post_json_with_headers`, but keeps the reply's status and headers.
+    let uploaded = body.len();
+    let started_at = Instant::now();
+    let res = send_post(http, url, headers, body).await;
+    net.record_latency(ENDPOINT_SUBMIT, started_at.elapsed());
+    let res = res?;
+    let response = ResponseMeta::of(&res);
     if !res.status().is_success() {
-        return Err(error_from_response(res).await);
+        return Err(SubmitError {
+            error: error_from_response(res).await,
+            response: Some(response),
+        });
+    }
+    let parsed = async {
+        let bytes = res.bytes().await?;
+        net.record_exchange(uploaded, bytes.len());
+        anyhow::Ok(serde_json::from_slice::<SubmitResponse>(&bytes)?)
     }
-    Ok(res.json().await?)
+    .await;
+    let mut parsed = parsed.map_err(|error| SubmitError {
+        error,
+        response: Some(response.clone()),
+    })?;
+    parsed.response = response;
+    net.record_submit();
+    Ok(parsed)
 }
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use crate::api::{EngineConfig, EngineControl, EngineEvent};
+use crate::engine::{EngineInner, start_engine};
+
+/// How often the control thread checks whether the engine already stopped.
+const CONTROL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub(crate) fn run_blocking(
+    cfg: EngineConfig,
+    ctrl_rx: Receiver<EngineControl>,
+) -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_name("bbr-engine")
+        .build()?;
+
+    runtime.block_on(async move {
+        let handle = start_engine(cfg);
+        let done = Arc::new(AtomicBool::new(false));
+
+        let control = {
+            let inner = handle.inner.clone();
+            let done = done.clone();
+            let rt = tokio::runtime::Handle::current();
+            std::thread::Builder::new()
+                .name("bbr-engine-control".to_string())
+                .spawn(move || run_control(&inner, &rt, &ctrl_rx, &done))?
+        };
+
+        let res = handle.wait().await;
+        done.store(true, Ordering::SeqCst);
+        let _ = control.join();
+        res
+    })
+}
+
+fn run_control(
+    inner: &Arc<EngineInner>,
+    rt: &tokio::runtime::Handle,
+    ctrl_rx: &Receiver<EngineControl>,
+    done: &AtomicBool,
+) {
+    while !done.load(Ordering::SeqCst) {
+        match ctrl_rx.recv_timeout(CONTROL_POLL_INTERVAL) {
+            Ok(EngineControl::Stop) => inner.request_stop(),
+            Ok(EngineControl::Subscribe(tx)) => {
+                let events = inner.event_tx.subscribe();
+                rt.spawn(forward_events(events, tx));
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                inner.request_stop();
+                return;
+            }
+        }
+    }
+}
+
+async fn forward_events(
+    mut events: broadcast::Receiver<EngineEvent>,
+    tx: std::sync::mpsc::Sender<EngineEvent>,
+) {
+    loop {
+        match events.recv().await {
+            Ok(evt) => {
+                if tx.send(evt).is_err() {
+                    break;
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::{Local, NaiveDate, NaiveTime, TimeDelta, TimeZone as _};
+
+use crate::api::{BudgetStatus, DailyBudget, JobHistoryEntry, JobOutcome};
+use crate::history::load_job_history;
+
+/// Work done towards [`DailyBudget`] on the current local day.
+pub(crate) struct BudgetTracker {
+    budget: DailyBudget,
+    day: NaiveDate,
+    proofs: u64,
+    compute_ms: u64,
+}
+
+impl BudgetTracker {
+    /// Start tracking, counting this backend's jobs finished earlier today.
+    pub(crate) fn load(budget: DailyBudget, backend_url: &str) -> Self {
+        let day = Local::now().date_naive();
+        // Without a readable job history the count starts from zero.
+        let history = load_job_history(Some(day_start(day))).unwrap_or_default();
+        let today: Vec<&JobHistoryEntry> = history
+            .iter()
+            .filter(|entry| entry.backend_url == backend_url)
+            .collect();
+        // Entries appended together share `finished_at`; one worker's share of
+        // them is a single (possibly grouped) computation.
+        let compute_ms = today
+            .chunk_by(|a, b| {
+                a.finished_at == b.finished_at && a.outcome.worker_idx == b.outcome.worker_idx
+            })
+            .map(|task| task.iter().map(|e| e.outcome.compute_ms).max().unwrap_or(0))
+            .sum();
+        Self {
+            budget,
+            day,
+            proofs: today.iter().filter(|e| is_submitted(&e.outcome)).count() as u64,
+            compute_ms,
+        }
+    }
+
+    /// Count finished jobs, as passed to the engine's `record_outcomes`.
+    pub(crate) fn record(&mut self, outcomes: &[JobOutcome]) {
+        let today = Local::now().date_naive();
+        if today != self.day {
+            self.day = today;
+            self.proofs = 0;
+            self.compute_ms = 0;
+        }
+        self.proofs += outcomes.iter().filter(|o| is_submitted(o)).count() as u64;
+        // Grouped proofs each carry the group's full compute time.
+        self.compute_ms += outcomes
+            .chunk_by(|a, b| a.worker_idx == b.worker_idx)
+            .map(|task| task.iter().map(|o| o.compute_ms).max().unwrap_or(0))
+            .sum::<u64>();
+    }
+
+    /// Whether today's budget is used up.
+    pub(crate) fn exhausted(&self) -> bool {
+        let (proofs, compute_ms) = self.today();
+        self.budget.max_proofs.is_some_and(|max| proofs >= max)
+            || self
+                .budget
+                .max_compute
+                .is_some_and(|max| u128::from(compute_ms) >= max.as_millis())
+    }
+
+    pub(crate) fn status(&self) -> BudgetStatus {
+        let (proofs_today, compute_ms_today) = self.today();
+        BudgetStatus {
+            proofs_today,
+            max_proofs: self.budget.max_proofs,
+            compute_ms_today,
+            max_compute_ms: self
+                .budget
+                .max_compute
+                .map(|max| max.as_millis().try_into().unwrap_or(u64::MAX)),
+            resumes_at: self
+                .day
+                .succ_opt()
+                .map(day_start)
+                .filter(|_| self.exhausted()),
+        }
+    }
+
+    /// Counts for the current day (zero once the day has rolled over).
+    fn today(&self) -> (u64, u64) {
+        if Local::now().date_naive() == self.day {
+            (self.proofs, self.compute_ms)
+        } else {
+            (0, 0)
+        }
+    }
+}
+
+fn is_submitted(outcome: &JobOutcome) -> bool {
+    outcome.error.is_none() && outcome.submit_reason.is_some()
+}
+
+/// Unix timestamp (seconds) of the local midnight starting `day`.
+fn day_start(day: NaiveDate) -> i64 {
+    let midnight = day.and_time(NaiveTime::MIN);
+    // Some time zones skip midnight when DST starts; the day then begins an hour later.
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .or_else(|| {
+            Local
+                .from_local_datetime(&(midnight + TimeDelta::hours(1)))
+                .earliest()
+        })
+        .map_or_else(|| midnight.and_utc().timestamp(), |t| t.timestamp())
+}
@@ -0,0 +1,255 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use bbr_client_chiavdf_fast::{ClassgroupElement, prove_one_weso_fast};
+
+const CALIBRATION_DISCRIMINANT_BITS: usize = 1024;
+/// Iterations per calibration proof; small enough to finish well within a phase.
+const CALIBRATION_CHUNK_ITERS: u64 = 200_000;
+const CALIBRATION_CHALLENGE: [u8; 32] = *b"bbr-client-calibration-v1\0\0\0\0\0\0\0";
+
+/// Per-thread throughput below this fraction of the single-thread speed when all
+/// logical CPUs are busy indicates SMT siblings fighting over the same core.
+const SMT_CONTENTION_RATIO: f64 = 0.6;
+
+const MIN_MEM_BUDGET_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_MEM_BUDGET_BYTES: u64 = 512 * 1024 * 1024;
+const DEFAULT_MEM_BUDGET_BYTES: u64 = 128 * 1024 * 1024;
+
+/// Host CPU/memory capabilities relevant to the prover.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CpuInfo {
+    /// Target architecture (e.g. `x86_64`, `aarch64`).
+    pub arch: String,
+    /// Target operating system.
+    pub os: String,
+    /// Number of logical CPUs available to this process.
+    pub logical_cpus: usize,
+    /// Detected instruction set extensions used by the fast prover (x86_64 only).
+    pub features: Vec<String>,
+    /// Total physical memory, when it can be determined.
+    pub total_memory_bytes: Option<u64>,
+}
+
+/// Measured squaring throughput.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CalibrationResult {
+    /// Iterations/second of a single worker running alone.
+    pub single_thread_iters_per_sec: u64,
+    /// Aggregate iterations/second with `threads` workers running concurrently.
+    pub all_threads_iters_per_sec: u64,
+    /// Number of concurrent workers used for the aggregate measurement.
+    pub threads: usize,
+    /// Total calibration wall time (milliseconds).
+    pub duration_ms: u64,
+}
+
+/// Worker settings suggested from [`CpuInfo`] and a [`CalibrationResult`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SuggestedSettings {
+    /// Suggested number of parallel workers.
+    pub parallel: usize,
+    /// Suggested per-worker memory budget for the streaming prover (bytes).
+    pub mem_budget_bytes: u64,
+    /// Whether grouped work is recommended.
+    pub use_groups: bool,
+}
+
+/// Detect the host CPU capabilities.
+pub fn detect_cpu() -> CpuInfo {
+    CpuInfo {
+        arch: std::env::consts::ARCH.to_string(),
+        os: std::env::consts::OS.to_string(),
+        logical_cpus: std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+        features: cpu_features(),
+        total_memory_bytes: total_memory_bytes(),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn cpu_features() -> Vec<String> {
+    let mut out = Vec::new();
+    if std::arch::is_x86_feature_detected!("avx2") {
+        out.push("avx2".to_string());
+    }
+    if std::arch::is_x86_feature_detected!("bmi2") {
+        out.push("bmi2".to_string());
+    }
+    if std::arch::is_x86_feature_detected!("adx") {
+        out.push("adx".to_string());
+    }
+    out
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn cpu_features() -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn meminfo_bytes(key: &str) -> Option<u64> {
+    let raw = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let prefix = format!("{key}:");
+    let line = raw.lines().find(|l| l.starts_with(&prefix))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    kib.checked_mul(1024)
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn total_memory_bytes() -> Option<u64> {
+    meminfo_bytes("MemTotal")
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn total_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Memory available for new allocations without swapping, if known.
+#[cfg(target_os = "linux")]
+pub(crate) fn available_memory_bytes() -> Option<u64> {
+    meminfo_bytes("MemAvailable")
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn available_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Run a blocking calibration for roughly `duration`.
+///
+/// The first third measures a single worker; the rest runs one worker per
+/// logical CPU. Call from a blocking context (e.g. `spawn_blocking`).
+pub fn calibrate(duration: Duration) -> anyhow::Result<CalibrationResult> {
+    let started_at = Instant::now();
+    let threads = detect_cpu().logical_cpus;
+
+    let single = measure(1, duration / 3).context("single-thread calibration")?;
+    let all = measure(threads, duration.saturating_sub(started_at.elapsed()))
+        .context("multi-thread calibration")?;
+
+    Ok(CalibrationResult {
+        single_thread_iters_per_sec: single,
+        all_threads_iters_per_sec: all,
+        threads,
+        duration_ms: started_at.elapsed().as_millis() as u64,
+    })
+}
+
+/// Run `threads` workers proving fixed-size chunks until `phase` elapses and return
+/// the aggregate iterations/second. Each worker always completes at least one chunk.
+fn measure(threads: usize, phase: Duration) -> anyhow::Result<u64> {
+    let threads = threads.max(1);
+    let deadline = Instant::now() + phase;
+    let iters_done = Arc::new(AtomicU64::new(0));
+    let x = ClassgroupElement::generator();
+
+    let started_at = Instant::now();
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let iters_done = iters_done.clone();
+            std::thread::spawn(move || -> anyhow::Result<()> {
+                loop {
+                    prove_one_weso_fast(
+                        &CALIBRATION_CHALLENGE,
+                        x.as_bytes(),
+                        CALIBRATION_DISCRIMINANT_BITS,
+                        CALIBRATION_CHUNK_ITERS,
+                    )
+                    .context("chiavdf prove_one_weso_fast")?;
+                    iters_done.fetch_add(CALIBRATION_CHUNK_ITERS, Ordering::Relaxed);
+                    if Instant::now() >= deadline {
+                        return Ok(());
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        match handle.join() {
+            Ok(res) => res?,
+            Err(_) => anyhow::bail!("calibration thread panicked"),
+        }
+    }
+
+    let secs = started_at.elapsed().as_secs_f64();
+    if secs <= 0.0 {
+        return Ok(0);
+    }
+    Ok((iters_done.load(Ordering::Relaxed) as f64 / secs).round() as u64)
+}
+
+/// Suggest worker settings for this host.
+pub fn suggest_settings(cpu: &CpuInfo, calibration: &CalibrationResult) -> SuggestedSettings {
+    let logical = cpu.logical_cpus.max(1);
+    let per_thread =
+        calibration.all_threads_iters_per_sec as f64 / calibration.threads.max(1) as f64;
+    let contended = calibration.single_thread_iters_per_sec > 0
+        && per_thread < calibration.single_thread_iters_per_sec as f64 * SMT_CONTENTION_RATIO;
+    let parallel = if contended && logical > 1 {
+        logical / 2
+    } else {
+        logical
+    };
+
+    // Keep total streaming buffers within a quarter of physical memory.
+    let mem_budget_bytes = match cpu.total_memory_bytes {
+        Some(total) => {
+            (total / 4 / parallel as u64).clamp(MIN_MEM_BUDGET_BYTES, MAX_MEM_BUDGET_BYTES)
+        }
+        None => DEFAULT_MEM_BUDGET_BYTES,
+    };
+
+    SuggestedSettings {
+        parallel,
+        mem_budget_bytes,
+        use_groups: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cpu(logical_cpus: usize, total_memory_bytes: Option<u64>) -> CpuInfo {
+        CpuInfo {
+            arch: "x86_64".to_string(),
+            os: "linux".to_string(),
+            logical_cpus,
+            features: Vec::new(),
+            total_memory_bytes,
+        }
+    }
+
+    fn calibration(single: u64, all: u64, threads: usize) -> CalibrationResult {
+        CalibrationResult {
+            single_thread_iters_per_sec: single,
+            all_threads_iters_per_sec: all,
+            threads,
+            duration_ms: 15_000,
+        }
+    }
+
+    #[test]
+    fn contended_smt_halves_the_suggested_workers() {
+        let gib = 1024 * 1024 * 1024;
+        let scaling = suggest_settings(&cpu(8, Some(16 * gib)), &calibration(100, 760, 8));
+        assert_eq!(scaling.parallel, 8);
+        assert_eq!(scaling.mem_budget_bytes, 512 * 1024 * 1024);
+
+        let contended = suggest_settings(&cpu(8, Some(gib)), &calibration(100, 400, 8));
+        assert_eq!(contended.parallel, 4);
+        assert_eq!(contended.mem_budget_bytes, MIN_MEM_BUDGET_BYTES);
+
+        let unknown = suggest_settings(&cpu(1, None), &calibration(100, 40, 1));
+        assert_eq!(unknown.parallel, 1);
+        assert_eq!(unknown.mem_budget_bytes, DEFAULT_MEM_BUDGET_BYTES);
+    }
+}
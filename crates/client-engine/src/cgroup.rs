@@ -0,0 +1,144 @@
+//! Optional cgroup v2 placement for compute threads (Linux only).
+//!
+//! The engine process moves into the configured cgroup, which carries the
+//! memory limit, and compute threads join its threaded `compute` child, which
+//! carries the CPU weight:
+//!
+//! ```text
+//! <path>/            memory.max, cgroup.subtree_control += cpu
+//! <path>/compute/    cgroup.type = threaded, cpu.weight
+//! ```
+//!
+//! The parent of `<path>` must already delegate the `cpu` and `memory`
+//! controllers (e.g. a systemd `Delegate=yes` slice, or running as root).
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Context as _;
+
+use crate::api::CgroupConfig;
+
+const CGROUP_MOUNT: &str = "/sys/fs/cgroup";
+const COMPUTE_CHILD: &str = "compute";
+
+/// The `compute` cgroup that worker threads join.
+#[derive(Debug, Clone)]
+pub(crate) struct ComputeCgroup {
+    root: PathBuf,
+    compute: PathBuf,
+}
+
+impl ComputeCgroup {
+    /// Create (or reuse) the cgroup, apply its limits and move this process into it.
+    pub(crate) fn setup(cfg: &CgroupConfig) -> anyhow::Result<Self> {
+        if !cfg!(target_os = "linux") {
+            anyhow::bail!("cgroups are only supported on Linux");
+        }
+        if !Path::new(CGROUP_MOUNT).join("cgroup.controllers").exists() {
+            anyhow::bail!("no cgroup v2 hierarchy mounted at {CGROUP_MOUNT}");
+        }
+        if let Some(weight) = cfg.cpu_weight
+            && !(1..=10_000).contains(&weight)
+        {
+            anyhow::bail!("cpu weight {weight} is out of range (expected 1..=10000)");
+        }
+
+        let root = resolve_cgroup_dir(&cfg.path)?;
+        let compute = root.join(COMPUTE_CHILD);
+        std::fs::create_dir_all(&compute)
+            .with_context(|| format!("create {}", compute.display()))?;
+
+        let compute_type = compute.join("cgroup.type");
+        let current_type = std::fs::read_to_string(&compute_type)
+            .with_context(|| format!("read {}", compute_type.display()))?;
+        if current_type.trim() != "threaded" {
+            write_file(&compute_type, "threaded")?;
+        }
+
+        if let Some(bytes) = cfg.memory_max_bytes {
+            write_file(&root.join("memory.max"), &bytes.to_string())?;
+        }
+        if let Some(weight) = cfg.cpu_weight {
+            write_file(&root.join("cgroup.subtree_control"), "+cpu")?;
+            write_file(&compute.join("cpu.weight"), &weight.to_string())?;
+        }
+
+        write_file(&root.join("cgroup.procs"), &std::process::id().to_string())?;
+        Ok(Self { root, compute })
+    }
+
+    /// The cgroup directory the engine process was moved into.
+    pub(crate) fn path(&self) -> &Path {
+        &self.root
+    }
+
+    /// Move the calling thread into the `compute` cgroup.
+    pub(crate) fn join_current_thread(&self) -> anyhow::Result<()> {
+        // `/proc/thread-self` links to `<pid>/task/<tid>`.
+        let link = std::fs::read_link("/proc/thread-self").context("read /proc/thread-self")?;
+        let tid = link
+            .file_name()
+            .and_then(|name| name.to_str())
+            .context("unexpected /proc/thread-self target")?;
+        write_file(&self.compute.join("cgroup.threads"), tid)
+    }
+}
+
+/// Map a configured path to its directory under the cgroup v2 mount.
+///
+/// Relative paths and absolute paths outside the mount are both taken relative
+/// to it; `..` is rejected so the cgroup can't escape the hierarchy.
+fn resolve_cgroup_dir(path: &Path) -> anyhow::Result<PathBuf> {
+    let relative = path.strip_prefix(CGROUP_MOUNT).unwrap_or(path);
+    let mut dir = PathBuf::from(CGROUP_MOUNT);
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => dir.push(part),
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir | Component::Prefix(_) => {
+                anyhow::bail!("invalid cgroup path {}", path.display());
+            }
+        }
+    }
+    if dir == Path::new(CGROUP_MOUNT) {
+        anyhow::bail!("cgroup path must name a cgroup below {CGROUP_MOUNT}");
+    }
+    Ok(dir)
+}
+
+fn write_file(path: &Path, value: &str) -> anyhow::Result<()> {
+    std::fs::write(path, value).with_context(|| format!("write {value:?} to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cgroup_paths_stay_below_the_mount() {
+        for (path, expected) in [
+            ("wesoforge", "/sys/fs/cgroup/wesoforge"),
+            (
+                "/wesoforge.slice/engine",
+                "/sys/fs/cgroup/wesoforge.slice/engine",
+            ),
+            ("/sys/fs/cgroup/wesoforge", "/sys/fs/cgroup/wesoforge"),
+            ("./wesoforge/", "/sys/fs/cgroup/wesoforge"),
+        ] {
+            assert_eq!(
+                resolve_cgroup_dir(Path::new(path)).unwrap(),
+                Path::new(expected),
+                "{path}"
+            );
+        }
+        for bad in [
+            "",
+            "/",
+            "/sys/fs/cgroup",
+            "wesoforge/../..",
+            "../system.slice",
+        ] {
+            assert!(resolve_cgroup_dir(Path::new(bad)).is_err(), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::inflight::xdg_state_home;
+
+/// How often an engine refreshes its entry and re-reads its peers'.
+pub(crate) const COOPERATIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Entries not refreshed for this long belong to engines that exited without
+/// cleaning up (crash, `kill -9`) and are removed by whichever peer sees them.
+const STALE_AFTER_SECS: i64 = 30;
+
+/// Distinguishes engines started by the same process (one per backend, or a
+/// GUI restarting its engine).
+static NEXT_ENGINE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// What one engine on this host asks for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PeerEntry {
+    pub(crate) pid: u32,
+    pub(crate) backend_url: String,
+    /// Workers the engine would run on its own (`parallel`, or the
+    /// `auto_parallel` limit).
+    pub(crate) workers: usize,
+    pub(crate) mem_budget_bytes: u64,
+    /// Unix timestamp (seconds) of the last refresh.
+    pub(crate) updated_at: i64,
+}
+
+/// This engine's entry in the host-wide peer directory.
+///
+/// Every engine writes only its own file (atomically, via rename), so no
+/// locking is needed; the entry is removed on drop.
+pub(crate) struct PeerRegistration {
+    dir: PathBuf,
+    path: PathBuf,
+    entry: PeerEntry,
+}
+
+impl PeerRegistration {
+    pub(crate) fn register(
+        backend_url: &reqwest::Url,
+        workers: usize,
+        mem_budget_bytes: u64,
+    ) -> anyhow::Result<Self> {
+        let dir = peers_dir()?;
+        std::fs::create_dir_all(&dir)?;
+        let pid = std::process::id();
+        let id = NEXT_ENGINE_ID.fetch_add(1, Ordering::Relaxed);
+        let registration = Self {
+            path: dir.join(format!("{pid}-{id}.json")),
+            dir,
+            entry: PeerEntry {
+                pid,
+                backend_url: backend_url.to_string(),
+                workers,
+                mem_budget_bytes,
+                updated_at: Utc::now().timestamp(),
+            },
+        };
+        registration.write()?;
+        Ok(registration)
+    }
+
+    /// Refresh this engine's entry and return the live peers' entries (without
+    /// this engine's own), removing stale ones.
+    pub(crate) fn refresh(&mut self) -> anyhow::Result<Vec<PeerEntry>> {
+        let now = Utc::now().timestamp();
+        self.entry.updated_at = now;
+        self.write()?;
+
+        let mut peers = Vec::new();
+        for dirent in std::fs::read_dir(&self.dir)? {
+            let path = dirent?.path();
+            if path == self.path || path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            // Peers may be mid-rename or exiting; unreadable entries are skipped.
+            let Some(entry) = read_entry(&path) else {
+                continue;
+            };
+            if now.saturating_sub(entry.updated_at) > STALE_AFTER_SECS {
+                let _ = std::fs::remove_file(&path);
+                continue;
+            }
+            peers.push(entry);
+        }
+        Ok(peers)
+    }
+
+    fn write(&self) -> anyhow::Result<()> {
+        let json = serde_json::to_vec(&self.entry)?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+impl Drop for PeerRegistration {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn read_entry(path: &Path) -> Option<PeerEntry> {
+    let raw = std::fs::read(path).ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+fn peers_dir() -> anyhow::Result<PathBuf> {
+    Ok(xdg_state_home()?.join("bbr-client").join("peers"))
+}
@@ -0,0 +1,261 @@
+use std::time::{Duration, Instant};
+
+use bbr_client_chiavdf_fast::{
+    ClassgroupElement, evaluate_weso, implementation_name, prove_one_weso_fast,
+};
+use bbr_client_core::format::format_duration;
+use bbr_client_core::network::{NetworkSettings, load_network_settings};
+use bbr_client_core::settings::load_worker_settings;
+use bbr_client_core::submitter::{load_submitter_config, validate_reward_address};
+use reqwest::Url;
+
+use crate::api::{CheckStatus, DoctorCheck, DoctorReport};
+use crate::engine::build_http_client;
+use crate::history::check_history_file;
+use crate::inflight::check_inflight_file;
+
+const SELFTEST_CHALLENGE: [u8; 32] = *b"bbr-client-doctor-selftest-chal!";
+const SELFTEST_DISCRIMINANT_BITS: usize = 1024;
+const SELFTEST_ITERATIONS: u64 = 10_000;
+
+/// A self-test proof slower than this points at a throttled or overloaded machine.
+const SELFTEST_SLOW: Duration = Duration::from_secs(10);
+
+impl DoctorCheck {
+    fn new(id: &str, title: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+pub(crate) async fn run_doctor(backend_url: &Url, state_namespace: Option<&str>) -> DoctorReport {
+    let (network, config) = check_config();
+    let native = tokio::task::spawn_blocking(check_native)
+        .await
+        .unwrap_or_else(|err| {
+            DoctorCheck::new(
+                "native",
+                "Native prover",
+                CheckStatus::Fail,
+                format!("self-test crashed: {err}"),
+            )
+            .with_fix(REINSTALL_FIX)
+        });
+    let backend = check_backend(backend_url, &network).await;
+    let namespace = state_namespace.map(str::to_string);
+    let state = tokio::task::spawn_blocking(move || check_state(namespace.as_deref()))
+        .await
+        .unwrap_or_else(|err| {
+            DoctorCheck::new(
+                "state",
+                "Local state",
+                CheckStatus::Fail,
+                format!("check crashed: {err}"),
+            )
+        });
+    DoctorReport {
+        checks: vec![native, backend, config, state],
+    }
+}
+
+const REINSTALL_FIX: &str = "Reinstall the client from an official release for this platform; if it keeps failing, report it with the environment line from the logs.";
+
+/// Prove a short VDF and check its output against plain squaring.
+fn check_native() -> DoctorCheck {
+    const ID: &str = "native";
+    const TITLE: &str = "Native prover";
+
+    let x = ClassgroupElement::generator().to_bytes();
+    let started_at = Instant::now();
+    let proof = match prove_one_weso_fast(
+        &SELFTEST_CHALLENGE,
+        &x,
+        SELFTEST_DISCRIMINANT_BITS,
+        SELFTEST_ITERATIONS,
+    ) {
+        Ok(proof) => proof,
+        Err(err) => {
+            return DoctorCheck::new(
+                ID,
+                TITLE,
+                CheckStatus::Fail,
+                format!("self-test failed: {err}"),
+            )
+            .with_fix(REINSTALL_FIX);
+        }
+    };
+    let elapsed = started_at.elapsed();
+
+    match evaluate_weso(
+        &SELFTEST_CHALLENGE,
+        &x,
+        SELFTEST_DISCRIMINANT_BITS,
+        SELFTEST_ITERATIONS,
+    ) {
+        Ok(y) if y == proof.y => {}
+        Ok(_) => {
+            return DoctorCheck::new(
+                ID,
+                TITLE,
+                CheckStatus::Fail,
+                "self-test proof does not match plain squaring",
+            )
+            .with_fix(
+                "Report the mismatch with this output; the native prover is miscomputing on this machine.",
+            );
+        }
+        Err(err) => {
+            return DoctorCheck::new(
+                ID,
+                TITLE,
+                CheckStatus::Fail,
+                format!("self-test evaluation failed: {err}"),
+            )
+            .with_fix(REINSTALL_FIX);
+        }
+    }
+
+    let detail = format!(
+        "{} prover, {} iterations in {}",
+        implementation_name(),
+        SELFTEST_ITERATIONS,
+        format_duration(elapsed)
+    );
+    if elapsed > SELFTEST_SLOW {
+        return DoctorCheck::new(ID, TITLE, CheckStatus::Warn, detail).with_fix(
+            "The machine is unusually slow; check for thermal throttling, power-saving modes or other heavy processes.",
+        );
+    }
+    DoctorCheck::new(ID, TITLE, CheckStatus::Pass, detail)
+}
+
+/// Fetch the backend's public stats with the configured network settings.
+async fn check_backend(backend_url: &Url, network: &NetworkSettings) -> DoctorCheck {
+    const ID: &str = "backend";
+    const TITLE: &str = "Backend reachability";
+
+    let http = match build_http_client(network) {
+        Ok(http) => http,
+        Err(err) => {
+            return DoctorCheck::new(
+                ID,
+                TITLE,
+                CheckStatus::Fail,
+                format!("cannot build an HTTP client: {err:#}"),
+            )
+            .with_fix("Fix the network settings (proxy, TLS certificates) reported below.");
+        }
+    };
+    let started_at = Instant::now();
+    match crate::backend::fetch_queue_status(&http, backend_url).await {
+        Ok(_) => DoctorCheck::new(
+            ID,
+            TITLE,
+            CheckStatus::Pass,
+            format!(
+                "{backend_url} answered in {}",
+                format_duration(started_at.elapsed())
+            ),
+        ),
+        Err(err) => DoctorCheck::new(
+            ID,
+            TITLE,
+            CheckStatus::Fail,
+            format!("{backend_url}: {err:#}"),
+        )
+        .with_fix(
+            "Check the internet connection and the backend URL; behind a firewall or proxy, set the proxy in the network settings.",
+        ),
+    }
+}
+
+/// Parse the network, submitter and worker settings; returns the network
+/// settings to use for the backend check (defaults when they are invalid).
+fn check_config() -> (NetworkSettings, DoctorCheck) {
+    const ID: &str = "config";
+    const TITLE: &str = "Configuration";
+
+    let mut problems = Vec::new();
+    let network = match load_network_settings() {
+        Ok(network) => match network.validate() {
+            Ok(()) => network,
+            Err(err) => {
+                problems.push(format!("network settings: {err:#}"));
+                NetworkSettings::default()
+            }
+        },
+        Err(err) => {
+            problems.push(format!("network settings: {err:#}"));
+            NetworkSettings::default()
+        }
+    };
+    match load_submitter_config() {
+        Ok(Some(cfg)) => {
+            if let Some(address) = cfg.reward_address.as_deref()
+                && let Err(err) = validate_reward_address(address)
+            {
+                problems.push(format!("reward address: {err:#}"));
+            }
+        }
+        Ok(None) => {}
+        Err(err) => problems.push(format!("submitter config: {err:#}")),
+    }
+    if let Err(err) = load_worker_settings() {
+        problems.push(format!("worker settings: {err:#}"));
+    }
+
+    let check = if problems.is_empty() {
+        DoctorCheck::new(ID, TITLE, CheckStatus::Pass, "settings files are valid")
+    } else {
+        DoctorCheck::new(ID, TITLE, CheckStatus::Fail, problems.join("; ")).with_fix(
+            "Correct or delete the reported settings file; deleted files are recreated with defaults.",
+        )
+    };
+    (network, check)
+}
+
+/// Validate (and repair, as startup would) the inflight and history files.
+fn check_state(namespace: Option<&str>) -> DoctorCheck {
+    const ID: &str = "state";
+    const TITLE: &str = "Local state";
+
+    let mut repaired = Vec::new();
+    let mut failed = Vec::new();
+    for (name, res) in [
+        ("inflight leases", check_inflight_file(namespace)),
+        ("job history", check_history_file()),
+    ] {
+        match res {
+            Ok(check) if check.is_clean() => {}
+            Ok(check) => repaired.push(check.describe(name)),
+            Err(err) => failed.push(format!("{name}: {err:#}")),
+        }
+    }
+
+    if !failed.is_empty() {
+        return DoctorCheck::new(ID, TITLE, CheckStatus::Fail, failed.join("; ")).with_fix(
+            "Make sure the state directory exists, is writable and the disk is not full.",
+        );
+    }
+    if !repaired.is_empty() {
+        return DoctorCheck::new(ID, TITLE, CheckStatus::Warn, repaired.join("; ")).with_fix(
+            "Damaged entries were moved aside and the files repaired; nothing else to do unless this keeps happening (then check the disk).",
+        );
+    }
+    DoctorCheck::new(
+        ID,
+        TITLE,
+        CheckStatus::Pass,
+        "inflight leases and job history are intact",
+    )
+}
@@ -1,35 +1,107 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
+use anyhow::Context as _;
+use bbr_client_core::format::format_number;
+use bbr_client_core::network::{IpPreference, NetworkSettings};
+use bbr_client_core::signing::SubmissionSigner;
+use bbr_client_core::submitter::SubmitterConfig;
 use chrono::Utc;
-use tokio::sync::{broadcast, mpsc, watch};
+use tokio::sync::{mpsc, watch};
 use tokio::task::JoinSet;
 
 use crate::api::{
-    EngineConfig, EngineEvent, EngineHandle, JobOutcome, JobSummary, PinMode, StatusSnapshot,
-    WorkerSnapshot, WorkerStage,
+    BackendMaintenance, EngineConfig, EngineError, EngineEvent, EngineHandle, EnvironmentInfo,
+    JobOutcome, JobSummary, PinMode, SessionStats, ShutdownReport, StatusSnapshot, WorkerSnapshot,
+    WorkerStage,
 };
+use crate::archive::SubmissionArchive;
 use crate::backend::{
-    BackendJobDto, BackendWorkBatch, BackendWorkGroup, fetch_batch_work, fetch_work,
+    BackendError, BackendJobDto, BackendWorkBatch, BackendWorkGroup, LeaseRenewed, LeaseTerms,
+    fetch_batch_work, fetch_work, release_jobs, renew_lease,
 };
+use crate::budget::BudgetTracker;
+use crate::calibrate::{available_memory_bytes, detect_cpu, total_memory_bytes};
+use crate::cgroup::ComputeCgroup;
+use crate::cooperative::{COOPERATIVE_INTERVAL, PeerRegistration};
+use crate::events::EventSender;
+use crate::fault::Faults;
+use crate::history::HistoryStore;
 use crate::inflight::InflightStore;
+use crate::lease::{LeaseDeadline, LeaseRenewals, LeaseTable};
+use crate::network::NetworkStats;
+use crate::parallelism::{
+    MEMORY_RESERVE, auto_worker_limit, cooperative_worker_limit, pressure_worker_limit,
+};
 use crate::pinning::PinningPlan;
-use crate::worker::{WorkerCommand, WorkerInternalEvent};
+use crate::proof_cache::ProofCache;
+use crate::rng::{SPLITMIX64_GAMMA, splitmix64};
+use crate::submit_queue::SubmitQueue;
+use crate::telemetry::TelemetryReporter;
+use crate::worker::{SharedGroupJobs, WorkerCommand, WorkerInternalEvent};
+
+type JobFinishedCallback = Arc<dyn Fn(JobOutcome) + Send + Sync>;
+type ErrorCallback = Arc<dyn Fn(EngineError) + Send + Sync>;
+/// Background lease request: the leased work and the terms it was granted under.
+type FetchTask = tokio::task::JoinHandle<anyhow::Result<(Vec<WorkItem>, LeaseTerms)>>;
+/// Background lease renewal: each renewed lease id with the backend's answer.
+type RenewTask = tokio::task::JoinHandle<Vec<(String, anyhow::Result<LeaseRenewed>)>>;
+
+/// Callbacks registered through `EngineHandle::on_job_finished` / `on_error`.
+#[derive(Default)]
+struct Callbacks {
+    job_finished: Vec<JobFinishedCallback>,
+    error: Vec<ErrorCallback>,
+}
 
 pub(crate) struct EngineInner {
-    pub(crate) event_tx: broadcast::Sender<EngineEvent>,
+    pub(crate) event_tx: EventSender,
     pub(crate) snapshot_rx: watch::Receiver<StatusSnapshot>,
+    pub(crate) network: Arc<NetworkStats>,
     stop_requested: AtomicBool,
+    paused: AtomicBool,
+    /// Per-worker pause flags (indexed by worker, `parallel` entries).
+    paused_workers: Vec<AtomicBool>,
     notify: tokio::sync::Notify,
+    callbacks: Arc<std::sync::RwLock<Callbacks>>,
+    /// Events queued for the callback dispatcher (see [`dispatch_callbacks`]).
+    callback_tx: mpsc::UnboundedSender<EngineEvent>,
 }
 
 impl EngineInner {
+    pub(crate) fn on_job_finished(&self, callback: JobFinishedCallback) {
+        self.callbacks_mut().job_finished.push(callback);
+    }
+
+    pub(crate) fn on_error(&self, callback: ErrorCallback) {
+        self.callbacks_mut().error.push(callback);
+    }
+
+    fn callbacks_mut(&self) -> std::sync::RwLockWriteGuard<'_, Callbacks> {
+        match self.callbacks.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Queue `event` for registered callbacks, then broadcast it to subscribers.
+    fn publish(&self, event: EngineEvent) {
+        if matches!(
+            event,
+            EngineEvent::JobFinished { .. } | EngineEvent::Error { .. }
+        ) {
+            let _ = self.callback_tx.send(event.clone());
+        }
+        let _ = self.event_tx.send(event);
+    }
+
     pub(crate) fn request_stop(&self) {
         if !self.stop_requested.swap(true, Ordering::SeqCst) {
-            let _ = self.event_tx.send(EngineEvent::StopRequested);
+            self.publish(EngineEvent::StopRequested);
             self.notify.notify_waiters();
         }
     }
@@ -37,6 +109,68 @@ impl EngineInner {
     fn should_stop(&self) -> bool {
         self.stop_requested.load(Ordering::SeqCst)
     }
+
+    /// Pause or resume leasing of new work; returns the previous state.
+    pub(crate) fn set_paused(&self, paused: bool) -> bool {
+        let was = self.paused.swap(paused, Ordering::SeqCst);
+        if was != paused {
+            self.notify.notify_waiters();
+        }
+        was
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Pause or resume a single worker; returns `false` if `worker_idx` is out of range.
+    pub(crate) fn set_worker_paused(&self, worker_idx: usize, paused: bool) -> bool {
+        let Some(flag) = self.paused_workers.get(worker_idx) else {
+            return false;
+        };
+        if flag.swap(paused, Ordering::SeqCst) != paused {
+            self.notify.notify_waiters();
+        }
+        true
+    }
+
+    pub(crate) fn is_worker_paused(&self, worker_idx: usize) -> bool {
+        self.paused_workers
+            .get(worker_idx)
+            .is_some_and(|flag| flag.load(Ordering::SeqCst))
+    }
+}
+
+/// Run callbacks for the events [`EngineInner::publish`] queues, in order, on a
+/// task of their own so a slow callback doesn't hold up the engine loop.
+///
+/// Each callback list is cloned out of the registry before running, so callbacks
+/// may register further callbacks. Ends once the engine is dropped.
+async fn dispatch_callbacks(
+    callbacks: Arc<std::sync::RwLock<Callbacks>>,
+    mut rx: mpsc::UnboundedReceiver<EngineEvent>,
+) {
+    let registered = || match callbacks.read() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    while let Some(event) = rx.recv().await {
+        match event {
+            EngineEvent::JobFinished { outcome } => {
+                let job_finished = registered().job_finished.clone();
+                for callback in job_finished {
+                    callback(outcome.clone());
+                }
+            }
+            EngineEvent::Error { error, .. } => {
+                let error_callbacks = registered().error.clone();
+                for callback in error_callbacks {
+                    callback(error.clone());
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -52,6 +186,257 @@ enum WorkItem {
     Group(BackendWorkGroup),
 }
 
+/// A leased work item waiting for an idle worker.
+#[derive(Debug)]
+struct PendingWork {
+    leased_at: Instant,
+    item: WorkItem,
+}
+
+impl PendingWork {
+    fn new(item: WorkItem) -> Self {
+        Self {
+            leased_at: Instant::now(),
+            item,
+        }
+    }
+}
+
+impl WorkItem {
+    fn lease_id(&self) -> &str {
+        match self {
+            WorkItem::Job(item) => &item.lease_id,
+            WorkItem::Group(group) => &group.lease_id,
+        }
+    }
+
+    fn lease_expires_at(&self) -> i64 {
+        match self {
+            WorkItem::Job(item) => item.lease_expires_at,
+            WorkItem::Group(group) => group.lease_expires_at,
+        }
+    }
+
+    /// Squaring iterations needed to finish this item.
+    fn squaring_iters(&self) -> u64 {
+        match self {
+            WorkItem::Job(item) => item.job.number_of_iterations,
+            WorkItem::Group(group) => group
+                .jobs
+                .iter()
+                .map(|j| j.number_of_iterations)
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Split into the part within `max_iterations` and the part above it (either
+    /// may be `None`). Groups are divided job by job and keep their lease.
+    fn partition_by_iterations(self, max_iterations: u64) -> (Option<Self>, Option<Self>) {
+        match self {
+            WorkItem::Job(item) if item.job.number_of_iterations > max_iterations => {
+                (None, Some(WorkItem::Job(item)))
+            }
+            WorkItem::Job(item) => (Some(WorkItem::Job(item)), None),
+            WorkItem::Group(mut group) => {
+                let (over, within): (Vec<_>, Vec<_>) = std::mem::take(&mut group.jobs)
+                    .into_iter()
+                    .partition(|job| job.number_of_iterations > max_iterations);
+                let over = (!over.is_empty()).then(|| {
+                    WorkItem::Group(BackendWorkGroup {
+                        group_id: group.group_id,
+                        lease_id: group.lease_id.clone(),
+                        lease_expires_at: group.lease_expires_at,
+                        jobs: over,
+                    })
+                });
+                group.jobs = within;
+                let within = (!group.jobs.is_empty()).then_some(WorkItem::Group(group));
+                (within, over)
+            }
+        }
+    }
+
+    /// Outcomes reported when the item is released without being computed.
+    fn released_outcomes(
+        self,
+        worker_idx: usize,
+        queue_wait_ms: u64,
+        error: &str,
+    ) -> Vec<JobOutcome> {
+        let jobs = match self {
+            WorkItem::Job(item) => vec![item.job],
+            WorkItem::Group(group) => group.jobs,
+        };
+        jobs.into_iter()
+            .map(|job| JobOutcome {
+                worker_idx,
+                job: JobSummary {
+                    job_id: job.job_id,
+                    group_proofs: None,
+                    height: job.height,
+                    field_vdf: job.field_vdf,
+                    number_of_iterations: job.number_of_iterations,
+                },
+                output_mismatch: false,
+                submit_reason: None,
+                submit_detail: None,
+                drop_inflight: true,
+                lease_expired: false,
+                error: Some(error.to_string()),
+                compute_ms: 0,
+                submit_ms: 0,
+                total_ms: queue_wait_ms,
+                queue_wait_ms,
+                squaring_ms: None,
+                finalize_ms: None,
+                submit_retries: 0,
+                mismatch_check: None,
+                diagnostics: None,
+                reused_witness: false,
+                lease_headroom_secs: None,
+            })
+            .collect()
+    }
+
+    /// Number of backend jobs in this item.
+    fn job_count(&self) -> usize {
+        match self {
+            WorkItem::Job(_) => 1,
+            WorkItem::Group(group) => group.jobs.len(),
+        }
+    }
+
+    /// Stable ordering key used by deterministic scheduling.
+    fn schedule_key(&self) -> (u8, u64) {
+        match self {
+            WorkItem::Group(group) => (0, group.group_id),
+            WorkItem::Job(item) => (1, item.job.job_id),
+        }
+    }
+}
+
+/// Weight of the newest job in the smoothed finalize/squaring time ratio.
+const FINALIZE_RATIO_SMOOTHING: f64 = 0.3;
+
+/// How often available memory is re-checked (`auto_parallel`,
+/// `low_memory_threshold_bytes`).
+const MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Bytes per MiB, for memory figures in warnings.
+const MIB: u64 = 1024 * 1024;
+
+/// First delay after an empty work fetch; doubles up to `idle_sleep`.
+const IDLE_BACKOFF_MIN: Duration = Duration::from_millis(500);
+
+/// Backoff for the next empty work fetch after one that waited `delay`.
+fn next_idle_backoff(delay: Duration, idle_sleep: Duration) -> Duration {
+    delay.saturating_mul(2).min(idle_sleep)
+}
+
+/// Fetch backoff during backend maintenance when no `Retry-After` is given.
+const MAINTENANCE_BACKOFF: Duration = Duration::from_secs(60);
+/// Upper bound on a backend-provided `Retry-After`.
+const MAINTENANCE_BACKOFF_MAX: Duration = Duration::from_secs(15 * 60);
+
+/// Busy workers expected to finish within this long count toward `prefetch`;
+/// prefetching also pauses once queued work has waited this long.
+const PREFETCH_HORIZON: Duration = Duration::from_secs(30);
+
+/// Smallest group that `split_groups` will divide between two workers.
+const SPLIT_GROUP_MIN_JOBS: usize = 4;
+/// Fewest not-yet-reached targets of a running group that `split_groups` hands
+/// to an idle worker.
+const SPLIT_RUNNING_MIN_TARGETS: usize = 2;
+
+/// Restarts allowed per worker before a crash stops the engine.
+const MAX_WORKER_RESTARTS: u32 = 5;
+/// Delay before a crashed worker's first restart; doubles with each restart.
+const WORKER_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Split off the higher-iteration half of `group`, leaving it the lower half.
+///
+/// Both halves share the lease and start from the same challenge; the worker
+/// holding the lower half finishes early instead of waiting on the longest proof.
+fn split_group(group: &mut BackendWorkGroup) -> BackendWorkGroup {
+    group.jobs.sort_by_key(|job| job.number_of_iterations);
+    BackendWorkGroup {
+        group_id: group.group_id,
+        lease_id: group.lease_id.clone(),
+        lease_expires_at: group.lease_expires_at,
+        jobs: group.jobs.split_off(group.jobs.len() / 2),
+    }
+}
+
+/// The jobs of a running `group` whose targets lie beyond the `iters_done`
+/// squarings already done, as a group of their own.
+fn unreached_targets(group: &BackendWorkGroup, iters_done: u64) -> BackendWorkGroup {
+    BackendWorkGroup {
+        group_id: group.group_id,
+        lease_id: group.lease_id.clone(),
+        lease_expires_at: group.lease_expires_at,
+        jobs: group
+            .jobs
+            .iter()
+            .filter(|job| job.number_of_iterations > iters_done)
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Estimated finish time must exceed the remaining lease time by this factor before
+/// work is considered clearly at risk.
+const PREEMPT_SLACK: f64 = 1.25;
+
+/// Whether `iters` squaring iterations at `iters_per_sec` clearly cannot finish
+/// before `lease_expires_at` (unix seconds).
+fn lease_at_risk(iters: u64, iters_per_sec: u64, lease_expires_at: i64) -> bool {
+    if iters_per_sec == 0 {
+        return false;
+    }
+    let remaining_secs = (lease_expires_at - Utc::now().timestamp()).max(0) as f64;
+    let needed_secs = iters as f64 / iters_per_sec as f64;
+    needed_secs > remaining_secs * PREEMPT_SLACK
+}
+
+fn environment_info(cfg: &EngineConfig, pinned: bool) -> EnvironmentInfo {
+    let cpu = detect_cpu();
+    EnvironmentInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_hash: option_env!("WESOFORGE_GIT_HASH").map(str::to_string),
+        os: cpu.os,
+        arch: cpu.arch,
+        logical_cpus: cpu.logical_cpus,
+        cpu_features: cpu.features,
+        native_implementation: bbr_client_chiavdf_fast::implementation_name().to_string(),
+        parallel: cfg.parallel,
+        auto_parallel: cfg.auto_parallel,
+        mem_budget_bytes: cfg.mem_budget_bytes,
+        backend_url: cfg.backend_url.to_string(),
+        pin_mode: if pinned { "l3" } else { "off" }.to_string(),
+    }
+}
+
+/// Order in which idle workers are filled.
+///
+/// Without a seed this is plain index order; with a seed it is a fixed
+/// permutation (splitmix64-driven Fisher-Yates) so runs are reproducible.
+fn worker_schedule_order(parallel: usize, seed: Option<u64>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..parallel).collect();
+    let Some(mut state) = seed else {
+        return order;
+    };
+    let mut next = || {
+        state = state.wrapping_add(SPLITMIX64_GAMMA);
+        splitmix64(state)
+    };
+    for i in (1..order.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        order.swap(i, j);
+    }
+    order
+}
+
 #[derive(Debug)]
 enum WorkProgress {
     Single { total_iters: u64 },
@@ -80,18 +465,40 @@ impl WorkProgress {
     }
 }
 
+/// A running group that may still hand its unreached targets to an idle worker
+/// (`split_groups`).
+#[derive(Debug)]
+struct SplittableGroup {
+    leased_at: Instant,
+    group: BackendWorkGroup,
+    shared_jobs: Arc<SharedGroupJobs>,
+}
+
 #[derive(Debug)]
 struct WorkerRuntime {
     stage: WorkerStage,
     job: Option<JobSummary>,
     group_id: Option<u64>,
+    /// Lease held for the current job (kept renewed while the worker runs).
+    lease_id: Option<String>,
+    /// Deadline of that lease, shared with the worker task.
+    lease_deadline: Option<LeaseDeadline>,
     work: Option<WorkProgress>,
+    splittable_group: Option<SplittableGroup>,
     compute_started_at: Option<Instant>,
+    /// When squaring reported completion and finalization began.
+    finalize_started_at: Option<Instant>,
     speed_its_per_sec: u64,
     effective_speed_its_per_sec: u64,
+    /// Squaring speed measured on the most recent job (kept across jobs).
+    last_speed_its_per_sec: u64,
     last_reported_squaring_iters_done: u64,
     last_reported_effective_iters_done: u64,
     last_emitted_iters_done: u64,
+    /// Times this worker's task crashed and was restarted.
+    restarts: u32,
+    /// A restarted worker takes no work before this.
+    restart_at: Option<Instant>,
 }
 
 impl WorkerRuntime {
@@ -100,13 +507,20 @@ impl WorkerRuntime {
             stage: WorkerStage::Idle,
             job: None,
             group_id: None,
+            lease_id: None,
+            lease_deadline: None,
             work: None,
+            splittable_group: None,
             compute_started_at: None,
+            finalize_started_at: None,
             speed_its_per_sec: 0,
             effective_speed_its_per_sec: 0,
+            last_speed_its_per_sec: 0,
             last_reported_squaring_iters_done: 0,
             last_reported_effective_iters_done: 0,
             last_emitted_iters_done: 0,
+            restarts: 0,
+            restart_at: None,
         }
     }
 
@@ -126,6 +540,7 @@ impl WorkerRuntime {
             total_iters: job.number_of_iterations,
         });
         self.compute_started_at = Some(Instant::now());
+        self.finalize_started_at = None;
         self.speed_its_per_sec = 0;
         self.effective_speed_its_per_sec = 0;
         self.last_reported_squaring_iters_done = 0;
@@ -139,6 +554,7 @@ impl WorkerRuntime {
         self.group_id = Some(group_id);
         self.work = Some(WorkProgress::Group { per_job_iters });
         self.compute_started_at = Some(Instant::now());
+        self.finalize_started_at = None;
         self.speed_its_per_sec = 0;
         self.effective_speed_its_per_sec = 0;
         self.last_reported_squaring_iters_done = 0;
@@ -150,12 +566,38 @@ impl WorkerRuntime {
         self.stage = stage;
     }
 
+    /// Enter the finalize phase once squaring reports completion (or the native
+    /// prover says it started finalizing).
+    fn begin_finalize(&mut self) -> bool {
+        if self.stage != WorkerStage::Computing {
+            return false;
+        }
+        self.stage = WorkerStage::Finalizing;
+        self.finalize_started_at = Some(Instant::now());
+        true
+    }
+
+    /// Finalize time as a fraction of squaring time, if the current job went
+    /// through a finalize phase.
+    fn take_finalize_ratio(&mut self) -> Option<f64> {
+        let finalize_started_at = self.finalize_started_at.take()?;
+        let squaring = finalize_started_at.checked_duration_since(self.compute_started_at?)?;
+        if squaring.is_zero() {
+            return None;
+        }
+        Some(finalize_started_at.elapsed().as_secs_f64() / squaring.as_secs_f64())
+    }
+
     fn finish_job(&mut self) {
         self.stage = WorkerStage::Idle;
         self.job = None;
         self.group_id = None;
+        self.lease_id = None;
+        self.lease_deadline = None;
         self.work = None;
+        self.splittable_group = None;
         self.compute_started_at = None;
+        self.finalize_started_at = None;
         self.speed_its_per_sec = 0;
         self.effective_speed_its_per_sec = 0;
         self.last_reported_squaring_iters_done = 0;
@@ -163,6 +605,46 @@ impl WorkerRuntime {
         self.last_emitted_iters_done = 0;
     }
 
+    /// Whether the current job should be done within `horizon` (at the last measured
+    /// speed). Finalizing and submitting workers are about to free up.
+    fn finishes_within(&self, horizon: Duration) -> bool {
+        match self.stage {
+            WorkerStage::Idle => false,
+            WorkerStage::Finalizing | WorkerStage::Submitting => true,
+            WorkerStage::Computing => {
+                let Some(work) = &self.work else {
+                    return false;
+                };
+                if self.last_speed_its_per_sec == 0 {
+                    return false;
+                }
+                let remaining = work
+                    .squaring_total_iters()
+                    .saturating_sub(self.last_reported_squaring_iters_done);
+                remaining as f64 / (self.last_speed_its_per_sec as f64) < horizon.as_secs_f64()
+            }
+        }
+    }
+
+    /// Whether the current job clearly cannot finish squaring before its lease
+    /// expires, at the last measured speed.
+    fn lease_at_risk(&self) -> bool {
+        if self.stage != WorkerStage::Computing {
+            return false;
+        }
+        let (Some(work), Some(lease_deadline)) = (&self.work, &self.lease_deadline) else {
+            return false;
+        };
+        let remaining = work
+            .squaring_total_iters()
+            .saturating_sub(self.last_reported_squaring_iters_done);
+        lease_at_risk(
+            remaining,
+            self.last_speed_its_per_sec,
+            lease_deadline.expires_at(),
+        )
+    }
+
     fn apply_progress(&mut self, iters_done: u64) -> Option<u64> {
         let Some(work) = &self.work else {
             return None;
@@ -185,8 +667,10 @@ impl WorkerRuntime {
         if let Some(started_at) = self.compute_started_at {
             let elapsed = now.duration_since(started_at);
             if elapsed.as_secs_f64() > 0.0 {
-                self.speed_its_per_sec =
-                    (iters_done as f64 / elapsed.as_secs_f64()).round() as u64;
+                self.speed_its_per_sec = (iters_done as f64 / elapsed.as_secs_f64()).round() as u64;
+                if self.speed_its_per_sec > 0 {
+                    self.last_speed_its_per_sec = self.speed_its_per_sec;
+                }
                 self.effective_speed_its_per_sec =
                     (effective_done as f64 / elapsed.as_secs_f64()).round() as u64;
             }
@@ -200,24 +684,121 @@ impl WorkerRuntime {
     }
 }
 
+/// What it takes to (re)start a worker task.
+struct WorkerSpawner {
+    http: reqwest::Client,
+    submitter: Arc<tokio::sync::RwLock<SubmitterConfig>>,
+    warned_invalid_reward_address: Arc<AtomicBool>,
+    internal_tx: mpsc::UnboundedSender<WorkerInternalEvent>,
+    pinning: Arc<PinningPlan>,
+    faults: Faults,
+    net: Arc<NetworkStats>,
+    submit_queue: Arc<SubmitQueue>,
+    proof_cache: Arc<ProofCache>,
+    archive: Option<Arc<SubmissionArchive>>,
+    signer: Option<SubmissionSigner>,
+}
+
+impl WorkerSpawner {
+    /// Start worker `worker_idx` in `join` once `delay` has passed. Returns its
+    /// command channel and task id.
+    fn spawn(
+        &self,
+        join: &mut JoinSet<()>,
+        worker_idx: usize,
+        progress: Arc<std::sync::atomic::AtomicU64>,
+        delay: Duration,
+    ) -> (mpsc::Sender<WorkerCommand>, tokio::task::Id) {
+        let (tx, rx) = mpsc::channel::<WorkerCommand>(1);
+        let http = self.http.clone();
+        let submitter = self.submitter.clone();
+        let warned = self.warned_invalid_reward_address.clone();
+        let internal_tx = self.internal_tx.clone();
+        let pinning = self.pinning.clone();
+        let faults = self.faults.clone();
+        let net = self.net.clone();
+        let submit_queue = self.submit_queue.clone();
+        let proof_cache = self.proof_cache.clone();
+        let archive = self.archive.clone();
+        let signer = self.signer.clone();
+
+        let task = join.spawn(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            crate::worker::run_worker_task(
+                worker_idx,
+                rx,
+                internal_tx,
+                progress,
+                http,
+                submitter,
+                warned,
+                pinning,
+                faults,
+                net,
+                submit_queue,
+                proof_cache,
+                archive,
+                signer,
+            )
+            .await;
+        });
+        (tx, task.id())
+    }
+}
+
 struct EngineRuntime {
     http: reqwest::Client,
     cfg: EngineConfig,
 
     workers: Vec<WorkerRuntime>,
+    worker_order: Vec<usize>,
     worker_cmds: Vec<mpsc::Sender<WorkerCommand>>,
     worker_progress: Vec<Arc<std::sync::atomic::AtomicU64>>,
     internal_rx: mpsc::UnboundedReceiver<WorkerInternalEvent>,
     worker_join: JoinSet<()>,
+    /// Worker index of each task in `worker_join`.
+    worker_tasks: HashMap<tokio::task::Id, usize>,
+    spawner: WorkerSpawner,
 
-    pending: VecDeque<WorkItem>,
-    fetch_task: Option<tokio::task::JoinHandle<anyhow::Result<Vec<WorkItem>>>>,
+    pending: VecDeque<PendingWork>,
+    fetch_task: Option<FetchTask>,
     fetch_backoff: Option<Pin<Box<tokio::time::Sleep>>>,
+    idle_backoff: Duration,
+    /// Renewed lease expiries, shared with the workers.
+    leases: Arc<LeaseTable>,
+    renewals: LeaseRenewals,
+    renew_task: Option<RenewTask>,
+    maintenance: Option<BackendMaintenance>,
     inflight: Option<InflightStore>,
+    history: Option<HistoryStore>,
+    /// Work done today towards `daily_budget`.
+    budget: Option<BudgetTracker>,
+    /// Whether fetches are paused because the daily budget is used up.
+    budget_reached: bool,
+    faults: Faults,
+    telemetry: Option<TelemetryReporter>,
+    /// Most workers that may be busy at once (`parallel`, or lower with
+    /// `auto_parallel` or under memory pressure).
+    worker_limit: usize,
+    memory_check: Option<tokio::time::Interval>,
+    /// Entry in the host-wide peer directory (`cooperative`).
+    peers: Option<PeerRegistration>,
+    peer_check: Option<tokio::time::Interval>,
+    /// This engine's share of the host when cooperating with peers; `None`
+    /// while it runs alone.
+    cooperative_limit: Option<usize>,
+    /// Finalize time relative to squaring time, smoothed over recent jobs.
+    finalize_ratio: Option<f64>,
 
     recent_jobs: VecDeque<JobOutcome>,
     snapshot_tx: watch::Sender<StatusSnapshot>,
     inner: Arc<EngineInner>,
+
+    started_at: Instant,
+    environment: EnvironmentInfo,
+    shutdown_report: ShutdownReport,
 }
 
 impl EngineRuntime {
@@ -241,59 +822,244 @@ impl EngineRuntime {
                     .map(|p| p.squaring_total_iters())
                     .unwrap_or(0),
                 iters_per_sec: w.speed_its_per_sec,
+                paused: self.inner.is_worker_paused(idx),
             })
             .collect();
 
         StatusSnapshot {
             stop_requested: self.inner.should_stop(),
+            paused: self.inner.is_paused(),
             workers,
             recent_jobs: self.recent_jobs.iter().cloned().collect(),
+            backend_maintenance: self.maintenance.clone(),
+            backend_url: self.cfg.backend_url.to_string(),
+            session: self.session_stats(),
+            last_event_seq: self.inner.event_tx.last_seq(),
+            budget: self.budget.as_ref().map(BudgetTracker::status),
         }
     }
 
+    fn session_stats(&self) -> SessionStats {
+        let mut session = self.shutdown_report.session.clone();
+        session.uptime_ms = self.started_at.elapsed().as_millis() as u64;
+        session
+    }
+
     fn push_snapshot(&self) {
         let snap = self.build_snapshot();
         let _ = self.snapshot_tx.send(snap);
     }
 
     fn emit(&self, event: EngineEvent) {
-        let _ = self.inner.event_tx.send(event);
+        self.inner.publish(event);
+    }
+
+    /// Whether `worker_idx` is idle and may be given work (not paused or waiting
+    /// out a restart backoff).
+    fn is_available(&self, worker_idx: usize) -> bool {
+        let worker = &self.workers[worker_idx];
+        worker.is_idle()
+            && worker.restart_at.is_none_or(|at| Instant::now() >= at)
+            && !self.inner.is_worker_paused(worker_idx)
+    }
+
+    fn available_count(&self) -> usize {
+        (0..self.workers.len())
+            .filter(|&idx| self.is_available(idx))
+            .count()
+    }
+
+    fn busy_count(&self) -> usize {
+        self.workers.iter().filter(|w| w.is_busy()).count()
+    }
+
+    /// Most workers that may be busy at once, including the cooperative share.
+    fn effective_worker_limit(&self) -> usize {
+        self.cooperative_limit
+            .map_or(self.worker_limit, |share| share.min(self.worker_limit))
+    }
+
+    /// Idle workers that may take work without exceeding the worker limit.
+    fn available_slots(&self) -> usize {
+        self.available_count().min(
+            self.effective_worker_limit()
+                .saturating_sub(self.busy_count()),
+        )
+    }
+
+    /// Re-read the peer directory and adjust this engine's share of the host.
+    fn check_peers(&mut self) {
+        let Some(registration) = self.peers.as_mut() else {
+            return;
+        };
+        let peers = match registration.refresh() {
+            Ok(peers) => peers,
+            Err(err) => {
+                self.emit(EngineEvent::Warning {
+                    message: format!("warning: cooperative mode: peer directory: {err:#}"),
+                });
+                return;
+            }
+        };
+
+        let share = if peers.is_empty() {
+            None
+        } else {
+            let demands: Vec<(usize, u64)> = peers
+                .iter()
+                .map(|peer| (peer.workers, peer.mem_budget_bytes))
+                .collect();
+            let cores = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1);
+            let reserve = self
+                .cfg
+                .low_memory_threshold_bytes
+                .unwrap_or(MEMORY_RESERVE);
+            Some(cooperative_worker_limit(
+                self.worker_limit,
+                self.cfg.mem_budget_bytes,
+                &demands,
+                cores,
+                total_memory_bytes(),
+                reserve,
+            ))
+        };
+        if share == self.cooperative_limit {
+            return;
+        }
+        self.cooperative_limit = share;
+        let message = match share {
+            Some(share) => format!(
+                "Cooperative mode: sharing this host with {} other engine(s); running {share} of {} worker(s).",
+                peers.len(),
+                self.worker_limit
+            ),
+            None => format!(
+                "Cooperative mode: no other engines on this host; running up to {} worker(s).",
+                self.worker_limit
+            ),
+        };
+        self.emit(EngineEvent::Warning { message });
     }
 
-    fn idle_count(&self) -> usize {
-        self.workers.iter().filter(|w| w.is_idle()).count()
+    /// Lower `worker_limit` if the system is running short on memory.
+    fn check_memory_pressure(&mut self) {
+        let Some(available) = available_memory_bytes() else {
+            return;
+        };
+        let reserve = self
+            .cfg
+            .low_memory_threshold_bytes
+            .unwrap_or(MEMORY_RESERVE);
+        let Some(limit) = pressure_worker_limit(
+            self.worker_limit,
+            self.busy_count(),
+            self.cfg.mem_budget_bytes,
+            available,
+            reserve,
+        ) else {
+            return;
+        };
+        self.worker_limit = limit;
+        self.emit(EngineEvent::Warning {
+            message: format!(
+                "warning: low memory ({} MiB available, keeping {} MiB free); reducing active workers to {limit} (busy workers finish their current job first)",
+                format_number(available / MIB),
+                format_number(reserve / MIB)
+            ),
+        });
     }
 
     fn all_idle(&self) -> bool {
         !self.workers.iter().any(|w| w.is_busy())
     }
 
+    /// Fold the worker's finished finalize phase (if any) into `finalize_ratio`.
+    fn record_finalize(&mut self, worker_idx: usize) {
+        let Some(ratio) = self
+            .workers
+            .get_mut(worker_idx)
+            .and_then(WorkerRuntime::take_finalize_ratio)
+        else {
+            return;
+        };
+        self.finalize_ratio = Some(match self.finalize_ratio {
+            Some(prev) => prev + FINALIZE_RATIO_SMOOTHING * (ratio - prev),
+            None => ratio,
+        });
+    }
+
+    /// Expected finalize duration (milliseconds) after squaring `iters_total`
+    /// iterations at `iters_per_sec`; 0 when unknown.
+    fn finalize_estimate_ms(&self, iters_total: u64, iters_per_sec: u64) -> u64 {
+        match self.finalize_ratio {
+            Some(ratio) if iters_per_sec > 0 => {
+                (iters_total as f64 / iters_per_sec as f64 * ratio * 1000.0) as u64
+            }
+            _ => 0,
+        }
+    }
+
+    /// Extra items to lease ahead for busy workers (see `EngineConfig::prefetch`).
+    fn prefetch_slots(&self) -> usize {
+        if self.cfg.prefetch == 0 || self.cfg.schedule_seed.is_some() {
+            return 0;
+        }
+        let stale = self
+            .pending
+            .iter()
+            .any(|pending| pending.leased_at.elapsed() >= PREFETCH_HORIZON);
+        if stale {
+            return 0;
+        }
+        let finishing = self
+            .workers
+            .iter()
+            .enumerate()
+            .filter(|(idx, w)| {
+                !self.inner.is_worker_paused(*idx) && w.finishes_within(PREFETCH_HORIZON)
+            })
+            .count();
+        self.cfg.prefetch.min(finishing)
+    }
+
     fn maybe_start_fetch(&mut self) {
-        if self.inner.should_stop() {
+        if self.inner.should_stop() || self.inner.is_paused() || self.budget_reached() {
             return;
         }
-        let count = self.idle_count();
+        let wanted = self.available_slots() + self.prefetch_slots();
+        let count = wanted.saturating_sub(self.pending.len());
         if count == 0 {
             return;
         }
-        if !self.pending.is_empty() || self.fetch_task.is_some() || self.fetch_backoff.is_some() {
+        if self.cfg.schedule_seed.is_some() && !self.all_idle() {
+            return;
+        }
+        if self.fetch_task.is_some() || self.fetch_backoff.is_some() {
             return;
         }
 
         let http = self.http.clone();
         let backend = self.cfg.backend_url.clone();
         let use_groups = self.cfg.use_groups;
-        // Only lease as many groups as needed to fill currently idle workers.
+        let faults = self.faults.clone();
+        let net = self.inner.network.clone();
+        let max_iterations = self.cfg.max_iterations;
+        // Only lease as many groups as needed to fill idle (and soon idle) workers.
         let group_count = count.min(32) as u32;
         let count = count;
         self.fetch_task = Some(tokio::spawn(async move {
+            faults.before_lease().await?;
             if use_groups {
-                let groups = fetch_batch_work(&http, &backend, group_count).await?;
-                return Ok(groups.into_iter().map(WorkItem::Group).collect());
+                let (groups, terms) =
+                    fetch_batch_work(&http, &net, &backend, group_count, max_iterations).await?;
+                return Ok((groups.into_iter().map(WorkItem::Group).collect(), terms));
             }
 
             let count = count.min(u32::MAX as usize) as u32;
-            let batch: BackendWorkBatch = fetch_work(&http, &backend, count).await?;
+            let batch: BackendWorkBatch =
+                fetch_work(&http, &net, &backend, count, max_iterations).await?;
             let items = batch
                 .jobs
                 .into_iter()
@@ -305,25 +1071,94 @@ impl EngineRuntime {
                     })
                 })
                 .collect();
-            Ok(items)
+            Ok((items, batch.terms))
         }));
     }
 
+    /// Pop the next pending item for `worker_idx`, releasing items whose lease would
+    /// expire before they could finish (when `preempt_at_risk` is enabled).
+    async fn next_pending(&mut self, worker_idx: usize) -> Option<PendingWork> {
+        while let Some(pending) = self.pending.pop_front() {
+            if !self.cfg.preempt_at_risk
+                || !lease_at_risk(
+                    pending.item.squaring_iters(),
+                    self.workers[worker_idx].last_speed_its_per_sec,
+                    self.leases
+                        .expires_at(pending.item.lease_id(), pending.item.lease_expires_at()),
+                )
+            {
+                return Some(pending);
+            }
+            let queue_wait_ms = pending.leased_at.elapsed().as_millis() as u64;
+            self.record_outcomes(pending.item.released_outcomes(
+                worker_idx,
+                queue_wait_ms,
+                "Error (preempted: lease would expire)",
+            ))
+            .await;
+            self.push_snapshot();
+        }
+        None
+    }
+
     async fn assign_jobs(&mut self) -> anyhow::Result<()> {
         if self.inner.should_stop() {
-            self.pending.clear();
+            self.abandon_pending();
+            return Ok(());
+        }
+
+        // Deterministic scheduling hands out work in rounds so assignment does not
+        // depend on which worker happens to finish first.
+        if self.cfg.schedule_seed.is_some() && !self.all_idle() {
             return Ok(());
         }
 
         let mut snapshot_dirty = false;
-        for idx in 0..self.workers.len() {
-            if !self.workers[idx].is_idle() {
+        for pos in 0..self.worker_order.len() {
+            let idx = self.worker_order[pos];
+            if !self.is_available(idx) {
                 continue;
             }
-            let Some(item) = self.pending.pop_front() else {
+            if self.available_slots() == 0 {
                 break;
+            }
+            // With nothing queued, an idle worker can help with a running group.
+            let (
+                PendingWork {
+                    leased_at,
+                    mut item,
+                },
+                shared_jobs,
+            ) = match self.next_pending(idx).await {
+                Some(pending) => (pending, None),
+                None => match self.take_unreached_targets() {
+                    Some((pending, shared_jobs)) => (pending, Some(shared_jobs)),
+                    None => break,
+                },
             };
 
+            // Share a large group with another idle worker when there is nothing
+            // else for it to do.
+            if self.cfg.split_groups
+                && shared_jobs.is_none()
+                && self.pending.is_empty()
+                && self.available_slots() > 1
+                && (0..self.workers.len()).any(|other| other != idx && self.is_available(other))
+                && let WorkItem::Group(group) = &mut item
+                && group.jobs.len() >= SPLIT_GROUP_MIN_JOBS
+            {
+                let upper = split_group(group);
+                self.pending.push_front(PendingWork {
+                    leased_at,
+                    item: WorkItem::Group(upper),
+                });
+            }
+
+            let lease_id = item.lease_id().to_string();
+            let lease_deadline = self
+                .leases
+                .deadline(item.lease_id(), item.lease_expires_at());
+            let mut splittable_group = None;
             let (job_summary, cmd, group_info): (
                 JobSummary,
                 WorkerCommand,
@@ -341,10 +1176,13 @@ impl EngineRuntime {
                     let cmd = WorkerCommand::Job {
                         worker_idx: idx,
                         backend_url: self.cfg.backend_url.clone(),
+                        lease_deadline: lease_deadline.clone(),
                         lease_id: item.lease_id,
-                        lease_expires_at: item.lease_expires_at,
+                        leased_at,
                         job: item.job,
-                        progress_steps: self.cfg.progress_steps,
+                        progress_min_interval: self.cfg.progress_tick,
+                        strict_output: self.cfg.strict_output,
+                        discriminant_bits: self.cfg.discriminant_bits,
                     };
 
                     (job_summary, cmd, None)
@@ -366,16 +1204,32 @@ impl EngineRuntime {
                         number_of_iterations: total_iters,
                     };
 
+                    // Groups handed over from a running worker are not shared again.
+                    let splittable =
+                        (self.cfg.split_groups && shared_jobs.is_none()).then(|| SplittableGroup {
+                            leased_at,
+                            group: group.clone(),
+                            shared_jobs: Arc::default(),
+                        });
+                    let shared_jobs = shared_jobs
+                        .or_else(|| splittable.as_ref().map(|s| s.shared_jobs.clone()))
+                        .unwrap_or_default();
+
                     let cmd = WorkerCommand::Group {
                         worker_idx: idx,
                         backend_url: self.cfg.backend_url.clone(),
+                        lease_deadline: lease_deadline.clone(),
                         lease_id: group.lease_id,
-                        lease_expires_at: group.lease_expires_at,
+                        leased_at,
                         group_id: group.group_id,
                         jobs: group.jobs,
-                        progress_steps: self.cfg.progress_steps,
+                        shared_jobs,
+                        progress_min_interval: self.cfg.progress_tick,
+                        strict_output: self.cfg.strict_output,
+                        discriminant_bits: self.cfg.discriminant_bits,
                     };
 
+                    splittable_group = splittable;
                     (job_summary, cmd, Some((group_id, group_iters)))
                 }
             };
@@ -387,6 +1241,9 @@ impl EngineRuntime {
                 } else {
                     worker.start_job(job_summary.clone());
                 }
+                worker.lease_id = Some(lease_id);
+                worker.lease_deadline = Some(lease_deadline);
+                worker.splittable_group = splittable_group;
             }
             if let Some(a) = self.worker_progress.get(idx) {
                 a.store(0, std::sync::atomic::Ordering::Relaxed);
@@ -416,23 +1273,144 @@ impl EngineRuntime {
         Ok(())
     }
 
-    async fn handle_fetch_result(
+    /// Targets of a running group that its worker hasn't squared up to yet, for an
+    /// idle worker to prove alongside it from the shared challenge
+    /// (`split_groups`). Each job is reported by whichever worker finishes it first.
+    fn take_unreached_targets(&mut self) -> Option<(PendingWork, Arc<SharedGroupJobs>)> {
+        if !self.cfg.split_groups {
+            return None;
+        }
+        for worker in &mut self.workers {
+            if worker.stage != WorkerStage::Computing {
+                continue;
+            }
+            let Some(running) = &worker.splittable_group else {
+                continue;
+            };
+            let unreached =
+                unreached_targets(&running.group, worker.last_reported_squaring_iters_done);
+            if unreached.jobs.len() < SPLIT_RUNNING_MIN_TARGETS {
+                continue;
+            }
+            let running = worker.splittable_group.take()?;
+            let pending = PendingWork {
+                leased_at: running.leased_at,
+                item: WorkItem::Group(unreached),
+            };
+            return Some((pending, running.shared_jobs));
+        }
+        None
+    }
+
+    /// Drop leased jobs above `max_iterations` (from backends that ignore the lease
+    /// filter) before they are queued, handing them back through the lease's
+    /// `release_url` when the backend offers one. Groups keep their other jobs.
+    fn release_above_max_iterations(
         &mut self,
-        res: Result<anyhow::Result<Vec<WorkItem>>, tokio::task::JoinError>,
-    ) {
-        self.fetch_task = None;
+        items: Vec<WorkItem>,
+        terms: &LeaseTerms,
+        max_iterations: u64,
+    ) -> Vec<WorkItem> {
+        let mut kept = Vec::with_capacity(items.len());
+        let mut above: HashMap<String, Vec<u64>> = HashMap::new();
+        for item in items {
+            let (within, over) = item.partition_by_iterations(max_iterations);
+            kept.extend(within);
+            let Some(over) = over else {
+                continue;
+            };
+            let job_ids = above.entry(over.lease_id().to_string()).or_default();
+            match over {
+                WorkItem::Job(item) => job_ids.push(item.job.job_id),
+                WorkItem::Group(group) => job_ids.extend(group.jobs.iter().map(|job| job.job_id)),
+            }
+        }
+        if above.is_empty() {
+            return kept;
+        }
 
-        match res {
-            Ok(Ok(items)) => {
-                if !self.inner.should_stop() {
-                    if let Some(store) = &mut self.inflight {
-                        let mut changed = false;
-                        for item in &items {
-                            match item {
-                                WorkItem::Job(item) => {
-                                    changed |= store.insert_job(
-                                        item.lease_id.clone(),
-                                        item.lease_expires_at,
+        let skipped: usize = above.values().map(Vec::len).sum();
+        let url = match terms.release_url.as_deref() {
+            Some(path) => self
+                .cfg
+                .backend_url
+                .join(path)
+                .with_context(|| format!("invalid lease release_url {path:?}")),
+            None => Err(anyhow::anyhow!("the backend offers no release endpoint")),
+        };
+        let note = match &url {
+            Ok(_) => "releasing them".to_string(),
+            Err(err) => format!("they stay leased until the lease expires ({err:#})"),
+        };
+        self.emit(EngineEvent::Warning {
+            message: format!(
+                "warning: skipped {skipped} leased job(s) above max iterations ({max_iterations}); {note}"
+            ),
+        });
+        let Ok(url) = url else {
+            return kept;
+        };
+
+        let http = self.http.clone();
+        let net = self.inner.network.clone();
+        let internal_tx = self.spawner.internal_tx.clone();
+        tokio::spawn(async move {
+            for (lease_id, job_ids) in above {
+                if let Err(err) = release_jobs(&http, &net, url.clone(), &lease_id, &job_ids).await
+                {
+                    let _ = internal_tx.send(WorkerInternalEvent::Warning {
+                        message: format!(
+                            "warning: failed to release jobs of lease {lease_id}: {err:#}"
+                        ),
+                    });
+                }
+            }
+        });
+        kept
+    }
+
+    async fn handle_fetch_result(
+        &mut self,
+        res: Result<anyhow::Result<(Vec<WorkItem>, LeaseTerms)>, tokio::task::JoinError>,
+    ) {
+        self.fetch_task = None;
+
+        match res {
+            Ok(Ok((mut items, terms))) => {
+                if self.maintenance.take().is_some() {
+                    self.push_snapshot();
+                    self.emit(EngineEvent::Warning {
+                        message: "backend maintenance ended; resuming work fetches".to_string(),
+                    });
+                }
+                if self.cfg.schedule_seed.is_some() {
+                    items.sort_by_key(WorkItem::schedule_key);
+                }
+                if let Some(max_iterations) = self.cfg.max_iterations {
+                    items = self.release_above_max_iterations(items, &terms, max_iterations);
+                }
+                if !self.inner.should_stop() {
+                    let lease_ids: HashSet<String> = items
+                        .iter()
+                        .map(|item| item.lease_id().to_string())
+                        .collect();
+                    for lease_id in lease_ids {
+                        if let Err(err) = self.renewals.track(&lease_id, &terms) {
+                            self.emit(EngineEvent::Warning {
+                                message: format!(
+                                    "warning: lease {lease_id} will not be renewed: {err:#}"
+                                ),
+                            });
+                        }
+                    }
+                    if let Some(store) = &mut self.inflight {
+                        let mut changed = false;
+                        for item in &items {
+                            match item {
+                                WorkItem::Job(item) => {
+                                    changed |= store.insert_job(
+                                        item.lease_id.clone(),
+                                        item.lease_expires_at,
                                         item.job.clone(),
                                     );
                                 }
@@ -460,8 +1438,8 @@ impl EngineRuntime {
                     if self.cfg.use_groups {
                         let mut seen_groups: HashSet<u64> =
                             self.workers.iter().filter_map(|w| w.group_id).collect();
-                        for item in &self.pending {
-                            if let WorkItem::Group(group) = item {
+                        for pending in &self.pending {
+                            if let WorkItem::Group(group) = &pending.item {
                                 seen_groups.insert(group.group_id);
                             }
                         }
@@ -470,48 +1448,228 @@ impl EngineRuntime {
                             match item {
                                 WorkItem::Group(group) => {
                                     if seen_groups.insert(group.group_id) {
-                                        self.pending.push_back(WorkItem::Group(group));
+                                        self.pending
+                                            .push_back(PendingWork::new(WorkItem::Group(group)));
                                     }
                                 }
-                                other => self.pending.push_back(other),
+                                other => self.pending.push_back(PendingWork::new(other)),
                             }
                         }
                     } else {
-                        self.pending.extend(items);
+                        self.pending.extend(items.into_iter().map(PendingWork::new));
                     }
                 }
                 if self.pending.is_empty() {
-                    self.fetch_backoff = Some(Box::pin(tokio::time::sleep(self.cfg.idle_sleep)));
+                    let delay = self.idle_backoff;
+                    self.idle_backoff = next_idle_backoff(delay, self.cfg.idle_sleep);
+                    self.fetch_backoff = Some(Box::pin(tokio::time::sleep(delay)));
+                } else {
+                    self.idle_backoff = IDLE_BACKOFF_MIN.min(self.cfg.idle_sleep);
                 }
             }
-            Ok(Err(err)) => {
+            Ok(Err(err)) => match err.downcast_ref::<BackendError>() {
+                Some(BackendError::Maintenance {
+                    retry_after,
+                    message,
+                }) => {
+                    let (retry_after, message) = (*retry_after, message.clone());
+                    self.enter_maintenance(retry_after, message);
+                }
+                _ => {
+                    self.fetch_backoff = Some(Box::pin(tokio::time::sleep(self.cfg.idle_sleep)));
+                    self.emit(
+                        EngineError::Backend {
+                            job_id: None,
+                            message: format!("work fetch error: {err:#}"),
+                        }
+                        .into(),
+                    );
+                }
+            },
+            Err(err) => {
                 self.fetch_backoff = Some(Box::pin(tokio::time::sleep(self.cfg.idle_sleep)));
-                self.emit(EngineEvent::Error {
-                    message: format!("work fetch error: {err:#}"),
-                });
+                self.emit(
+                    EngineError::Internal {
+                        message: format!("work fetch task join error: {err:#}"),
+                    }
+                    .into(),
+                );
+            }
+        }
+    }
+
+    /// Whether the daily budget is used up, reporting when fetches stop for the
+    /// day and when they resume.
+    fn budget_reached(&mut self) -> bool {
+        let reached = self.budget.as_ref().is_some_and(BudgetTracker::exhausted);
+        if reached != self.budget_reached {
+            self.budget_reached = reached;
+            self.push_snapshot();
+            let message = if reached {
+                "daily budget reached; pausing work fetches until local midnight"
+            } else {
+                "new day; resuming work fetches"
+            };
+            self.emit(EngineEvent::Warning {
+                message: message.to_string(),
+            });
+        }
+        reached
+    }
+
+    /// Pause work fetches while the backend reports maintenance.
+    ///
+    /// Only the first maintenance response is reported; later ones just extend the
+    /// backoff, so the log isn't flooded with identical errors.
+    fn enter_maintenance(&mut self, retry_after: Option<Duration>, message: String) {
+        let backoff = retry_after
+            .unwrap_or(MAINTENANCE_BACKOFF)
+            .min(MAINTENANCE_BACKOFF_MAX)
+            .max(self.cfg.idle_sleep);
+        self.fetch_backoff = Some(Box::pin(tokio::time::sleep(backoff)));
+
+        let now = Utc::now().timestamp();
+        let retry_at = now.saturating_add(backoff.as_secs() as i64);
+        let first = self.maintenance.is_none();
+        let warning = if message.is_empty() {
+            format!(
+                "backend under maintenance; pausing work fetches (retrying in {}s)",
+                backoff.as_secs()
+            )
+        } else {
+            format!(
+                "backend under maintenance; pausing work fetches (retrying in {}s): {message}",
+                backoff.as_secs()
+            )
+        };
+        let since = self.maintenance.as_ref().map_or(now, |m| m.since);
+        self.maintenance = Some(BackendMaintenance {
+            since,
+            retry_at,
+            message,
+        });
+        self.push_snapshot();
+        if first {
+            self.emit(EngineEvent::Warning { message: warning });
+        }
+    }
+
+    /// Renew the leases that are due, dropping those no longer held by pending
+    /// work, a worker or the inflight store.
+    fn start_renewals(&mut self) {
+        let mut held: HashSet<&str> = self
+            .pending
+            .iter()
+            .map(|pending| pending.item.lease_id())
+            .collect();
+        held.extend(self.workers.iter().filter_map(|w| w.lease_id.as_deref()));
+        let inflight = self.inflight.as_ref();
+        self.renewals.retain(|lease_id| {
+            held.contains(lease_id) || inflight.is_some_and(|store| store.holds_lease(lease_id))
+        });
+
+        let due = self.renewals.take_due();
+        if due.is_empty() {
+            return;
+        }
+        let http = self.http.clone();
+        let net = self.inner.network.clone();
+        self.renew_task = Some(tokio::spawn(async move {
+            let mut results = Vec::with_capacity(due.len());
+            for (lease_id, url) in due {
+                let res = renew_lease(&http, &net, url, &lease_id).await;
+                results.push((lease_id, res));
             }
+            results
+        }));
+    }
+
+    async fn handle_renew_result(
+        &mut self,
+        res: Result<Vec<(String, anyhow::Result<LeaseRenewed>)>, tokio::task::JoinError>,
+    ) {
+        self.renew_task = None;
+        let results = match res {
+            Ok(results) => results,
             Err(err) => {
-                self.fetch_backoff = Some(Box::pin(tokio::time::sleep(self.cfg.idle_sleep)));
-                self.emit(EngineEvent::Error {
-                    message: format!("work fetch task join error: {err:#}"),
-                });
+                self.emit(
+                    EngineError::Internal {
+                        message: format!("lease renewal task join error: {err:#}"),
+                    }
+                    .into(),
+                );
+                return;
             }
+        };
+
+        let mut changed = false;
+        for (lease_id, res) in results {
+            match res {
+                Ok(renewed) => {
+                    self.renewals.renewed(&lease_id, &renewed);
+                    if let Some(store) = &mut self.inflight {
+                        changed |= store.renew_lease(&lease_id, renewed.lease_expires_at);
+                    }
+                }
+                Err(err) => {
+                    if matches!(
+                        err.downcast_ref::<BackendError>(),
+                        Some(BackendError::LeaseInvalid | BackendError::JobNotFound)
+                    ) {
+                        self.renewals.drop_lease(&lease_id);
+                        self.emit(EngineEvent::Warning {
+                            message: format!(
+                                "warning: backend no longer recognizes lease {lease_id}; renewals stopped"
+                            ),
+                        });
+                    } else if self.renewals.failed(&lease_id) {
+                        self.emit(EngineEvent::Warning {
+                            message: format!(
+                                "warning: lease {lease_id} renewal failed: {err:#}; will retry"
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        if changed
+            && let Some(store) = &self.inflight
+            && let Err(err) = store.persist().await
+        {
+            self.emit(EngineEvent::Warning {
+                message: format!("warning: failed to persist inflight leases: {err:#}"),
+            });
         }
     }
 
     async fn handle_internal_event(&mut self, ev: WorkerInternalEvent) {
         match ev {
             WorkerInternalEvent::StageChanged { worker_idx, stage } => {
+                self.record_finalize(worker_idx);
                 if let Some(worker) = self.workers.get_mut(worker_idx) {
                     worker.set_stage(stage);
                 }
                 self.emit(EngineEvent::WorkerStage { worker_idx, stage });
                 self.push_snapshot();
             }
+            WorkerInternalEvent::FinalizeStarted { worker_idx } => {
+                if self
+                    .workers
+                    .get_mut(worker_idx)
+                    .is_some_and(WorkerRuntime::begin_finalize)
+                {
+                    self.emit(EngineEvent::WorkerStage {
+                        worker_idx,
+                        stage: WorkerStage::Finalizing,
+                    });
+                    self.push_snapshot();
+                }
+            }
             WorkerInternalEvent::WorkFinished {
                 worker_idx,
                 outcomes,
             } => {
+                self.record_finalize(worker_idx);
                 if let Some(worker) = self.workers.get_mut(worker_idx) {
                     worker.finish_job();
                 }
@@ -519,44 +1677,88 @@ impl EngineRuntime {
                     a.store(0, Ordering::Relaxed);
                 }
 
-                let mut remove_inflight_job_ids = Vec::new();
-                for outcome in outcomes {
-                    self.recent_jobs.push_back(outcome.clone());
-                    while self.recent_jobs.len() > self.cfg.recent_jobs_max.max(1) {
-                        self.recent_jobs.pop_front();
-                    }
-                    if outcome.drop_inflight
-                        || (outcome.error.is_none() && outcome.submit_reason.is_some())
-                    {
-                        remove_inflight_job_ids.push(outcome.job.job_id);
-                    }
-                    self.emit(EngineEvent::JobFinished { outcome });
-                }
+                self.record_outcomes(outcomes).await;
+                self.push_snapshot();
 
-                if !remove_inflight_job_ids.is_empty() {
-                    if let Some(store) = &mut self.inflight {
-                        let mut changed = false;
-                        for job_id in remove_inflight_job_ids {
-                            changed |= store.remove_job(job_id);
-                        }
-                        if changed {
-                            if let Err(err) = store.persist().await {
-                                self.emit(EngineEvent::Warning {
-                                    message: format!(
-                                        "warning: failed to persist inflight leases: {err:#}"
-                                    ),
-                                });
-                            }
-                        }
-                    }
+                // A worker just freed up: poll again right away instead of waiting out
+                // the idle backoff (unless the backend asked us to back off).
+                if self.maintenance.is_none() {
+                    self.fetch_backoff = None;
                 }
-                self.push_snapshot();
             }
             WorkerInternalEvent::Warning { message } => {
                 self.emit(EngineEvent::Warning { message });
             }
-            WorkerInternalEvent::Error { message } => {
-                self.emit(EngineEvent::Error { message });
+            WorkerInternalEvent::Error { error } => {
+                self.emit(error.into());
+            }
+        }
+    }
+
+    /// Record finished (or released) jobs: history, recent jobs, telemetry, events and
+    /// inflight bookkeeping.
+    async fn record_outcomes(&mut self, outcomes: Vec<JobOutcome>) {
+        if let Some(history) = &self.history
+            && let Err(err) = history.append(&outcomes, Utc::now().timestamp()).await
+        {
+            self.emit(EngineEvent::Warning {
+                message: format!("warning: failed to record job history: {err:#}"),
+            });
+        }
+        if let Some(budget) = &mut self.budget {
+            budget.record(&outcomes);
+        }
+
+        let mut remove_inflight_job_ids = Vec::new();
+        for outcome in outcomes {
+            self.recent_jobs.push_back(outcome.clone());
+            while self.recent_jobs.len() > self.cfg.recent_jobs_max.max(1) {
+                self.recent_jobs.pop_front();
+            }
+            if outcome.drop_inflight || (outcome.error.is_none() && outcome.submit_reason.is_some())
+            {
+                remove_inflight_job_ids.push(outcome.job.job_id);
+            }
+            let session = &mut self.shutdown_report.session;
+            session.jobs_finished += 1;
+            session.compute_ms += outcome.compute_ms;
+            if outcome.reused_witness {
+                session.duplicates_suppressed += 1;
+            }
+            if outcome.error.is_none() && outcome.submit_reason.is_some() {
+                session.jobs_submitted += 1;
+                session.iterations_submitted += outcome.job.number_of_iterations;
+                if outcome.submit_reason.as_deref() == Some("accepted") {
+                    session.jobs_accepted += 1;
+                }
+            }
+            if outcome.error.is_some() {
+                session.jobs_failed += 1;
+                // A submission was attempted, so the proof was computed but not accepted.
+                if outcome.submit_ms > 0 && !outcome.drop_inflight {
+                    self.shutdown_report.unsubmitted_jobs += 1;
+                }
+                if outcome.lease_expired {
+                    self.shutdown_report.expired_jobs += 1;
+                }
+            }
+            if let Some(telemetry) = &mut self.telemetry {
+                telemetry.record(&outcome);
+            }
+            self.emit(EngineEvent::JobFinished { outcome });
+        }
+
+        if !remove_inflight_job_ids.is_empty()
+            && let Some(store) = &mut self.inflight
+        {
+            let mut changed = false;
+            for job_id in remove_inflight_job_ids {
+                changed |= store.remove_job(job_id);
+            }
+            if changed && let Err(err) = store.persist().await {
+                self.emit(EngineEvent::Warning {
+                    message: format!("warning: failed to persist inflight leases: {err:#}"),
+                });
             }
         }
     }
@@ -599,8 +1801,18 @@ impl EngineRuntime {
                 iters_total,
                 iters_per_sec,
                 effective_iters_per_sec,
+                finalize_ms_estimate: self.finalize_estimate_ms(iters_total, iters_per_sec),
             });
             snapshot_dirty = true;
+
+            // Squaring is done but the witness still has to be folded, which can take
+            // a while on long jobs.
+            if iters_total > 0 && iters_done >= iters_total && self.workers[idx].begin_finalize() {
+                self.emit(EngineEvent::WorkerStage {
+                    worker_idx: idx,
+                    stage: WorkerStage::Finalizing,
+                });
+            }
         }
 
         if snapshot_dirty {
@@ -608,6 +1820,162 @@ impl EngineRuntime {
         }
     }
 
+    /// Tell workers to give up running work whose lease would expire before it
+    /// finishes (when `preempt_at_risk` is enabled); they report it as preempted
+    /// and pick up fresh work.
+    fn preempt_at_risk_work(&self) {
+        if !self.cfg.preempt_at_risk {
+            return;
+        }
+        for worker in &self.workers {
+            if let Some(lease_deadline) = &worker.lease_deadline
+                && !lease_deadline.is_preempted()
+                && worker.lease_at_risk()
+            {
+                lease_deadline.preempt();
+            }
+        }
+    }
+
+    fn report_telemetry(&self) {
+        let Some(telemetry) = &self.telemetry else {
+            return;
+        };
+        let iters_per_sec: u64 = self
+            .workers
+            .iter()
+            .map(|w| w.effective_speed_its_per_sec)
+            .sum();
+        telemetry.spawn_post(&self.http, &self.inner.event_tx, iters_per_sec);
+    }
+
+    /// Drop leased work that no worker started; its leases stay in the inflight store.
+    fn abandon_pending(&mut self) {
+        self.shutdown_report.abandoned_leases += self
+            .pending
+            .drain(..)
+            .map(|pending| pending.item.job_count())
+            .sum::<usize>();
+    }
+
+    fn finish_shutdown_report(&mut self) -> ShutdownReport {
+        let mut report = std::mem::take(&mut self.shutdown_report);
+        report.resumable_jobs = self
+            .inflight
+            .as_ref()
+            .map(InflightStore::total_jobs)
+            .unwrap_or(0);
+        report.session.uptime_ms = self.started_at.elapsed().as_millis() as u64;
+        report
+    }
+
+    /// Replace a worker task that crashed (or exited), requeueing the work it held.
+    ///
+    /// Each worker is restarted at most [`MAX_WORKER_RESTARTS`] times, after a
+    /// doubling backoff; past that the crash is returned as an engine error.
+    fn restart_worker(&mut self, task_id: tokio::task::Id, reason: String) -> anyhow::Result<()> {
+        let Some(worker_idx) = self.worker_tasks.remove(&task_id) else {
+            return Err(anyhow::anyhow!("{reason}"));
+        };
+        let restarts = self.workers[worker_idx].restarts + 1;
+        if restarts > MAX_WORKER_RESTARTS {
+            return Err(anyhow::anyhow!(
+                "worker {} crashed {restarts} times: {reason}",
+                worker_idx + 1
+            ));
+        }
+
+        let delay = WORKER_RESTART_BACKOFF * 2u32.pow(restarts - 1);
+        let lease_id = {
+            let worker = &mut self.workers[worker_idx];
+            let lease_id = worker.lease_id.clone();
+            worker.finish_job();
+            worker.restarts = restarts;
+            worker.restart_at = Some(Instant::now() + delay);
+            lease_id
+        };
+        let progress = self.worker_progress[worker_idx].clone();
+        progress.store(0, Ordering::Relaxed);
+        let (tx, task_id) = self
+            .spawner
+            .spawn(&mut self.worker_join, worker_idx, progress, delay);
+        self.worker_cmds[worker_idx] = tx;
+        self.worker_tasks.insert(task_id, worker_idx);
+
+        self.emit(EngineEvent::WorkerRestarted {
+            worker_idx,
+            restarts,
+            reason,
+        });
+        if let Some(lease_id) = lease_id {
+            let requeued = self.requeue_lease(&lease_id);
+            if requeued > 0 {
+                self.emit(EngineEvent::Warning {
+                    message: format!(
+                        "Requeued {requeued} job(s) held by crashed worker {}.",
+                        worker_idx + 1
+                    ),
+                });
+            }
+        }
+        self.push_snapshot();
+        Ok(())
+    }
+
+    /// Queue the inflight jobs of `lease_id` again. Returns the number of jobs
+    /// requeued: none when resume state is disabled, or when another worker or
+    /// queued item still holds part of the lease (a split group); those jobs are
+    /// left for the next run.
+    fn requeue_lease(&mut self, lease_id: &str) -> usize {
+        let held = self
+            .workers
+            .iter()
+            .any(|w| w.lease_id.as_deref() == Some(lease_id))
+            || self
+                .pending
+                .iter()
+                .any(|pending| pending.item.lease_id() == lease_id);
+        let Some(store) = self.inflight.as_ref() else {
+            return 0;
+        };
+        if held {
+            return 0;
+        }
+
+        let mut items = Vec::new();
+        for group in store.group_entries().filter(|g| g.lease_id == lease_id) {
+            if self.cfg.use_groups {
+                items.push(WorkItem::Group(BackendWorkGroup {
+                    group_id: group.group_id,
+                    lease_id: group.lease_id.clone(),
+                    lease_expires_at: group.lease_expires_at,
+                    jobs: group.jobs.clone(),
+                }));
+            } else {
+                for job in &group.jobs {
+                    items.push(WorkItem::Job(WorkJobItem {
+                        lease_id: group.lease_id.clone(),
+                        lease_expires_at: group.lease_expires_at,
+                        job: job.clone(),
+                    }));
+                }
+            }
+        }
+        for entry in store.job_entries().filter(|e| e.lease_id == lease_id) {
+            items.push(WorkItem::Job(WorkJobItem {
+                lease_id: entry.lease_id.clone(),
+                lease_expires_at: entry.lease_expires_at,
+                job: entry.job.clone(),
+            }));
+        }
+
+        let requeued = items.iter().map(WorkItem::job_count).sum();
+        for item in items.into_iter().rev() {
+            self.pending.push_front(PendingWork::new(item));
+        }
+        requeued
+    }
+
     async fn shutdown_workers(&mut self) {
         for tx in &self.worker_cmds {
             let _ = tx.send(WorkerCommand::Stop).await;
@@ -621,6 +1989,9 @@ impl EngineRuntime {
 
     async fn run(mut self) -> anyhow::Result<()> {
         self.emit(EngineEvent::Started);
+        self.emit(EngineEvent::Environment {
+            environment: self.environment.clone(),
+        });
         self.push_snapshot();
 
         let mut progress_tick = tokio::time::interval(self.cfg.progress_tick);
@@ -634,7 +2005,7 @@ impl EngineRuntime {
                     task.abort();
                 }
                 self.fetch_backoff = None;
-                self.pending.clear();
+                self.abandon_pending();
                 break;
             }
 
@@ -643,13 +2014,30 @@ impl EngineRuntime {
                 break;
             }
             self.maybe_start_fetch();
+            let renew_at = if self.renew_task.is_none() {
+                self.renewals.next_due()
+            } else {
+                None
+            };
+            let now = Instant::now();
+            let restart_at = self
+                .workers
+                .iter()
+                .filter_map(|w| w.restart_at)
+                .filter(|at| *at > now)
+                .min();
 
             let loop_result: anyhow::Result<()> = tokio::select! {
                 _ = progress_tick.tick() => {
                     self.sample_progress();
+                    self.preempt_at_risk_work();
+                    Ok(())
+                }
+                _ = self.inner.notify.notified() => {
+                    // Pause/resume and stop requests are reflected in the snapshot.
+                    self.push_snapshot();
                     Ok(())
                 }
-                _ = self.inner.notify.notified() => Ok(()),
                 ev_opt = self.internal_rx.recv() => {
                     if let Some(ev) = ev_opt {
                         self.handle_internal_event(ev).await;
@@ -659,7 +2047,7 @@ impl EngineRuntime {
                 res = async {
                     match self.fetch_task.as_mut() {
                         Some(task) => task.await,
-                        None => std::future::pending::<Result<anyhow::Result<Vec<WorkItem>>, tokio::task::JoinError>>().await,
+                        None => std::future::pending::<Result<anyhow::Result<(Vec<WorkItem>, LeaseTerms)>, tokio::task::JoinError>>().await,
                     }
                 } => {
                     self.handle_fetch_result(res).await;
@@ -674,10 +2062,69 @@ impl EngineRuntime {
                     self.fetch_backoff = None;
                     Ok(())
                 }
-                res = self.worker_join.join_next() => {
+                _ = async {
+                    match renew_at {
+                        Some(at) => tokio::time::sleep_until(at).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    self.start_renewals();
+                    Ok(())
+                }
+                _ = async {
+                    match restart_at {
+                        Some(at) => tokio::time::sleep_until(at.into()).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    // A restarted worker's backoff elapsed; it can take work again.
+                    Ok(())
+                }
+                res = async {
+                    match self.renew_task.as_mut() {
+                        Some(task) => task.await,
+                        None => std::future::pending::<Result<Vec<(String, anyhow::Result<LeaseRenewed>)>, tokio::task::JoinError>>().await,
+                    }
+                } => {
+                    self.handle_renew_result(res).await;
+                    Ok(())
+                }
+                _ = async {
+                    match self.memory_check.as_mut() {
+                        Some(interval) => interval.tick().await,
+                        None => std::future::pending::<tokio::time::Instant>().await,
+                    }
+                } => {
+                    self.check_memory_pressure();
+                    Ok(())
+                }
+                _ = async {
+                    match self.peer_check.as_mut() {
+                        Some(interval) => interval.tick().await,
+                        None => std::future::pending::<tokio::time::Instant>().await,
+                    }
+                } => {
+                    self.check_peers();
+                    Ok(())
+                }
+                _ = async {
+                    match self.telemetry.as_mut() {
+                        Some(telemetry) => telemetry.tick().await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    self.report_telemetry();
+                    Ok(())
+                }
+                res = self.worker_join.join_next_with_id() => {
                     match res {
-                        Some(Ok(())) => Err(anyhow::anyhow!("worker task exited unexpectedly")),
-                        Some(Err(err)) => Err(anyhow::anyhow!("worker task join error: {err:#}")),
+                        Some(Ok((task_id, ()))) => self.restart_worker(
+                            task_id,
+                            "worker task exited unexpectedly".to_string(),
+                        ),
+                        Some(Err(err)) => {
+                            self.restart_worker(err.id(), format!("worker task join error: {err:#}"))
+                        }
                         None => Err(anyhow::anyhow!("worker join set empty unexpectedly")),
                     }
                 }
@@ -690,18 +2137,26 @@ impl EngineRuntime {
         }
 
         if let Err(err) = &result {
-            self.emit(EngineEvent::Error {
-                message: format!("engine error: {err:#}"),
-            });
+            self.emit(
+                EngineError::Internal {
+                    message: format!("engine error: {err:#}"),
+                }
+                .into(),
+            );
         }
 
         if let Some(task) = self.fetch_task.take() {
             task.abort();
         }
+        if let Some(task) = self.renew_task.take() {
+            task.abort();
+        }
         self.fetch_backoff = None;
-        self.pending.clear();
+        self.abandon_pending();
 
         self.shutdown_workers().await;
+        let report = self.finish_shutdown_report();
+        self.emit(EngineEvent::ShutdownReport { report });
         self.emit(EngineEvent::Stopped);
         self.push_snapshot();
         result
@@ -709,28 +2164,111 @@ impl EngineRuntime {
 }
 
 pub(crate) fn start_engine(cfg: EngineConfig) -> EngineHandle {
-    let (event_tx, _) = broadcast::channel::<EngineEvent>(1024);
+    start_engine_with_faults(cfg, Faults::default())
+}
+
+/// Default whole-request timeout for backend calls.
+const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub(crate) fn build_http_client(network: &NetworkSettings) -> anyhow::Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder()
+        .timeout(network.request_timeout().unwrap_or(HTTP_REQUEST_TIMEOUT));
+    if let Some(timeout) = network.connect_timeout() {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(url) = &network.proxy_url {
+        builder = builder.proxy(reqwest::Proxy::all(url.trim()).context("network.proxy_url")?);
+    }
+    if let Some(path) = &network.ca_path {
+        let pem = std::fs::read(path).with_context(|| format!("read {}", path.display()))?;
+        for cert in reqwest::Certificate::from_pem_bundle(&pem).context("network.ca_path")? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    if let Some(path) = &network.client_cert_path {
+        let pem = std::fs::read(path).with_context(|| format!("read {}", path.display()))?;
+        let identity = reqwest::Identity::from_pem(&pem).context("network.client_cert_path")?;
+        builder = builder.identity(identity);
+    }
+    builder = match network.ip_preference {
+        IpPreference::Any => builder,
+        IpPreference::Ipv4 => builder.local_address(IpAddr::from(Ipv4Addr::UNSPECIFIED)),
+        IpPreference::Ipv6 => builder.local_address(IpAddr::from(Ipv6Addr::UNSPECIFIED)),
+    };
+    Ok(builder.build()?)
+}
+
+pub(crate) fn start_engine_with_faults(cfg: EngineConfig, faults: Faults) -> EngineHandle {
+    let event_tx = EventSender::new();
     let (snapshot_tx, snapshot_rx) = watch::channel(StatusSnapshot {
         stop_requested: false,
+        paused: false,
         workers: Vec::new(),
         recent_jobs: Vec::new(),
+        backend_maintenance: None,
+        backend_url: cfg.backend_url.to_string(),
+        session: SessionStats::default(),
+        last_event_seq: 0,
+        budget: None,
     });
 
+    let callbacks = Arc::new(std::sync::RwLock::new(Callbacks::default()));
+    let (callback_tx, callback_rx) = mpsc::unbounded_channel();
+    tokio::spawn(dispatch_callbacks(callbacks.clone(), callback_rx));
+
     let inner = Arc::new(EngineInner {
         event_tx,
         snapshot_rx,
+        network: Arc::new(NetworkStats::default()),
         stop_requested: AtomicBool::new(false),
+        paused: AtomicBool::new(false),
+        paused_workers: (0..cfg.parallel).map(|_| AtomicBool::new(false)).collect(),
         notify: tokio::sync::Notify::new(),
+        callbacks,
+        callback_tx,
     });
 
-    let join = tokio::spawn(run_engine(inner.clone(), snapshot_tx, cfg));
+    let engine_threads = cfg.engine_threads;
+    let engine = run_engine(inner.clone(), snapshot_tx, cfg, faults);
+    let join = match engine_threads {
+        Some(threads) => spawn_on_dedicated_runtime(threads, engine),
+        None => tokio::spawn(engine),
+    };
     EngineHandle { inner, join }
 }
 
+/// Drive `engine` on its own runtime (owned by a dedicated thread), returning a
+/// handle on the caller's runtime that resolves with the engine result.
+fn spawn_on_dedicated_runtime(
+    threads: usize,
+    engine: impl Future<Output = anyhow::Result<()>> + Send + 'static,
+) -> tokio::task::JoinHandle<anyhow::Result<()>> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let spawned = std::thread::Builder::new()
+        .name("bbr-engine-rt".to_string())
+        .spawn(move || {
+            let res = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(threads.max(1))
+                .thread_name("bbr-engine")
+                .enable_all()
+                .build()
+                .map_err(anyhow::Error::from)
+                .and_then(|runtime| runtime.block_on(engine));
+            let _ = tx.send(res);
+        });
+
+    tokio::spawn(async move {
+        spawned.map_err(|err| anyhow::anyhow!("spawn engine runtime thread: {err}"))?;
+        rx.await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("engine runtime exited unexpectedly")))
+    })
+}
+
 async fn run_engine(
     inner: Arc<EngineInner>,
     snapshot_tx: watch::Sender<StatusSnapshot>,
     mut cfg: EngineConfig,
+    faults: Faults,
 ) -> anyhow::Result<()> {
     if cfg.parallel == 0 {
         cfg.parallel = 1;
@@ -738,33 +2276,48 @@ async fn run_engine(
     if cfg.idle_sleep == Duration::ZERO {
         cfg.idle_sleep = EngineConfig::DEFAULT_IDLE_SLEEP;
     }
-    if cfg.progress_steps == 0 {
-        cfg.progress_steps = EngineConfig::DEFAULT_PROGRESS_STEPS;
-    }
     if cfg.progress_tick == Duration::ZERO {
         cfg.progress_tick = EngineConfig::DEFAULT_PROGRESS_TICK;
     }
     if cfg.recent_jobs_max == 0 {
         cfg.recent_jobs_max = EngineConfig::DEFAULT_RECENT_JOBS_MAX;
     }
+    if cfg.discriminant_bits == 0 {
+        cfg.discriminant_bits = EngineConfig::DEFAULT_DISCRIMINANT_BITS;
+    } else if !EngineConfig::SUPPORTED_DISCRIMINANT_BITS.contains(&cfg.discriminant_bits) {
+        let _ = inner.event_tx.send(EngineEvent::Warning {
+            message: format!(
+                "warning: unsupported discriminant size {} bits; using {}",
+                cfg.discriminant_bits,
+                EngineConfig::DEFAULT_DISCRIMINANT_BITS
+            ),
+        });
+        cfg.discriminant_bits = EngineConfig::DEFAULT_DISCRIMINANT_BITS;
+    }
 
     bbr_client_chiavdf_fast::set_bucket_memory_budget_bytes(cfg.mem_budget_bytes);
 
-    let http = match reqwest::Client::builder()
-        .timeout(Duration::from_secs(60))
-        .build()
-    {
+    let http = match build_http_client(&cfg.network) {
         Ok(http) => http,
         Err(err) => {
             let message = format!("build http client: {err:#}");
-            let _ = inner.event_tx.send(EngineEvent::Error {
-                message: message.clone(),
-            });
+            inner.publish(
+                EngineError::Config {
+                    message: message.clone(),
+                }
+                .into(),
+            );
             let _ = inner.event_tx.send(EngineEvent::Stopped);
             let _ = snapshot_tx.send(StatusSnapshot {
                 stop_requested: inner.should_stop(),
+                paused: inner.is_paused(),
                 workers: Vec::new(),
                 recent_jobs: Vec::new(),
+                backend_maintenance: None,
+                backend_url: cfg.backend_url.to_string(),
+                session: SessionStats::default(),
+                last_event_seq: inner.event_tx.last_seq(),
+                budget: None,
             });
             return Err(anyhow::anyhow!("{message}"));
         }
@@ -773,7 +2326,22 @@ async fn run_engine(
     let submitter = Arc::new(tokio::sync::RwLock::new(cfg.submitter.clone()));
     let warned_invalid_reward_address = Arc::new(AtomicBool::new(false));
 
-    let pinning = Arc::new(PinningPlan::build(cfg.pin_mode));
+    let cgroup = match cfg.cgroup.as_ref().map(ComputeCgroup::setup) {
+        None => None,
+        Some(Ok(cgroup)) => {
+            let _ = inner.event_tx.send(EngineEvent::Warning {
+                message: format!("cgroup: compute threads in {}", cgroup.path().display()),
+            });
+            Some(cgroup)
+        }
+        Some(Err(err)) => {
+            let _ = inner.event_tx.send(EngineEvent::Warning {
+                message: format!("warning: cgroup placement disabled: {err:#}"),
+            });
+            None
+        }
+    };
+    let pinning = Arc::new(PinningPlan::build(cfg.pin_mode).with_cgroup(cgroup));
     match cfg.pin_mode {
         PinMode::Off => {}
         PinMode::L3 => {
@@ -793,44 +2361,56 @@ async fn run_engine(
         }
     }
 
+    let archive = if cfg.archive_submissions {
+        match SubmissionArchive::open(&cfg.backend_url) {
+            Ok(archive) => Some(Arc::new(archive)),
+            Err(err) => {
+                let message = format!("warning: submission archive disabled: {err:#}");
+                let _ = inner.event_tx.send(EngineEvent::Warning { message });
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     let (internal_tx, internal_rx) = mpsc::unbounded_channel::<WorkerInternalEvent>();
 
+    let spawner = WorkerSpawner {
+        http: http.clone(),
+        submitter: submitter.clone(),
+        warned_invalid_reward_address: warned_invalid_reward_address.clone(),
+        internal_tx: internal_tx.clone(),
+        pinning: pinning.clone(),
+        faults: faults.clone(),
+        net: inner.network.clone(),
+        submit_queue: Arc::new(SubmitQueue::default()),
+        proof_cache: Arc::new(ProofCache::default()),
+        archive: archive.clone(),
+        signer: cfg.signer.clone(),
+    };
+
     let mut worker_cmds = Vec::with_capacity(cfg.parallel);
     let mut worker_progress = Vec::with_capacity(cfg.parallel);
     let mut worker_join = JoinSet::new();
+    let mut worker_tasks = HashMap::with_capacity(cfg.parallel);
 
     for worker_idx in 0..cfg.parallel {
-        let (tx, rx) = mpsc::channel::<WorkerCommand>(1);
-        worker_cmds.push(tx);
-
         let progress = Arc::new(std::sync::atomic::AtomicU64::new(0));
         worker_progress.push(progress.clone());
 
-        let http = http.clone();
-        let submitter = submitter.clone();
-        let warned = warned_invalid_reward_address.clone();
-        let internal_tx = internal_tx.clone();
-        let progress = progress.clone();
-        let pinning = pinning.clone();
-
-        worker_join.spawn(async move {
-            crate::worker::run_worker_task(
-                worker_idx,
-                rx,
-                internal_tx,
-                progress,
-                http,
-                submitter,
-                warned,
-                pinning,
-            )
-            .await;
-        });
+        let (tx, task_id) = spawner.spawn(&mut worker_join, worker_idx, progress, Duration::ZERO);
+        worker_cmds.push(tx);
+        worker_tasks.insert(task_id, worker_idx);
     }
 
-    let workers = (0..cfg.parallel).map(|_| WorkerRuntime::new()).collect();
+    let workers: Vec<WorkerRuntime> = (0..cfg.parallel).map(|_| WorkerRuntime::new()).collect();
 
-    let mut inflight = match InflightStore::load() {
+    if let Some(message) = crate::integrity::check_state_dir(cfg.state_namespace.as_deref()) {
+        let _ = inner.event_tx.send(EngineEvent::Warning { message });
+    }
+
+    let mut inflight = match InflightStore::load(cfg.state_namespace.as_deref()) {
         Ok(Some(store)) => Some(store),
         Ok(None) => None,
         Err(err) => {
@@ -898,31 +2478,31 @@ async fn run_engine(
     if let Some(store) = inflight.as_ref() {
         if cfg.use_groups {
             for group in store.group_entries() {
-                pending.push_back(WorkItem::Group(BackendWorkGroup {
+                pending.push_back(PendingWork::new(WorkItem::Group(BackendWorkGroup {
                     group_id: group.group_id,
                     lease_id: group.lease_id.clone(),
                     lease_expires_at: group.lease_expires_at,
                     jobs: group.jobs.clone(),
-                }));
+                })));
             }
         }
 
         for entry in store.job_entries() {
-            pending.push_back(WorkItem::Job(WorkJobItem {
+            pending.push_back(PendingWork::new(WorkItem::Job(WorkJobItem {
                 lease_id: entry.lease_id.clone(),
                 lease_expires_at: entry.lease_expires_at,
                 job: entry.job.clone(),
-            }));
+            })));
         }
 
         if !cfg.use_groups {
             for group in store.group_entries() {
                 for job in &group.jobs {
-                    pending.push_back(WorkItem::Job(WorkJobItem {
+                    pending.push_back(PendingWork::new(WorkItem::Job(WorkJobItem {
                         lease_id: group.lease_id.clone(),
                         lease_expires_at: group.lease_expires_at,
                         job: job.clone(),
-                    }));
+                    })));
                 }
             }
         }
@@ -935,23 +2515,292 @@ async fn run_engine(
         }
     }
 
+    // Resumed leases get the configured renewal cadence; their original lease
+    // responses were not kept.
+    let leases = Arc::new(LeaseTable::default());
+    let mut renewals = LeaseRenewals::new(
+        cfg.backend_url.clone(),
+        cfg.lease_renew_interval,
+        leases.clone(),
+    );
+    for pending in &pending {
+        let _ = renewals.track(pending.item.lease_id(), &LeaseTerms::default());
+    }
+
+    let history = match HistoryStore::open(&cfg.backend_url) {
+        Ok(store) => Some(store),
+        Err(err) => {
+            let message = format!("warning: job history disabled: {err:#}");
+            let _ = inner.event_tx.send(EngineEvent::Warning { message });
+            None
+        }
+    };
+
+    let budget = cfg
+        .daily_budget
+        .map(|budget| BudgetTracker::load(budget, cfg.backend_url.as_str()));
+
+    let telemetry = cfg
+        .telemetry
+        .as_ref()
+        .map(|t| TelemetryReporter::new(t, cfg.parallel, cfg.use_groups));
+    if let Some(t) = &cfg.telemetry {
+        let _ = inner.event_tx.send(EngineEvent::Warning {
+            message: format!("Anonymous telemetry enabled: reporting to {}", t.endpoint),
+        });
+    }
+
+    let worker_order = worker_schedule_order(workers.len(), cfg.schedule_seed);
+
+    let worker_limit = if cfg.auto_parallel {
+        let reserve = cfg.low_memory_threshold_bytes.unwrap_or(MEMORY_RESERVE);
+        let limit = auto_worker_limit(cfg.parallel, cfg.mem_budget_bytes, reserve);
+        let _ = inner.event_tx.send(EngineEvent::Warning {
+            message: format!(
+                "Auto parallelism: running {limit} of {} worker(s) (cores and available memory).",
+                cfg.parallel
+            ),
+        });
+        limit
+    } else {
+        cfg.parallel
+    };
+    let memory_check = (cfg.auto_parallel || cfg.low_memory_threshold_bytes.is_some()).then(|| {
+        let mut interval = tokio::time::interval_at(
+            tokio::time::Instant::now() + MEMORY_CHECK_INTERVAL,
+            MEMORY_CHECK_INTERVAL,
+        );
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        interval
+    });
+    let peers = if cfg.cooperative {
+        match PeerRegistration::register(&cfg.backend_url, worker_limit, cfg.mem_budget_bytes) {
+            Ok(registration) => Some(registration),
+            Err(err) => {
+                let _ = inner.event_tx.send(EngineEvent::Warning {
+                    message: format!("warning: cooperative mode disabled: {err:#}"),
+                });
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let peer_check = peers.as_ref().map(|_| {
+        // The first tick fires immediately, so the share is known before work is leased.
+        let mut interval = tokio::time::interval(COOPERATIVE_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        interval
+    });
+    let idle_backoff = IDLE_BACKOFF_MIN.min(cfg.idle_sleep);
+    let environment = environment_info(&cfg, pinning.is_effective());
+
     let runtime = EngineRuntime {
         http,
         cfg,
         workers,
+        worker_order,
         worker_cmds,
         worker_progress,
         internal_rx,
         worker_join,
+        worker_tasks,
+        spawner,
         pending,
         fetch_task: None,
         fetch_backoff: None,
+        idle_backoff,
+        leases,
+        renewals,
+        renew_task: None,
+        maintenance: None,
         inflight: inflight.take(),
+        history,
+        budget,
+        budget_reached: false,
+        faults,
+        telemetry,
+        worker_limit,
+        memory_check,
+        peers,
+        peer_check,
+        cooperative_limit: None,
         recent_jobs: VecDeque::new(),
         snapshot_tx,
         inner,
+        finalize_ratio: None,
+        started_at: Instant::now(),
+        environment,
+        shutdown_report: ShutdownReport::default(),
     };
 
     runtime.push_snapshot();
     runtime.run().await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(job_id: u64, number_of_iterations: u64) -> BackendJobDto {
+        BackendJobDto {
+            job_id,
+            height: 1,
+            field_vdf: 1,
+            challenge_b64: String::new(),
+            number_of_iterations,
+            output_b64: String::new(),
+            discriminant_size_bits: None,
+            input_b64: None,
+        }
+    }
+
+    fn group(iterations: &[u64]) -> BackendWorkGroup {
+        BackendWorkGroup {
+            group_id: 1,
+            lease_id: "lease".to_string(),
+            lease_expires_at: 1_000,
+            jobs: iterations
+                .iter()
+                .zip(1..)
+                .map(|(&iters, job_id)| job(job_id, iters))
+                .collect(),
+        }
+    }
+
+    fn iterations(group: &BackendWorkGroup) -> Vec<u64> {
+        group
+            .jobs
+            .iter()
+            .map(|job| job.number_of_iterations)
+            .collect()
+    }
+
+    #[test]
+    fn split_groups_keep_the_shorter_half() {
+        let mut lower = group(&[500, 100, 400, 200, 300]);
+        let upper = split_group(&mut lower);
+        assert_eq!(iterations(&lower), [100, 200]);
+        assert_eq!(iterations(&upper), [300, 400, 500]);
+        assert_eq!(
+            (
+                upper.group_id,
+                upper.lease_id.as_str(),
+                upper.lease_expires_at
+            ),
+            (1, "lease", 1_000)
+        );
+    }
+
+    #[test]
+    fn unreached_targets_skip_passed_ones() {
+        let running = group(&[100, 200, 300]);
+        assert_eq!(iterations(&unreached_targets(&running, 0)), [100, 200, 300]);
+        assert_eq!(iterations(&unreached_targets(&running, 200)), [300]);
+        assert!(unreached_targets(&running, 300).jobs.is_empty());
+    }
+
+    #[test]
+    fn leases_are_at_risk_only_beyond_the_slack() {
+        let now = Utc::now().timestamp();
+        // 100s of squaring left.
+        assert!(!lease_at_risk(1_000, 10, now + 90));
+        assert!(lease_at_risk(1_000, 10, now + 60));
+        assert!(lease_at_risk(1, 10, now - 1));
+        // No speed measured yet.
+        assert!(!lease_at_risk(1_000, 0, now));
+    }
+
+    #[test]
+    fn only_computing_workers_are_preempted() {
+        let table = Arc::new(LeaseTable::default());
+        let mut worker = WorkerRuntime::new();
+        worker.start_job(JobSummary {
+            job_id: 1,
+            group_proofs: None,
+            height: 1,
+            field_vdf: 1,
+            number_of_iterations: 1_000,
+        });
+        worker.last_speed_its_per_sec = 10;
+        assert!(!worker.lease_at_risk());
+
+        worker.lease_deadline = Some(table.deadline("lease", Utc::now().timestamp() + 10));
+        assert!(worker.lease_at_risk());
+        worker.last_reported_squaring_iters_done = 950;
+        assert!(!worker.lease_at_risk());
+
+        worker.last_reported_squaring_iters_done = 0;
+        worker.set_stage(WorkerStage::Finalizing);
+        assert!(!worker.lease_at_risk());
+    }
+
+    #[test]
+    fn seeded_schedules_are_reproducible_permutations() {
+        assert_eq!(worker_schedule_order(4, None), [0, 1, 2, 3]);
+
+        let order = worker_schedule_order(16, Some(7));
+        assert_eq!(order, worker_schedule_order(16, Some(7)));
+        assert_ne!(order, worker_schedule_order(16, Some(8)));
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..16).collect::<Vec<_>>());
+        assert!(worker_schedule_order(0, Some(7)).is_empty());
+    }
+
+    #[test]
+    fn idle_backoff_doubles_up_to_idle_sleep() {
+        let idle_sleep = Duration::from_secs(5);
+        let mut delay = IDLE_BACKOFF_MIN;
+        let mut delays = Vec::new();
+        for _ in 0..6 {
+            delays.push(delay.as_millis());
+            delay = next_idle_backoff(delay, idle_sleep);
+        }
+        assert_eq!(delays, [500, 1_000, 2_000, 4_000, 5_000, 5_000]);
+    }
+
+    #[test]
+    fn environment_reflects_the_config() {
+        let cfg = EngineConfig {
+            backend_url: reqwest::Url::parse("https://backend.example/").unwrap(),
+            parallel: 3,
+            auto_parallel: true,
+            low_memory_threshold_bytes: None,
+            cooperative: false,
+            use_groups: false,
+            mem_budget_bytes: 64 * MIB,
+            submitter: SubmitterConfig::default(),
+            signer: None,
+            idle_sleep: Duration::from_secs(1),
+            progress_tick: Duration::ZERO,
+            recent_jobs_max: 0,
+            pin_mode: PinMode::L3,
+            cgroup: None,
+            telemetry: None,
+            schedule_seed: None,
+            preempt_at_risk: false,
+            max_iterations: None,
+            prefetch: 0,
+            lease_renew_interval: None,
+            archive_submissions: false,
+            strict_output: false,
+            discriminant_bits: EngineConfig::DEFAULT_DISCRIMINANT_BITS,
+            state_namespace: None,
+            engine_threads: None,
+            split_groups: false,
+            network: NetworkSettings::default(),
+            daily_budget: None,
+        };
+
+        let environment = environment_info(&cfg, true);
+        assert_eq!(environment.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(environment.os, std::env::consts::OS);
+        assert_eq!((environment.parallel, environment.auto_parallel), (3, true));
+        assert_eq!(environment.mem_budget_bytes, 64 * MIB);
+        assert_eq!(environment.backend_url, "https://backend.example/");
+        assert_eq!(environment.pin_mode, "l3");
+        // L3 pinning that found no cache domains runs unpinned.
+        assert_eq!(environment_info(&cfg, false).pin_mode, "off");
+    }
+}
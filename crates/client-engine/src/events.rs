@@ -0,0 +1,78 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::broadcast;
+
+use crate::api::{EngineEvent, SequencedEvent};
+
+const EVENT_CAPACITY: usize = 1024;
+
+/// Error from [`EventSender::send`]: no plain subscriber was listening.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct NoSubscribers;
+
+/// Broadcasts engine events to plain and sequenced subscribers.
+///
+/// Every event gets the next sequence number (starting at 1), whether or not
+/// anyone is listening, so a gap seen by a sequenced subscriber always means
+/// events were dropped.
+#[derive(Clone)]
+pub(crate) struct EventSender {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    plain: broadcast::Sender<EngineEvent>,
+    sequenced: broadcast::Sender<SequencedEvent>,
+    /// Sequence number of the last event sent. Held while sending so both
+    /// channels see events in sequence order.
+    last_seq: Mutex<u64>,
+}
+
+impl EventSender {
+    pub(crate) fn new() -> Self {
+        let (plain, _) = broadcast::channel(EVENT_CAPACITY);
+        let (sequenced, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            shared: Arc::new(Shared {
+                plain,
+                sequenced,
+                last_seq: Mutex::new(0),
+            }),
+        }
+    }
+
+    /// Number and broadcast `event`. Like [`broadcast::Sender::send`] for the plain
+    /// channel, returns how many plain subscribers it reached, but without handing
+    /// the event back when there are none.
+    pub(crate) fn send(&self, event: EngineEvent) -> Result<usize, NoSubscribers> {
+        let mut last_seq = self.lock_seq();
+        *last_seq += 1;
+        if self.shared.sequenced.receiver_count() > 0 {
+            let _ = self.shared.sequenced.send(SequencedEvent {
+                seq: *last_seq,
+                event: event.clone(),
+            });
+        }
+        self.shared.plain.send(event).map_err(|_| NoSubscribers)
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<EngineEvent> {
+        self.shared.plain.subscribe()
+    }
+
+    pub(crate) fn subscribe_sequenced(&self) -> broadcast::Receiver<SequencedEvent> {
+        self.shared.sequenced.subscribe()
+    }
+
+    /// Sequence number of the most recently sent event (0 before the first).
+    pub(crate) fn last_seq(&self) -> u64 {
+        *self.lock_seq()
+    }
+
+    fn lock_seq(&self) -> std::sync::MutexGuard<'_, u64> {
+        match self.shared.last_seq.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// The injector is only reachable from outside the crate with the `fault-injection`
+// feature; without it every hook is a no-op (`Faults::default()`).
+#![cfg_attr(not(feature = "fault-injection"), allow(dead_code, unreachable_pub))]
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::backend::BackendError;
+use crate::rng::{SPLITMIX64_GAMMA, splitmix64};
+
+/// Failure injection settings for chaos testing.
+///
+/// Each probability is in `0.0..=1.0` and is rolled independently every time the
+/// engine reaches the corresponding hook. Rolls are driven by `seed`, so a given
+/// configuration injects the same sequence of faults on every run (modulo task
+/// interleaving across workers).
+#[derive(Debug, Clone, Default)]
+pub struct FaultInjector {
+    /// Probability that a work lease request fails before reaching the backend.
+    pub lease_failure: f64,
+    /// Probability that a submission is rejected with a lease conflict (HTTP 409).
+    pub submit_conflict: f64,
+    /// Probability that a proof computation attempt fails.
+    pub compute_error: f64,
+    /// Probability that a backend request (lease or submit) is delayed.
+    pub delay: f64,
+    /// Delay applied when `delay` triggers.
+    pub delay_duration: Duration,
+    /// Number of work items whose worker task panics on receipt (the first ones
+    /// handed out); the engine restarts the worker and requeues the work.
+    pub worker_panics: u32,
+    /// Seed for the fault rolls.
+    pub seed: u64,
+}
+
+/// Engine-side handle to an optional [`FaultInjector`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Faults(Option<Arc<FaultState>>);
+
+#[derive(Debug)]
+struct FaultState {
+    cfg: FaultInjector,
+    rng: AtomicU64,
+    worker_panics_left: AtomicU32,
+}
+
+impl Faults {
+    pub(crate) fn new(cfg: FaultInjector) -> Self {
+        let rng = AtomicU64::new(cfg.seed);
+        let worker_panics_left = AtomicU32::new(cfg.worker_panics);
+        Self(Some(Arc::new(FaultState {
+            cfg,
+            rng,
+            worker_panics_left,
+        })))
+    }
+
+    /// Hook run before each work lease request.
+    pub(crate) async fn before_lease(&self) -> anyhow::Result<()> {
+        let Some(state) = &self.0 else {
+            return Ok(());
+        };
+        state.maybe_delay().await;
+        if state.roll(state.cfg.lease_failure) {
+            anyhow::bail!("injected fault: lease request failed");
+        }
+        Ok(())
+    }
+
+    /// Hook run before each submission attempt.
+    pub(crate) async fn before_submit(&self) -> anyhow::Result<()> {
+        let Some(state) = &self.0 else {
+            return Ok(());
+        };
+        state.maybe_delay().await;
+        if state.roll(state.cfg.submit_conflict) {
+            return Err(BackendError::LeaseConflict.into());
+        }
+        Ok(())
+    }
+
+    /// Hook run by a worker task when it receives work.
+    pub(crate) fn on_work_received(&self) {
+        let Some(state) = &self.0 else {
+            return;
+        };
+        let left = &state.worker_panics_left;
+        if left
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            panic!("injected fault: worker task panicked");
+        }
+    }
+
+    /// Hook run (on the compute thread) before each proof computation attempt.
+    pub(crate) fn before_compute(&self) -> anyhow::Result<()> {
+        let Some(state) = &self.0 else {
+            return Ok(());
+        };
+        if state.roll(state.cfg.compute_error) {
+            anyhow::bail!("injected fault: compute failed");
+        }
+        Ok(())
+    }
+}
+
+impl FaultState {
+    fn roll(&self, probability: f64) -> bool {
+        if probability <= 0.0 {
+            return false;
+        }
+        if probability >= 1.0 {
+            return true;
+        }
+        let z = splitmix64(
+            self.rng
+                .fetch_add(SPLITMIX64_GAMMA, Ordering::Relaxed)
+                .wrapping_add(SPLITMIX64_GAMMA),
+        );
+        ((z >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    async fn maybe_delay(&self) {
+        if !self.cfg.delay_duration.is_zero() && self.roll(self.cfg.delay) {
+            tokio::time::sleep(self.cfg.delay_duration).await;
+        }
+    }
+}
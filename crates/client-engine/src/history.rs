@@ -0,0 +1,203 @@
+use std::io::{BufRead as _, Write as _};
+use std::path::{Path, PathBuf};
+
+use crate::api::{JobHistoryEntry, JobOutcome};
+use crate::inflight::xdg_state_home;
+use crate::integrity::{FileCheck, move_aside};
+
+/// Size past which the history file is rotated. One rotated file is kept, so the
+/// history takes at most about twice this on disk.
+const HISTORY_ROTATE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Append-only JSONL store of finished jobs (one [`JobHistoryEntry`] per line).
+///
+/// Once the file reaches [`HISTORY_ROTATE_BYTES`] it replaces the previous
+/// rotated file ([`rotated_history_path`]) and a fresh one is started.
+pub(crate) struct HistoryStore {
+    path: PathBuf,
+    backend_url: String,
+}
+
+impl HistoryStore {
+    pub(crate) fn open(backend_url: &reqwest::Url) -> anyhow::Result<Self> {
+        Ok(Self {
+            path: history_path()?,
+            backend_url: backend_url.to_string(),
+        })
+    }
+
+    pub(crate) async fn append(
+        &self,
+        outcomes: &[JobOutcome],
+        finished_at: i64,
+    ) -> anyhow::Result<()> {
+        if outcomes.is_empty() {
+            return Ok(());
+        }
+        let entries: Vec<JobHistoryEntry> = outcomes
+            .iter()
+            .map(|outcome| JobHistoryEntry {
+                finished_at,
+                backend_url: self.backend_url.clone(),
+                outcome: outcome.clone(),
+            })
+            .collect();
+        let path = self.path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            rotate_if_full(&path, HISTORY_ROTATE_BYTES)?;
+            append_entries(&path, &entries)
+        })
+        .await
+        .map_err(|err| anyhow::anyhow!("append job history: {err:#}"))??;
+        Ok(())
+    }
+}
+
+/// Move `path` to [`rotated_history_path`] once it holds `limit` bytes or more.
+fn rotate_if_full(path: &Path, limit: u64) -> anyhow::Result<()> {
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.len() >= limit => {
+            std::fs::rename(path, rotated_history_path(path))?;
+            Ok(())
+        }
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn append_entries(path: &Path, entries: &[JobHistoryEntry]) -> anyhow::Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid history path: {}", path.display()))?;
+    std::fs::create_dir_all(dir)?;
+
+    let mut buf = Vec::new();
+    for entry in entries {
+        serde_json::to_writer(&mut buf, entry)?;
+        buf.push(b'\n');
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(&buf)?;
+    Ok(())
+}
+
+pub(crate) fn history_path() -> anyhow::Result<PathBuf> {
+    Ok(xdg_state_home()?
+        .join("bbr-client")
+        .join("job-history.jsonl"))
+}
+
+/// Where the previous history file is kept after rotation (`job-history.1.jsonl`).
+fn rotated_history_path(path: &Path) -> PathBuf {
+    path.with_extension("1.jsonl")
+}
+
+/// Entries finished at or after `since`, oldest first, including the rotated file.
+pub(crate) fn load_job_history(since: Option<i64>) -> anyhow::Result<Vec<JobHistoryEntry>> {
+    let path = history_path()?;
+    let mut out = Vec::new();
+    read_entries(&rotated_history_path(&path), since, &mut out)?;
+    read_entries(&path, since, &mut out)?;
+    Ok(out)
+}
+
+fn read_entries(
+    path: &Path,
+    since: Option<i64>,
+    out: &mut Vec<JobHistoryEntry>,
+) -> anyhow::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let file = std::fs::File::open(path)?;
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        // Skip lines that fail to parse (e.g. a torn write after a crash).
+        let Ok(entry) = serde_json::from_str::<JobHistoryEntry>(line) else {
+            continue;
+        };
+        if since.is_some_and(|since| entry.finished_at < since) {
+            continue;
+        }
+        out.push(entry);
+    }
+    Ok(())
+}
+
+/// Validate the job history file line by line.
+///
+/// If any line fails to parse, the original is moved aside and the parseable
+/// entries are rewritten into a fresh file.
+pub(crate) fn check_history_file() -> anyhow::Result<FileCheck> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(FileCheck::default());
+    }
+
+    let raw = std::fs::read(&path)?;
+    let mut check = FileCheck::default();
+    let mut kept = Vec::with_capacity(raw.len());
+    for line in raw.split(|b| *b == b'\n') {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        if serde_json::from_slice::<JobHistoryEntry>(line).is_ok() {
+            check.recovered += 1;
+            kept.extend_from_slice(line);
+            kept.push(b'\n');
+        } else {
+            check.discarded += 1;
+        }
+    }
+    if check.discarded == 0 {
+        return Ok(check);
+    }
+
+    check.moved_to = Some(move_aside(&path)?);
+    let tmp = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp, kept)?;
+    std::fs::rename(tmp, &path)?;
+    Ok(check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_history_is_rotated_once() {
+        let dir = std::env::temp_dir().join(format!("bbr-history-rotate-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("job-history.jsonl");
+        let rotated = dir.join("job-history.1.jsonl");
+
+        // A missing or small file stays put.
+        rotate_if_full(&path, 8).unwrap();
+        std::fs::write(&path, "old\n").unwrap();
+        rotate_if_full(&path, 8).unwrap();
+        assert!(path.exists());
+
+        std::fs::write(&path, "older entries\n").unwrap();
+        std::fs::write(&rotated, "oldest entries\n").unwrap();
+        rotate_if_full(&path, 8).unwrap();
+        assert!(!path.exists());
+        assert_eq!(
+            std::fs::read_to_string(&rotated).unwrap(),
+            "older entries\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,8 +2,13 @@ use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
 
 use crate::backend::BackendJobDto;
+use crate::integrity::{FileCheck, move_aside};
+
+/// Current inflight file schema version.
+const INFLIGHT_VERSION: u32 = 3;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct InflightJobEntry {
@@ -28,6 +33,17 @@ struct InflightFile {
     jobs: Vec<InflightJobEntry>,
     #[serde(default)]
     groups: Vec<InflightGroupEntry>,
+    /// Hex SHA-256 of the serialized `jobs` and `groups` (version 3+).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+}
+
+fn entries_checksum(
+    jobs: &[InflightJobEntry],
+    groups: &[InflightGroupEntry],
+) -> anyhow::Result<String> {
+    let digest = Sha256::digest(serde_json::to_vec(&(jobs, groups))?);
+    Ok(digest.iter().map(|b| format!("{b:02x}")).collect())
 }
 
 pub(crate) struct InflightStore {
@@ -38,8 +54,8 @@ pub(crate) struct InflightStore {
 }
 
 impl InflightStore {
-    pub(crate) fn load() -> anyhow::Result<Option<Self>> {
-        let path = inflight_path()?;
+    pub(crate) fn load(namespace: Option<&str>) -> anyhow::Result<Option<Self>> {
+        let path = inflight_path(namespace)?;
         if !path.exists() {
             return Ok(Some(Self {
                 path,
@@ -155,7 +171,9 @@ impl InflightStore {
         };
         match self.jobs_by_id.insert(job_id, entry) {
             None => true,
-            Some(prev) => prev.lease_id != lease_id_for_cmp || prev.lease_expires_at != lease_expires_at,
+            Some(prev) => {
+                prev.lease_id != lease_id_for_cmp || prev.lease_expires_at != lease_expires_at
+            }
         }
     }
 
@@ -207,7 +225,9 @@ impl InflightStore {
         }
 
         // Ensure group entry doesn't contain jobs that we may have just moved out of it above.
-        entry.jobs.retain(|j| self.job_to_group.get(&j.job_id).copied() == Some(group_id));
+        entry
+            .jobs
+            .retain(|j| self.job_to_group.get(&j.job_id).copied() == Some(group_id));
 
         let prev = self.groups_by_id.insert(group_id, entry);
         if prev.is_none() {
@@ -239,12 +259,39 @@ impl InflightStore {
         removed
     }
 
+    /// Extend every entry held under `lease_id` to `lease_expires_at`.
+    pub(crate) fn renew_lease(&mut self, lease_id: &str, lease_expires_at: i64) -> bool {
+        let mut changed = false;
+        for entry in self.jobs_by_id.values_mut() {
+            if entry.lease_id == lease_id && entry.lease_expires_at < lease_expires_at {
+                entry.lease_expires_at = lease_expires_at;
+                changed = true;
+            }
+        }
+        for group in self.groups_by_id.values_mut() {
+            if group.lease_id == lease_id && group.lease_expires_at < lease_expires_at {
+                group.lease_expires_at = lease_expires_at;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Whether any entry is held under `lease_id`.
+    pub(crate) fn holds_lease(&self, lease_id: &str) -> bool {
+        self.jobs_by_id.values().any(|e| e.lease_id == lease_id)
+            || self.groups_by_id.values().any(|g| g.lease_id == lease_id)
+    }
+
     pub(crate) async fn persist(&self) -> anyhow::Result<()> {
         let path = self.path.clone();
+        let jobs: Vec<InflightJobEntry> = self.jobs_by_id.values().cloned().collect();
+        let groups: Vec<InflightGroupEntry> = self.groups_by_id.values().cloned().collect();
         let file = InflightFile {
-            version: 2,
-            jobs: self.jobs_by_id.values().cloned().collect(),
-            groups: self.groups_by_id.values().cloned().collect(),
+            version: INFLIGHT_VERSION,
+            checksum: Some(entries_checksum(&jobs, &groups)?),
+            jobs,
+            groups,
         };
 
         tokio::task::spawn_blocking(move || persist_file(&path, &file))
@@ -274,7 +321,7 @@ fn persist_file(path: &Path, file: &InflightFile) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn xdg_state_home() -> anyhow::Result<PathBuf> {
+pub(crate) fn xdg_state_home() -> anyhow::Result<PathBuf> {
     if let Some(dir) = std::env::var_os("XDG_STATE_HOME") {
         let dir = PathBuf::from(dir);
         if dir.as_os_str().is_empty() {
@@ -322,8 +369,159 @@ fn xdg_state_home() -> anyhow::Result<PathBuf> {
     Ok(home.join(".local").join("state"))
 }
 
-fn inflight_path() -> anyhow::Result<PathBuf> {
-    Ok(xdg_state_home()?
-        .join("bbr-client")
-        .join("inflight-leases.json"))
+/// Validate the inflight file (schema version, checksum, per-entry parse).
+///
+/// A file that fails validation is moved aside; entries that still parse are
+/// rewritten into a fresh file so they can be resumed.
+pub(crate) fn check_inflight_file(namespace: Option<&str>) -> anyhow::Result<FileCheck> {
+    let path = inflight_path(namespace)?;
+    if !path.exists() {
+        return Ok(FileCheck::default());
+    }
+
+    let raw = std::fs::read(&path)?;
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(&raw) else {
+        return Ok(FileCheck {
+            moved_to: Some(move_aside(&path)?),
+            ..FileCheck::default()
+        });
+    };
+
+    let version = value["version"].as_u64().unwrap_or(0);
+    if version > u64::from(INFLIGHT_VERSION) {
+        // Written by a newer client: keep it intact rather than guessing.
+        return Ok(FileCheck {
+            moved_to: Some(move_aside(&path)?),
+            ..FileCheck::default()
+        });
+    }
+
+    let mut check = FileCheck::default();
+    let mut jobs = Vec::new();
+    for entry in value["jobs"].as_array().into_iter().flatten() {
+        match serde_json::from_value::<InflightJobEntry>(entry.clone()) {
+            Ok(entry) => jobs.push(entry),
+            Err(_) => check.discarded += 1,
+        }
+    }
+    let mut groups = Vec::new();
+    for entry in value["groups"].as_array().into_iter().flatten() {
+        match serde_json::from_value::<InflightGroupEntry>(entry.clone()) {
+            Ok(entry) => groups.push(entry),
+            Err(_) => check.discarded += 1,
+        }
+    }
+    check.recovered = jobs.len() + groups.iter().map(|g| g.jobs.len()).sum::<usize>();
+
+    // Files from before version 3 carry no checksum.
+    let checksum_ok = match value["checksum"].as_str() {
+        Some(expected) => entries_checksum(&jobs, &groups)? == expected,
+        None => version < INFLIGHT_VERSION.into(),
+    };
+    if check.discarded == 0 && checksum_ok {
+        return Ok(check);
+    }
+
+    check.moved_to = Some(move_aside(&path)?);
+    let file = InflightFile {
+        version: INFLIGHT_VERSION,
+        checksum: Some(entries_checksum(&jobs, &groups)?),
+        jobs,
+        groups,
+    };
+    persist_file(&path, &file)?;
+    Ok(check)
+}
+
+fn inflight_path(namespace: Option<&str>) -> anyhow::Result<PathBuf> {
+    let name = match namespace {
+        Some(ns) => format!("inflight-leases-{ns}.json"),
+        None => "inflight-leases.json".to_string(),
+    };
+    Ok(xdg_state_home()?.join("bbr-client").join(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_store() -> InflightStore {
+        InflightStore {
+            path: PathBuf::from("inflight-leases.json"),
+            jobs_by_id: BTreeMap::new(),
+            groups_by_id: BTreeMap::new(),
+            job_to_group: BTreeMap::new(),
+        }
+    }
+
+    fn job(job_id: u64, challenge_b64: &str) -> BackendJobDto {
+        BackendJobDto {
+            job_id,
+            height: 1,
+            field_vdf: 1,
+            challenge_b64: challenge_b64.to_string(),
+            number_of_iterations: 1_000,
+            output_b64: String::new(),
+            discriminant_size_bits: None,
+            input_b64: None,
+        }
+    }
+
+    #[test]
+    fn jobs_move_between_single_entries_and_groups() {
+        let mut store = empty_store();
+        assert!(store.insert_job("a".to_string(), 100, job(1, "c")));
+        assert!(!store.insert_job("a".to_string(), 100, job(1, "c")));
+
+        // Grouping job 1 takes it out of the single entries.
+        assert!(store.insert_group(10, "b".to_string(), 200, vec![job(1, "c"), job(2, "c")]));
+        assert_eq!(store.job_entries().count(), 0);
+        assert_eq!(store.total_jobs(), 2);
+
+        // Re-leasing job 2 on its own takes it out of the group.
+        assert!(store.insert_job("c".to_string(), 300, job(2, "c")));
+        assert_eq!(store.total_jobs(), 2);
+        assert!(store.remove_job(1));
+        assert_eq!(store.group_entries().count(), 0);
+        assert!(!store.remove_job(1));
+        assert!(store.remove_job(2));
+        assert_eq!(store.total_jobs(), 0);
+    }
+
+    #[test]
+    fn renewals_only_extend_their_own_lease() {
+        let mut store = empty_store();
+        store.insert_job("a".to_string(), 100, job(1, "c"));
+        store.insert_group(10, "b".to_string(), 100, vec![job(2, "c")]);
+
+        assert!(store.renew_lease("b", 500));
+        assert!(!store.renew_lease("b", 400));
+        assert_eq!(store.group_entries().next().unwrap().lease_expires_at, 500);
+        assert_eq!(store.job_entries().next().unwrap().lease_expires_at, 100);
+
+        assert!(store.holds_lease("a"));
+        assert!(store.holds_lease("b"));
+        assert!(!store.holds_lease("c"));
+    }
+
+    #[test]
+    fn resumed_jobs_are_grouped_by_lease_and_challenge() {
+        let mut store = empty_store();
+        for job_id in 1..=3 {
+            store.insert_job("a".to_string(), 100, job(job_id, "c"));
+        }
+        store.insert_job("a".to_string(), 100, job(4, "d"));
+        store.insert_job("b".to_string(), 100, job(5, "c"));
+
+        assert!(store.promote_jobs_to_groups_by_challenge(2));
+        let groups: Vec<Vec<u64>> = store
+            .group_entries()
+            .map(|group| group.jobs.iter().map(|job| job.job_id).collect())
+            .collect();
+        assert_eq!(groups, [vec![1, 2], vec![3], vec![4], vec![5]]);
+        assert_eq!(store.job_entries().count(), 0);
+        assert_eq!(store.total_jobs(), 5);
+        // Already grouped: nothing left to promote.
+        assert!(!store.promote_jobs_to_groups_by_challenge(2));
+    }
 }
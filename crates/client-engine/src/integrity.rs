@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use crate::history::check_history_file;
+use crate::inflight::check_inflight_file;
+
+/// Result of validating one state file.
+#[derive(Debug, Default)]
+pub(crate) struct FileCheck {
+    /// Entries kept.
+    pub(crate) recovered: usize,
+    /// Entries dropped because they could not be parsed.
+    pub(crate) discarded: usize,
+    /// Where the original file was moved, when it failed validation.
+    pub(crate) moved_to: Option<PathBuf>,
+}
+
+impl FileCheck {
+    pub(crate) fn is_clean(&self) -> bool {
+        self.discarded == 0 && self.moved_to.is_none()
+    }
+
+    pub(crate) fn describe(&self, name: &str) -> String {
+        let mut out = format!(
+            "{name}: {} recovered, {} discarded",
+            self.recovered, self.discarded
+        );
+        if let Some(path) = &self.moved_to {
+            out.push_str(&format!(" (original moved to {})", path.display()));
+        }
+        out
+    }
+}
+
+/// Move a state file that failed validation aside, next to the original, with a
+/// timestamp suffix (e.g. `inflight-leases.json.corrupt-20250101T120000Z`).
+pub(crate) fn move_aside(path: &Path) -> anyhow::Result<PathBuf> {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+    let mut name = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("invalid state file path: {}", path.display()))?
+        .to_os_string();
+    name.push(format!(".corrupt-{stamp}"));
+    let dest = path.with_file_name(name);
+    std::fs::rename(path, &dest)?;
+    Ok(dest)
+}
+
+/// Validate the inflight lease and job history files, repairing them in place.
+///
+/// Returns a single warning describing what was recovered and discarded, or
+/// `None` when every file was intact.
+pub(crate) fn check_state_dir(namespace: Option<&str>) -> Option<String> {
+    let mut problems = Vec::new();
+    for (name, res) in [
+        ("inflight leases", check_inflight_file(namespace)),
+        ("job history", check_history_file()),
+    ] {
+        match res {
+            Ok(check) if check.is_clean() => {}
+            Ok(check) => problems.push(check.describe(name)),
+            Err(err) => problems.push(format!("{name}: check failed: {err:#}")),
+        }
+    }
+
+    if problems.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "warning: state directory integrity check repaired files: {}",
+        problems.join("; ")
+    ))
+}
@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Context as _;
+use reqwest::Url;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::backend::{LeaseRenewed, LeaseTerms};
+
+/// Renewal endpoint used when a lease response does not name one.
+const DEFAULT_RENEW_PATH: &str = "api/jobs/renew_lease";
+
+/// Shortest renewal interval honored, whatever the backend or config asks for.
+const MIN_RENEW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lease expiries extended by renewals, shared by the engine and its workers.
+#[derive(Debug, Default)]
+pub(crate) struct LeaseTable {
+    renewed: Mutex<HashMap<String, i64>>,
+}
+
+impl LeaseTable {
+    /// Current expiry of `lease_id`: the latest renewal, or `leased_expires_at`
+    /// if it was never renewed.
+    pub(crate) fn expires_at(&self, lease_id: &str, leased_expires_at: i64) -> i64 {
+        self.lock()
+            .get(lease_id)
+            .map_or(leased_expires_at, |&renewed| renewed.max(leased_expires_at))
+    }
+
+    /// Handle for a worker holding `lease_id`.
+    pub(crate) fn deadline(
+        self: &Arc<Self>,
+        lease_id: &str,
+        leased_expires_at: i64,
+    ) -> LeaseDeadline {
+        LeaseDeadline {
+            table: self.clone(),
+            lease_id: lease_id.to_string(),
+            leased_expires_at,
+            preemption: Arc::default(),
+        }
+    }
+
+    fn extend(&self, lease_id: &str, expires_at: i64) {
+        self.lock().insert(lease_id.to_string(), expires_at);
+    }
+
+    fn forget(&self, lease_id: &str) {
+        self.lock().remove(lease_id);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, i64>> {
+        match self.renewed.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Expiry of the lease a worker is holding, following renewals.
+///
+/// Clones share a preemption flag, through which the engine tells the worker to
+/// give up work that cannot finish before the lease expires.
+#[derive(Debug, Clone)]
+pub(crate) struct LeaseDeadline {
+    table: Arc<LeaseTable>,
+    lease_id: String,
+    leased_expires_at: i64,
+    preemption: Arc<Preemption>,
+}
+
+#[derive(Debug, Default)]
+struct Preemption {
+    set: AtomicBool,
+    notify: Notify,
+}
+
+impl LeaseDeadline {
+    /// Current expiry (unix seconds).
+    pub(crate) fn expires_at(&self) -> i64 {
+        self.table
+            .expires_at(&self.lease_id, self.leased_expires_at)
+    }
+
+    /// Ask the worker holding the lease to give up its work.
+    pub(crate) fn preempt(&self) {
+        self.preemption.set.store(true, Ordering::SeqCst);
+        self.preemption.notify.notify_waiters();
+    }
+
+    /// Whether [`Self::preempt`] was called.
+    pub(crate) fn is_preempted(&self) -> bool {
+        self.preemption.set.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the work is preempted.
+    pub(crate) async fn preempted(&self) {
+        // Registered before the flag check, so a concurrent `preempt` is not missed.
+        let notified = self.preemption.notify.notified();
+        if self.is_preempted() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+#[derive(Debug)]
+struct Renewal {
+    url: Url,
+    interval: Duration,
+    next_at: Instant,
+    /// A failure was reported since the last successful renewal.
+    warned: bool,
+}
+
+/// Renewal schedule for the leases the engine holds.
+///
+/// Each lease is renewed on the cadence its backend asked for (`renew_interval`,
+/// `renew_url` in the lease response), falling back to the configured interval
+/// and [`DEFAULT_RENEW_PATH`]. Leases with neither are not renewed.
+#[derive(Debug)]
+pub(crate) struct LeaseRenewals {
+    backend_url: Url,
+    default_interval: Option<Duration>,
+    leases: HashMap<String, Renewal>,
+    table: Arc<LeaseTable>,
+}
+
+impl LeaseRenewals {
+    pub(crate) fn new(
+        backend_url: Url,
+        default_interval: Option<Duration>,
+        table: Arc<LeaseTable>,
+    ) -> Self {
+        Self {
+            backend_url,
+            default_interval,
+            leases: HashMap::new(),
+            table,
+        }
+    }
+
+    /// Start renewing `lease_id` (no-op if it is already tracked or no interval applies).
+    pub(crate) fn track(&mut self, lease_id: &str, terms: &LeaseTerms) -> anyhow::Result<()> {
+        if self.leases.contains_key(lease_id) {
+            return Ok(());
+        }
+        let Some(interval) = self.interval(terms) else {
+            return Ok(());
+        };
+        let path = terms.renew_url.as_deref().unwrap_or(DEFAULT_RENEW_PATH);
+        let url = self
+            .backend_url
+            .join(path)
+            .with_context(|| format!("invalid lease renew_url {path:?}"))?;
+        self.leases.insert(
+            lease_id.to_string(),
+            Renewal {
+                url,
+                interval,
+                next_at: Instant::now() + interval,
+                warned: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop renewing leases for which `keep` returns false.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        let table = &self.table;
+        self.leases.retain(|lease_id, _| {
+            let kept = keep(lease_id);
+            if !kept {
+                table.forget(lease_id);
+            }
+            kept
+        });
+    }
+
+    /// When the next renewal is due.
+    pub(crate) fn next_due(&self) -> Option<Instant> {
+        self.leases.values().map(|renewal| renewal.next_at).min()
+    }
+
+    /// Leases due for renewal now, with their renewal endpoints. Each is
+    /// rescheduled a full interval ahead, so a failed renewal is retried then.
+    pub(crate) fn take_due(&mut self) -> Vec<(String, Url)> {
+        let now = Instant::now();
+        let mut due = Vec::new();
+        for (lease_id, renewal) in &mut self.leases {
+            if renewal.next_at <= now {
+                renewal.next_at = now + renewal.interval;
+                due.push((lease_id.clone(), renewal.url.clone()));
+            }
+        }
+        due
+    }
+
+    /// Record a successful renewal; the backend may change the cadence with it.
+    pub(crate) fn renewed(&mut self, lease_id: &str, renewed: &LeaseRenewed) {
+        let interval = self.interval(&renewed.terms);
+        let Some(renewal) = self.leases.get_mut(lease_id) else {
+            return;
+        };
+        renewal.warned = false;
+        if let Some(interval) = interval
+            && interval != renewal.interval
+        {
+            renewal.interval = interval;
+            renewal.next_at = Instant::now() + interval;
+        }
+        self.table.extend(lease_id, renewed.lease_expires_at);
+    }
+
+    /// Record a failed renewal. Returns whether it should be reported (the first
+    /// failure since the last success).
+    pub(crate) fn failed(&mut self, lease_id: &str) -> bool {
+        match self.leases.get_mut(lease_id) {
+            Some(renewal) => !std::mem::replace(&mut renewal.warned, true),
+            None => false,
+        }
+    }
+
+    /// Stop renewing a lease the backend no longer recognizes.
+    pub(crate) fn drop_lease(&mut self, lease_id: &str) {
+        self.leases.remove(lease_id);
+        self.table.forget(lease_id);
+    }
+
+    fn interval(&self, terms: &LeaseTerms) -> Option<Duration> {
+        terms
+            .renew_interval
+            .map(Duration::from_secs)
+            .or(self.default_interval)
+            .map(|interval| interval.max(MIN_RENEW_INTERVAL))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renewals() -> LeaseRenewals {
+        let backend_url = Url::parse("https://backend.example/").unwrap();
+        LeaseRenewals::new(backend_url, None, Arc::default())
+    }
+
+    #[test]
+    fn renewals_follow_the_lease_terms() {
+        let mut renewals = renewals();
+        renewals.track("untimed", &LeaseTerms::default()).unwrap();
+        assert!(renewals.next_due().is_none());
+
+        let terms = LeaseTerms {
+            renew_interval: Some(0),
+            renew_url: Some("api/leases/renew".to_string()),
+            ..LeaseTerms::default()
+        };
+        renewals.track("lease", &terms).unwrap();
+        let renewal = &renewals.leases["lease"];
+        assert_eq!(renewal.interval, MIN_RENEW_INTERVAL);
+        assert_eq!(
+            renewal.url.as_str(),
+            "https://backend.example/api/leases/renew"
+        );
+        assert!(renewals.take_due().is_empty());
+
+        // Only the first failure since the last success is reported.
+        assert!(renewals.failed("lease"));
+        assert!(!renewals.failed("lease"));
+        let renewed = LeaseRenewed {
+            lease_expires_at: 2_000,
+            terms: LeaseTerms {
+                renew_interval: Some(30),
+                ..LeaseTerms::default()
+            },
+        };
+        renewals.renewed("lease", &renewed);
+        assert!(renewals.failed("lease"));
+        assert_eq!(renewals.leases["lease"].interval, Duration::from_secs(30));
+        assert_eq!(renewals.table.expires_at("lease", 1_000), 2_000);
+        assert!(!renewals.failed("unknown"));
+
+        renewals.drop_lease("lease");
+        assert!(renewals.next_due().is_none());
+        assert_eq!(renewals.table.expires_at("lease", 1_000), 1_000);
+    }
+
+    #[tokio::test]
+    async fn preemption_reaches_every_clone() {
+        let table = Arc::new(LeaseTable::default());
+        let deadline = table.deadline("lease", 1_000);
+        let worker_deadline = deadline.clone();
+        let unrelated = table.deadline("lease", 1_000);
+
+        let waiter = tokio::spawn(async move {
+            worker_deadline.preempted().await;
+            worker_deadline.is_preempted()
+        });
+        deadline.preempt();
+        assert!(waiter.await.unwrap());
+        // Already preempted: resolves without another notification.
+        deadline.preempted().await;
+        assert!(!unrelated.is_preempted());
+    }
+}
@@ -7,13 +7,46 @@
 /// Public API for the engine crate.
 pub mod api;
 
+mod archive;
 mod backend;
+mod blocking;
+mod budget;
+mod calibrate;
+mod cgroup;
+mod cooperative;
+mod doctor;
 mod engine;
+mod events;
+mod fault;
+mod history;
 mod inflight;
+mod integrity;
+mod lease;
+mod network;
+mod parallelism;
 mod pinning;
+mod proof_cache;
+mod rng;
+mod submit_queue;
+mod summary;
+mod telemetry;
 mod worker;
 
 pub use api::{
-    EngineConfig, EngineEvent, EngineHandle, JobOutcome, JobSummary, PinMode, StatusSnapshot,
-    WorkerSnapshot, WorkerStage, start_engine,
+    BackendMaintenance, BackendQueueStatus, BudgetStatus, CgroupConfig, CheckStatus, DailyBudget,
+    DoctorCheck, DoctorReport, EndpointLatency, Engine, EngineConfig, EngineControl, EngineError,
+    EngineEvent, EngineHandle, EnvironmentInfo, JobHistoryEntry, JobOutcome, JobSummary,
+    LatencyBucket, MismatchCheck, NetworkStatus, PinMode, SequencedEvent, SessionStats,
+    ShutdownReport, StatusSnapshot, SubmissionDiagnostics, SubmissionRecord, TelemetryConfig,
+    WorkerSnapshot, WorkerStage, fetch_backend_queue_status, job_history_path, load_job_history,
+    run_doctor, start_engine, submission_archive_path, verify_submission_archive,
 };
+
+pub use calibrate::{
+    CalibrationResult, CpuInfo, SuggestedSettings, calibrate, detect_cpu, suggest_settings,
+};
+
+#[cfg(feature = "fault-injection")]
+pub use api::start_engine_with_faults;
+#[cfg(feature = "fault-injection")]
+pub use fault::FaultInjector;
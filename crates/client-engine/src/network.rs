@@ -0,0 +1,268 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use bbr_client_core::format::format_duration;
+use chrono::{Local, NaiveDate, Utc};
+
+use crate::api::{EndpointLatency, LatencyBucket, NetworkStatus};
+
+/// Upper bounds (milliseconds) of the request latency histogram buckets; one
+/// more bucket collects everything slower.
+const LATENCY_BUCKETS_MS: [u64; 9] = [50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000];
+
+/// Requests per endpoint used for the percentiles.
+const LATENCY_WINDOW: usize = 100;
+
+/// Submits needed in the window before slow submits are reported.
+const SLOW_SUBMIT_MIN_SAMPLES: usize = 10;
+
+/// p95 submit latency above which a warning is emitted.
+const SLOW_SUBMIT_P95: Duration = Duration::from_secs(10);
+
+/// Backend endpoint names used for latency accounting.
+pub(crate) const ENDPOINT_LEASE: &str = "lease";
+pub(crate) const ENDPOINT_SUBMIT: &str = "submit";
+pub(crate) const ENDPOINT_RENEW: &str = "renew";
+pub(crate) const ENDPOINT_RELEASE: &str = "release";
+
+/// Backend traffic accounting shared by the fetch task and all workers.
+///
+/// Counts JSON payload bytes of successful backend exchanges (not HTTP framing).
+#[derive(Debug, Default)]
+pub(crate) struct NetworkStats {
+    counters: Mutex<NetworkCounters>,
+}
+
+#[derive(Debug, Default)]
+struct NetworkCounters {
+    day: Option<NaiveDate>,
+    uploaded_today: u64,
+    downloaded_today: u64,
+    uploaded_total: u64,
+    downloaded_total: u64,
+    last_contact_at: Option<i64>,
+    last_submit_at: Option<i64>,
+    latency: BTreeMap<&'static str, LatencyStats>,
+    /// Set while the slow-submit warning is active, so it is emitted once per episode.
+    warned_slow_submit: bool,
+}
+
+/// Latency histogram for one endpoint, plus a window of recent samples for
+/// percentiles.
+#[derive(Debug, Default)]
+struct LatencyStats {
+    buckets: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    count: u64,
+    max_ms: u64,
+    recent: VecDeque<u64>,
+}
+
+impl LatencyStats {
+    fn record(&mut self, ms: u64) {
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&le| ms <= le)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.max_ms = self.max_ms.max(ms);
+        if self.recent.len() == LATENCY_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(ms);
+    }
+
+    /// `p`-th percentile (0..=100) of the recent window.
+    fn percentile(&self, p: usize) -> u64 {
+        let mut sorted: Vec<u64> = self.recent.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * p).div_ceil(100).max(1);
+        sorted.get(rank - 1).copied().unwrap_or(0)
+    }
+
+    fn summary(&self, endpoint: &str) -> EndpointLatency {
+        EndpointLatency {
+            endpoint: endpoint.to_string(),
+            count: self.count,
+            buckets: self
+                .buckets
+                .iter()
+                .enumerate()
+                .map(|(idx, &count)| LatencyBucket {
+                    le_ms: LATENCY_BUCKETS_MS.get(idx).copied(),
+                    count,
+                })
+                .collect(),
+            p50_ms: self.percentile(50),
+            p95_ms: self.percentile(95),
+            max_ms: self.max_ms,
+        }
+    }
+}
+
+impl NetworkCounters {
+    fn roll_day(&mut self) {
+        let today = Local::now().date_naive();
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.uploaded_today = 0;
+            self.downloaded_today = 0;
+        }
+    }
+}
+
+impl NetworkStats {
+    fn with_counters<R>(&self, f: impl FnOnce(&mut NetworkCounters) -> R) -> R {
+        let mut counters = match self.counters.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        counters.roll_day();
+        f(&mut counters)
+    }
+
+    /// Record a successful request/response exchange with the backend.
+    pub(crate) fn record_exchange(&self, uploaded: usize, downloaded: usize) {
+        self.with_counters(|c| {
+            c.uploaded_today = c.uploaded_today.saturating_add(uploaded as u64);
+            c.downloaded_today = c.downloaded_today.saturating_add(downloaded as u64);
+            c.uploaded_total = c.uploaded_total.saturating_add(uploaded as u64);
+            c.downloaded_total = c.downloaded_total.saturating_add(downloaded as u64);
+            c.last_contact_at = Some(Utc::now().timestamp());
+        });
+    }
+
+    /// Record a submission accepted for processing by the backend.
+    pub(crate) fn record_submit(&self) {
+        self.with_counters(|c| c.last_submit_at = Some(Utc::now().timestamp()));
+    }
+
+    /// Record how long a request to `endpoint` took, whether or not it succeeded.
+    pub(crate) fn record_latency(&self, endpoint: &'static str, elapsed: Duration) {
+        let ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        self.with_counters(|c| c.latency.entry(endpoint).or_default().record(ms));
+    }
+
+    /// Warning text when recent submits have become slow, once per slow episode.
+    ///
+    /// Lease latency is included for comparison: slow submits with fast leases
+    /// point at the backend, slow everything at the local network.
+    pub(crate) fn slow_submit_warning(&self) -> Option<String> {
+        self.with_counters(|c| {
+            let submit = c.latency.get(ENDPOINT_SUBMIT)?;
+            if submit.recent.len() < SLOW_SUBMIT_MIN_SAMPLES {
+                return None;
+            }
+            let threshold_ms = SLOW_SUBMIT_P95.as_millis() as u64;
+            let p95 = submit.percentile(95);
+            if p95 <= threshold_ms {
+                c.warned_slow_submit = false;
+                return None;
+            }
+            if std::mem::replace(&mut c.warned_slow_submit, true) {
+                return None;
+            }
+
+            let mut message = format!(
+                "warning: slow submits: p50 {}, p95 {} over the last {} submits",
+                format_ms(submit.percentile(50)),
+                format_ms(p95),
+                submit.recent.len()
+            );
+            if let Some(lease) = c.latency.get(ENDPOINT_LEASE)
+                && !lease.recent.is_empty()
+            {
+                let lease_p95 = lease.percentile(95);
+                let hint = if lease_p95 <= threshold_ms {
+                    "leases are fast, so the backend is slow to accept proofs"
+                } else {
+                    "leases are slow too; check the local network or proxy"
+                };
+                message.push_str(&format!(" (lease p95 {}; {hint})", format_ms(lease_p95)));
+            }
+            Some(message)
+        })
+    }
+
+    pub(crate) fn status(&self) -> NetworkStatus {
+        self.with_counters(|c| NetworkStatus {
+            uploaded_bytes_today: c.uploaded_today,
+            downloaded_bytes_today: c.downloaded_today,
+            uploaded_bytes_total: c.uploaded_total,
+            downloaded_bytes_total: c.downloaded_total,
+            last_contact_at: c.last_contact_at,
+            last_submit_at: c.last_submit_at,
+            latency: c
+                .latency
+                .iter()
+                .map(|(endpoint, stats)| stats.summary(endpoint))
+                .collect(),
+        })
+    }
+}
+
+fn format_ms(ms: u64) -> String {
+    format_duration(Duration::from_millis(ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exchanges_and_latency_are_accounted() {
+        let stats = NetworkStats::default();
+        stats.record_exchange(100, 2_000);
+        stats.record_exchange(50, 0);
+        stats.record_latency(ENDPOINT_LEASE, Duration::from_millis(40));
+        stats.record_latency(ENDPOINT_LEASE, Duration::from_millis(700));
+        stats.record_latency(ENDPOINT_LEASE, Duration::from_secs(60));
+
+        let status = stats.status();
+        assert_eq!(status.uploaded_bytes_today, 150);
+        assert_eq!(status.downloaded_bytes_today, 2_000);
+        assert_eq!(status.uploaded_bytes_total, 150);
+        assert_eq!(status.downloaded_bytes_total, 2_000);
+        assert!(status.last_contact_at.is_some());
+        assert!(status.last_submit_at.is_none());
+
+        let [lease] = status.latency.as_slice() else {
+            panic!("expected one endpoint, got {:?}", status.latency);
+        };
+        assert_eq!(lease.endpoint, ENDPOINT_LEASE);
+        assert_eq!(lease.count, 3);
+        assert_eq!(lease.max_ms, 60_000);
+        assert_eq!(lease.p50_ms, 700);
+        let counts: Vec<u64> = lease.buckets.iter().map(|bucket| bucket.count).collect();
+        assert_eq!(counts, [1, 0, 0, 0, 1, 0, 0, 0, 0, 1]);
+        assert_eq!(lease.buckets.last().unwrap().le_ms, None);
+    }
+
+    #[test]
+    fn slow_submits_are_reported_once_per_episode() {
+        let stats = NetworkStats::default();
+        for _ in 0..SLOW_SUBMIT_MIN_SAMPLES - 1 {
+            stats.record_latency(ENDPOINT_SUBMIT, Duration::from_secs(20));
+        }
+        assert_eq!(stats.slow_submit_warning(), None);
+
+        stats.record_latency(ENDPOINT_LEASE, Duration::from_millis(100));
+        stats.record_latency(ENDPOINT_SUBMIT, Duration::from_secs(20));
+        let warning = stats
+            .slow_submit_warning()
+            .expect("slow submits not reported");
+        assert!(warning.contains("leases are fast"), "{warning}");
+        assert_eq!(stats.slow_submit_warning(), None);
+
+        // Recovering ends the episode; the next slow spell is reported again.
+        for _ in 0..LATENCY_WINDOW {
+            stats.record_latency(ENDPOINT_SUBMIT, Duration::from_millis(100));
+        }
+        assert_eq!(stats.slow_submit_warning(), None);
+        for _ in 0..LATENCY_WINDOW {
+            stats.record_latency(ENDPOINT_SUBMIT, Duration::from_secs(20));
+        }
+        assert!(stats.slow_submit_warning().is_some());
+    }
+}
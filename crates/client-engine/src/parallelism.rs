@@ -0,0 +1,146 @@
+use crate::calibrate::available_memory_bytes;
+
+/// Memory a worker needs beyond its streaming budget (native state, buffers).
+const WORKER_MEMORY_OVERHEAD: u64 = 64 * 1024 * 1024;
+
+/// Memory left for the rest of the system when sizing workers, unless
+/// configured (`low_memory_threshold_bytes`).
+pub(crate) const MEMORY_RESERVE: u64 = 512 * 1024 * 1024;
+
+fn per_worker_bytes(mem_budget_bytes: u64) -> u64 {
+    mem_budget_bytes.saturating_add(WORKER_MEMORY_OVERHEAD)
+}
+
+/// Safe worker count for `parallel: Auto`, at most `max_workers`.
+///
+/// Bounded by logical cores and by how many per-worker memory budgets fit in the
+/// currently available memory (minus `reserve`). Memory is ignored where it can't
+/// be detected.
+pub(crate) fn auto_worker_limit(max_workers: usize, mem_budget_bytes: u64, reserve: u64) -> usize {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let mut limit = max_workers.min(cores);
+    if let Some(available) = available_memory_bytes() {
+        let fit = available.saturating_sub(reserve) / per_worker_bytes(mem_budget_bytes);
+        limit = limit.min(usize::try_from(fit).unwrap_or(usize::MAX));
+    }
+    limit.max(1)
+}
+
+/// Lowered worker limit when `available` memory is short, or `None` if
+/// `current_limit` is still affordable.
+///
+/// `busy` workers already hold their memory, so only the remaining headroom (or
+/// deficit) below `reserve` is converted into workers.
+pub(crate) fn pressure_worker_limit(
+    current_limit: usize,
+    busy: usize,
+    mem_budget_bytes: u64,
+    available: u64,
+    reserve: u64,
+) -> Option<usize> {
+    let per_worker = per_worker_bytes(mem_budget_bytes);
+    let affordable = if available >= reserve {
+        let extra = (available - reserve) / per_worker;
+        busy.saturating_add(usize::try_from(extra).unwrap_or(usize::MAX))
+    } else {
+        let deficit = (reserve - available).div_ceil(per_worker);
+        busy.saturating_sub(usize::try_from(deficit).unwrap_or(usize::MAX))
+    };
+    let affordable = affordable.max(1);
+    (affordable < current_limit).then_some(affordable)
+}
+
+/// Worker count for this engine when it shares the host with `peers`
+/// (`(workers, mem_budget_bytes)` each engine asks for), at most `workers`.
+///
+/// Only an oversubscribed machine is divided: cores are split in proportion to
+/// the requested workers, and `total_memory` (minus `reserve`) in proportion to
+/// the memory those workers need. Every engine keeps at least one worker.
+pub(crate) fn cooperative_worker_limit(
+    workers: usize,
+    mem_budget_bytes: u64,
+    peers: &[(usize, u64)],
+    cores: usize,
+    total_memory: Option<u64>,
+    reserve: u64,
+) -> usize {
+    let mut limit = workers;
+
+    let demand = peers.iter().fold(workers, |sum, &(peer_workers, _)| {
+        sum.saturating_add(peer_workers)
+    });
+    if demand > cores {
+        let share = cores as u128 * workers as u128 / demand as u128;
+        limit = limit.min(usize::try_from(share).unwrap_or(usize::MAX));
+    }
+
+    if let Some(total) = total_memory {
+        let pool = u128::from(total.saturating_sub(reserve));
+        let own = workers as u128 * u128::from(per_worker_bytes(mem_budget_bytes));
+        let demand = peers.iter().fold(own, |sum, &(peer_workers, peer_budget)| {
+            sum + peer_workers as u128 * u128::from(per_worker_bytes(peer_budget))
+        });
+        if demand > pool {
+            let share = pool * own / demand;
+            let fit = share / u128::from(per_worker_bytes(mem_budget_bytes));
+            limit = limit.min(usize::try_from(fit).unwrap_or(usize::MAX));
+        }
+    }
+
+    limit.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+    /// Budget that, with the overhead, needs 128 MiB per worker.
+    const BUDGET: u64 = 64 * MIB;
+
+    #[test]
+    fn memory_pressure_only_lowers_the_limit() {
+        let reserve = 512 * MIB;
+        // Room for two more workers beside the busy one.
+        let available = reserve + 2 * 128 * MIB;
+        assert_eq!(
+            pressure_worker_limit(4, 1, BUDGET, available, reserve),
+            Some(3)
+        );
+        assert_eq!(
+            pressure_worker_limit(3, 1, BUDGET, available, reserve),
+            None
+        );
+
+        // 129 MiB short of the reserve: two busy workers' worth.
+        let available = reserve - 129 * MIB;
+        assert_eq!(
+            pressure_worker_limit(4, 3, BUDGET, available, reserve),
+            Some(1)
+        );
+        // One worker always stays.
+        assert_eq!(pressure_worker_limit(1, 1, BUDGET, 0, reserve), None);
+    }
+
+    #[test]
+    fn oversubscribed_hosts_are_shared() {
+        assert_eq!(cooperative_worker_limit(4, BUDGET, &[], 8, None, 0), 4);
+        assert_eq!(
+            cooperative_worker_limit(4, BUDGET, &[(4, BUDGET)], 4, None, 0),
+            2
+        );
+
+        // Plenty of cores, but memory for only four workers in total.
+        let total = Some(512 * MIB + 4 * 128 * MIB);
+        assert_eq!(
+            cooperative_worker_limit(4, BUDGET, &[(4, BUDGET)], 64, total, 512 * MIB),
+            2
+        );
+        assert_eq!(
+            cooperative_worker_limit(1, BUDGET, &[(8, BUDGET)], 1, None, 0),
+            1
+        );
+    }
+}
@@ -2,11 +2,14 @@
 use std::collections::BTreeMap;
 
 use crate::api::PinMode;
+use crate::cgroup::ComputeCgroup;
 
+/// Where worker compute threads run: L3 CPU sets and/or a compute cgroup.
 #[derive(Debug, Clone)]
 pub(crate) struct PinningPlan {
     mode: PinMode,
     l3_domains: Vec<Vec<usize>>,
+    cgroup: Option<ComputeCgroup>,
 }
 
 impl PinningPlan {
@@ -15,24 +18,28 @@ impl PinningPlan {
             PinMode::Off => Self {
                 mode,
                 l3_domains: Vec::new(),
+                cgroup: None,
             },
             PinMode::L3 => {
                 #[cfg(target_os = "linux")]
-                {
-                    let l3_domains = discover_l3_domains_linux();
-                    return Self { mode, l3_domains };
-                }
+                let (mode, l3_domains) = (mode, discover_l3_domains_linux());
                 #[cfg(not(target_os = "linux"))]
-                {
-                    Self {
-                        mode: PinMode::Off,
-                        l3_domains: Vec::new(),
-                    }
+                let (mode, l3_domains) = (PinMode::Off, Vec::new());
+                Self {
+                    mode,
+                    l3_domains,
+                    cgroup: None,
                 }
             }
         }
     }
 
+    /// Also move compute threads into `cgroup`.
+    pub(crate) fn with_cgroup(mut self, cgroup: Option<ComputeCgroup>) -> Self {
+        self.cgroup = cgroup;
+        self
+    }
+
     pub(crate) fn is_effective(&self) -> bool {
         match self.mode {
             PinMode::Off => false,
@@ -48,6 +55,11 @@ impl PinningPlan {
     }
 
     pub(crate) fn pin_current_thread_for_worker(&self, worker_idx: usize) -> Result<(), String> {
+        if let Some(cgroup) = &self.cgroup {
+            cgroup
+                .join_current_thread()
+                .map_err(|e| format!("cgroup: {e:#}"))?;
+        }
         if self.mode == PinMode::Off {
             return Ok(());
         }
@@ -67,6 +79,26 @@ impl PinningPlan {
         }
     }
 
+    /// CPUs the native prover should pin worker `worker_idx`'s computation
+    /// threads to; empty when not pinning.
+    pub(crate) fn cpu_set_for_worker(&self, worker_idx: usize) -> Vec<usize> {
+        if self.mode == PinMode::Off {
+            return Vec::new();
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.l3_cpus_for_worker(worker_idx)
+                .map(<[usize]>::to_vec)
+                .unwrap_or_default()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = worker_idx;
+            Vec::new()
+        }
+    }
+
     #[cfg(target_os = "linux")]
     fn l3_cpus_for_worker(&self, worker_idx: usize) -> Option<&[usize]> {
         let domains = &self.l3_domains;
@@ -145,3 +177,38 @@ fn parse_cpu_list(input: &str) -> Option<Vec<usize>> {
     }
     Some(cpus)
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_lists_are_parsed() {
+        assert_eq!(
+            parse_cpu_list("0-3,8,10-11\n"),
+            Some(vec![0, 1, 2, 3, 8, 10, 11])
+        );
+        assert_eq!(parse_cpu_list(" 5 "), Some(vec![5]));
+        assert_eq!(parse_cpu_list(""), Some(Vec::new()));
+        assert_eq!(parse_cpu_list("3-1"), None);
+        assert_eq!(parse_cpu_list("0-x"), None);
+    }
+
+    #[test]
+    fn workers_are_spread_over_l3_domains() {
+        let plan = PinningPlan {
+            mode: PinMode::L3,
+            l3_domains: vec![vec![0, 1], vec![2, 3]],
+            cgroup: None,
+        };
+        assert!(plan.is_effective());
+        assert_eq!(plan.domain_count(), 2);
+        assert_eq!(plan.cpu_set_for_worker(0), [0, 1]);
+        assert_eq!(plan.cpu_set_for_worker(1), [2, 3]);
+        assert_eq!(plan.cpu_set_for_worker(2), [0, 1]);
+
+        let off = PinningPlan::build(PinMode::Off);
+        assert!(!off.is_effective());
+        assert!(off.cpu_set_for_worker(0).is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+use crate::backend::BackendJobDto;
+
+/// Finished witnesses kept for reuse; older ones are forgotten first.
+const CACHE_CAPACITY: usize = 64;
+
+/// What makes two jobs the same proof.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ProofKey {
+    challenge_b64: String,
+    output_b64: String,
+    input_b64: Option<String>,
+    iterations: u64,
+    discriminant_bits: usize,
+}
+
+impl ProofKey {
+    pub(crate) fn of(job: &BackendJobDto, discriminant_bits: usize) -> Self {
+        Self {
+            challenge_b64: job.challenge_b64.clone(),
+            output_b64: job.output_b64.clone(),
+            input_b64: job.input_b64.clone(),
+            iterations: job.number_of_iterations,
+            discriminant_bits,
+        }
+    }
+}
+
+/// Witnesses computed this session, keyed by proof.
+///
+/// The backend occasionally leases the same (challenge, iterations) pair under
+/// two job ids. The first worker to [`claim`](ProofCache::claim) a key computes
+/// it; a worker holding a duplicate waits for that witness instead of squaring
+/// again, and computes it itself only if the first worker gives up.
+#[derive(Debug, Default)]
+pub(crate) struct ProofCache {
+    state: Mutex<CacheState>,
+    changed: Notify,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    slots: HashMap<ProofKey, Slot>,
+    /// Keys of finished witnesses, oldest first.
+    finished: VecDeque<ProofKey>,
+}
+
+#[derive(Debug)]
+enum Slot {
+    Computing,
+    Finished(Vec<u8>),
+}
+
+/// Result of [`ProofCache::claim`].
+#[derive(Debug)]
+pub(crate) enum Claim<'a> {
+    /// Nobody has this proof: compute it and hand it to the lease.
+    Compute(ProofLease<'a>),
+    /// Another job already produced this witness.
+    Reuse(Vec<u8>),
+}
+
+impl ProofCache {
+    /// Claim `key` for computing, or wait for the worker already computing it.
+    pub(crate) async fn claim(&self, key: ProofKey) -> Claim<'_> {
+        loop {
+            let changed = self.changed.notified();
+            {
+                let mut state = self.lock();
+                match state.slots.get(&key) {
+                    Some(Slot::Finished(witness)) => return Claim::Reuse(witness.clone()),
+                    Some(Slot::Computing) => {}
+                    None => {
+                        state.slots.insert(key.clone(), Slot::Computing);
+                        return Claim::Compute(ProofLease {
+                            cache: self,
+                            key: Some(key),
+                        });
+                    }
+                }
+            }
+            changed.await;
+        }
+    }
+
+    /// Record a verified witness for `key`, e.g. one proven as part of a group.
+    pub(crate) fn insert(&self, key: ProofKey, witness: Vec<u8>) {
+        let mut state = self.lock();
+        if matches!(state.slots.get(&key), Some(Slot::Finished(_))) {
+            return;
+        }
+        state.slots.insert(key.clone(), Slot::Finished(witness));
+        state.finished.push_back(key);
+        while state.finished.len() > CACHE_CAPACITY {
+            if let Some(oldest) = state.finished.pop_front() {
+                state.slots.remove(&oldest);
+            }
+        }
+        drop(state);
+        self.changed.notify_waiters();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// The right to compute a claimed proof. Dropping it without a witness releases
+/// the claim so a waiting duplicate computes the proof itself.
+#[derive(Debug)]
+pub(crate) struct ProofLease<'a> {
+    cache: &'a ProofCache,
+    key: Option<ProofKey>,
+}
+
+impl ProofLease<'_> {
+    /// Publish the witness to jobs waiting on the same proof.
+    pub(crate) fn finish(mut self, witness: Vec<u8>) {
+        if let Some(key) = self.key.take() {
+            self.cache.insert(key, witness);
+        }
+    }
+}
+
+impl Drop for ProofLease<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.cache.lock().slots.remove(&key);
+            self.cache.changed.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(iterations: u64) -> ProofKey {
+        ProofKey {
+            challenge_b64: "challenge".to_string(),
+            output_b64: "output".to_string(),
+            input_b64: None,
+            iterations,
+            discriminant_bits: 1024,
+        }
+    }
+
+    #[tokio::test]
+    async fn duplicates_wait_for_the_first_witness() {
+        let cache = ProofCache::default();
+        let Claim::Compute(lease) = cache.claim(key(1)).await else {
+            panic!("first claim should compute");
+        };
+
+        let (duplicate, ()) = tokio::join!(cache.claim(key(1)), async {
+            tokio::task::yield_now().await;
+            lease.finish(vec![7]);
+        });
+        assert!(matches!(duplicate, Claim::Reuse(witness) if witness == [7]));
+    }
+
+    #[tokio::test]
+    async fn abandoned_claims_pass_to_a_duplicate() {
+        let cache = ProofCache::default();
+        let Claim::Compute(lease) = cache.claim(key(1)).await else {
+            panic!("first claim should compute");
+        };
+
+        let (duplicate, ()) = tokio::join!(cache.claim(key(1)), async {
+            tokio::task::yield_now().await;
+            drop(lease);
+        });
+        assert!(matches!(duplicate, Claim::Compute(_)));
+    }
+
+    #[tokio::test]
+    async fn oldest_witnesses_are_forgotten() {
+        let cache = ProofCache::default();
+        for iterations in 0..=CACHE_CAPACITY as u64 {
+            cache.insert(key(iterations), vec![1]);
+        }
+        assert!(matches!(cache.claim(key(0)).await, Claim::Compute(_)));
+        assert!(matches!(cache.claim(key(1)).await, Claim::Reuse(_)));
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+/// Submissions waiting to be (re)sent, ordered by lease expiry.
+///
+/// A first attempt goes straight out while nobody is waiting. Once a submission
+/// fails it queues here, and queued submissions go out one at a time, nearest
+/// lease expiry first: after a backend outage the work closest to losing its
+/// lease is submitted before work that can still wait.
+#[derive(Debug, Default)]
+pub(crate) struct SubmitQueue {
+    state: Mutex<QueueState>,
+    turn_changed: Notify,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    /// `(lease expiry, ticket)` of every waiting submission.
+    waiting: BTreeSet<(i64, u64)>,
+    next_ticket: u64,
+    /// Whether the most recent attempt failed to reach the backend.
+    backend_failing: bool,
+}
+
+impl SubmitQueue {
+    /// Whether no submission is waiting for its turn.
+    pub(crate) fn is_idle(&self) -> bool {
+        self.lock().waiting.is_empty()
+    }
+
+    /// Whether the most recent attempt failed to reach the backend, in which
+    /// case the next queued attempt should back off first.
+    pub(crate) fn backend_failing(&self) -> bool {
+        self.lock().backend_failing
+    }
+
+    /// Record whether an attempt reached the backend.
+    pub(crate) fn record_attempt(&self, reached_backend: bool) {
+        self.lock().backend_failing = !reached_backend;
+    }
+
+    /// Wait until no queued submission has an earlier lease expiry than
+    /// `expires_at`. The turn lasts until the returned guard is dropped.
+    pub(crate) async fn turn(&self, expires_at: i64) -> SubmitTurn<'_> {
+        let key = {
+            let mut state = self.lock();
+            let key = (expires_at, state.next_ticket);
+            state.next_ticket = state.next_ticket.wrapping_add(1);
+            state.waiting.insert(key);
+            key
+        };
+        let turn = SubmitTurn { queue: self, key };
+        loop {
+            let changed = self.turn_changed.notified();
+            if self.lock().waiting.first() == Some(&key) {
+                return turn;
+            }
+            changed.await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// A submission's place in the [`SubmitQueue`]; leaving it passes the turn on.
+#[derive(Debug)]
+pub(crate) struct SubmitTurn<'a> {
+    queue: &'a SubmitQueue,
+    key: (i64, u64),
+}
+
+impl Drop for SubmitTurn<'_> {
+    fn drop(&mut self) {
+        self.queue.lock().waiting.remove(&self.key);
+        self.queue.turn_changed.notify_waiters();
+    }
+}
@@ -0,0 +1,177 @@
+//! Plain-language descriptions of engine state for screen readers and other
+//! text-only frontends.
+//!
+//! Sentences avoid symbols and abbreviations (no `%`, `it/s` or progress bars) so
+//! they read well aloud.
+
+use crate::api::{
+    BudgetStatus, JobOutcome, JobSummary, StatusSnapshot, WorkerSnapshot, WorkerStage,
+};
+
+fn field_vdf_name(field_vdf: i32) -> String {
+    match field_vdf {
+        1 => "challenge chain end of slot".to_string(),
+        2 => "infused challenge chain end of slot".to_string(),
+        3 => "challenge chain signage point".to_string(),
+        4 => "challenge chain infusion point".to_string(),
+        other => format!("unknown field {other}"),
+    }
+}
+
+fn plural(count: usize, singular: &str, plural: &str) -> String {
+    if count == 1 {
+        format!("{count} {singular}")
+    } else {
+        format!("{count} {plural}")
+    }
+}
+
+/// Speak large rates as "1.2 million" rather than a long digit string.
+fn spoken_number(value: u64) -> String {
+    match value {
+        0..1_000 => value.to_string(),
+        1_000..1_000_000 => format!("{:.1} thousand", value as f64 / 1e3),
+        1_000_000..1_000_000_000 => format!("{:.1} million", value as f64 / 1e6),
+        _ => format!("{:.1} billion", value as f64 / 1e9),
+    }
+}
+
+fn hours(ms: u64) -> String {
+    format!("{:.1}", ms as f64 / 3_600_000.0)
+}
+
+/// Progress towards the daily budget, e.g. "Today 3 of 10 proofs."
+fn budget_sentence(budget: &BudgetStatus) -> String {
+    if budget.resumes_at.is_some() {
+        return "Daily budget reached, resuming tomorrow.".to_string();
+    }
+    let mut parts = Vec::new();
+    if let Some(max) = budget.max_proofs {
+        parts.push(format!("{} of {max} proofs", budget.proofs_today));
+    }
+    if let Some(max) = budget.max_compute_ms {
+        parts.push(format!(
+            "{} of {} compute hours",
+            hours(budget.compute_ms_today),
+            hours(max)
+        ));
+    }
+    if parts.is_empty() {
+        return String::new();
+    }
+    format!("Today {}.", parts.join(" and "))
+}
+
+impl JobSummary {
+    /// One-sentence description of the job, e.g. "height 123, challenge chain end of slot".
+    pub fn summary_text(&self) -> String {
+        let mut text = format!("height {}, {}", self.height, field_vdf_name(self.field_vdf));
+        if let Some(proofs) = self.group_proofs.filter(|n| *n > 1) {
+            text.push_str(&format!(", group of {proofs} proofs"));
+        }
+        text
+    }
+}
+
+impl WorkerSnapshot {
+    /// Description of what this worker is doing, e.g.
+    /// "Worker 1 computing height 123, challenge chain end of slot: 45 percent, 1.2 million iterations per second."
+    pub fn summary_text(&self) -> String {
+        let name = format!("Worker {}", self.worker_idx + 1);
+        let Some(job) = &self.job else {
+            if self.paused {
+                return format!("{name} paused.");
+            }
+            return format!("{name} idle.");
+        };
+        match self.stage {
+            WorkerStage::Idle => format!("{name} idle."),
+            WorkerStage::Finalizing => format!("{name} finalizing {}.", job.summary_text()),
+            WorkerStage::Submitting => format!("{name} submitting {}.", job.summary_text()),
+            WorkerStage::Computing => {
+                let percent = (self.iters_done.min(self.iters_total) * 100)
+                    .checked_div(self.iters_total)
+                    .unwrap_or(0);
+                let mut text =
+                    format!("{name} computing {}: {percent} percent", job.summary_text());
+                if self.iters_per_sec > 0 {
+                    text.push_str(&format!(
+                        ", {} iterations per second",
+                        spoken_number(self.iters_per_sec)
+                    ));
+                }
+                text.push('.');
+                text
+            }
+        }
+    }
+}
+
+impl JobOutcome {
+    /// Description of how the job ended, e.g. "Job at height 123, challenge chain end of slot: accepted."
+    pub fn summary_text(&self) -> String {
+        let job = self.job.summary_text();
+        let result = match (&self.error, &self.submit_reason) {
+            (Some(error), _) => {
+                let error = error.trim();
+                let error = error
+                    .strip_prefix("Error (")
+                    .and_then(|e| e.strip_suffix(')'))
+                    .unwrap_or(error);
+                format!("failed, {error}")
+            }
+            (None, Some(reason)) => reason.replace('_', " "),
+            (None, None) => "finished".to_string(),
+        };
+        let mut text = format!("Job at {job}: {result}");
+        if self.output_mismatch && self.error.is_none() {
+            text.push_str(", output mismatch");
+        }
+        text.push('.');
+        text
+    }
+}
+
+impl StatusSnapshot {
+    /// Multi-line description of the engine: an overview sentence, then one line
+    /// per worker, then the most recent job.
+    pub fn summary_text(&self) -> String {
+        let busy = self
+            .workers
+            .iter()
+            .filter(|w| w.stage != WorkerStage::Idle)
+            .count();
+        let mut overview = if self.stop_requested {
+            "Stopping after current work.".to_string()
+        } else if self.paused {
+            "Paused, not taking new work.".to_string()
+        } else {
+            "Running.".to_string()
+        };
+        overview.push_str(&format!(
+            " {busy} of {} busy.",
+            plural(self.workers.len(), "worker", "workers")
+        ));
+        if let Some(maintenance) = &self.backend_maintenance {
+            overview.push_str(" Backend under maintenance");
+            if !maintenance.message.is_empty() {
+                overview.push_str(&format!(": {}", maintenance.message));
+            }
+            overview.push('.');
+        }
+        if let Some(budget) = &self.budget {
+            let sentence = budget_sentence(budget);
+            if !sentence.is_empty() {
+                overview.push(' ');
+                overview.push_str(&sentence);
+            }
+        }
+
+        let mut lines = vec![overview];
+        lines.extend(self.workers.iter().map(WorkerSnapshot::summary_text));
+        if let Some(last) = self.recent_jobs.last() {
+            lines.push(format!("Last job: {}", last.summary_text()));
+        }
+        lines.join("\n")
+    }
+}
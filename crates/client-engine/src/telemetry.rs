@@ -0,0 +1,123 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use reqwest::Url;
+use serde::Serialize;
+
+use crate::api::{EngineEvent, JobOutcome, TelemetryConfig};
+use crate::events::EventSender;
+
+#[derive(Debug, Clone, Serialize)]
+struct TelemetryReport {
+    version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    logical_cpus: usize,
+    parallel: usize,
+    use_groups: bool,
+    uptime_secs: u64,
+    jobs_finished: u64,
+    jobs_accepted: u64,
+    acceptance_rate: f64,
+    iters_per_sec: u64,
+}
+
+pub(crate) struct TelemetryReporter {
+    endpoint: Url,
+    interval: tokio::time::Interval,
+    started_at: Instant,
+    parallel: usize,
+    use_groups: bool,
+    jobs_finished: u64,
+    jobs_accepted: u64,
+    warned_failure: Arc<AtomicBool>,
+}
+
+impl TelemetryReporter {
+    pub(crate) fn new(cfg: &TelemetryConfig, parallel: usize, use_groups: bool) -> Self {
+        let period = if cfg.interval.is_zero() {
+            TelemetryConfig::DEFAULT_INTERVAL
+        } else {
+            cfg.interval
+        };
+        // Skip the immediate first tick: there is nothing to report at startup.
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        Self {
+            endpoint: cfg.endpoint.clone(),
+            interval,
+            started_at: Instant::now(),
+            parallel,
+            use_groups,
+            jobs_finished: 0,
+            jobs_accepted: 0,
+            warned_failure: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub(crate) async fn tick(&mut self) {
+        self.interval.tick().await;
+    }
+
+    pub(crate) fn record(&mut self, outcome: &JobOutcome) {
+        self.jobs_finished = self.jobs_finished.saturating_add(1);
+        if outcome.error.is_none() && outcome.submit_reason.as_deref() == Some("accepted") {
+            self.jobs_accepted = self.jobs_accepted.saturating_add(1);
+        }
+    }
+
+    fn report(&self, iters_per_sec: u64) -> TelemetryReport {
+        let acceptance_rate = if self.jobs_finished == 0 {
+            0.0
+        } else {
+            self.jobs_accepted as f64 / self.jobs_finished as f64
+        };
+        TelemetryReport {
+            version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            logical_cpus: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            parallel: self.parallel,
+            use_groups: self.use_groups,
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            jobs_finished: self.jobs_finished,
+            jobs_accepted: self.jobs_accepted,
+            acceptance_rate,
+            iters_per_sec,
+        }
+    }
+
+    /// Post a report in the background. Failures are reported once as a warning.
+    pub(crate) fn spawn_post(
+        &self,
+        http: &reqwest::Client,
+        event_tx: &EventSender,
+        iters_per_sec: u64,
+    ) {
+        let http = http.clone();
+        let event_tx = event_tx.clone();
+        let endpoint = self.endpoint.clone();
+        let report = self.report(iters_per_sec);
+        let warned = self.warned_failure.clone();
+
+        tokio::spawn(async move {
+            let res = http
+                .post(endpoint)
+                .json(&report)
+                .send()
+                .await
+                .and_then(|res| res.error_for_status());
+            if let Err(err) = res
+                && !warned.swap(true, Ordering::Relaxed)
+            {
+                let _ = event_tx.send(EngineEvent::Warning {
+                    message: format!("warning: telemetry report failed: {err:#}"),
+                });
+            }
+        });
+    }
+}
@@ -1,5 +1,6 @@
-use std::sync::Arc;
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use anyhow::Context;
@@ -10,27 +11,118 @@ use reqwest::Url;
 use tokio::sync::mpsc;
 
 use bbr_client_chiavdf_fast::{
-    ChiavdfBatchJob, prove_one_weso_fast_streaming_getblock_opt_batch,
-    prove_one_weso_fast_streaming_getblock_opt_batch_with_progress,
+    ChiavdfBatchJob, ChiavdfFastError, ClassgroupElement, ProgressInfo, ProgressPhase,
+    ProveOptions, ProveReport, ProverBuilder, StreamingParameters, implementation_name,
+    run_with_progress_info,
 };
+use bbr_client_core::signing::SubmissionSigner;
 use bbr_client_core::submitter::SubmitterConfig;
 
-use crate::api::{JobOutcome, JobSummary, WorkerStage};
-use crate::backend::{BackendError, BackendJobDto, SubmitResponse, submit_job};
+use crate::api::{
+    EngineError, JobOutcome, JobSummary, MismatchCheck, SubmissionDiagnostics, WorkerStage,
+};
+use crate::archive::SubmissionArchive;
+use crate::backend::{
+    BackendError, BackendJobDto, ResponseMeta, SubmitError, SubmitResponse, submit_job,
+};
+use crate::fault::Faults;
+use crate::lease::LeaseDeadline;
+use crate::network::NetworkStats;
 use crate::pinning::PinningPlan;
+use crate::proof_cache::{Claim, ProofCache, ProofKey};
+use crate::submit_queue::SubmitQueue;
 
-const DISCRIMINANT_BITS: usize = 1024;
-
-fn default_classgroup_element() -> [u8; 100] {
-    let mut el = [0u8; 100];
-    el[0] = 0x08;
-    el
-}
+/// Squarings between native progress checks; the callbacks themselves are
+/// throttled by time (`progress_min_interval`).
+const PROGRESS_CHECK_ITERS: u64 = 1 << 12;
 
 #[derive(Debug)]
 struct SubmitFailure {
     message: String,
     drop_inflight: bool,
+    /// The lease expired while the submission was waiting to be sent.
+    lease_expired: bool,
+    retries: u32,
+    /// The backend's reply, when it rejected the submission.
+    response: Option<ResponseMeta>,
+}
+
+/// Streaming parameters and squaring vs finalize split of a proof computation.
+///
+/// The split is only populated when native streaming stats are enabled.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ComputeBreakdown {
+    parameters: Option<StreamingParameters>,
+    squaring_ms: Option<u64>,
+    finalize_ms: Option<u64>,
+}
+
+impl ComputeBreakdown {
+    /// Split `prove_elapsed` using the streaming stats reported by the proof.
+    fn from_report<T>(prove_elapsed: Duration, report: &ProveReport<T>) -> Self {
+        let parameters = Some(report.parameters);
+        match report.stats {
+            Some(stats) => Self {
+                parameters,
+                squaring_ms: Some(
+                    prove_elapsed
+                        .saturating_sub(stats.finalize_time)
+                        .as_millis() as u64,
+                ),
+                finalize_ms: Some(stats.finalize_time.as_millis() as u64),
+            },
+            None => Self {
+                parameters,
+                ..Self::default()
+            },
+        }
+    }
+}
+
+/// Environment snapshot for a submission the backend answered with anything but
+/// `accepted` or `already_compact`; `None` when it was accepted or the backend
+/// never answered.
+fn rejection_diagnostics(
+    reason: Option<&str>,
+    response: Option<&ResponseMeta>,
+    breakdown: &ComputeBreakdown,
+    compute_ms: u64,
+    submit_ms: u64,
+    submit_retries: u32,
+) -> Option<SubmissionDiagnostics> {
+    if matches!(reason, Some("accepted" | "already_compact")) {
+        return None;
+    }
+    let response = response?;
+    Some(SubmissionDiagnostics {
+        client_version: env!("CARGO_PKG_VERSION").to_string(),
+        prover: implementation_name().to_string(),
+        streaming_k: breakdown.parameters.map(|parameters| parameters.k),
+        streaming_l: breakdown.parameters.map(|parameters| parameters.l),
+        compute_ms,
+        squaring_ms: breakdown.squaring_ms,
+        finalize_ms: breakdown.finalize_ms,
+        submit_ms,
+        submit_retries,
+        http_status: response.status,
+        response_headers: response.headers.clone(),
+    })
+}
+
+/// Jobs of a group that two workers may prove at once (see
+/// `EngineConfig::split_groups`): the first worker to reach a job's outcome
+/// reports it, and the other drops its copy.
+#[derive(Debug, Default)]
+pub(crate) struct SharedGroupJobs(Mutex<HashSet<u64>>);
+
+impl SharedGroupJobs {
+    /// Whether this worker reports `job_id` (false once the other worker did).
+    fn take(&self, job_id: u64) -> bool {
+        match self.0.lock() {
+            Ok(mut taken) => taken.insert(job_id),
+            Err(poisoned) => poisoned.into_inner().insert(job_id),
+        }
+    }
 }
 
 pub(crate) enum WorkerCommand {
@@ -38,18 +130,25 @@ pub(crate) enum WorkerCommand {
         worker_idx: usize,
         backend_url: Url,
         lease_id: String,
-        lease_expires_at: i64,
-        progress_steps: u64,
+        lease_deadline: LeaseDeadline,
+        leased_at: Instant,
+        progress_min_interval: Duration,
+        strict_output: bool,
+        discriminant_bits: usize,
         job: BackendJobDto,
     },
     Group {
         worker_idx: usize,
         backend_url: Url,
         lease_id: String,
-        lease_expires_at: i64,
-        progress_steps: u64,
+        lease_deadline: LeaseDeadline,
+        leased_at: Instant,
+        progress_min_interval: Duration,
+        strict_output: bool,
+        discriminant_bits: usize,
         group_id: u64,
         jobs: Vec<BackendJobDto>,
+        shared_jobs: Arc<SharedGroupJobs>,
     },
     Stop,
 }
@@ -59,6 +158,9 @@ pub(crate) enum WorkerInternalEvent {
         worker_idx: usize,
         stage: WorkerStage,
     },
+    FinalizeStarted {
+        worker_idx: usize,
+    },
     WorkFinished {
         worker_idx: usize,
         outcomes: Vec<JobOutcome>,
@@ -67,7 +169,7 @@ pub(crate) enum WorkerInternalEvent {
         message: String,
     },
     Error {
-        message: String,
+        error: EngineError,
     },
 }
 
@@ -80,17 +182,29 @@ pub(crate) async fn run_worker_task(
     submitter: Arc<tokio::sync::RwLock<SubmitterConfig>>,
     warned_invalid_reward_address: Arc<AtomicBool>,
     pinning: Arc<PinningPlan>,
+    faults: Faults,
+    net: Arc<NetworkStats>,
+    submit_queue: Arc<SubmitQueue>,
+    proof_cache: Arc<ProofCache>,
+    archive: Option<Arc<SubmissionArchive>>,
+    signer: Option<SubmissionSigner>,
 ) {
     let warned_pinning_failed = Arc::new(AtomicBool::new(false));
     while let Some(cmd) = rx.recv().await {
+        if !matches!(cmd, WorkerCommand::Stop) {
+            faults.on_work_received();
+        }
         match cmd {
             WorkerCommand::Stop => break,
             WorkerCommand::Job {
                 worker_idx,
                 backend_url,
                 lease_id,
-                lease_expires_at,
-                progress_steps,
+                lease_deadline,
+                leased_at,
+                progress_min_interval,
+                strict_output,
+                discriminant_bits,
                 job,
             } => {
                 let outcome = run_job(
@@ -102,10 +216,19 @@ pub(crate) async fn run_worker_task(
                     warned_invalid_reward_address.clone(),
                     pinning.clone(),
                     warned_pinning_failed.clone(),
+                    &faults,
+                    &net,
+                    &submit_queue,
+                    &proof_cache,
+                    archive.as_ref(),
+                    signer.as_ref(),
                     backend_url,
                     lease_id,
-                    lease_expires_at,
-                    progress_steps,
+                    lease_deadline,
+                    leased_at,
+                    progress_min_interval,
+                    strict_output,
+                    discriminant_bits,
                     job,
                 )
                 .await;
@@ -118,10 +241,14 @@ pub(crate) async fn run_worker_task(
                 worker_idx,
                 backend_url,
                 lease_id,
-                lease_expires_at,
-                progress_steps,
+                lease_deadline,
+                leased_at,
+                progress_min_interval,
+                strict_output,
+                discriminant_bits,
                 group_id,
                 jobs,
+                shared_jobs,
             } => {
                 let outcomes = run_group(
                     worker_idx,
@@ -132,12 +259,22 @@ pub(crate) async fn run_worker_task(
                     warned_invalid_reward_address.clone(),
                     pinning.clone(),
                     warned_pinning_failed.clone(),
+                    &faults,
+                    &net,
+                    &submit_queue,
+                    &proof_cache,
+                    archive.as_ref(),
+                    signer.as_ref(),
                     backend_url,
                     lease_id,
-                    lease_expires_at,
-                    progress_steps,
+                    lease_deadline,
+                    leased_at,
+                    progress_min_interval,
+                    strict_output,
+                    discriminant_bits,
                     group_id,
                     jobs,
+                    &shared_jobs,
                 )
                 .await;
                 let _ = internal_tx.send(WorkerInternalEvent::WorkFinished {
@@ -158,13 +295,23 @@ async fn run_job(
     warned_invalid_reward_address: Arc<AtomicBool>,
     pinning: Arc<PinningPlan>,
     warned_pinning_failed: Arc<AtomicBool>,
+    faults: &Faults,
+    net: &NetworkStats,
+    submit_queue: &SubmitQueue,
+    proof_cache: &ProofCache,
+    archive: Option<&Arc<SubmissionArchive>>,
+    signer: Option<&SubmissionSigner>,
     backend_url: Url,
     lease_id: String,
-    lease_expires_at: i64,
-    progress_steps: u64,
+    lease_deadline: LeaseDeadline,
+    leased_at: Instant,
+    progress_min_interval: Duration,
+    strict_output: bool,
+    discriminant_bits: usize,
     job: BackendJobDto,
 ) -> JobOutcome {
     let started_at = Instant::now();
+    let queue_wait_ms = started_at.saturating_duration_since(leased_at).as_millis() as u64;
 
     let job_summary = JobSummary {
         job_id: job.job_id,
@@ -174,6 +321,34 @@ async fn run_job(
         number_of_iterations: job.number_of_iterations,
     };
 
+    let discriminant_bits = job.discriminant_bits(discriminant_bits);
+    if let Err(reason) = job.validate(discriminant_bits) {
+        return JobOutcome {
+            worker_idx,
+            job: job_summary,
+            output_mismatch: false,
+            submit_reason: None,
+            submit_detail: None,
+            drop_inflight: true,
+            lease_expired: false,
+            error: Some(format!("Error (invalid job: {reason})")),
+            compute_ms: 0,
+            submit_ms: 0,
+            total_ms: started_at.elapsed().as_millis() as u64,
+            queue_wait_ms,
+            squaring_ms: None,
+            finalize_ms: None,
+            submit_retries: 0,
+            mismatch_check: None,
+            diagnostics: None,
+            reused_witness: false,
+            lease_headroom_secs: None,
+        };
+    }
+
+    // `validate` already checked `input_b64`.
+    let x = job.initial_element().unwrap_or_default();
+
     let output = match B64.decode(job.output_b64.as_bytes()) {
         Ok(v) => v,
         Err(err) => {
@@ -184,10 +359,19 @@ async fn run_job(
                 submit_reason: None,
                 submit_detail: None,
                 drop_inflight: false,
+                lease_expired: false,
                 error: Some(format!("Error (bad output_b64: {err:#})")),
                 compute_ms: 0,
                 submit_ms: 0,
                 total_ms: started_at.elapsed().as_millis() as u64,
+                queue_wait_ms,
+                squaring_ms: None,
+                finalize_ms: None,
+                submit_retries: 0,
+                mismatch_check: None,
+                diagnostics: None,
+                reused_witness: false,
+                lease_headroom_secs: None,
             };
         }
     };
@@ -201,10 +385,19 @@ async fn run_job(
                 submit_reason: None,
                 submit_detail: None,
                 drop_inflight: false,
+                lease_expired: false,
                 error: Some(format!("Error (bad challenge_b64: {err:#})")),
                 compute_ms: 0,
                 submit_ms: 0,
                 total_ms: started_at.elapsed().as_millis() as u64,
+                queue_wait_ms,
+                squaring_ms: None,
+                finalize_ms: None,
+                submit_retries: 0,
+                mismatch_check: None,
+                diagnostics: None,
+                reused_witness: false,
+                lease_headroom_secs: None,
             };
         }
     };
@@ -214,20 +407,35 @@ async fn run_job(
         stage: WorkerStage::Computing,
     });
 
+    // A duplicate of a proof another worker is computing waits for its witness.
+    let claim = proof_cache
+        .claim(ProofKey::of(&job, discriminant_bits))
+        .await;
     let compute_started_at = Instant::now();
-    let (witness, output_mismatch) = match compute_witness(
-        worker_idx,
-        internal_tx,
-        progress.clone(),
-        pinning.clone(),
-        warned_pinning_failed.clone(),
-        job.number_of_iterations,
-        progress_steps,
-        challenge,
-        output.clone(),
-    )
-    .await
-    {
+    let (proof_lease, computed) = match claim {
+        Claim::Reuse(witness) => (None, Ok((witness, None, ComputeBreakdown::default()))),
+        Claim::Compute(lease) => {
+            let computed = compute_witness(
+                worker_idx,
+                internal_tx,
+                progress.clone(),
+                pinning.clone(),
+                warned_pinning_failed.clone(),
+                faults.clone(),
+                job.number_of_iterations,
+                &lease_deadline,
+                progress_min_interval,
+                discriminant_bits,
+                challenge.clone(),
+                x,
+                output.clone(),
+            )
+            .await;
+            (Some(lease), computed)
+        }
+    };
+    let reused_witness = proof_lease.is_none();
+    let (mut witness, mut mismatched_y, breakdown) = match computed {
         Ok(v) => v,
         Err(status) => {
             return JobOutcome {
@@ -236,15 +444,79 @@ async fn run_job(
                 output_mismatch: false,
                 submit_reason: None,
                 submit_detail: None,
-                drop_inflight: false,
+                drop_inflight: true,
+                lease_expired: false,
                 error: Some(status),
                 compute_ms: compute_started_at.elapsed().as_millis() as u64,
                 submit_ms: 0,
                 total_ms: started_at.elapsed().as_millis() as u64,
+                queue_wait_ms,
+                squaring_ms: None,
+                finalize_ms: None,
+                submit_retries: 0,
+                mismatch_check: None,
+                diagnostics: None,
+                reused_witness: false,
+                lease_headroom_secs: None,
             };
         }
     };
+    let mut mismatch_check = None;
+    if let Some(y) = &mismatched_y {
+        let mismatch = OutputMismatch {
+            job: &job,
+            discriminant_bits,
+            challenge: &challenge,
+            expected_y: &output,
+            streaming_y: y,
+        };
+        let (check, recomputed) =
+            recheck_mismatch(worker_idx, internal_tx, &mismatch, &lease_deadline).await;
+        mismatch_check = Some(check);
+        if let Some(recomputed) = recomputed {
+            witness = recomputed;
+            mismatched_y = None;
+        }
+    }
+    if let Some(lease) = proof_lease
+        && mismatched_y.is_none()
+    {
+        lease.finish(witness.clone());
+    }
     let compute_ms = compute_started_at.elapsed().as_millis() as u64;
+    let output_mismatch = mismatched_y.is_some();
+
+    if strict_output && let Some(y) = &mismatched_y {
+        return JobOutcome {
+            worker_idx,
+            error: Some(report_output_mismatch(
+                worker_idx,
+                internal_tx,
+                &job,
+                discriminant_bits,
+                &challenge,
+                &output,
+                y,
+            )),
+            job: job_summary,
+            output_mismatch,
+            submit_reason: None,
+            submit_detail: None,
+            drop_inflight: true,
+            lease_expired: false,
+            compute_ms,
+            submit_ms: 0,
+            total_ms: started_at.elapsed().as_millis() as u64,
+            queue_wait_ms,
+            squaring_ms: breakdown.squaring_ms,
+            finalize_ms: breakdown.finalize_ms,
+            submit_retries: 0,
+            mismatch_check,
+            diagnostics: None,
+            reused_witness,
+            lease_headroom_secs: None,
+        };
+    }
 
     let _ = internal_tx.send(WorkerInternalEvent::StageChanged {
         worker_idx,
@@ -255,42 +527,85 @@ async fn run_job(
     let submit_res = submit_witness(
         http,
         submitter,
+        faults,
+        net,
+        submit_queue,
+        archive,
+        signer,
         warned_invalid_reward_address,
         internal_tx,
         &backend_url,
         job.job_id,
         &lease_id,
-        lease_expires_at,
+        &lease_deadline,
         &witness,
     )
     .await;
     let submit_ms = submit_started_at.elapsed().as_millis() as u64;
 
     match submit_res {
-        Ok(res) => JobOutcome {
-            worker_idx,
-            job: job_summary,
-            output_mismatch,
-            submit_reason: Some(res.reason),
-            submit_detail: Some(res.detail),
-            drop_inflight: false,
-            error: None,
-            compute_ms,
-            submit_ms,
-            total_ms: started_at.elapsed().as_millis() as u64,
-        },
-        Err(err) => JobOutcome {
-            worker_idx,
-            job: job_summary,
-            output_mismatch,
-            submit_reason: None,
-            submit_detail: None,
-            drop_inflight: err.drop_inflight,
-            error: Some(err.message),
-            compute_ms,
-            submit_ms,
-            total_ms: started_at.elapsed().as_millis() as u64,
-        },
+        Ok((res, retries)) => {
+            let diagnostics = rejection_diagnostics(
+                Some(&res.reason),
+                Some(&res.response),
+                &breakdown,
+                compute_ms,
+                submit_ms,
+                retries,
+            );
+            JobOutcome {
+                worker_idx,
+                job: job_summary,
+                output_mismatch,
+                submit_reason: Some(res.reason),
+                submit_detail: Some(res.detail),
+                drop_inflight: false,
+                lease_expired: false,
+                error: None,
+                compute_ms,
+                submit_ms,
+                total_ms: started_at.elapsed().as_millis() as u64,
+                queue_wait_ms,
+                squaring_ms: breakdown.squaring_ms,
+                finalize_ms: breakdown.finalize_ms,
+                submit_retries: retries,
+                mismatch_check,
+                diagnostics,
+                reused_witness,
+                lease_headroom_secs: Some(remaining_lease_secs(&lease_deadline)),
+            }
+        }
+        Err(err) => {
+            let diagnostics = rejection_diagnostics(
+                None,
+                err.response.as_ref(),
+                &breakdown,
+                compute_ms,
+                submit_ms,
+                err.retries,
+            );
+            JobOutcome {
+                worker_idx,
+                job: job_summary,
+                output_mismatch,
+                submit_reason: None,
+                submit_detail: None,
+                drop_inflight: err.drop_inflight,
+                lease_expired: err.lease_expired,
+                error: Some(err.message),
+                compute_ms,
+                submit_ms,
+                total_ms: started_at.elapsed().as_millis() as u64,
+                queue_wait_ms,
+                squaring_ms: breakdown.squaring_ms,
+                finalize_ms: breakdown.finalize_ms,
+                submit_retries: err.retries,
+                mismatch_check,
+                diagnostics,
+                reused_witness,
+                lease_headroom_secs: None,
+            }
+        }
     }
 }
 
@@ -303,22 +618,34 @@ async fn run_group(
     warned_invalid_reward_address: Arc<AtomicBool>,
     pinning: Arc<PinningPlan>,
     warned_pinning_failed: Arc<AtomicBool>,
+    faults: &Faults,
+    net: &NetworkStats,
+    submit_queue: &SubmitQueue,
+    proof_cache: &ProofCache,
+    archive: Option<&Arc<SubmissionArchive>>,
+    signer: Option<&SubmissionSigner>,
     backend_url: Url,
     lease_id: String,
-    lease_expires_at: i64,
-    progress_steps: u64,
+    lease_deadline: LeaseDeadline,
+    leased_at: Instant,
+    progress_min_interval: Duration,
+    strict_output: bool,
+    discriminant_bits: usize,
     group_id: u64,
     jobs: Vec<BackendJobDto>,
+    shared_jobs: &SharedGroupJobs,
 ) -> Vec<JobOutcome> {
     let started_at = Instant::now();
+    let queue_wait_ms = started_at.saturating_duration_since(leased_at).as_millis() as u64;
     if jobs.is_empty() {
         return Vec::new();
     }
 
     let now = Utc::now().timestamp();
-    if now >= lease_expires_at {
+    if now >= lease_deadline.expires_at() {
         return jobs
             .into_iter()
+            .filter(|job| shared_jobs.take(job.job_id))
             .map(|job| JobOutcome {
                 worker_idx,
                 job: JobSummary {
@@ -332,10 +659,19 @@ async fn run_group(
                 submit_reason: None,
                 submit_detail: None,
                 drop_inflight: true,
+                lease_expired: false,
                 error: Some("Error (lease expired)".to_string()),
                 compute_ms: 0,
                 submit_ms: 0,
                 total_ms: started_at.elapsed().as_millis() as u64,
+                queue_wait_ms,
+                squaring_ms: None,
+                finalize_ms: None,
+                submit_retries: 0,
+                mismatch_check: None,
+                diagnostics: None,
+                reused_witness: false,
+                lease_headroom_secs: None,
             })
             .collect();
     }
@@ -354,17 +690,68 @@ async fn run_group(
                 warned_invalid_reward_address,
                 pinning,
                 warned_pinning_failed,
+                faults,
+                net,
+                submit_queue,
+                proof_cache,
+                archive,
+                signer,
                 backend_url,
                 lease_id,
-                lease_expires_at,
-                progress_steps,
+                lease_deadline,
+                leased_at,
+                progress_min_interval,
+                strict_output,
+                discriminant_bits,
                 job,
             )
             .await,
         ];
     }
 
+    if let Some((job_id, reason)) = jobs.iter().find_map(|job| {
+        job.validate(job.discriminant_bits(discriminant_bits))
+            .err()
+            .map(|reason| (job.job_id, reason))
+    }) {
+        let err = format!("Error (invalid job {job_id} in group {group_id}: {reason})");
+        return jobs
+            .into_iter()
+            .map(|job| JobOutcome {
+                worker_idx,
+                job: JobSummary {
+                    job_id: job.job_id,
+                    group_proofs: None,
+                    height: job.height,
+                    field_vdf: job.field_vdf,
+                    number_of_iterations: job.number_of_iterations,
+                },
+                output_mismatch: false,
+                submit_reason: None,
+                submit_detail: None,
+                drop_inflight: true,
+                lease_expired: false,
+                error: Some(err.clone()),
+                compute_ms: 0,
+                submit_ms: 0,
+                total_ms: started_at.elapsed().as_millis() as u64,
+                queue_wait_ms,
+                squaring_ms: None,
+                finalize_ms: None,
+                submit_retries: 0,
+                mismatch_check: None,
+                diagnostics: None,
+                reused_witness: false,
+                lease_headroom_secs: None,
+            })
+            .collect();
+    }
+
     let challenge_b64 = jobs[0].challenge_b64.clone();
+    let input_b64 = jobs[0].input_b64.clone();
+    let discriminant_bits = jobs[0].discriminant_bits(discriminant_bits);
+    // Every job was validated above, and all must share the first one's x.
+    let x = jobs[0].initial_element().unwrap_or_default();
     let challenge = match B64.decode(challenge_b64.as_bytes()) {
         Ok(v) => v,
         Err(err) => {
@@ -384,10 +771,19 @@ async fn run_group(
                     submit_reason: None,
                     submit_detail: None,
                     drop_inflight: false,
+                    lease_expired: false,
                     error: Some(err.clone()),
                     compute_ms: 0,
                     submit_ms: 0,
                     total_ms: started_at.elapsed().as_millis() as u64,
+                    queue_wait_ms,
+                    squaring_ms: None,
+                    finalize_ms: None,
+                    submit_retries: 0,
+                    mismatch_check: None,
+                    diagnostics: None,
+                    reused_witness: false,
+                    lease_headroom_secs: None,
                 })
                 .collect();
         }
@@ -397,9 +793,12 @@ async fn run_group(
     let mut iterations: Vec<u64> = Vec::with_capacity(jobs.len());
     let mut total_iters: u64 = 0;
     for job in &jobs {
-        if job.challenge_b64 != challenge_b64 {
+        if job.challenge_b64 != challenge_b64
+            || job.input_b64 != input_b64
+            || job.discriminant_bits(discriminant_bits) != discriminant_bits
+        {
             let err = format!(
-                "Error (group {group_id}: inconsistent challenge_b64 for job {})",
+                "Error (group {group_id}: inconsistent challenge_b64/input_b64/discriminant_size_bits for job {})",
                 job.job_id
             );
             return jobs
@@ -417,10 +816,19 @@ async fn run_group(
                     submit_reason: None,
                     submit_detail: None,
                     drop_inflight: false,
+                    lease_expired: false,
                     error: Some(err.clone()),
                     compute_ms: 0,
                     submit_ms: 0,
                     total_ms: started_at.elapsed().as_millis() as u64,
+                    queue_wait_ms,
+                    squaring_ms: None,
+                    finalize_ms: None,
+                    submit_retries: 0,
+                    mismatch_check: None,
+                    diagnostics: None,
+                    reused_witness: false,
+                    lease_headroom_secs: None,
                 })
                 .collect();
         }
@@ -444,10 +852,19 @@ async fn run_group(
                         submit_reason: None,
                         submit_detail: None,
                         drop_inflight: false,
+                        lease_expired: false,
                         error: Some(err.clone()),
                         compute_ms: 0,
                         submit_ms: 0,
                         total_ms: started_at.elapsed().as_millis() as u64,
+                        queue_wait_ms,
+                        squaring_ms: None,
+                        finalize_ms: None,
+                        submit_retries: 0,
+                        mismatch_check: None,
+                        diagnostics: None,
+                        reused_witness: false,
+                        lease_headroom_secs: None,
                     })
                     .collect();
             }
@@ -463,16 +880,19 @@ async fn run_group(
     });
 
     let compute_started_at = Instant::now();
-    let witnesses = match compute_witness_batch(
+    let (witnesses, breakdown) = match compute_witness_batch(
         worker_idx,
         internal_tx.clone(),
         progress.clone(),
         pinning.clone(),
         warned_pinning_failed.clone(),
+        faults.clone(),
         total_iters,
-        lease_expires_at,
-        progress_steps,
-        challenge,
+        &lease_deadline,
+        progress_min_interval,
+        discriminant_bits,
+        challenge.clone(),
+        x,
         outputs,
         iterations,
     )
@@ -483,6 +903,7 @@ async fn run_group(
             let compute_ms = compute_started_at.elapsed().as_millis() as u64;
             return jobs
                 .into_iter()
+                .filter(|job| shared_jobs.take(job.job_id))
                 .map(|job| JobOutcome {
                     worker_idx,
                     job: JobSummary {
@@ -496,10 +917,19 @@ async fn run_group(
                     submit_reason: None,
                     submit_detail: None,
                     drop_inflight: true,
+                    lease_expired: false,
                     error: Some(err.clone()),
                     compute_ms,
                     submit_ms: 0,
                     total_ms: started_at.elapsed().as_millis() as u64,
+                    queue_wait_ms,
+                    squaring_ms: None,
+                    finalize_ms: None,
+                    submit_retries: 0,
+                    mismatch_check: None,
+                    diagnostics: None,
+                    reused_witness: false,
+                    lease_headroom_secs: None,
                 })
                 .collect();
         }
@@ -512,7 +942,53 @@ async fn run_group(
     });
 
     let mut out = Vec::with_capacity(jobs.len());
-    for (job, (witness, output_mismatch)) in jobs.into_iter().zip(witnesses.into_iter()) {
+    for (job, witness) in jobs.into_iter().zip(witnesses) {
+        if !shared_jobs.take(job.job_id) {
+            continue;
+        }
+        // Only this job's result was unusable: prove it again on its own rather
+        // than throwing away the rest of the batch.
+        let (mut witness, mut mismatched_y) = match witness {
+            Ok(witness) => witness,
+            Err(err) => {
+                let _ = internal_tx.send(WorkerInternalEvent::Warning {
+                    message: format!(
+                        "warning: worker {} batch result for job {} unusable ({}); proving it alone",
+                        worker_idx + 1,
+                        job.job_id,
+                        err
+                    ),
+                });
+                out.push(
+                    run_job(
+                        worker_idx,
+                        internal_tx,
+                        progress.clone(),
+                        http,
+                        submitter,
+                        warned_invalid_reward_address.clone(),
+                        pinning.clone(),
+                        warned_pinning_failed.clone(),
+                        faults,
+                        net,
+                        submit_queue,
+                        proof_cache,
+                        archive,
+                        signer,
+                        backend_url.clone(),
+                        lease_id.clone(),
+                        lease_deadline.clone(),
+                        leased_at,
+                        progress_min_interval,
+                        strict_output,
+                        discriminant_bits,
+                        job,
+                    )
+                    .await,
+                );
+                continue;
+            }
+        };
         let job_summary = JobSummary {
             job_id: job.job_id,
             group_proofs: None,
@@ -520,100 +996,208 @@ async fn run_group(
             field_vdf: job.field_vdf,
             number_of_iterations: job.number_of_iterations,
         };
+        let mut mismatch_check = None;
+        if let Some(y) = &mismatched_y {
+            let expected = B64.decode(job.output_b64.as_bytes()).unwrap_or_default();
+            let mismatch = OutputMismatch {
+                job: &job,
+                discriminant_bits,
+                challenge: &challenge,
+                expected_y: &expected,
+                streaming_y: y,
+            };
+            let (check, recomputed) =
+                recheck_mismatch(worker_idx, internal_tx, &mismatch, &lease_deadline).await;
+            mismatch_check = Some(check);
+            if let Some(recomputed) = recomputed {
+                witness = recomputed;
+                mismatched_y = None;
+            }
+        }
+        let output_mismatch = mismatched_y.is_some();
+        if !output_mismatch {
+            proof_cache.insert(ProofKey::of(&job, discriminant_bits), witness.clone());
+        }
+
+        if strict_output && let Some(y) = &mismatched_y {
+            let expected = B64.decode(job.output_b64.as_bytes()).unwrap_or_default();
+            out.push(JobOutcome {
+                worker_idx,
+                error: Some(report_output_mismatch(
+                    worker_idx,
+                    internal_tx,
+                    &job,
+                    discriminant_bits,
+                    &challenge,
+                    &expected,
+                    y,
+                )),
+                job: job_summary,
+                output_mismatch,
+                submit_reason: None,
+                submit_detail: None,
+                drop_inflight: true,
+                lease_expired: false,
+                compute_ms,
+                submit_ms: 0,
+                total_ms: started_at.elapsed().as_millis() as u64,
+                queue_wait_ms,
+                squaring_ms: breakdown.squaring_ms,
+                finalize_ms: breakdown.finalize_ms,
+                submit_retries: 0,
+                mismatch_check,
+                diagnostics: None,
+                reused_witness: false,
+                lease_headroom_secs: None,
+            });
+            continue;
+        }
 
         let submit_started_at = Instant::now();
         let submit_res = submit_witness(
             http,
             submitter,
+            faults,
+            net,
+            submit_queue,
+            archive,
+            signer,
             warned_invalid_reward_address.clone(),
             internal_tx,
             &backend_url,
             job.job_id,
             &lease_id,
-            lease_expires_at,
+            &lease_deadline,
             &witness,
         )
         .await;
         let submit_ms = submit_started_at.elapsed().as_millis() as u64;
 
         match submit_res {
-            Ok(res) => out.push(JobOutcome {
-                worker_idx,
-                job: job_summary,
-                output_mismatch,
-                submit_reason: Some(res.reason),
-                submit_detail: Some(res.detail),
-                drop_inflight: false,
-                error: None,
-                compute_ms,
-                submit_ms,
-                total_ms: started_at.elapsed().as_millis() as u64,
-            }),
-            Err(err) => out.push(JobOutcome {
-                worker_idx,
-                job: job_summary,
-                output_mismatch,
-                submit_reason: None,
-                submit_detail: None,
-                drop_inflight: err.drop_inflight,
-                error: Some(err.message),
-                compute_ms,
-                submit_ms,
-                total_ms: started_at.elapsed().as_millis() as u64,
-            }),
+            Ok((res, retries)) => {
+                let diagnostics = rejection_diagnostics(
+                    Some(&res.reason),
+                    Some(&res.response),
+                    &breakdown,
+                    compute_ms,
+                    submit_ms,
+                    retries,
+                );
+                out.push(JobOutcome {
+                    worker_idx,
+                    job: job_summary,
+                    output_mismatch,
+                    submit_reason: Some(res.reason),
+                    submit_detail: Some(res.detail),
+                    drop_inflight: false,
+                    lease_expired: false,
+                    error: None,
+                    compute_ms,
+                    submit_ms,
+                    total_ms: started_at.elapsed().as_millis() as u64,
+                    queue_wait_ms,
+                    squaring_ms: breakdown.squaring_ms,
+                    finalize_ms: breakdown.finalize_ms,
+                    submit_retries: retries,
+                    mismatch_check,
+                    diagnostics,
+                    reused_witness: false,
+                    lease_headroom_secs: Some(remaining_lease_secs(&lease_deadline)),
+                });
+            }
+            Err(err) => {
+                let diagnostics = rejection_diagnostics(
+                    None,
+                    err.response.as_ref(),
+                    &breakdown,
+                    compute_ms,
+                    submit_ms,
+                    err.retries,
+                );
+                out.push(JobOutcome {
+                    worker_idx,
+                    job: job_summary,
+                    output_mismatch,
+                    submit_reason: None,
+                    submit_detail: None,
+                    drop_inflight: err.drop_inflight,
+                    lease_expired: err.lease_expired,
+                    error: Some(err.message),
+                    compute_ms,
+                    submit_ms,
+                    total_ms: started_at.elapsed().as_millis() as u64,
+                    queue_wait_ms,
+                    squaring_ms: breakdown.squaring_ms,
+                    finalize_ms: breakdown.finalize_ms,
+                    submit_retries: err.retries,
+                    mismatch_check,
+                    diagnostics,
+                    reused_witness: false,
+                    lease_headroom_secs: None,
+                });
+            }
         }
     }
 
     out
 }
 
+/// One job's witness from a batch and its computed `y` when that differs from
+/// the job's expected output, or why the batch's result for it is unusable.
+type BatchWitness = Result<(Vec<u8>, Option<Vec<u8>>), String>;
+
 async fn compute_witness_batch(
     worker_idx: usize,
     internal_tx: mpsc::UnboundedSender<WorkerInternalEvent>,
     progress: Arc<AtomicU64>,
     pinning: Arc<PinningPlan>,
     warned_pinning_failed: Arc<AtomicBool>,
+    faults: Faults,
     total_iters: u64,
-    lease_expires_at: i64,
-    progress_steps: u64,
+    lease_deadline: &LeaseDeadline,
+    progress_min_interval: Duration,
+    discriminant_bits: usize,
     challenge: Vec<u8>,
+    x: ClassgroupElement,
     outputs: Vec<Vec<u8>>,
     iterations: Vec<u64>,
-) -> Result<Vec<(Vec<u8>, bool)>, String> {
+) -> Result<(Vec<BatchWitness>, ComputeBreakdown), String> {
     let mut last_compute_err: Option<String> = None;
 
     loop {
         let now = Utc::now().timestamp();
-        if now >= lease_expires_at {
+        if now >= lease_deadline.expires_at() {
             return Err("Error (lease expired)".to_string());
         }
 
         let total_iters = total_iters.max(1);
-        let progress_interval = progress_interval(total_iters, progress_steps);
-
         let challenge = challenge.clone();
         let outputs = outputs.clone();
         let iterations = iterations.clone();
         let progress_clone = progress.clone();
         let pinning = pinning.clone();
         let warned_pinning_failed = warned_pinning_failed.clone();
-        let internal_tx = internal_tx.clone();
+        let faults = faults.clone();
+        let internal_tx_for_compute = internal_tx.clone();
+        let abandoned = Abandoned::default();
+        let abandoned_for_compute = abandoned.clone();
 
-        let compute =
-            tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<(Vec<u8>, bool)>> {
+        let compute = tokio::task::spawn_blocking(
+            move || -> anyhow::Result<(Vec<BatchWitness>, ComputeBreakdown)> {
                 if let Err(err) = pinning.pin_current_thread_for_worker(worker_idx) {
                     if !warned_pinning_failed.swap(true, Ordering::Relaxed) {
-                        let _ = internal_tx.send(WorkerInternalEvent::Warning {
+                        let _ = internal_tx_for_compute.send(WorkerInternalEvent::Warning {
                             message: format!(
-                                "warning: failed to pin worker {} to L3 CPU set: {}",
+                                "warning: failed to pin worker {} to its CPU set/cgroup: {}",
                                 worker_idx + 1,
                                 err
                             ),
                         });
                     }
                 }
-                let x = default_classgroup_element();
-
+                faults.before_compute()?;
+                let prove_options = ProveOptions::pinned(pinning.cpu_set_for_worker(worker_idx))
+                    .with_progress_min_interval(progress_min_interval);
                 let batch_jobs: Vec<ChiavdfBatchJob<'_>> = outputs
                     .iter()
                     .zip(iterations.iter())
@@ -623,31 +1207,33 @@ async fn compute_witness_batch(
                     })
                     .collect();
 
-                let results =
-                    if progress_steps == 0 {
-                        prove_one_weso_fast_streaming_getblock_opt_batch(
-                            &challenge,
-                            &x,
-                            DISCRIMINANT_BITS,
-                            &batch_jobs,
-                        )
-                        .context("chiavdf prove_one_weso_fast_streaming_getblock_opt_batch")?
-                    } else {
-                        let progress_for_cb = progress_clone.clone();
-                        prove_one_weso_fast_streaming_getblock_opt_batch_with_progress(
-                    &challenge,
-                    &x,
-                    DISCRIMINANT_BITS,
-                    &batch_jobs,
-                    progress_interval,
-                    move |iters_done| {
-                        progress_for_cb.store(iters_done, Ordering::Relaxed);
-                    },
-                )
-                .context("chiavdf prove_one_weso_fast_streaming_getblock_opt_batch_with_progress")?
-                    };
+                let prove_started_at = Instant::now();
+                let progress_for_cb = progress_clone.clone();
+                let abandoned_for_cb = abandoned_for_compute.clone();
+                let on_progress = finalize_notifier(
+                    worker_idx,
+                    abandoned_for_compute.clone(),
+                    internal_tx_for_compute.clone(),
+                );
+                let report = prove_options
+                    .run(|| {
+                        run_with_progress_info(on_progress, || {
+                            ProverBuilder::new(&challenge, x.as_bytes(), discriminant_bits)
+                                .progress(PROGRESS_CHECK_ITERS, move |iters_done| {
+                                    if !abandoned_for_cb.is_set() {
+                                        progress_for_cb.store(iters_done, Ordering::Relaxed);
+                                    }
+                                })
+                                .prove_batch_reported(&batch_jobs)
+                        })
+                    })
+                    .context("chiavdf batch prove")?;
+                let breakdown = ComputeBreakdown::from_report(prove_started_at.elapsed(), &report);
+                let results = report.output;
 
-                progress_clone.store(total_iters, Ordering::Relaxed);
+                if !abandoned_for_compute.is_set() {
+                    progress_clone.store(total_iters, Ordering::Relaxed);
+                }
 
                 if results.len() != batch_jobs.len() {
                     anyhow::bail!(
@@ -658,24 +1244,45 @@ async fn compute_witness_batch(
                 }
 
                 let mut out = Vec::with_capacity(batch_jobs.len());
-                for (idx, blob) in results.into_iter().enumerate() {
-                    let half = blob.len() / 2;
-                    let y = &blob[..half];
-                    let witness = blob[half..].to_vec();
-                    let output_mismatch = y != batch_jobs[idx].y_ref_s;
-                    out.push((witness, output_mismatch));
+                for (result, job) in results.into_iter().zip(&batch_jobs) {
+                    out.push(
+                        result
+                            .map(|proof| {
+                                let mismatched_y = (proof.y != job.y_ref_s).then_some(proof.y);
+                                (proof.witness, mismatched_y)
+                            })
+                            .map_err(|err| format!("Error (compute: {err})")),
+                    );
                 }
 
-                Ok(out)
-            })
-            .await;
+                Ok((out, breakdown))
+            },
+        );
+
+        let Some(compute) = join_compute(compute, &abandoned, lease_deadline).await else {
+            return Err(abandoned_status(lease_deadline));
+        };
 
         match compute {
             Ok(Ok(v)) => return Ok(v),
             Ok(Err(err)) => {
+                if let Some(status) = permanent_compute_failure(&err) {
+                    return Err(status);
+                }
                 let err_msg = format!("{err:#}");
                 if last_compute_err.as_deref() != Some(&err_msg) {
                     last_compute_err = Some(err_msg.clone());
+                    let _ = internal_tx.send(WorkerInternalEvent::Error {
+                        error: EngineError::Compute {
+                            worker_idx,
+                            job_id: None,
+                            message: format!(
+                                "error: worker {} batch compute failed: {}; retrying in 2s",
+                                worker_idx + 1,
+                                err_msg
+                            ),
+                        },
+                    });
                 }
                 tokio::time::sleep(Duration::from_secs(2)).await;
                 continue;
@@ -684,6 +1291,17 @@ async fn compute_witness_batch(
                 let err_msg = format!("{err:#}");
                 if last_compute_err.as_deref() != Some(&err_msg) {
                     last_compute_err = Some(err_msg.clone());
+                    let _ = internal_tx.send(WorkerInternalEvent::Error {
+                        error: EngineError::Compute {
+                            worker_idx,
+                            job_id: None,
+                            message: format!(
+                                "error: worker {} batch compute join failed: {}; retrying in 2s",
+                                worker_idx + 1,
+                                err_msg
+                            ),
+                        },
+                    });
                 }
                 tokio::time::sleep(Duration::from_secs(2)).await;
                 continue;
@@ -692,17 +1310,86 @@ async fn compute_witness_batch(
     }
 }
 
+/// Outcome error for a proof failure that retrying cannot fix (bad input, form
+/// or `y_ref` size); `None` for failures worth another attempt.
+fn permanent_compute_failure(err: &anyhow::Error) -> Option<String> {
+    let err = err.downcast_ref::<ChiavdfFastError>()?;
+    (!err.is_retryable()).then(|| format!("Error (compute: {err})"))
+}
+
+/// Set once the worker stops waiting for a proof.
+///
+/// The prover can't be stopped mid-proof, so an abandoned proof runs on to the
+/// end on its blocking thread; its callbacks check this to keep off the
+/// worker's progress, which belongs to the next job by then.
+#[derive(Clone, Default)]
+struct Abandoned(Arc<AtomicBool>);
+
+impl Abandoned {
+    fn is_set(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Abandons a proof when dropped, including when the worker task itself is
+/// aborted while waiting for it.
+struct AbandonOnDrop(Abandoned);
+
+impl Drop for AbandonOnDrop {
+    fn drop(&mut self) {
+        self.0.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Wait for a proof task, abandoning it once the lease expires or the engine
+/// preempts it.
+///
+/// The deadline is re-read whenever it passes, so lease renewals made meanwhile
+/// keep the wait going. Returns `None` when the proof was abandoned, without
+/// waiting for it, so the worker can move on to fresh work.
+async fn join_compute<T>(
+    mut compute: tokio::task::JoinHandle<T>,
+    abandoned: &Abandoned,
+    lease_deadline: &LeaseDeadline,
+) -> Option<Result<T, tokio::task::JoinError>> {
+    let _abandon_on_drop = AbandonOnDrop(abandoned.clone());
+    loop {
+        let remaining = remaining_lease_secs(lease_deadline);
+        if remaining == 0 {
+            return None;
+        }
+        tokio::select! {
+            res = &mut compute => return Some(res),
+            _ = lease_deadline.preempted() => return None,
+            _ = tokio::time::sleep(Duration::from_secs(remaining)) => {}
+        }
+    }
+}
+
+/// Outcome error for a proof [`join_compute`] abandoned.
+fn abandoned_status(lease_deadline: &LeaseDeadline) -> String {
+    if lease_deadline.is_preempted() {
+        "Error (preempted: lease would expire)".to_string()
+    } else {
+        "Error (lease expired)".to_string()
+    }
+}
+
 pub(crate) async fn compute_witness(
     worker_idx: usize,
     internal_tx: &mpsc::UnboundedSender<WorkerInternalEvent>,
     progress: Arc<AtomicU64>,
     pinning: Arc<PinningPlan>,
     warned_pinning_failed: Arc<AtomicBool>,
+    faults: Faults,
     total_iters: u64,
-    progress_steps: u64,
+    lease_deadline: &LeaseDeadline,
+    progress_min_interval: Duration,
+    discriminant_bits: usize,
     challenge: Vec<u8>,
+    x: ClassgroupElement,
     output: Vec<u8>,
-) -> Result<(Vec<u8>, bool), String> {
+) -> Result<(Vec<u8>, Option<Vec<u8>>, ComputeBreakdown), String> {
     let mut last_compute_err: Option<String> = None;
     let mut last_log_at = Instant::now()
         .checked_sub(Duration::from_secs(3600))
@@ -710,65 +1397,83 @@ pub(crate) async fn compute_witness(
     let mut attempts: u32 = 0;
 
     loop {
+        if Utc::now().timestamp() >= lease_deadline.expires_at() {
+            return Err("Error (lease expired)".to_string());
+        }
+
         let total_iters = total_iters.max(1);
-        let progress_interval = progress_interval(total_iters, progress_steps);
         let challenge = challenge.clone();
         let output = output.clone();
         let progress_clone = progress.clone();
         let pinning = pinning.clone();
         let warned_pinning_failed = warned_pinning_failed.clone();
+        let faults = faults.clone();
         let internal_tx_for_pin = internal_tx.clone();
+        let internal_tx_for_finalize = internal_tx.clone();
+        let abandoned = Abandoned::default();
+        let abandoned_for_compute = abandoned.clone();
 
-        let compute = tokio::task::spawn_blocking(move || -> anyhow::Result<(Vec<u8>, bool)> {
-            if let Err(err) = pinning.pin_current_thread_for_worker(worker_idx) {
-                if !warned_pinning_failed.swap(true, Ordering::Relaxed) {
-                    let _ = internal_tx_for_pin.send(WorkerInternalEvent::Warning {
-                        message: format!(
-                            "warning: failed to pin worker {} to L3 CPU set: {}",
-                            worker_idx + 1,
-                            err
-                        ),
-                    });
+        let compute = tokio::task::spawn_blocking(
+            move || -> anyhow::Result<(Vec<u8>, Option<Vec<u8>>, ComputeBreakdown)> {
+                if let Err(err) = pinning.pin_current_thread_for_worker(worker_idx) {
+                    if !warned_pinning_failed.swap(true, Ordering::Relaxed) {
+                        let _ = internal_tx_for_pin.send(WorkerInternalEvent::Warning {
+                            message: format!(
+                                "warning: failed to pin worker {} to its CPU set/cgroup: {}",
+                                worker_idx + 1,
+                                err
+                            ),
+                        });
+                    }
                 }
-            }
-            let x = default_classgroup_element();
-            let out = if progress_steps == 0 {
-                bbr_client_chiavdf_fast::prove_one_weso_fast_streaming_getblock_opt(
-                    &challenge,
-                    &x,
-                    &output,
-                    DISCRIMINANT_BITS,
-                    total_iters,
-                )
-                .context("chiavdf prove_one_weso_fast_streaming_getblock_opt")?
-            } else {
+                faults.before_compute()?;
+                let prove_options = ProveOptions::pinned(pinning.cpu_set_for_worker(worker_idx))
+                    .with_progress_min_interval(progress_min_interval);
+                let prove_started_at = Instant::now();
+                let on_progress = finalize_notifier(
+                    worker_idx,
+                    abandoned_for_compute.clone(),
+                    internal_tx_for_finalize,
+                );
                 let progress_for_cb = progress_clone.clone();
-                bbr_client_chiavdf_fast::prove_one_weso_fast_streaming_getblock_opt_with_progress(
-                    &challenge,
-                    &x,
-                    &output,
-                    DISCRIMINANT_BITS,
-                    total_iters,
-                    progress_interval,
-                    move |iters_done| {
-                        progress_for_cb.store(iters_done, Ordering::Relaxed);
-                    },
-                )
-                .context("chiavdf prove_one_weso_fast_streaming_getblock_opt_with_progress")?
-            };
+                let abandoned_for_cb = abandoned_for_compute.clone();
+                let report = prove_options
+                    .run(|| {
+                        run_with_progress_info(on_progress, || {
+                            ProverBuilder::new(&challenge, x.as_bytes(), discriminant_bits)
+                                .iterations(total_iters)
+                                .y_ref(&output)
+                                .progress(PROGRESS_CHECK_ITERS, move |iters_done| {
+                                    if !abandoned_for_cb.is_set() {
+                                        progress_for_cb.store(iters_done, Ordering::Relaxed);
+                                    }
+                                })
+                                .prove_reported()
+                        })
+                    })
+                    .context("chiavdf streaming prove")?;
+                let breakdown = ComputeBreakdown::from_report(prove_started_at.elapsed(), &report);
+                let proof = report.output;
 
-            progress_clone.store(total_iters, Ordering::Relaxed);
+                if !abandoned_for_compute.is_set() {
+                    progress_clone.store(total_iters, Ordering::Relaxed);
+                }
 
-            let half = out.len() / 2;
-            let y = &out[..half];
-            let witness = out[half..].to_vec();
-            Ok((witness, y != output))
-        })
-        .await;
+                let mismatched_y = (proof.y != output).then_some(proof.y);
+                Ok((proof.witness, mismatched_y, breakdown))
+            },
+        );
+
+        let Some(compute) = join_compute(compute, &abandoned, lease_deadline).await else {
+            return Err(abandoned_status(lease_deadline));
+        };
 
         match compute {
-            Ok(Ok((witness, output_mismatch))) => return Ok((witness, output_mismatch)),
+            Ok(Ok(v)) => return Ok(v),
             Ok(Err(err)) => {
+                if let Some(status) = permanent_compute_failure(&err) {
+                    return Err(status);
+                }
                 attempts = attempts.saturating_add(1);
                 let err_msg = format!("{err:#}");
                 let should_log = last_compute_err.as_deref() != Some(&err_msg)
@@ -777,12 +1482,16 @@ pub(crate) async fn compute_witness(
                     last_compute_err = Some(err_msg.clone());
                     last_log_at = Instant::now();
                     let _ = internal_tx.send(WorkerInternalEvent::Error {
-                        message: format!(
-                            "error: worker {} compute failed (attempt {}): {}; retrying in 5s",
-                            worker_idx + 1,
-                            attempts,
-                            err_msg
-                        ),
+                        error: EngineError::Compute {
+                            worker_idx,
+                            job_id: None,
+                            message: format!(
+                                "error: worker {} compute failed (attempt {}): {}; retrying in 5s",
+                                worker_idx + 1,
+                                attempts,
+                                err_msg
+                            ),
+                        },
                     });
                 }
                 tokio::time::sleep(Duration::from_secs(5)).await;
@@ -797,12 +1506,16 @@ pub(crate) async fn compute_witness(
                     last_compute_err = Some(err_msg.clone());
                     last_log_at = Instant::now();
                     let _ = internal_tx.send(WorkerInternalEvent::Error {
-                        message: format!(
-                            "error: worker {} compute join failed (attempt {}): {}; retrying in 5s",
-                            worker_idx + 1,
-                            attempts,
-                            err_msg
-                        ),
+                        error: EngineError::Compute {
+                            worker_idx,
+                            job_id: None,
+                            message: format!(
+                                "error: worker {} compute join failed (attempt {}): {}; retrying in 5s",
+                                worker_idx + 1,
+                                attempts,
+                                err_msg
+                            ),
+                        },
                     });
                 }
                 tokio::time::sleep(Duration::from_secs(5)).await;
@@ -812,27 +1525,159 @@ pub(crate) async fn compute_witness(
     }
 }
 
-fn progress_interval(total_iters: u64, progress_steps: u64) -> u64 {
-    if progress_steps == 0 {
-        return 0;
-    }
-    if total_iters == 0 {
-        return 1;
+/// A streaming proof whose `y` differs from the backend's expected output.
+struct OutputMismatch<'a> {
+    job: &'a BackendJobDto,
+    discriminant_bits: usize,
+    challenge: &'a [u8],
+    expected_y: &'a [u8],
+    streaming_y: &'a [u8],
+}
+
+/// Recompute a mismatched streaming proof once with the non-streaming prover, to
+/// tell streaming-mode bugs apart from bad backend data.
+///
+/// Returns the diagnosis and, when the non-streaming output matches the expected
+/// output, the recomputed witness to submit instead. The recheck is abandoned
+/// when the lease runs out.
+async fn recheck_mismatch(
+    worker_idx: usize,
+    internal_tx: &mpsc::UnboundedSender<WorkerInternalEvent>,
+    mismatch: &OutputMismatch<'_>,
+    lease_deadline: &LeaseDeadline,
+) -> (MismatchCheck, Option<Vec<u8>>) {
+    let &OutputMismatch {
+        job,
+        discriminant_bits,
+        challenge,
+        expected_y,
+        streaming_y,
+    } = mismatch;
+    let num_iterations = job.number_of_iterations.max(1);
+    let x = job.initial_element().unwrap_or_default();
+    let challenge_for_compute = challenge.to_vec();
+    let recompute = tokio::task::spawn_blocking(move || {
+        ProverBuilder::new(&challenge_for_compute, x.as_bytes(), discriminant_bits)
+            .iterations(num_iterations)
+            .prove()
+    });
+    let recomputed = match join_compute(recompute, &Abandoned::default(), lease_deadline).await {
+        Some(Ok(res)) => res.context("chiavdf prove_one_weso_fast"),
+        Some(Err(err)) => Err(anyhow::Error::new(err).context("chiavdf prove_one_weso_fast")),
+        None => Err(anyhow::anyhow!("lease expired before the recheck finished")),
+    };
+
+    let (check, witness) = match recomputed {
+        Ok(proof) => {
+            if proof.y == expected_y {
+                (MismatchCheck::StreamingPath, Some(proof.witness))
+            } else if proof.y == streaming_y {
+                (MismatchCheck::BackendOutput, None)
+            } else {
+                (MismatchCheck::Inconsistent, None)
+            }
+        }
+        Err(err) => {
+            let _ = internal_tx.send(WorkerInternalEvent::Warning {
+                message: format!(
+                    "warning: worker {} mismatch recheck failed for job {}: {err:#}",
+                    worker_idx + 1,
+                    job.job_id
+                ),
+            });
+            (MismatchCheck::RecheckFailed, None)
+        }
+    };
+
+    let diagnosis = match check {
+        MismatchCheck::StreamingPath => "streaming prover wrong; submitting non-streaming proof",
+        MismatchCheck::BackendOutput => "both provers agree; backend expected output differs",
+        MismatchCheck::Inconsistent => "provers disagree with each other and the backend",
+        MismatchCheck::RecheckFailed => "recheck failed",
+    };
+    let _ = internal_tx.send(WorkerInternalEvent::Warning {
+        message: format!(
+            "warning: worker {} output mismatch for job {}: {diagnosis}",
+            worker_idx + 1,
+            job.job_id
+        ),
+    });
+
+    (check, witness)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Log diagnostics for a proof whose `y` differs from the backend's expected output
+/// (strict output mode) and return the outcome error.
+fn report_output_mismatch(
+    worker_idx: usize,
+    internal_tx: &mpsc::UnboundedSender<WorkerInternalEvent>,
+    job: &BackendJobDto,
+    discriminant_bits: usize,
+    challenge: &[u8],
+    expected_y: &[u8],
+    computed_y: &[u8],
+) -> String {
+    let _ = internal_tx.send(WorkerInternalEvent::Error {
+        error: EngineError::Compute {
+            worker_idx,
+            job_id: Some(job.job_id),
+            message: format!(
+                "error: worker {} output mismatch for job {} (height {}, field_vdf {}, iterations {}); not submitting: discriminant_bits={} challenge={} expected_y={} computed_y={}",
+                worker_idx + 1,
+                job.job_id,
+                job.height,
+                job.field_vdf,
+                job.number_of_iterations,
+                discriminant_bits,
+                hex(challenge),
+                hex(expected_y),
+                hex(computed_y),
+            ),
+        },
+    });
+    "Error (output mismatch)".to_string()
+}
+
+/// Progress callback that tells the engine when the prover starts folding the
+/// witnesses, which it reports once all squaring is done; squaring progress alone
+/// sits at 100% meanwhile.
+fn finalize_notifier(
+    worker_idx: usize,
+    abandoned: Abandoned,
+    internal_tx: mpsc::UnboundedSender<WorkerInternalEvent>,
+) -> impl FnMut(ProgressInfo) + Send + 'static {
+    move |info| {
+        if info.phase == ProgressPhase::Finalize && !abandoned.is_set() {
+            let _ = internal_tx.send(WorkerInternalEvent::FinalizeStarted { worker_idx });
+        }
     }
-    (total_iters.saturating_add(progress_steps - 1) / progress_steps).max(1)
+}
+
+/// Seconds left before the lease expires (0 once it has).
+fn remaining_lease_secs(lease_deadline: &LeaseDeadline) -> u64 {
+    (lease_deadline.expires_at() - Utc::now().timestamp()).max(0) as u64
 }
 
 async fn submit_witness(
     http: &reqwest::Client,
     submitter: &tokio::sync::RwLock<SubmitterConfig>,
+    faults: &Faults,
+    net: &NetworkStats,
+    submit_queue: &SubmitQueue,
+    archive: Option<&Arc<SubmissionArchive>>,
+    signer: Option<&SubmissionSigner>,
     warned_invalid_reward_address: Arc<AtomicBool>,
     internal_tx: &mpsc::UnboundedSender<WorkerInternalEvent>,
     backend: &Url,
     job_id: u64,
     lease_id: &str,
-    lease_expires_at: i64,
+    lease_deadline: &LeaseDeadline,
     witness: &[u8],
-) -> Result<SubmitResponse, SubmitFailure> {
+) -> Result<(SubmitResponse, u32), SubmitFailure> {
     let mut last_submit_err: Option<String> = None;
     let mut attempts: u32 = 0;
     let mut last_log_at = Instant::now()
@@ -840,6 +1685,35 @@ async fn submit_witness(
         .unwrap_or_else(Instant::now);
 
     loop {
+        // Once submissions are queued (or this one already failed), wait for the
+        // ones whose leases expire sooner; the turn is held through the attempt.
+        let _turn = if attempts == 0 && submit_queue.is_idle() {
+            None
+        } else {
+            let turn = submit_queue.turn(lease_deadline.expires_at()).await;
+            if submit_queue.backend_failing() {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+            if lease_deadline.expires_at() <= Utc::now().timestamp() {
+                let _ = internal_tx.send(WorkerInternalEvent::Error {
+                    error: EngineError::Lease {
+                        lease_id: Some(lease_id.to_string()),
+                        job_id: Some(job_id),
+                        message: format!(
+                            "error: dropping job {job_id}: lease expired before it could be submitted"
+                        ),
+                    },
+                });
+                return Err(SubmitFailure {
+                    message: "Error (lease expired before submit)".to_string(),
+                    drop_inflight: true,
+                    lease_expired: true,
+                    retries: attempts,
+                    response: None,
+                });
+            }
+            Some(turn)
+        };
         let now = Utc::now().timestamp();
 
         let (reward_address, name) = {
@@ -847,32 +1721,97 @@ async fn submit_witness(
             (cfg.reward_address.clone(), cfg.name.clone())
         };
 
-        match submit_job(
-            http,
-            backend,
-            job_id,
-            lease_id,
-            witness,
-            reward_address.as_deref(),
-            name.as_deref(),
-        )
-        .await
-        {
-            Ok(res) => return Ok(res),
-            Err(err) => {
+        let res = match faults.before_submit().await {
+            Ok(()) => {
+                submit_job(
+                    http,
+                    net,
+                    backend,
+                    job_id,
+                    lease_id,
+                    witness,
+                    reward_address.as_deref(),
+                    name.as_deref(),
+                    signer,
+                )
+                .await
+            }
+            Err(err) => Err(err.into()),
+        };
+        if let Some(message) = net.slow_submit_warning() {
+            let _ = internal_tx.send(WorkerInternalEvent::Warning { message });
+        }
+
+        submit_queue.record_attempt(match &res {
+            Ok(_) => true,
+            Err(err) => err.error.downcast_ref::<BackendError>().is_some_and(|e| {
+                matches!(
+                    e,
+                    BackendError::LeaseInvalid
+                        | BackendError::LeaseConflict
+                        | BackendError::JobNotFound
+                        | BackendError::InvalidRewardAddress
+                )
+            }),
+        });
+
+        match res {
+            Ok(res) => {
+                archive_submission(
+                    archive,
+                    internal_tx,
+                    job_id,
+                    lease_id,
+                    witness,
+                    &res.reason,
+                    &res.detail,
+                )
+                .await;
+                return Ok((res, attempts));
+            }
+            Err(SubmitError {
+                error: err,
+                response,
+            }) => {
                 attempts = attempts.saturating_add(1);
+                if let Some(rejection) = err.downcast_ref::<BackendError>().filter(|e| {
+                    matches!(
+                        e,
+                        BackendError::LeaseInvalid
+                            | BackendError::LeaseConflict
+                            | BackendError::JobNotFound
+                    )
+                }) {
+                    archive_submission(
+                        archive,
+                        internal_tx,
+                        job_id,
+                        lease_id,
+                        witness,
+                        "rejected",
+                        &rejection.to_string(),
+                    )
+                    .await;
+                }
                 if matches!(
                     err.downcast_ref::<BackendError>(),
                     Some(BackendError::LeaseInvalid)
                 ) {
                     let _ = internal_tx.send(WorkerInternalEvent::Error {
-                        message: format!(
-                            "error: submit rejected for job {job_id}: lease invalid/expired"
-                        ),
+                        error: EngineError::Lease {
+                            lease_id: Some(lease_id.to_string()),
+                            job_id: Some(job_id),
+                            message: format!(
+                                "error: submit rejected for job {job_id}: lease invalid/expired"
+                            ),
+                        },
                     });
                     return Err(SubmitFailure {
                         message: "Error (lease invalid/expired)".to_string(),
                         drop_inflight: true,
+                        lease_expired: false,
+                        retries: attempts.saturating_sub(1),
+                        response,
                     });
                 }
                 if matches!(
@@ -880,13 +1819,20 @@ async fn submit_witness(
                     Some(BackendError::LeaseConflict)
                 ) {
                     let _ = internal_tx.send(WorkerInternalEvent::Error {
-                        message: format!(
-                            "error: submit rejected for job {job_id}: lease conflict (already leased by someone else)"
-                        ),
+                        error: EngineError::Lease {
+                            lease_id: Some(lease_id.to_string()),
+                            job_id: Some(job_id),
+                            message: format!(
+                                "error: submit rejected for job {job_id}: lease conflict (already leased by someone else)"
+                            ),
+                        },
                     });
                     return Err(SubmitFailure {
                         message: "Error (lease conflict)".to_string(),
                         drop_inflight: true,
+                        lease_expired: false,
+                        retries: attempts.saturating_sub(1),
+                        response,
                     });
                 }
                 if matches!(
@@ -894,11 +1840,20 @@ async fn submit_witness(
                     Some(BackendError::JobNotFound)
                 ) {
                     let _ = internal_tx.send(WorkerInternalEvent::Error {
-                        message: format!("error: submit rejected for job {job_id}: job not found"),
+                        error: EngineError::Lease {
+                            lease_id: Some(lease_id.to_string()),
+                            job_id: Some(job_id),
+                            message: format!(
+                                "error: submit rejected for job {job_id}: job not found"
+                            ),
+                        },
                     });
                     return Err(SubmitFailure {
                         message: "Error (job not found)".to_string(),
                         drop_inflight: true,
+                        lease_expired: false,
+                        retries: attempts.saturating_sub(1),
+                        response,
                     });
                 }
                 if matches!(
@@ -927,16 +1882,192 @@ async fn submit_witness(
                 if should_log {
                     last_submit_err = Some(err_msg.clone());
                     last_log_at = Instant::now();
-                    let expires_in = (lease_expires_at - now).max(0);
+                    let expires_in = (lease_deadline.expires_at() - now).max(0);
                     let _ = internal_tx.send(WorkerInternalEvent::Error {
-                        message: format!(
-                            "error: submit failed for job {job_id} (attempt {attempts}, lease expires in {expires_in}s): {err_msg}; retrying in 5s"
-                        ),
+                        error: EngineError::Backend {
+                            job_id: Some(job_id),
+                            message: format!(
+                                "error: submit failed for job {job_id} (attempt {attempts}, lease expires in {expires_in}s): {err_msg}; retrying in lease expiry order"
+                            ),
+                        },
                     });
                 }
-                tokio::time::sleep(Duration::from_secs(5)).await;
                 continue;
             }
         }
     }
 }
+
+/// Append a submission to the archive (when enabled); failures are reported as
+/// warnings and never affect the submission itself.
+async fn archive_submission(
+    archive: Option<&Arc<SubmissionArchive>>,
+    internal_tx: &mpsc::UnboundedSender<WorkerInternalEvent>,
+    job_id: u64,
+    lease_id: &str,
+    witness: &[u8],
+    reason: &str,
+    detail: &str,
+) {
+    let Some(archive) = archive.cloned() else {
+        return;
+    };
+    let lease_id = lease_id.to_string();
+    let witness_b64 = B64.encode(witness);
+    let reason = reason.to_string();
+    let detail = detail.to_string();
+    let submitted_at = Utc::now().timestamp();
+
+    let res = tokio::task::spawn_blocking(move || {
+        archive.append(
+            job_id,
+            &lease_id,
+            witness_b64,
+            &reason,
+            &detail,
+            submitted_at,
+        )
+    })
+    .await
+    .map_err(|err| anyhow::anyhow!("{err:#}"))
+    .and_then(|res| res);
+    if let Err(err) = res {
+        let _ = internal_tx.send(WorkerInternalEvent::Warning {
+            message: format!("warning: failed to archive submission for job {job_id}: {err:#}"),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lease::LeaseTable;
+
+    fn job(job_id: u64) -> BackendJobDto {
+        BackendJobDto {
+            job_id,
+            height: 7,
+            field_vdf: 1,
+            challenge_b64: String::new(),
+            number_of_iterations: 1_000,
+            output_b64: String::new(),
+            discriminant_size_bits: None,
+            input_b64: None,
+        }
+    }
+
+    fn deadline_in(secs: i64) -> LeaseDeadline {
+        Arc::new(LeaseTable::default()).deadline("lease", Utc::now().timestamp() + secs)
+    }
+
+    #[test]
+    fn strict_output_mismatch_logs_the_proof_inputs() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let status = report_output_mismatch(1, &tx, &job(5), 1024, &[0xab], &[0x01], &[0x02]);
+        assert_eq!(status, "Error (output mismatch)");
+
+        let Ok(WorkerInternalEvent::Error {
+            error:
+                EngineError::Compute {
+                    worker_idx,
+                    job_id,
+                    message,
+                },
+        }) = rx.try_recv()
+        else {
+            panic!("no compute error reported");
+        };
+        assert_eq!((worker_idx, job_id), (1, Some(5)));
+        assert!(message.contains("worker 2"), "{message}");
+        assert!(
+            message.contains("challenge=ab expected_y=01 computed_y=02"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn only_rejected_submissions_carry_diagnostics() {
+        let response = ResponseMeta {
+            status: 409,
+            headers: [("x-request-id".to_string(), "abc".to_string())].into(),
+        };
+        let breakdown = ComputeBreakdown::default();
+        for reason in [Some("accepted"), Some("already_compact")] {
+            assert!(
+                rejection_diagnostics(reason, Some(&response), &breakdown, 10, 20, 0).is_none()
+            );
+        }
+        assert!(rejection_diagnostics(Some("invalid"), None, &breakdown, 10, 20, 0).is_none());
+
+        let diagnostics =
+            rejection_diagnostics(Some("invalid"), Some(&response), &breakdown, 10, 20, 2)
+                .expect("rejection without diagnostics");
+        assert_eq!(diagnostics.http_status, 409);
+        assert_eq!(diagnostics.response_headers["x-request-id"], "abc");
+        assert_eq!(
+            (
+                diagnostics.compute_ms,
+                diagnostics.submit_ms,
+                diagnostics.submit_retries
+            ),
+            (10, 20, 2)
+        );
+    }
+
+    #[test]
+    fn only_input_failures_are_permanent() {
+        let invalid = anyhow::Error::new(ChiavdfFastError::InvalidInput("challenge"));
+        assert_eq!(
+            permanent_compute_failure(&invalid).as_deref(),
+            Some("Error (compute: invalid input: challenge)")
+        );
+        let native = anyhow::Error::new(ChiavdfFastError::NativeFailure("crash".to_string()));
+        assert_eq!(permanent_compute_failure(&native), None);
+        assert_eq!(permanent_compute_failure(&anyhow::anyhow!("join")), None);
+    }
+
+    #[test]
+    fn shared_group_jobs_are_reported_once() {
+        let shared = SharedGroupJobs::default();
+        assert!(shared.take(1));
+        assert!(shared.take(2));
+        assert!(!shared.take(1));
+    }
+
+    #[tokio::test]
+    async fn join_compute_gives_up_on_preempted_or_expired_leases() {
+        let deadline = deadline_in(3_600);
+        let finished = tokio::spawn(async { 42 });
+        let abandoned = Abandoned::default();
+        assert_eq!(
+            join_compute(finished, &abandoned, &deadline)
+                .await
+                .map(Result::unwrap),
+            Some(42)
+        );
+
+        let preempted = deadline_in(3_600);
+        preempted.preempt();
+        let pending = tokio::spawn(std::future::pending::<()>());
+        let abandoned = Abandoned::default();
+        assert!(
+            join_compute(pending, &abandoned, &preempted)
+                .await
+                .is_none()
+        );
+        assert!(abandoned.is_set());
+        assert_eq!(
+            abandoned_status(&preempted),
+            "Error (preempted: lease would expire)"
+        );
+
+        let expired = deadline_in(-1);
+        let pending = tokio::spawn(std::future::pending::<()>());
+        assert!(
+            join_compute(pending, &Abandoned::default(), &expired)
+                .await
+                .is_none()
+        );
+        assert_eq!(abandoned_status(&expired), "Error (lease expired)");
+    }
+}
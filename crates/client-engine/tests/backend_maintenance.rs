@@ -0,0 +1,52 @@
+//! Backend maintenance responses pause fetching without failing the engine.
+
+use std::sync::atomic::Ordering;
+
+use tokio::sync::broadcast;
+
+use bbr_client_engine::{EngineEvent, start_engine};
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, spawn_backend, stop_and_check_idle,
+    test_config, test_job,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn backend_maintenance_pauses_fetches_and_warns_once() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("maintenance");
+
+    let backend = MockBackend::with_jobs(vec![test_job(21)]);
+    backend.maintenance_leases.store(2, Ordering::SeqCst);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine(test_config(url));
+    let mut events = engine.subscribe();
+
+    let mut maintenance_warnings = 0usize;
+    let mut saw_snapshot = false;
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, async {
+        loop {
+            match events.recv().await {
+                Ok(EngineEvent::Warning { message }) if message.contains("under maintenance") => {
+                    maintenance_warnings += 1;
+                    saw_snapshot |= engine.snapshot().backend_maintenance.is_some();
+                }
+                Ok(EngineEvent::Error { message, .. }) => panic!("unexpected error: {message}"),
+                Ok(EngineEvent::JobFinished { outcome }) => return outcome,
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => panic!("engine event stream closed"),
+            }
+        }
+    })
+    .await
+    .expect("job did not finish after maintenance");
+    assert_eq!(outcome.job.job_id, 21);
+    assert_eq!(maintenance_warnings, 1);
+    assert!(saw_snapshot);
+    assert!(engine.snapshot().backend_maintenance.is_none());
+
+    stop_and_check_idle(engine).await;
+}
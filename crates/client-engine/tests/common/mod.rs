@@ -0,0 +1,328 @@
+//! Shared fixture for the engine integration tests: a local mock backend and
+//! helpers to start, observe and stop an engine against it.
+
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as B64;
+use reqwest::Url;
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+
+use bbr_client_chiavdf_fast::{ClassgroupElement, prove_one_weso_fast};
+use bbr_client_core::network::NetworkSettings;
+use bbr_client_core::submitter::SubmitterConfig;
+use bbr_client_engine::{
+    EngineConfig, EngineEvent, EngineHandle, JobOutcome, PinMode, WorkerStage,
+};
+
+/// The engine keeps its inflight store under `XDG_STATE_HOME`, which is process-wide:
+/// tests in one test binary must not run concurrently.
+pub static SERIAL: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+pub const TEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+pub struct MockBackend {
+    pub jobs: Mutex<VecDeque<Value>>,
+    /// Groups (the jobs of each) handed out by `/api/jobs/lease_batch`.
+    pub groups: Mutex<VecDeque<Vec<Value>>>,
+    pub leases: AtomicUsize,
+    pub submits: AtomicUsize,
+    /// Number of upcoming lease requests answered with a maintenance response.
+    pub maintenance_leases: AtomicUsize,
+    /// `renew_interval` advertised with leases (renewals go to `/api/leases/renew`).
+    pub renew_interval: Option<u64>,
+    pub renews: AtomicUsize,
+    /// Advertise a `release_url` with leases (releases go to `/api/leases/release`).
+    pub release: bool,
+    /// Job ids handed back through the release endpoint.
+    pub released: Mutex<Vec<u64>>,
+    /// Lifetime of granted leases (seconds); one hour when unset.
+    pub lease_secs: Option<i64>,
+    /// Number of upcoming submits answered with a server error.
+    pub failing_submits: AtomicUsize,
+    /// Path of every request received, in order.
+    pub paths: Mutex<Vec<String>>,
+    /// Reports posted to `/telemetry`.
+    pub telemetry: Mutex<Vec<Value>>,
+}
+
+impl MockBackend {
+    pub fn with_jobs(jobs: Vec<Value>) -> Arc<Self> {
+        Arc::new(Self {
+            jobs: Mutex::new(jobs.into()),
+            ..Self::default()
+        })
+    }
+
+    fn route(&self, path: &str, body: &[u8]) -> (u16, Value) {
+        self.paths.lock().unwrap().push(path.to_string());
+        if path == "/telemetry" {
+            let report = serde_json::from_slice(body).unwrap_or(Value::Null);
+            self.telemetry.lock().unwrap().push(report);
+            return (200, json!({}));
+        }
+        if path == "/api/jobs/lease_proofs" || path == "/api/jobs/lease_batch" {
+            self.leases.fetch_add(1, Ordering::SeqCst);
+            if self
+                .maintenance_leases
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return (
+                    503,
+                    json!({ "code": "maintenance", "message": "upgrading" }),
+                );
+            }
+            let count = serde_json::from_slice::<Value>(body)
+                .ok()
+                .and_then(|v| v["count"].as_u64())
+                .unwrap_or(1) as usize;
+            let mut lease = if path == "/api/jobs/lease_batch" {
+                let mut queue = self.groups.lock().unwrap();
+                let take = count.min(queue.len());
+                let groups: Vec<Value> = queue
+                    .drain(..take)
+                    .map(|jobs| json!({ "jobs": jobs }))
+                    .collect();
+                json!({ "groups": groups })
+            } else {
+                let mut queue = self.jobs.lock().unwrap();
+                let take = count.min(queue.len());
+                let jobs: Vec<Value> = queue.drain(..take).collect();
+                json!({ "jobs": jobs })
+            };
+            lease["lease_id"] = json!("lease-1");
+            lease["lease_expires_at"] =
+                json!(chrono::Utc::now().timestamp() + self.lease_secs.unwrap_or(3600));
+            if let Some(interval) = self.renew_interval {
+                lease["renew_interval"] = json!(interval);
+                lease["renew_url"] = json!("api/leases/renew");
+            }
+            if self.release {
+                lease["release_url"] = json!("api/leases/release");
+            }
+            return (200, lease);
+        }
+        if path == "/api/leases/release" {
+            let job_ids = serde_json::from_slice::<Value>(body)
+                .ok()
+                .and_then(|v| serde_json::from_value::<Vec<u64>>(v["job_ids"].clone()).ok())
+                .unwrap_or_default();
+            self.released.lock().unwrap().extend(job_ids);
+            return (200, json!({}));
+        }
+        if path == "/api/leases/renew" {
+            self.renews.fetch_add(1, Ordering::SeqCst);
+            return (
+                200,
+                json!({ "lease_expires_at": chrono::Utc::now().timestamp() + 3600 }),
+            );
+        }
+        if path.starts_with("/api/jobs/") && path.ends_with("/submit") {
+            if self
+                .failing_submits
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return (500, json!({ "code": "internal", "message": "boom" }));
+            }
+            self.submits.fetch_add(1, Ordering::SeqCst);
+            return (200, json!({ "reason": "accepted", "detail": "ok" }));
+        }
+        (404, json!({ "code": "not_found", "message": path }))
+    }
+}
+
+pub async fn serve_connection(mut stream: TcpStream, backend: Arc<MockBackend>) {
+    let mut buf = Vec::new();
+    loop {
+        let header_end = loop {
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+            let mut chunk = [0u8; 4096];
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+        let path = head
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("/")
+            .to_string();
+        let content_length = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+
+        while buf.len() < header_end + content_length {
+            let mut chunk = [0u8; 4096];
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            }
+        }
+        let body: Vec<u8> = buf.drain(..header_end + content_length).collect();
+
+        let (status, payload) = backend.route(&path, &body[header_end..]);
+        let payload = payload.to_string();
+        let retry_after = if status == 503 {
+            "retry-after: 1\r\n"
+        } else {
+            ""
+        };
+        let response = format!(
+            "HTTP/1.1 {status} MOCK\r\ncontent-type: application/json\r\n{retry_after}content-length: {}\r\n\r\n{payload}",
+            payload.len()
+        );
+        if stream.write_all(response.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+pub async fn spawn_backend(backend: Arc<MockBackend>) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(serve_connection(stream, backend.clone()));
+        }
+    });
+    Url::parse(&format!("http://{addr}/")).unwrap()
+}
+
+const TEST_CHALLENGE: [u8; 32] = [7u8; 32];
+const TEST_ITERATIONS: u64 = 1000;
+
+/// `y` for the test challenge, so test jobs prove without an output mismatch.
+fn test_output() -> &'static [u8] {
+    static OUTPUT: OnceLock<Vec<u8>> = OnceLock::new();
+    OUTPUT.get_or_init(|| {
+        prove_one_weso_fast(
+            &TEST_CHALLENGE,
+            &ClassgroupElement::generator().to_bytes(),
+            EngineConfig::DEFAULT_DISCRIMINANT_BITS,
+            TEST_ITERATIONS,
+        )
+        .expect("prove test output")
+        .y
+    })
+}
+
+pub fn test_job(job_id: u64) -> Value {
+    json!({
+        "job_id": job_id,
+        "height": 1,
+        "field_vdf": 1,
+        "challenge_b64": B64.encode(TEST_CHALLENGE),
+        "number_of_iterations": TEST_ITERATIONS,
+        "output_b64": B64.encode(test_output()),
+    })
+}
+
+pub fn fresh_state_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "bbr-client-engine-tests-{}-{name}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    // SAFETY: tests in each binary are serialized by `SERIAL`, and the engine only
+    // reads the variable from tasks started after this point.
+    unsafe { std::env::set_var("XDG_STATE_HOME", &dir) };
+    dir
+}
+
+pub fn test_config(backend_url: Url) -> EngineConfig {
+    EngineConfig {
+        backend_url,
+        parallel: 1,
+        auto_parallel: false,
+        low_memory_threshold_bytes: None,
+        cooperative: false,
+        use_groups: false,
+        mem_budget_bytes: 0,
+        submitter: SubmitterConfig::default(),
+        signer: None,
+        idle_sleep: Duration::from_millis(20),
+        progress_tick: Duration::ZERO,
+        recent_jobs_max: 0,
+        pin_mode: PinMode::Off,
+        cgroup: None,
+        telemetry: None,
+        schedule_seed: None,
+        preempt_at_risk: false,
+        max_iterations: None,
+        prefetch: 0,
+        lease_renew_interval: None,
+        archive_submissions: false,
+        strict_output: false,
+        discriminant_bits: EngineConfig::DEFAULT_DISCRIMINANT_BITS,
+        state_namespace: None,
+        engine_threads: None,
+        split_groups: false,
+        network: NetworkSettings::default(),
+        daily_budget: None,
+    }
+}
+
+pub async fn next_outcome(events: &mut broadcast::Receiver<EngineEvent>) -> JobOutcome {
+    loop {
+        match events.recv().await {
+            Ok(EngineEvent::JobFinished { outcome }) => return outcome,
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => panic!("engine event stream closed"),
+        }
+    }
+}
+
+pub async fn stop_and_check_idle(engine: EngineHandle) {
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        loop {
+            let snapshot = engine.snapshot();
+            if snapshot
+                .workers
+                .iter()
+                .all(|w| w.stage == WorkerStage::Idle && w.job.is_none())
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("workers did not return to idle");
+
+    engine.request_stop();
+    tokio::time::timeout(TEST_TIMEOUT, engine.wait())
+        .await
+        .expect("engine did not stop in time")
+        .expect("engine failed");
+}
+
+pub fn inflight_job_count(state_dir: &std::path::Path) -> usize {
+    let path = state_dir.join("bbr-client").join("inflight-leases.json");
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return 0;
+    };
+    let file: Value = serde_json::from_str(&raw).unwrap();
+    file["jobs"].as_array().map_or(0, Vec::len) + file["groups"].as_array().map_or(0, Vec::len)
+}
@@ -0,0 +1,144 @@
+//! Worker pause controls, event callbacks and the blocking engine facade.
+
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use bbr_client_engine::{
+    Engine, EngineControl, EngineEvent, EngineHandle, JobOutcome, start_engine,
+};
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, inflight_job_count, next_outcome,
+    spawn_backend, stop_and_check_idle, test_config, test_job,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn paused_worker_takes_no_work_until_resumed() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("pause-worker");
+
+    let backend = MockBackend::with_jobs(Vec::new());
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine(test_config(url));
+    let mut events = engine.subscribe();
+    assert!(engine.pause_worker(0));
+    assert!(!engine.pause_worker(1), "test config runs a single worker");
+
+    backend.jobs.lock().unwrap().push_back(test_job(5));
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(
+        backend.jobs.lock().unwrap().len(),
+        1,
+        "paused worker leased work"
+    );
+    assert!(engine.snapshot().workers[0].paused);
+
+    assert!(engine.resume_worker(0));
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish after resume");
+    assert_eq!(outcome.job.job_id, 5);
+    assert_eq!(outcome.submit_reason.as_deref(), Some("accepted"));
+    assert!(!engine.is_worker_paused(0));
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn blocking_facade_runs_and_stops() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("blocking");
+
+    let backend = MockBackend::with_jobs(vec![test_job(11)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let (ctrl_tx, ctrl_rx) = std::sync::mpsc::channel();
+    let (event_tx, event_rx) = std::sync::mpsc::channel();
+    ctrl_tx.send(EngineControl::Subscribe(event_tx)).unwrap();
+
+    let config = test_config(url);
+    let engine = tokio::task::spawn_blocking(move || Engine::run_blocking(config, ctrl_rx));
+
+    let outcome = tokio::task::spawn_blocking(move || {
+        loop {
+            match event_rx.recv_timeout(TEST_TIMEOUT) {
+                Ok(EngineEvent::JobFinished { outcome }) => return outcome,
+                Ok(_) => continue,
+                Err(err) => panic!("job did not finish: {err}"),
+            }
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(outcome.job.job_id, 11);
+    assert_eq!(outcome.submit_reason.as_deref(), Some("accepted"));
+
+    ctrl_tx.send(EngineControl::Stop).unwrap();
+    tokio::time::timeout(TEST_TIMEOUT, engine)
+        .await
+        .expect("engine did not stop in time")
+        .unwrap()
+        .expect("engine failed");
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn callbacks_may_register_callbacks() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("nested-callbacks");
+
+    let backend = MockBackend::with_jobs(vec![test_job(12)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = Arc::new(start_engine(test_config(url)));
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<JobOutcome>();
+    let weak: Weak<EngineHandle> = Arc::downgrade(&engine);
+    engine.on_job_finished(move |outcome| {
+        if let Some(engine) = weak.upgrade() {
+            engine.on_error(|_| {});
+        }
+        let _ = tx.send(outcome);
+    });
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, rx.recv())
+        .await
+        .expect("job did not finish")
+        .expect("callback dropped");
+    assert_eq!(outcome.job.job_id, 12);
+
+    let engine = Arc::into_inner(engine).expect("callback still holds the engine");
+    stop_and_check_idle(engine).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn slow_callbacks_do_not_hold_up_the_engine() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("slow-callback");
+
+    let backend = MockBackend::with_jobs(vec![test_job(13), test_job(14)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine(test_config(url));
+    let mut events = engine.subscribe();
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+    let release_rx = std::sync::Mutex::new(release_rx);
+    engine.on_job_finished(move |_| {
+        let _ = release_rx.lock().unwrap().recv_timeout(TEST_TIMEOUT);
+    });
+
+    // Both jobs finish while the callback for the first one is still blocked.
+    for _ in 0..2 {
+        tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+            .await
+            .expect("engine stalled behind a slow callback");
+    }
+    release_tx.send(()).unwrap();
+    release_tx.send(()).unwrap();
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
@@ -0,0 +1,53 @@
+//! Engine event stream guarantees.
+
+use serde_json::json;
+use tokio::sync::broadcast;
+
+use bbr_client_engine::{EngineEvent, start_engine};
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, spawn_backend, test_config, test_job,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn sequenced_events_have_no_gaps() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("event-seq");
+
+    let backend = MockBackend::with_jobs(vec![test_job(61)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine(test_config(url));
+    let mut events = engine.subscribe_sequenced();
+
+    let seqs = tokio::time::timeout(TEST_TIMEOUT, async {
+        let mut seqs = Vec::new();
+        loop {
+            match events.recv().await {
+                Ok(ev) => {
+                    if seqs.is_empty() {
+                        let wire = serde_json::to_value(&ev).expect("serialize event");
+                        assert_eq!(wire["seq"], json!(ev.seq));
+                        assert!(wire["type"].is_string());
+                    }
+                    seqs.push(ev.seq);
+                    if matches!(ev.event, EngineEvent::JobFinished { .. }) {
+                        engine.request_stop();
+                    }
+                    if matches!(ev.event, EngineEvent::Stopped) {
+                        break seqs;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(n)) => panic!("lagged by {n} events"),
+                Err(broadcast::error::RecvError::Closed) => panic!("engine event stream closed"),
+            }
+        }
+    })
+    .await
+    .expect("engine did not stop");
+
+    assert!(seqs[0] >= 1);
+    assert!(seqs.windows(2).all(|pair| pair[1] == pair[0] + 1));
+}
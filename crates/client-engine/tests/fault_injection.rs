@@ -0,0 +1,315 @@
+//! Chaos tests: run the engine against a local mock backend with injected faults
+//! and check it recovers without leaking workers or inflight entries.
+//!
+//! Run with `cargo test -p bbr-client-engine --features fault-injection`.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use bbr_client_engine::{
+    EngineError, EngineEvent, FaultInjector, JobOutcome, start_engine_with_faults,
+};
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, inflight_job_count, next_outcome,
+    spawn_backend, stop_and_check_idle, test_config, test_job,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn recovers_from_lease_failures_and_delays() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("lease");
+
+    let backend = MockBackend::with_jobs(Vec::new());
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine_with_faults(
+        test_config(url),
+        FaultInjector {
+            lease_failure: 0.5,
+            delay: 0.5,
+            delay_duration: Duration::from_millis(20),
+            seed: 1,
+            ..FaultInjector::default()
+        },
+    );
+    let mut events = engine.subscribe();
+
+    let mut injected = 0usize;
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        while injected == 0 || backend.leases.load(Ordering::SeqCst) < 3 {
+            if let Ok(EngineEvent::Error { message, error }) = events.recv().await
+                && message.contains("injected fault")
+            {
+                assert!(matches!(error, EngineError::Backend { job_id: None, .. }));
+                injected += 1;
+            }
+        }
+    })
+    .await
+    .expect("engine stopped leasing after injected failures");
+
+    stop_and_check_idle(engine).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn submit_conflict_drops_inflight_entry() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("submit");
+
+    let backend = MockBackend::with_jobs(vec![test_job(42)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine_with_faults(
+        test_config(url),
+        FaultInjector {
+            submit_conflict: 1.0,
+            ..FaultInjector::default()
+        },
+    );
+    let mut events = engine.subscribe();
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    assert_eq!(outcome.job.job_id, 42);
+    assert_eq!(outcome.error.as_deref(), Some("Error (lease conflict)"));
+    assert!(outcome.drop_inflight);
+    assert_eq!(outcome.submit_retries, 0);
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 0);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn held_leases_are_renewed_on_backend_schedule() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("renew");
+
+    let backend = Arc::new(MockBackend {
+        jobs: Mutex::new(vec![test_job(9)].into()),
+        renew_interval: Some(1),
+        ..MockBackend::default()
+    });
+    let url = spawn_backend(backend.clone()).await;
+
+    // Slow backend requests keep the lease held long enough for renewals.
+    let engine = start_engine_with_faults(
+        test_config(url),
+        FaultInjector {
+            delay: 1.0,
+            delay_duration: Duration::from_millis(2500),
+            ..FaultInjector::default()
+        },
+    );
+    let mut events = engine.subscribe();
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    assert_eq!(outcome.job.job_id, 9);
+    assert_eq!(outcome.submit_reason.as_deref(), Some("accepted"));
+    assert!(backend.renews.load(Ordering::SeqCst) >= 1);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn job_finished_callback_receives_outcome() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("callback");
+
+    let backend = MockBackend::with_jobs(vec![test_job(9)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine_with_faults(
+        test_config(url),
+        FaultInjector {
+            submit_conflict: 1.0,
+            ..FaultInjector::default()
+        },
+    );
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<JobOutcome>();
+    engine.on_job_finished(move |outcome| {
+        let _ = tx.send(outcome);
+    });
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, rx.recv())
+        .await
+        .expect("job did not finish")
+        .expect("callback dropped");
+    assert_eq!(outcome.job.job_id, 9);
+    assert_eq!(outcome.error.as_deref(), Some("Error (lease conflict)"));
+
+    stop_and_check_idle(engine).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn compute_errors_are_retried() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("compute");
+
+    let backend = MockBackend::with_jobs(vec![test_job(7)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    // Seed 3 fails the first compute attempt and passes the second.
+    let engine = start_engine_with_faults(
+        test_config(url.clone()),
+        FaultInjector {
+            compute_error: 0.5,
+            seed: 3,
+            ..FaultInjector::default()
+        },
+    );
+    let mut events = engine.subscribe();
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    assert_eq!(outcome.job.job_id, 7);
+    assert_eq!(outcome.error, None);
+    assert_eq!(outcome.submit_reason.as_deref(), Some("accepted"));
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 1);
+
+    // The snapshot names its backend and carries the session totals so far.
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        while engine.snapshot().session.jobs_finished == 0 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("snapshot did not record the job");
+    let snapshot = engine.snapshot();
+    assert_eq!(snapshot.backend_url, url.to_string());
+    assert_eq!(snapshot.session.jobs_submitted, 1);
+    assert_eq!(snapshot.session.jobs_accepted, 1);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn crashed_workers_are_restarted_and_work_requeued() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("restart");
+
+    let backend = MockBackend::with_jobs(vec![test_job(15)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine_with_faults(
+        test_config(url),
+        FaultInjector {
+            worker_panics: 1,
+            ..FaultInjector::default()
+        },
+    );
+    let mut events = engine.subscribe();
+
+    let mut restarted = None;
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, async {
+        loop {
+            match events.recv().await {
+                Ok(EngineEvent::WorkerRestarted {
+                    worker_idx,
+                    restarts,
+                    ..
+                }) => restarted = Some((worker_idx, restarts)),
+                Ok(EngineEvent::JobFinished { outcome }) => return outcome,
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => panic!("engine event stream closed"),
+            }
+        }
+    })
+    .await
+    .expect("requeued job did not finish");
+    assert_eq!(restarted, Some((0, 1)));
+    assert_eq!(outcome.job.job_id, 15);
+    assert_eq!(outcome.error, None);
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 1);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn paused_engine_stops_leasing_until_resumed() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("pause");
+
+    let backend = MockBackend::with_jobs(Vec::new());
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine_with_faults(
+        test_config(url),
+        FaultInjector {
+            submit_conflict: 1.0,
+            ..FaultInjector::default()
+        },
+    );
+    let mut events = engine.subscribe();
+    engine.pause();
+    assert!(engine.is_paused());
+
+    // Let any fetch started before the pause settle, then check no new leases happen.
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let leases = backend.leases.load(Ordering::SeqCst);
+    backend.jobs.lock().unwrap().push_back(test_job(31));
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(backend.leases.load(Ordering::SeqCst), leases);
+    assert!(engine.snapshot().paused);
+
+    engine.resume();
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish after resume");
+    assert_eq!(outcome.job.job_id, 31);
+    assert!(!engine.snapshot().paused);
+
+    stop_and_check_idle(engine).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn shutdown_report_summarizes_session() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("shutdown-report");
+
+    let backend = MockBackend::with_jobs(vec![test_job(51)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine_with_faults(
+        test_config(url),
+        FaultInjector {
+            submit_conflict: 1.0,
+            ..FaultInjector::default()
+        },
+    );
+    let mut events = engine.subscribe();
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    assert_eq!(outcome.job.job_id, 51);
+    stop_and_check_idle(engine).await;
+
+    let report = loop {
+        match events.recv().await {
+            Ok(EngineEvent::ShutdownReport { report }) => break report,
+            Ok(EngineEvent::Stopped) => panic!("stopped without a shutdown report"),
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => panic!("engine event stream closed"),
+        }
+    };
+    assert_eq!(report.session.jobs_finished, 1);
+    assert_eq!(report.session.jobs_failed, 1);
+    assert_eq!(report.session.jobs_submitted, 0);
+    assert_eq!(report.unsubmitted_jobs, 0);
+    assert_eq!(report.resumable_jobs, 0);
+}
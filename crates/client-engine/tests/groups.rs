@@ -0,0 +1,69 @@
+//! Group mode: sharing groups between workers.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::Ordering;
+
+use tokio::sync::broadcast;
+
+use bbr_client_engine::{EngineEvent, start_engine};
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, inflight_job_count, spawn_backend,
+    stop_and_check_idle, test_config, test_job,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn idle_workers_take_unreached_targets_of_running_groups() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("split-running");
+
+    // Too small to be halved when assigned, so the second worker can only help
+    // by taking over the running group's targets.
+    let backend = Arc::new(MockBackend {
+        groups: Mutex::new(vec![vec![test_job(81), test_job(82), test_job(83)]].into()),
+        ..MockBackend::default()
+    });
+    let url = spawn_backend(backend.clone()).await;
+
+    let mut cfg = test_config(url);
+    cfg.parallel = 2;
+    cfg.use_groups = true;
+    cfg.split_groups = true;
+    let engine = start_engine(cfg);
+    let mut events = engine.subscribe();
+
+    let mut started = Vec::new();
+    let mut finished = HashMap::new();
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        while finished.len() < 3 {
+            match events.recv().await {
+                Ok(EngineEvent::WorkerJobStarted { worker_idx, job }) => {
+                    started.push((worker_idx, job.group_proofs));
+                }
+                Ok(EngineEvent::JobFinished { outcome }) => {
+                    assert_eq!(outcome.submit_reason.as_deref(), Some("accepted"));
+                    let previous = finished.insert(outcome.job.job_id, outcome.worker_idx);
+                    assert!(previous.is_none(), "job reported twice");
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => panic!("engine event stream closed"),
+            }
+        }
+    })
+    .await
+    .expect("group did not finish");
+
+    started.sort_unstable();
+    assert_eq!(started, vec![(0, Some(3)), (1, Some(3))]);
+    let mut job_ids: Vec<u64> = finished.into_keys().collect();
+    job_ids.sort_unstable();
+    assert_eq!(job_ids, vec![81, 82, 83]);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 3);
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
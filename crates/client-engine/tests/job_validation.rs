@@ -0,0 +1,75 @@
+//! Leased jobs that fail validation never reach the prover.
+
+use std::sync::atomic::Ordering;
+
+use serde_json::json;
+
+use bbr_client_engine::start_engine;
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, inflight_job_count, next_outcome,
+    spawn_backend, stop_and_check_idle, test_config, test_job,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invalid_jobs_are_rejected_before_compute() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("invalid");
+
+    let mut job = test_job(13);
+    job["field_vdf"] = json!(9);
+    let backend = MockBackend::with_jobs(vec![job]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine(test_config(url));
+    let mut events = engine.subscribe();
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    assert_eq!(outcome.job.job_id, 13);
+    assert!(
+        outcome
+            .error
+            .as_deref()
+            .is_some_and(|err| err.starts_with("Error (invalid job: field_vdf")),
+        "unexpected outcome: {:?}",
+        outcome.error
+    );
+    assert!(outcome.drop_inflight);
+    assert_eq!(outcome.compute_ms, 0);
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 0);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unsupported_discriminant_sizes_are_rejected() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("discriminant-bits");
+
+    let mut job = test_job(14);
+    job["discriminant_size_bits"] = json!(512);
+    let backend = MockBackend::with_jobs(vec![job]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine(test_config(url));
+    let mut events = engine.subscribe();
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    assert_eq!(outcome.job.job_id, 14);
+    assert_eq!(
+        outcome.error.as_deref(),
+        Some("Error (invalid job: discriminant_size_bits 512 not supported)")
+    );
+    assert!(outcome.drop_inflight);
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 0);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
@@ -0,0 +1,123 @@
+//! Lease handling: expiry before submit, iteration caps and prefetching.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_json::json;
+use tokio::sync::broadcast;
+
+use bbr_client_engine::{EngineEvent, start_engine};
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, inflight_job_count, next_outcome,
+    spawn_backend, stop_and_check_idle, test_config, test_job,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn witnesses_whose_lease_expires_while_queued_are_dropped() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("submit-expired");
+
+    // Long enough for the proof to finish well inside the lease, which then runs
+    // out while every submit fails.
+    let backend = Arc::new(MockBackend {
+        jobs: Mutex::new(vec![test_job(44)].into()),
+        lease_secs: Some(10),
+        failing_submits: AtomicUsize::new(usize::MAX),
+        ..MockBackend::default()
+    });
+    let url = spawn_backend(backend.clone()).await;
+
+    let engine = start_engine(test_config(url));
+    let mut events = engine.subscribe();
+
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    assert_eq!(outcome.job.job_id, 44);
+    assert!(outcome.lease_expired);
+    assert!(outcome.drop_inflight);
+    assert_eq!(
+        outcome.error.as_deref(),
+        Some("Error (lease expired before submit)")
+    );
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 0);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn jobs_above_max_iterations_are_released() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("max-iters");
+
+    // The mock backend ignores the lease filter, so the engine must hand the long job back.
+    let mut long_job = test_job(1);
+    long_job["number_of_iterations"] = json!(5000);
+    let backend = Arc::new(MockBackend {
+        jobs: Mutex::new(vec![long_job, test_job(2)].into()),
+        release: true,
+        ..MockBackend::default()
+    });
+    let url = spawn_backend(backend.clone()).await;
+
+    let mut cfg = test_config(url);
+    cfg.max_iterations = Some(2000);
+    let engine = start_engine(cfg);
+    let mut events = engine.subscribe();
+
+    // The long job is never reported as finished.
+    let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("short job did not finish");
+    assert_eq!(outcome.job.job_id, 2);
+    assert_eq!(outcome.submit_reason.as_deref(), Some("accepted"));
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 1);
+    assert_eq!(*backend.released.lock().unwrap(), vec![1]);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+    let report = loop {
+        match events.recv().await {
+            Ok(EngineEvent::ShutdownReport { report }) => break report,
+            Ok(EngineEvent::Stopped) => panic!("stopped without a shutdown report"),
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => panic!("engine event stream closed"),
+        }
+    };
+    assert_eq!(report.session.jobs_finished, 1);
+    assert_eq!(report.session.jobs_failed, 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn prefetched_jobs_are_all_computed() {
+    let _serial = SERIAL.lock().await;
+    let state_dir = fresh_state_dir("prefetch");
+
+    let backend = MockBackend::with_jobs((1..=4).map(test_job).collect());
+    let url = spawn_backend(backend.clone()).await;
+
+    let mut cfg = test_config(url);
+    cfg.prefetch = 2;
+    let engine = start_engine(cfg);
+    let mut events = engine.subscribe();
+
+    let mut finished = Vec::new();
+    for _ in 0..4 {
+        let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+            .await
+            .expect("prefetched job did not finish");
+        assert_eq!(outcome.submit_reason.as_deref(), Some("accepted"));
+        finished.push(outcome.job.job_id);
+    }
+    finished.sort_unstable();
+    assert_eq!(finished, vec![1, 2, 3, 4]);
+    assert_eq!(backend.submits.load(Ordering::SeqCst), 4);
+
+    stop_and_check_idle(engine).await;
+    assert_eq!(inflight_job_count(&state_dir), 0);
+}
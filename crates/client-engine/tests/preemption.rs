@@ -0,0 +1,68 @@
+//! At-risk preemption of running proofs.
+//!
+//! Kept in its own test binary: the preempted proof can't be stopped and keeps a
+//! blocking thread busy until the process exits.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+
+use serde_json::json;
+
+use bbr_client_engine::start_engine;
+
+mod common;
+
+use common::{
+    MockBackend, TEST_TIMEOUT, fresh_state_dir, inflight_job_count, next_outcome, spawn_backend,
+    stop_and_check_idle, test_config, test_job,
+};
+
+#[test]
+fn running_proofs_that_cannot_finish_are_preempted() {
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let state_dir = fresh_state_dir("preempt-running");
+
+        // Far more squaring than any prover gets through within the lease.
+        let mut long_job = test_job(71);
+        long_job["number_of_iterations"] = json!(100_000_000u64);
+        let backend = Arc::new(MockBackend {
+            jobs: Mutex::new(vec![long_job].into()),
+            lease_secs: Some(30),
+            ..MockBackend::default()
+        });
+        let url = spawn_backend(backend.clone()).await;
+
+        let mut cfg = test_config(url);
+        cfg.preempt_at_risk = true;
+        let engine = start_engine(cfg);
+        let mut events = engine.subscribe();
+
+        let started_at = Instant::now();
+        let outcome = tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+            .await
+            .expect("running job was not preempted");
+        assert_eq!(outcome.job.job_id, 71);
+        assert_eq!(
+            outcome.error.as_deref(),
+            Some("Error (preempted: lease would expire)")
+        );
+        assert!(outcome.drop_inflight);
+        assert!(
+            started_at.elapsed().as_secs() < 30,
+            "waited for the lease to expire"
+        );
+        assert_eq!(backend.submits.load(Ordering::SeqCst), 0);
+
+        stop_and_check_idle(engine).await;
+        assert_eq!(inflight_job_count(&state_dir), 0);
+    });
+    // Don't wait for the abandoned proof.
+    rt.shutdown_background();
+}
@@ -0,0 +1,59 @@
+//! Deterministic scheduling (`schedule_seed`).
+
+use tokio::sync::broadcast;
+
+use bbr_client_engine::{EngineEvent, start_engine};
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, spawn_backend, stop_and_check_idle,
+    test_config, test_job,
+};
+
+/// Run six jobs on three workers and return which worker started each job, in
+/// start order.
+async fn assignments(seed: u64) -> Vec<(usize, u64)> {
+    fresh_state_dir("schedule-seed");
+
+    let backend = MockBackend::with_jobs((1..=6).map(test_job).collect());
+    let url = spawn_backend(backend).await;
+
+    let mut cfg = test_config(url);
+    cfg.parallel = 3;
+    cfg.schedule_seed = Some(seed);
+    let engine = start_engine(cfg);
+    let mut events = engine.subscribe();
+
+    let mut started = Vec::new();
+    let mut finished = 0;
+    tokio::time::timeout(TEST_TIMEOUT, async {
+        while finished < 6 {
+            match events.recv().await {
+                Ok(EngineEvent::WorkerJobStarted { worker_idx, job }) => {
+                    started.push((worker_idx, job.job_id));
+                }
+                Ok(EngineEvent::JobFinished { .. }) => finished += 1,
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => panic!("engine event stream closed"),
+            }
+        }
+    })
+    .await
+    .expect("jobs did not finish");
+
+    stop_and_check_idle(engine).await;
+    started
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn same_seed_assigns_jobs_in_the_same_order() {
+    let _serial = SERIAL.lock().await;
+
+    let first = assignments(7).await;
+    let mut job_ids: Vec<u64> = first.iter().map(|&(_, job_id)| job_id).collect();
+    job_ids.sort_unstable();
+    assert_eq!(job_ids, vec![1, 2, 3, 4, 5, 6]);
+
+    assert_eq!(assignments(7).await, first);
+}
@@ -0,0 +1,86 @@
+use bbr_client_engine::{
+    BudgetStatus, JobSummary, SessionStats, StatusSnapshot, WorkerSnapshot, WorkerStage,
+};
+
+#[test]
+fn status_summary_describes_workers_in_words() {
+    let snapshot = StatusSnapshot {
+        stop_requested: false,
+        paused: false,
+        workers: vec![
+            WorkerSnapshot {
+                worker_idx: 0,
+                stage: WorkerStage::Computing,
+                job: Some(JobSummary {
+                    job_id: 7,
+                    group_proofs: None,
+                    height: 123,
+                    field_vdf: 1,
+                    number_of_iterations: 1000,
+                }),
+                iters_done: 450,
+                iters_total: 1000,
+                iters_per_sec: 1_200_000,
+                paused: false,
+            },
+            WorkerSnapshot {
+                worker_idx: 1,
+                stage: WorkerStage::Idle,
+                job: None,
+                iters_done: 0,
+                iters_total: 0,
+                iters_per_sec: 0,
+                paused: false,
+            },
+        ],
+        recent_jobs: Vec::new(),
+        backend_maintenance: None,
+        backend_url: "https://backend.example/".to_string(),
+        session: SessionStats::default(),
+        last_event_seq: 0,
+        budget: None,
+    };
+
+    assert_eq!(
+        snapshot.summary_text(),
+        "Running. 1 of 2 workers busy.\n\
+         Worker 1 computing height 123, challenge chain end of slot: 45 percent, 1.2 million iterations per second.\n\
+         Worker 2 idle."
+    );
+}
+
+#[test]
+fn status_summary_reports_daily_budget() {
+    let mut snapshot = StatusSnapshot {
+        stop_requested: false,
+        paused: false,
+        workers: Vec::new(),
+        recent_jobs: Vec::new(),
+        backend_maintenance: None,
+        backend_url: "https://backend.example/".to_string(),
+        session: SessionStats::default(),
+        last_event_seq: 0,
+        budget: Some(BudgetStatus {
+            proofs_today: 3,
+            max_proofs: Some(10),
+            compute_ms_today: 5_400_000,
+            max_compute_ms: Some(8 * 3_600_000),
+            resumes_at: None,
+        }),
+    };
+    assert_eq!(
+        snapshot.summary_text(),
+        "Running. 0 of 0 workers busy. Today 3 of 10 proofs and 1.5 of 8.0 compute hours."
+    );
+
+    snapshot.budget = Some(BudgetStatus {
+        proofs_today: 10,
+        max_proofs: Some(10),
+        resumes_at: Some(1_700_000_000),
+        ..BudgetStatus::default()
+    });
+    assert_eq!(
+        snapshot.summary_text(),
+        "Running. 0 of 0 workers busy. Daily budget reached, resuming tomorrow."
+    );
+}
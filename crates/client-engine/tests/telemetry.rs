@@ -0,0 +1,117 @@
+//! Opt-in telemetry reports.
+
+use std::time::Duration;
+
+use bbr_client_core::submitter::SubmitterConfig;
+use bbr_client_engine::{TelemetryConfig, start_engine};
+
+mod common;
+
+use common::{
+    MockBackend, SERIAL, TEST_TIMEOUT, fresh_state_dir, next_outcome, spawn_backend,
+    stop_and_check_idle, test_config, test_job,
+};
+
+/// Every field a report may carry (see `TelemetryConfig`).
+const REPORT_FIELDS: [&str; 11] = [
+    "acceptance_rate",
+    "arch",
+    "iters_per_sec",
+    "jobs_accepted",
+    "jobs_finished",
+    "logical_cpus",
+    "os",
+    "parallel",
+    "uptime_secs",
+    "use_groups",
+    "version",
+];
+
+fn identified_submitter() -> SubmitterConfig {
+    SubmitterConfig {
+        reward_address: Some("xch1telemetrytestaddress".to_string()),
+        name: Some("telemetry-test-farm".to_string()),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn nothing_is_reported_without_telemetry() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("telemetry-off");
+
+    let backend = MockBackend::with_jobs(vec![test_job(91)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let mut cfg = test_config(url);
+    cfg.submitter = identified_submitter();
+    let engine = start_engine(cfg);
+    let mut events = engine.subscribe();
+
+    tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    stop_and_check_idle(engine).await;
+
+    assert!(backend.telemetry.lock().unwrap().is_empty());
+    let paths = backend.paths.lock().unwrap();
+    assert!(
+        paths.iter().all(|path| path.starts_with("/api/jobs/")),
+        "unexpected requests: {paths:?}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn reports_carry_only_aggregate_fields() {
+    let _serial = SERIAL.lock().await;
+    fresh_state_dir("telemetry-on");
+
+    let backend = MockBackend::with_jobs(vec![test_job(92)]);
+    let url = spawn_backend(backend.clone()).await;
+
+    let mut cfg = test_config(url.clone());
+    cfg.submitter = identified_submitter();
+    cfg.telemetry = Some(TelemetryConfig {
+        endpoint: url.join("telemetry").unwrap(),
+        interval: Duration::from_millis(200),
+    });
+    let engine = start_engine(cfg);
+    let mut events = engine.subscribe();
+
+    tokio::time::timeout(TEST_TIMEOUT, next_outcome(&mut events))
+        .await
+        .expect("job did not finish");
+    let report = tokio::time::timeout(TEST_TIMEOUT, async {
+        loop {
+            let reported = backend
+                .telemetry
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|report| report["jobs_finished"] == 1)
+                .cloned();
+            if let Some(report) = reported {
+                break report;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("no report after the job finished");
+    stop_and_check_idle(engine).await;
+
+    let mut fields: Vec<&str> = report
+        .as_object()
+        .expect("report is a JSON object")
+        .keys()
+        .map(String::as_str)
+        .collect();
+    fields.sort_unstable();
+    assert_eq!(fields, REPORT_FIELDS);
+    assert_eq!(report["jobs_accepted"], 1);
+    assert_eq!(report["parallel"], 1);
+
+    let raw = report.to_string();
+    assert!(!raw.contains("xch1telemetrytestaddress"));
+    assert!(!raw.contains("telemetry-test-farm"));
+}
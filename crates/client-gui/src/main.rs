@@ -1,24 +1,42 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, State, WindowEvent};
 use tokio::sync::Mutex;
 
-#[cfg(feature = "support-devtools")]
-use tauri::Manager;
-
-use bbr_client_core::submitter::{SubmitterConfig, load_submitter_config, save_submitter_config};
+use bbr_client_core::format::{
+    NumberLocale, format_duration_with, format_eta, format_number_with, humanize_submit_reason,
+};
+use bbr_client_core::network::load_network_settings;
+use bbr_client_core::settings::{
+    EventVerbosity, WorkerSettings, load_worker_settings, save_worker_settings,
+};
+use bbr_client_core::signing::{SubmissionSigner, generate_signing_key, load_signing_key};
+use bbr_client_core::submitter::{
+    SubmitterConfig, load_submitter_config, save_submitter_config, validate_reward_address,
+};
+use bbr_client_core::ui_state::{UiState, WindowState, load_ui_state, save_ui_state};
 use bbr_client_engine::{
-    EngineConfig, EngineEvent, EngineHandle, PinMode, StatusSnapshot, start_engine,
+    BackendQueueStatus, CalibrationResult, CpuInfo, DailyBudget, DoctorReport, EngineConfig,
+    EngineEvent, EngineHandle, JobHistoryEntry, NetworkStatus, PinMode, StatusSnapshot,
+    SuggestedSettings, calibrate, detect_cpu, fetch_backend_queue_status, load_job_history,
+    run_doctor, start_engine, suggest_settings,
 };
 
 struct GuiState {
     engine: Mutex<Option<EngineHandle>>,
     progress: Mutex<Vec<WorkerProgressUpdate>>,
+    queue_status: Mutex<Option<QueueStatusCache>>,
+}
+
+/// Last backend queue status fetch (successful or not).
+struct QueueStatusCache {
+    fetched_at: Instant,
+    result: Result<BackendQueueStatus, String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -34,6 +52,7 @@ impl Default for GuiState {
         Self {
             engine: Mutex::new(None),
             progress: Mutex::new(Vec::new()),
+            queue_status: Mutex::new(None),
         }
     }
 }
@@ -76,8 +95,77 @@ fn default_use_groups() -> bool {
     }
 }
 
-const GUI_PROGRESS_STEPS: u64 = 200;
 const GUI_PROGRESS_TICK: Duration = Duration::from_millis(100);
+const ONBOARDING_CALIBRATION: Duration = Duration::from_secs(15);
+/// Minimum time between backend stats requests, however often the UI asks.
+const QUEUE_STATUS_MIN_INTERVAL: Duration = Duration::from_secs(60);
+/// With [`EventVerbosity::Full`], forward a worker's progress each time it
+/// moves by this fraction of the job (1/100 = 1%).
+const PROGRESS_EVENT_STEPS: u64 = 100;
+
+/// Decides which engine events reach the webview, per [`EventVerbosity`].
+struct EventFilter {
+    verbosity: EventVerbosity,
+    /// `iters_done` last forwarded, per worker.
+    last_progress: Vec<u64>,
+}
+
+impl EventFilter {
+    fn new(verbosity: EventVerbosity) -> Self {
+        Self {
+            verbosity,
+            last_progress: Vec::new(),
+        }
+    }
+
+    fn forward(&mut self, event: &EngineEvent) -> bool {
+        match event {
+            EngineEvent::WorkerProgress {
+                worker_idx,
+                iters_done,
+                iters_total,
+                ..
+            } => {
+                if self.verbosity != EventVerbosity::Full {
+                    return false;
+                }
+                let last = self.last_progress(*worker_idx);
+                let step = (*iters_total / PROGRESS_EVENT_STEPS).max(1);
+                if iters_done.abs_diff(*last) < step {
+                    return false;
+                }
+                *last = *iters_done;
+                true
+            }
+            EngineEvent::WorkerJobStarted { worker_idx, .. } => {
+                *self.last_progress(*worker_idx) = 0;
+                self.verbosity != EventVerbosity::Quiet
+            }
+            EngineEvent::WorkerStage { .. } => self.verbosity != EventVerbosity::Quiet,
+            _ => true,
+        }
+    }
+
+    fn last_progress(&mut self, worker_idx: usize) -> &mut u64 {
+        if self.last_progress.len() <= worker_idx {
+            self.last_progress.resize(worker_idx + 1, 0);
+        }
+        &mut self.last_progress[worker_idx]
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CalibrationReport {
+    cpu: CpuInfo,
+    calibration: CalibrationResult,
+    suggested: SuggestedSettings,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OnboardingConfig {
+    submitter: SubmitterConfig,
+    settings: WorkerSettings,
+}
 
 #[tauri::command]
 async fn get_submitter_config() -> Result<Option<SubmitterConfig>, String> {
@@ -89,6 +177,134 @@ async fn set_submitter_config(cfg: SubmitterConfig) -> Result<(), String> {
     save_submitter_config(&cfg).map_err(|e| format!("{e:#}"))
 }
 
+#[tauri::command]
+async fn get_worker_settings() -> Result<Option<WorkerSettings>, String> {
+    load_worker_settings().map_err(|e| format!("{e:#}"))
+}
+
+#[tauri::command]
+async fn detect_cpu_capability() -> Result<CpuInfo, String> {
+    Ok(detect_cpu())
+}
+
+#[tauri::command]
+async fn run_calibration(state: State<'_, Arc<GuiState>>) -> Result<CalibrationReport, String> {
+    // Hold the engine lock so the client cannot be started mid-calibration.
+    let guard = state.engine.lock().await;
+    if guard.is_some() {
+        return Err("Stop the client before running calibration.".to_string());
+    }
+
+    let calibration = tokio::task::spawn_blocking(|| calibrate(ONBOARDING_CALIBRATION))
+        .await
+        .map_err(|e| format!("calibration task failed: {e}"))?
+        .map_err(|e| format!("{e:#}"))?;
+    drop(guard);
+
+    let cpu = detect_cpu();
+    let suggested = suggest_settings(&cpu, &calibration);
+    Ok(CalibrationReport {
+        cpu,
+        calibration,
+        suggested,
+    })
+}
+
+/// Run the same checks as `wesoforge doctor` and return the report for the
+/// troubleshooting panel.
+#[tauri::command]
+async fn troubleshoot(state: State<'_, Arc<GuiState>>) -> Result<DoctorReport, String> {
+    // The state check repairs files in place, so the engine must not be running.
+    let guard = state.engine.lock().await;
+    if guard.is_some() {
+        return Err("Stop the client before running troubleshooting.".to_string());
+    }
+    let report = run_doctor(&default_backend_url(), None).await;
+    drop(guard);
+    Ok(report)
+}
+
+#[tauri::command]
+async fn check_reward_address(address: String) -> Result<(), String> {
+    validate_reward_address(&address).map_err(|e| format!("{e:#}"))
+}
+
+/// A value for the frontend to display, formatted the same way as the CLI.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+enum FormatRequest {
+    Number(u64),
+    DurationMs(u64),
+    EtaSecs(u64),
+    SubmitReason(String),
+}
+
+#[tauri::command]
+async fn format_values(
+    values: Vec<FormatRequest>,
+    locale: Option<NumberLocale>,
+) -> Result<Vec<String>, String> {
+    let locale = locale.unwrap_or_default();
+    Ok(values
+        .into_iter()
+        .map(|value| match value {
+            FormatRequest::Number(n) => format_number_with(n, locale),
+            FormatRequest::DurationMs(ms) => {
+                format_duration_with(Duration::from_millis(ms), locale)
+            }
+            FormatRequest::EtaSecs(secs) => format_eta(secs),
+            FormatRequest::SubmitReason(reason) => humanize_submit_reason(&reason),
+        })
+        .collect())
+}
+
+/// Public half of the submission signing key, for registering with a backend.
+#[derive(Debug, Clone, Serialize)]
+struct SigningKeyInfo {
+    public_key: String,
+    fingerprint: String,
+}
+
+impl From<&SubmissionSigner> for SigningKeyInfo {
+    fn from(signer: &SubmissionSigner) -> Self {
+        Self {
+            public_key: signer.public_key_b64(),
+            fingerprint: signer.fingerprint(),
+        }
+    }
+}
+
+#[tauri::command]
+async fn get_signing_key() -> Result<Option<SigningKeyInfo>, String> {
+    load_signing_key()
+        .map(|signer| signer.as_ref().map(SigningKeyInfo::from))
+        .map_err(|e| format!("{e:#}"))
+}
+
+/// Create the signing key; submissions are signed from the next start on.
+#[tauri::command]
+async fn create_signing_key(overwrite: bool) -> Result<SigningKeyInfo, String> {
+    generate_signing_key(overwrite)
+        .map(|signer| SigningKeyInfo::from(&signer))
+        .map_err(|e| format!("{e:#}"))
+}
+
+#[tauri::command]
+async fn save_onboarding(cfg: OnboardingConfig) -> Result<(), String> {
+    if let Some(address) = cfg.submitter.reward_address.as_deref() {
+        if !address.trim().is_empty() {
+            validate_reward_address(address).map_err(|e| format!("{e:#}"))?;
+        }
+    }
+    if let Some(parallel) = cfg.settings.parallel {
+        if !(1..=512).contains(&parallel) {
+            return Err("Parallel workers must be between 1 and 512.".to_string());
+        }
+    }
+    save_submitter_config(&cfg.submitter).map_err(|e| format!("{e:#}"))?;
+    save_worker_settings(&cfg.settings).map_err(|e| format!("{e:#}"))
+}
+
 #[tauri::command]
 async fn engine_progress(
     state: State<'_, Arc<GuiState>>,
@@ -114,6 +330,21 @@ async fn start_client(
         Ok(None) => SubmitterConfig::default(),
         Err(err) => return Err(format!("{err:#}")),
     };
+    let signer = load_signing_key().map_err(|err| format!("{err:#}"))?;
+
+    let network = load_network_settings().map_err(|err| format!("{err:#}"))?;
+    let settings = load_worker_settings()
+        .map_err(|err| format!("{err:#}"))?
+        .unwrap_or_default();
+    let max_iterations = settings.max_iterations;
+    let daily_budget = DailyBudget {
+        max_proofs: settings.daily_max_proofs.filter(|n| *n > 0),
+        max_compute: settings
+            .daily_max_cpu_hours
+            .and_then(|hours| Duration::try_from_secs_f64(hours * 3600.0).ok())
+            .filter(|d| !d.is_zero()),
+    };
+    let mut filter = EventFilter::new(settings.event_verbosity.unwrap_or_default());
 
     let parallel = opts.parallel.unwrap_or(4);
     if !(1..=512).contains(&parallel) {
@@ -138,17 +369,35 @@ async fn start_client(
     let engine = start_engine(EngineConfig {
         backend_url: default_backend_url(),
         parallel,
+        auto_parallel: false,
+        low_memory_threshold_bytes: None,
+        cooperative: true,
         use_groups,
         mem_budget_bytes,
         submitter,
+        signer,
         idle_sleep: Duration::ZERO,
-        progress_steps: GUI_PROGRESS_STEPS,
         progress_tick: GUI_PROGRESS_TICK,
         recent_jobs_max: EngineConfig::DEFAULT_RECENT_JOBS_MAX,
         pin_mode: PinMode::Off,
+        cgroup: None,
+        telemetry: None,
+        schedule_seed: None,
+        preempt_at_risk: false,
+        max_iterations,
+        prefetch: 0,
+        lease_renew_interval: None,
+        archive_submissions: false,
+        strict_output: false,
+        discriminant_bits: EngineConfig::DEFAULT_DISCRIMINANT_BITS,
+        state_namespace: None,
+        engine_threads: None,
+        split_groups: false,
+        network,
+        daily_budget: (daily_budget != DailyBudget::default()).then_some(daily_budget),
     });
 
-    let mut events = engine.subscribe();
+    let mut events = engine.subscribe_sequenced();
     let app = app.clone();
 
     {
@@ -165,14 +414,26 @@ async fn start_client(
         }
     }
     tokio::spawn(async move {
+        let mut last_seq: Option<u64> = None;
         loop {
             let ev = match events.recv().await {
                 Ok(ev) => ev,
+                // The gap shows up in the next event's sequence number.
                 Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                 Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
             };
+            if let Some(last) = last_seq {
+                if ev.seq != last + 1 {
+                    // Events were dropped; have the UI reload the snapshot instead
+                    // of showing stale workers.
+                    let missed = ev.seq.saturating_sub(last + 1);
+                    let _ = app.emit("engine-resync", missed);
+                }
+            }
+            last_seq = Some(ev.seq);
 
-            match &ev {
+            let forward = filter.forward(&ev.event);
+            match &ev.event {
                 EngineEvent::WorkerProgress {
                     worker_idx,
                     iters_done,
@@ -196,6 +457,9 @@ async fn start_client(
                         iters_total: *iters_total,
                         iters_per_sec: *effective_iters_per_sec,
                     };
+                    if forward {
+                        let _ = app.emit("engine-event", ev);
+                    }
                 }
                 EngineEvent::WorkerJobStarted { worker_idx, job } => {
                     {
@@ -216,7 +480,9 @@ async fn start_client(
                             iters_per_sec: 0,
                         };
                     }
-                    let _ = app.emit("engine-event", ev);
+                    if forward {
+                        let _ = app.emit("engine-event", ev);
+                    }
                 }
                 EngineEvent::JobFinished { outcome } => {
                     let worker_idx = outcome.worker_idx;
@@ -238,15 +504,37 @@ async fn start_client(
                             iters_per_sec: 0,
                         };
                     }
-                    let _ = app.emit("engine-event", ev);
+                    if forward {
+                        let _ = app.emit("engine-event", ev);
+                    }
                 }
-                EngineEvent::Error { message } => {
+                EngineEvent::WorkerRestarted { worker_idx, .. } => {
+                    {
+                        let mut progress = state_for_task.progress.lock().await;
+                        if let Some(slot) = progress.get_mut(*worker_idx) {
+                            *slot = WorkerProgressUpdate {
+                                worker_idx: *worker_idx,
+                                iters_done: 0,
+                                iters_total: 0,
+                                iters_per_sec: 0,
+                            };
+                        }
+                    }
+                    if forward {
+                        let _ = app.emit("engine-event", ev);
+                    }
+                }
+                EngineEvent::Error { message, .. } => {
                     eprintln!("{message}");
-                    let _ = app.emit("engine-event", ev);
+                    if forward {
+                        let _ = app.emit("engine-event", ev);
+                    }
                 }
                 _ => {
-                    let is_stopped = matches!(ev, EngineEvent::Stopped);
-                    let _ = app.emit("engine-event", ev);
+                    let is_stopped = matches!(ev.event, EngineEvent::Stopped);
+                    if forward {
+                        let _ = app.emit("engine-event", ev);
+                    }
                     if is_stopped {
                         break;
                     }
@@ -275,6 +563,37 @@ async fn stop_client(state: State<'_, Arc<GuiState>>) -> Result<(), String> {
     Ok(())
 }
 
+/// Finish the worker's current job, then keep it idle until `resume_worker`.
+#[tauri::command]
+async fn pause_worker(state: State<'_, Arc<GuiState>>, worker_idx: usize) -> Result<(), String> {
+    set_worker_paused(&state, worker_idx, true).await
+}
+
+#[tauri::command]
+async fn resume_worker(state: State<'_, Arc<GuiState>>, worker_idx: usize) -> Result<(), String> {
+    set_worker_paused(&state, worker_idx, false).await
+}
+
+async fn set_worker_paused(
+    state: &GuiState,
+    worker_idx: usize,
+    paused: bool,
+) -> Result<(), String> {
+    let guard = state.engine.lock().await;
+    let Some(engine) = guard.as_ref() else {
+        return Err("client is not running".to_string());
+    };
+    let found = if paused {
+        engine.pause_worker(worker_idx)
+    } else {
+        engine.resume_worker(worker_idx)
+    };
+    if !found {
+        return Err(format!("no worker {}", worker_idx + 1));
+    }
+    Ok(())
+}
+
 #[tauri::command]
 async fn client_running(state: State<'_, Arc<GuiState>>) -> Result<bool, String> {
     let guard = state.engine.lock().await;
@@ -289,6 +608,191 @@ async fn engine_snapshot(
     Ok(guard.as_ref().map(|engine| engine.snapshot()))
 }
 
+/// Screen-reader friendly description of the engine state (`None` when not running).
+#[tauri::command]
+async fn status_summary(state: State<'_, Arc<GuiState>>) -> Result<Option<String>, String> {
+    let guard = state.engine.lock().await;
+    Ok(guard
+        .as_ref()
+        .map(|engine| engine.snapshot().summary_text()))
+}
+
+/// Global compaction progress from the backend's public stats endpoint.
+///
+/// Results (including failures) are cached for `QUEUE_STATUS_MIN_INTERVAL`;
+/// concurrent calls wait on the same request.
+#[tauri::command]
+async fn backend_queue_status(
+    state: State<'_, Arc<GuiState>>,
+) -> Result<BackendQueueStatus, String> {
+    let mut cache = state.queue_status.lock().await;
+    if let Some(cached) = cache.as_ref() {
+        if cached.fetched_at.elapsed() < QUEUE_STATUS_MIN_INTERVAL {
+            return cached.result.clone();
+        }
+    }
+
+    let result = match load_network_settings() {
+        Ok(network) => fetch_backend_queue_status(&default_backend_url(), &network)
+            .await
+            .map_err(|e| format!("{e:#}")),
+        Err(err) => Err(format!("{err:#}")),
+    };
+    *cache = Some(QueueStatusCache {
+        fetched_at: Instant::now(),
+        result: result.clone(),
+    });
+    result
+}
+
+#[tauri::command]
+async fn network_status(state: State<'_, Arc<GuiState>>) -> Result<Option<NetworkStatus>, String> {
+    let guard = state.engine.lock().await;
+    Ok(guard.as_ref().map(|engine| engine.network_status()))
+}
+
+/// Time window for [`export_history`], as unix timestamps (seconds, inclusive).
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+struct HistoryRange {
+    since: Option<i64>,
+    until: Option<i64>,
+}
+
+const HISTORY_CSV_HEADER: &str = "finished_at,backend_url,job_id,height,field_vdf,number_of_iterations,group_proofs,submit_reason,submit_detail,error,output_mismatch,compute_ms,submit_ms,total_ms";
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn history_csv_row(entry: &JobHistoryEntry) -> String {
+    let outcome = &entry.outcome;
+    let job = &outcome.job;
+    [
+        entry.finished_at.to_string(),
+        csv_field(&entry.backend_url),
+        job.job_id.to_string(),
+        job.height.to_string(),
+        job.field_vdf.to_string(),
+        job.number_of_iterations.to_string(),
+        job.group_proofs.map(|n| n.to_string()).unwrap_or_default(),
+        csv_field(outcome.submit_reason.as_deref().unwrap_or_default()),
+        csv_field(outcome.submit_detail.as_deref().unwrap_or_default()),
+        csv_field(outcome.error.as_deref().unwrap_or_default()),
+        outcome.output_mismatch.to_string(),
+        outcome.compute_ms.to_string(),
+        outcome.submit_ms.to_string(),
+        outcome.total_ms.to_string(),
+    ]
+    .join(",")
+}
+
+fn write_history_export(path: &std::path::Path, range: HistoryRange) -> anyhow::Result<usize> {
+    let entries: Vec<JobHistoryEntry> = load_job_history(range.since)?
+        .into_iter()
+        .filter(|entry| range.until.is_none_or(|until| entry.finished_at <= until))
+        .collect();
+
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let body = if is_json {
+        serde_json::to_vec_pretty(&entries)?
+    } else {
+        let mut out = String::from(HISTORY_CSV_HEADER);
+        out.push('\n');
+        for entry in &entries {
+            out.push_str(&history_csv_row(entry));
+            out.push('\n');
+        }
+        out.into_bytes()
+    };
+    std::fs::write(path, body)?;
+    Ok(entries.len())
+}
+
+/// Export the local job history to `path` (JSON when it ends in `.json`, CSV
+/// otherwise). Returns the number of jobs written.
+#[tauri::command]
+async fn export_history(path: String, range: Option<HistoryRange>) -> Result<usize, String> {
+    let range = range.unwrap_or_default();
+    tokio::task::spawn_blocking(move || write_history_export(std::path::Path::new(&path), range))
+        .await
+        .map_err(|e| format!("export task failed: {e}"))?
+        .map_err(|e| format!("{e:#}"))
+}
+
+#[tauri::command]
+async fn get_ui_state() -> Result<UiState, String> {
+    load_ui_state().map_err(|e| format!("{e:#}"))
+}
+
+/// Save the dashboard layout. The window geometry is recorded by the backend
+/// when the window closes, so an unset `window` keeps the saved one.
+#[tauri::command]
+async fn set_ui_state(ui_state: UiState) -> Result<(), String> {
+    let mut ui_state = ui_state;
+    if ui_state.window.is_none() {
+        ui_state.window = load_ui_state().ok().and_then(|saved| saved.window);
+    }
+    save_ui_state(&ui_state).map_err(|e| format!("{e:#}"))
+}
+
+/// Put `window` back where it was when the GUI last closed. The position is only
+/// restored when it still lies on a connected monitor.
+fn restore_window_state(window: &tauri::WebviewWindow) {
+    let Some(saved) = load_ui_state().ok().and_then(|ui_state| ui_state.window) else {
+        return;
+    };
+    if !saved.is_restorable() {
+        return;
+    }
+    let _ = window.set_size(PhysicalSize::new(saved.width, saved.height));
+    let on_screen = window.available_monitors().is_ok_and(|monitors| {
+        monitors.iter().any(|monitor| {
+            let origin = monitor.position();
+            let size = monitor.size();
+            (origin.x..origin.x.saturating_add_unsigned(size.width)).contains(&saved.x)
+                && (origin.y..origin.y.saturating_add_unsigned(size.height)).contains(&saved.y)
+        })
+    });
+    if on_screen {
+        let _ = window.set_position(PhysicalPosition::new(saved.x, saved.y));
+    }
+    if saved.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Record `window`'s geometry in the `ui_state` section. A maximized window
+/// keeps the saved normal geometry so unmaximizing after a restart still works.
+fn save_window_state(window: &tauri::Window) -> anyhow::Result<()> {
+    let mut ui_state = load_ui_state()?;
+    let maximized = window.is_maximized()?;
+    let current = match ui_state.window {
+        Some(saved) if maximized => WindowState { maximized, ..saved },
+        _ => {
+            let position = window.outer_position()?;
+            let size = window.inner_size()?;
+            WindowState {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                maximized,
+            }
+        }
+    };
+    if window.is_minimized()? || !current.is_restorable() {
+        return Ok(());
+    }
+    ui_state.window = Some(current);
+    save_ui_state(&ui_state)
+}
+
 fn main() {
     #[cfg(target_os = "linux")]
     {
@@ -307,23 +811,47 @@ fn main() {
     tauri::Builder::default()
         .manage(state)
         .setup(|app| {
-            let _ = app;
-            #[cfg(feature = "support-devtools")]
-            {
-                if let Some(win) = app.get_webview_window("main") {
-                    win.open_devtools();
-                }
+            if let Some(win) = app.get_webview_window("main") {
+                restore_window_state(&win);
+                #[cfg(feature = "support-devtools")]
+                win.open_devtools();
             }
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if let WindowEvent::CloseRequested { .. } = event {
+                if window.label() == "main" {
+                    if let Err(err) = save_window_state(window) {
+                        eprintln!("failed to save window state: {err:#}");
+                    }
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             get_submitter_config,
             set_submitter_config,
+            get_worker_settings,
+            detect_cpu_capability,
+            run_calibration,
+            troubleshoot,
+            check_reward_address,
+            format_values,
+            get_signing_key,
+            create_signing_key,
+            save_onboarding,
             engine_progress,
             start_client,
             stop_client,
+            pause_worker,
+            resume_worker,
             client_running,
-            engine_snapshot
+            engine_snapshot,
+            status_summary,
+            network_status,
+            backend_queue_status,
+            export_history,
+            get_ui_state,
+            set_ui_state
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
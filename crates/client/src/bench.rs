@@ -1,27 +1,31 @@
-use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use base64::Engine as _;
 use base64::engine::general_purpose::STANDARD as B64;
 
 use bbr_client_chiavdf_fast::{
-    ChiavdfBatchJob, prove_one_weso_fast, prove_one_weso_fast_streaming_getblock_opt,
-    prove_one_weso_fast_streaming_getblock_opt_batch,
+    ChiavdfBatchJob, ClassgroupElement, ProofOutput, ProverBuilder, prove_one_weso_fast,
+    prove_one_weso_fast_streaming_getblock_opt, prove_one_weso_fast_streaming_getblock_opt_batch,
 };
+use bbr_client_engine::EngineConfig;
 
 use crate::cli::WorkMode;
-use crate::constants::default_classgroup_element;
 use crate::format::{format_duration, format_number};
 
-const BENCH_DISCRIMINANT_BITS: usize = 1024;
+const BENCH_DISCRIMINANT_BITS: usize = EngineConfig::DEFAULT_DISCRIMINANT_BITS;
 const BENCH_ITERS: u64 = 14_576_841;
 const WARMUP_ITERS: u64 = 10_000;
 const GROUP_PROOFS_PER_BATCH: usize = 8;
 const PROOF_ROUNDS_PER_WORKER: usize = 1;
 const GROUP_ROUNDS_PER_WORKER: usize = 3;
+/// Progress callback spacing for soak rounds; gaps between callbacks are the "pauses".
+const SOAK_PROGRESS_INTERVAL: u64 = 100_000;
+/// How many distinct native error messages the soak report lists.
+const SOAK_MAX_ERRORS_SHOWN: usize = 5;
 const BENCH_Y_REF_B64: &str = "AABi49IsOPkm3kNS+NW8BLw7jLR/QG2nKwsJ4VIRB+o+C5HAtC7XLoCvOHx/8CIA7fxD1esqHcB+RftlEwdKIMM692W2YUI7xwt4VJe3UoPc3zffkeZ5elOWDP/PO7DL00QBAA==";
 const BENCH_CHALLENGE: [u8; 32] = [
     0x62, 0x62, 0x72, 0x2d, 0x63, 0x6c, 0x69, 0x65, 0x6e, 0x74, 0x2d, 0x62, 0x65, 0x6e, 0x63, 0x68,
@@ -50,7 +54,7 @@ pub fn run_benchmark(mode: WorkMode, parallel: usize) -> anyhow::Result<()> {
         .checked_mul(proofs_per_task)
         .ok_or_else(|| anyhow::anyhow!("benchmark proof count overflow"))?;
 
-    let x = default_classgroup_element();
+    let x = ClassgroupElement::generator();
 
     if BENCH_Y_REF_B64.starts_with("<fill-me") {
         anyhow::bail!("bench vector missing: set BENCH_Y_REF_B64 to a valid base64-encoded y_ref")
@@ -60,8 +64,13 @@ pub fn run_benchmark(mode: WorkMode, parallel: usize) -> anyhow::Result<()> {
         .decode(BENCH_Y_REF_B64.as_bytes())
         .context("decode BENCH_Y_REF_B64")?;
 
-    let _ = prove_one_weso_fast(&BENCH_CHALLENGE, &x, BENCH_DISCRIMINANT_BITS, WARMUP_ITERS)
-        .context("warmup prove_one_weso_fast")?;
+    let _ = prove_one_weso_fast(
+        &BENCH_CHALLENGE,
+        x.as_bytes(),
+        BENCH_DISCRIMINANT_BITS,
+        WARMUP_ITERS,
+    )
+    .context("warmup prove_one_weso_fast")?;
 
     println!("Benchmark mode: {mode_label}");
     println!("Parallel workers: {}", format_number(parallel as u64));
@@ -95,8 +104,8 @@ pub fn run_benchmark(mode: WorkMode, parallel: usize) -> anyhow::Result<()> {
                 }
 
                 match mode {
-                    WorkMode::Proof => run_proof_task(&x, y_ref.as_slice())?,
-                    WorkMode::Group => run_group_task(&x, y_ref.as_slice())?,
+                    WorkMode::Proof => run_proof_task(x.as_bytes(), y_ref.as_slice())?,
+                    WorkMode::Group => run_group_task(x.as_bytes(), y_ref.as_slice())?,
                 }
             }
             Ok(())
@@ -118,6 +127,234 @@ pub fn run_benchmark(mode: WorkMode, parallel: usize) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// One finished soak round (a group of proofs computed by one worker).
+#[derive(Debug, Clone, Copy)]
+struct SoakRound {
+    duration: Duration,
+    proofs: usize,
+}
+
+impl SoakRound {
+    fn proofs_per_sec(&self) -> f64 {
+        self.proofs as f64 / self.duration.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Stability metrics over the rounds of a soak run, in completion order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SoakStats {
+    mean: f64,
+    stddev: f64,
+    /// Relative change from the first third of rounds to the last third.
+    drift: f64,
+}
+
+impl SoakStats {
+    fn from_rounds(rounds: &[SoakRound]) -> Option<Self> {
+        if rounds.is_empty() {
+            return None;
+        }
+        let rates: Vec<f64> = rounds.iter().map(SoakRound::proofs_per_sec).collect();
+        let mean = rates.iter().sum::<f64>() / rates.len() as f64;
+        let variance = rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / rates.len() as f64;
+
+        let window = (rates.len() / 3).max(1);
+        let window_mean = |w: &[f64]| w.iter().sum::<f64>() / w.len() as f64;
+        let first = window_mean(&rates[..window]);
+        let last = window_mean(&rates[rates.len() - window..]);
+        let drift = if first > 0.0 {
+            (last - first) / first
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            mean,
+            stddev: variance.sqrt(),
+            drift,
+        })
+    }
+}
+
+/// Run group proofs on `parallel` workers until `duration` has elapsed, then report
+/// throughput drift, per-round variance, the longest stall between progress updates
+/// and any native errors. Fails if any round errored.
+///
+/// Rounds already running at the deadline are allowed to finish.
+pub fn run_soak(duration: Duration, parallel: usize) -> anyhow::Result<()> {
+    let parallel = parallel.max(1);
+    let x = ClassgroupElement::generator();
+    let y_ref = B64
+        .decode(BENCH_Y_REF_B64.as_bytes())
+        .context("decode BENCH_Y_REF_B64")?;
+
+    let _ = prove_one_weso_fast(
+        &BENCH_CHALLENGE,
+        x.as_bytes(),
+        BENCH_DISCRIMINANT_BITS,
+        WARMUP_ITERS,
+    )
+    .context("warmup prove_one_weso_fast")?;
+
+    println!("Soak mode: group");
+    println!("Parallel workers: {}", format_number(parallel as u64));
+    println!(
+        "Group size: {} proofs",
+        format_number(GROUP_PROOFS_PER_BATCH as u64)
+    );
+    println!("Iterations per proof: {}", format_number(BENCH_ITERS));
+    println!("Soak duration: {}", format_duration(duration));
+
+    let y_ref = Arc::new(y_ref);
+    let rounds = Arc::new(Mutex::new(Vec::<SoakRound>::new()));
+    let errors = Arc::new(Mutex::new(Vec::<String>::new()));
+    let max_pause_us = Arc::new(AtomicU64::new(0));
+
+    let started_at = Instant::now();
+    let deadline = started_at + duration;
+    let mut handles = Vec::with_capacity(parallel);
+    for worker_idx in 0..parallel {
+        let y_ref = y_ref.clone();
+        let rounds = rounds.clone();
+        let errors = errors.clone();
+        let max_pause_us = max_pause_us.clone();
+
+        handles.push(thread::spawn(move || {
+            while Instant::now() < deadline {
+                let round_started = Instant::now();
+                let result = run_soak_round(x.as_bytes(), y_ref.as_slice(), max_pause_us.clone());
+                let round_duration = round_started.elapsed();
+                match result {
+                    Ok(()) => {
+                        let mut rounds = match rounds.lock() {
+                            Ok(g) => g,
+                            Err(poisoned) => poisoned.into_inner(),
+                        };
+                        rounds.push(SoakRound {
+                            duration: round_duration,
+                            proofs: GROUP_PROOFS_PER_BATCH,
+                        });
+                        let done = rounds.len();
+                        drop(rounds);
+                        println!(
+                            "round {done} (worker {}): {:.2} proofs/s after {}",
+                            worker_idx + 1,
+                            GROUP_PROOFS_PER_BATCH as f64 / round_duration.as_secs_f64(),
+                            format_duration(started_at.elapsed())
+                        );
+                    }
+                    Err(err) => {
+                        let err = format!("{err:#}");
+                        eprintln!("worker {}: {err}", worker_idx + 1);
+                        match errors.lock() {
+                            Ok(mut g) => g.push(err),
+                            Err(poisoned) => poisoned.into_inner().push(err),
+                        }
+                    }
+                }
+            }
+        }));
+    }
+
+    for handle in handles {
+        if handle.join().is_err() {
+            anyhow::bail!("soak worker thread panicked");
+        }
+    }
+
+    let elapsed = started_at.elapsed();
+    let rounds = match rounds.lock() {
+        Ok(g) => g.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    let errors = match errors.lock() {
+        Ok(g) => g.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    let total_proofs: usize = rounds.iter().map(|r| r.proofs).sum();
+
+    println!("Duration: {}", format_duration(elapsed));
+    println!("Rounds: {}", format_number(rounds.len() as u64));
+    println!(
+        "Throughput: {:.2} proofs/s",
+        total_proofs as f64 / elapsed.as_secs_f64()
+    );
+    if let Some(stats) = SoakStats::from_rounds(&rounds) {
+        println!(
+            "Per-round throughput: {:.3} proofs/s mean, {:.3} stddev ({:.1}% variation)",
+            stats.mean,
+            stats.stddev,
+            100.0 * stats.stddev / stats.mean.max(f64::EPSILON)
+        );
+        println!(
+            "Throughput drift (first vs last third): {:+.1}%",
+            100.0 * stats.drift
+        );
+    }
+    println!(
+        "Max pause between progress updates: {}",
+        format_duration(Duration::from_micros(max_pause_us.load(Ordering::Relaxed)))
+    );
+    println!("Native errors: {}", format_number(errors.len() as u64));
+
+    if !errors.is_empty() {
+        let mut distinct: Vec<&String> = Vec::new();
+        for err in &errors {
+            if !distinct.contains(&err) {
+                distinct.push(err);
+            }
+        }
+        for err in distinct.iter().take(SOAK_MAX_ERRORS_SHOWN) {
+            println!("  {err}");
+        }
+        anyhow::bail!("soak finished with {} failed round(s)", errors.len());
+    }
+    Ok(())
+}
+
+/// One group round that records the longest gap between progress callbacks
+/// (including the start and end of the round) into `max_pause_us`.
+fn run_soak_round(x: &[u8], y_ref: &[u8], max_pause_us: Arc<AtomicU64>) -> anyhow::Result<()> {
+    let jobs = vec![
+        ChiavdfBatchJob {
+            y_ref_s: y_ref,
+            num_iterations: BENCH_ITERS,
+        };
+        GROUP_PROOFS_PER_BATCH
+    ];
+    let last_progress = Arc::new(Mutex::new(Instant::now()));
+    let record_pause = move || {
+        let now = Instant::now();
+        let mut last = match last_progress.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let pause = now.duration_since(*last).as_micros() as u64;
+        *last = now;
+        max_pause_us.fetch_max(pause, Ordering::Relaxed);
+    };
+    let on_progress = record_pause.clone();
+
+    let out = ProverBuilder::new(&BENCH_CHALLENGE, x, BENCH_DISCRIMINANT_BITS)
+        .progress(SOAK_PROGRESS_INTERVAL, move |_| on_progress())
+        .prove_batch(&jobs)
+        .context("soak batch prove with progress")?;
+    record_pause();
+
+    if out.len() != GROUP_PROOFS_PER_BATCH {
+        anyhow::bail!(
+            "unexpected batch output count (got {}, expected {})",
+            out.len(),
+            GROUP_PROOFS_PER_BATCH
+        );
+    }
+    for item in out {
+        let item = item.context("soak batch job")?;
+        validate_output(&item, y_ref)?;
+    }
+    Ok(())
+}
+
 fn run_proof_task(x: &[u8], y_ref: &[u8]) -> anyhow::Result<()> {
     let out = prove_one_weso_fast_streaming_getblock_opt(
         &BENCH_CHALLENGE,
@@ -161,15 +398,11 @@ fn run_group_task(x: &[u8], y_ref: &[u8]) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn validate_output(out: &[u8], y_ref: &[u8]) -> anyhow::Result<()> {
-    if out.len() < 2 || out.len() % 2 != 0 {
-        anyhow::bail!("unexpected output length {}", out.len());
-    }
-    let (y, witness) = out.split_at(out.len() / 2);
-    if y != y_ref {
+fn validate_output(out: &ProofOutput, y_ref: &[u8]) -> anyhow::Result<()> {
+    if out.y != y_ref {
         anyhow::bail!("output mismatch against benchmark y_ref");
     }
-    if witness.is_empty() {
+    if out.witness.is_empty() {
         anyhow::bail!("empty witness");
     }
     Ok(())
@@ -177,14 +410,36 @@ fn validate_output(out: &[u8], y_ref: &[u8]) -> anyhow::Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::validate_output;
+    use super::{ProofOutput, SoakRound, SoakStats, validate_output};
+    use std::time::Duration;
 
     #[test]
-    fn validate_output_checks_length_and_payload() {
+    fn validate_output_checks_y_and_witness() {
         let y_ref = vec![1_u8, 2_u8];
+        let output = |y: &[u8], witness: &[u8]| ProofOutput {
+            y: y.to_vec(),
+            witness: witness.to_vec(),
+        };
+
+        assert!(validate_output(&output(&[1, 2], &[9, 9]), &y_ref).is_ok());
+        assert!(validate_output(&output(&[1, 2], &[]), &y_ref).is_err());
+        assert!(validate_output(&output(&[1, 3], &[9, 9]), &y_ref).is_err());
+    }
+
+    #[test]
+    fn soak_stats_report_variance_and_drift() {
+        assert_eq!(SoakStats::from_rounds(&[]), None);
 
-        assert!(validate_output(&[1_u8, 2_u8, 9_u8, 9_u8], &y_ref).is_ok());
-        assert!(validate_output(&[1_u8, 2_u8, 9_u8], &y_ref).is_err());
-        assert!(validate_output(&[1_u8, 3_u8, 9_u8, 9_u8], &y_ref).is_err());
+        let rounds: Vec<SoakRound> = [4, 4, 4, 5, 5, 5]
+            .into_iter()
+            .map(|secs| SoakRound {
+                duration: Duration::from_secs(secs),
+                proofs: 20,
+            })
+            .collect();
+        let stats = SoakStats::from_rounds(&rounds).unwrap();
+        assert!((stats.mean - 4.5).abs() < 1e-9);
+        assert!((stats.stddev - 0.5).abs() < 1e-9);
+        assert!((stats.drift + 0.2).abs() < 1e-9);
     }
 }
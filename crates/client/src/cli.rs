@@ -1,4 +1,8 @@
-use clap::{Parser, ValueEnum};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use reqwest::Url;
 
 use bbr_client_engine::PinMode;
@@ -60,6 +64,71 @@ fn parse_mem_budget_bytes(input: &str) -> Result<u64, String> {
         .ok_or_else(|| format!("mem budget too large: {input:?}"))
 }
 
+fn parse_since(input: &str) -> Result<Duration, String> {
+    let s = input.trim().to_ascii_lowercase();
+    let (num, scale) = if let Some(raw) = s.strip_suffix('s') {
+        (raw, 1u64)
+    } else if let Some(raw) = s.strip_suffix('m') {
+        (raw, 60u64)
+    } else if let Some(raw) = s.strip_suffix('h') {
+        (raw, 60u64 * 60)
+    } else if let Some(raw) = s.strip_suffix('d') {
+        (raw, 24u64 * 60 * 60)
+    } else if let Some(raw) = s.strip_suffix('w') {
+        (raw, 7u64 * 24 * 60 * 60)
+    } else {
+        return Err(format!(
+            "invalid duration {input:?} (expected e.g. `30m`, `12h`, `7d`, `2w`)"
+        ));
+    };
+
+    let value: u64 = num
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid duration number: {input:?}"))?;
+    value
+        .checked_mul(scale)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration too large: {input:?}"))
+}
+
+fn parse_cpu_hours(input: &str) -> Result<Duration, String> {
+    let hours: f64 = input
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid number of hours: {input:?}"))?;
+    Duration::try_from_secs_f64(hours * 3600.0)
+        .ok()
+        .filter(|d| !d.is_zero())
+        .ok_or_else(|| format!("number of hours must be positive: {input:?}"))
+}
+
+/// A backend and the number of workers contributing to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendAllocation {
+    pub url: Url,
+    pub workers: u16,
+}
+
+fn parse_backend_allocation(input: &str) -> Result<BackendAllocation, String> {
+    let (url, workers) = input
+        .trim()
+        .rsplit_once('=')
+        .ok_or_else(|| format!("invalid backend {input:?} (expected `URL=WORKERS`)"))?;
+    let url =
+        Url::parse(url.trim()).map_err(|err| format!("invalid backend URL {url:?}: {err}"))?;
+    let workers: u16 = workers
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid backend worker count: {input:?}"))?;
+    if !(1..=512).contains(&workers) {
+        return Err(format!(
+            "backend worker count must be in 1..=512: {input:?}"
+        ));
+    }
+    Ok(BackendAllocation { url, workers })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum WorkMode {
     /// Fetch and compute individual proofs.
@@ -68,6 +137,18 @@ pub enum WorkMode {
     Group,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines (default).
+    Text,
+    /// Timestamped single lines: `<ts> <LEVEL> <event> <message>`.
+    Compact,
+    /// One JSON object per line.
+    Json,
+    /// One logfmt (`key=value`) line per event.
+    Logfmt,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum PinArg {
     /// Do not pin worker compute threads (default).
@@ -91,6 +172,19 @@ pub struct Cli {
     #[arg(long, env = "BBR_BACKEND_URL", default_value_t = default_backend_url())]
     pub backend_url: Url,
 
+    /// Contribute to several backends at once, as `URL=WORKERS` (repeatable).
+    ///
+    /// Runs one engine per backend with its own workers, e.g.
+    /// `--backend https://a.example/=12 --backend https://b.example/=4`.
+    /// Overrides `--backend-url` and `--parallel`.
+    #[arg(
+        long = "backend",
+        env = "BBR_BACKENDS",
+        value_delimiter = ',',
+        value_parser = parse_backend_allocation
+    )]
+    pub backends: Vec<BackendAllocation>,
+
     /// Number of workers to run in parallel.
     #[arg(
         short = 'p',
@@ -108,10 +202,43 @@ pub struct Cli {
     #[arg(long, env = "BBR_NO_TUI", default_value_t = false)]
     pub no_tui: bool,
 
+    /// Draw the TUI inline, without the alternate screen or raw mode, fully
+    /// redrawn every second. For terminals and multiplexers that mishandle the
+    /// alternate screen; keyboard shortcuts are unavailable (Ctrl-C still stops).
+    #[arg(
+        long,
+        env = "BBR_TUI_INLINE",
+        default_value_t = false,
+        conflicts_with = "no_tui"
+    )]
+    pub tui_inline: bool,
+
+    /// Format of non-TUI output. `compact`, `json` and `logfmt` write one
+    /// timestamped line per event to stdout (no colors) and imply `--no-tui`.
+    #[arg(long, env = "BBR_LOG_FORMAT", value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+
+    /// Serve a read-only status page (and `/api/status` JSON) on this address,
+    /// e.g. `127.0.0.1:9090`. Unauthenticated: keep it on a trusted network.
+    #[arg(long, env = "BBR_WEB_DASHBOARD", value_name = "ADDR")]
+    pub web_dashboard: Option<SocketAddr>,
+
     /// CPU pinning strategy (Linux only; ignored on other platforms).
     #[arg(long, env = "BBR_PIN", value_enum, default_value_t = PinArg::Off)]
     pub pin: PinArg,
 
+    /// Skip the startup self-test of the native prover.
+    ///
+    /// By default a short proof runs in a child process first; if the native
+    /// prover crashes there (e.g. SIGILL on a CPU it doesn't support), the client
+    /// exits with an error instead of crashing mid-job.
+    #[arg(long, env = "BBR_NO_SELFTEST", default_value_t = false)]
+    pub no_selftest: bool,
+
+    /// Internal: run the startup self-test and exit (see `--no-selftest`).
+    #[arg(long, hide = true)]
+    pub selftest_child: bool,
+
     /// Memory budget per worker for streaming proof generation (e.g. `128MB`).
     ///
     /// This is used by the `(k,l)` parameter tuner in the native prover.
@@ -124,9 +251,237 @@ pub struct Cli {
     )]
     pub mem_budget_bytes: u64,
 
+    /// Opt-in: periodically post anonymous aggregate performance stats to this URL.
+    ///
+    /// Reports contain hardware class, speed, acceptance rate and client version only.
+    /// Disabled unless set.
+    #[arg(long, env = "BBR_TELEMETRY_URL")]
+    pub telemetry_url: Option<Url>,
+
+    /// Deterministic scheduling: stable work ordering and seeded worker assignment.
+    ///
+    /// Work is only handed out once all workers are idle, so throughput is lower.
+    /// Intended for debugging and reproducing scheduling issues.
+    #[arg(long, env = "BBR_SEED")]
+    pub seed: Option<u64>,
+
+    /// Release leased work that cannot finish before its lease expires.
+    ///
+    /// Uses each worker's measured speed; queued jobs are dropped and running
+    /// proofs abandoned instead of computing proofs the backend would reject as
+    /// stale.
+    #[arg(long, env = "BBR_PREEMPT_AT_RISK", default_value_t = false)]
+    pub preempt_at_risk: bool,
+
+    /// Archive every submitted witness and backend response.
+    ///
+    /// Appends to a hash-chained JSONL file in the state directory, as local
+    /// evidence in disputes with a backend about what was submitted and when.
+    #[arg(long, env = "BBR_ARCHIVE_SUBMISSIONS", default_value_t = false)]
+    pub archive_submissions: bool,
+
+    /// Do not submit proofs whose output differs from the backend's expected output.
+    ///
+    /// Logs diagnostics (challenge, discriminant size, expected and computed `y`)
+    /// instead, to help track down output mismatches.
+    #[arg(long, env = "BBR_STRICT_OUTPUT", default_value_t = false)]
+    pub strict_output: bool,
+
+    /// Run the engine's networking and bookkeeping on a dedicated runtime with this
+    /// many threads, leaving the remaining cores to proof computation.
+    #[arg(long, env = "BBR_ENGINE_THREADS", value_parser = clap::value_parser!(u16).range(1..=64))]
+    pub engine_threads: Option<u16>,
+
+    /// Split large groups between two workers when other workers would otherwise idle.
+    ///
+    /// Idle workers also take the not-yet-reached targets of a running group.
+    #[arg(long, env = "BBR_SPLIT_GROUPS", default_value_t = false)]
+    pub split_groups: bool,
+
+    /// Treat --parallel as an upper bound and pick a safe worker count from cores and
+    /// available memory, lowering it if the system runs short on memory.
+    #[arg(long, env = "BBR_AUTO_PARALLEL", default_value_t = false)]
+    pub auto_parallel: bool,
+
+    /// Keep at least this much memory available (e.g. `1GiB`; `0` disables the check).
+    ///
+    /// Available memory is checked every few seconds; when it runs short, fewer
+    /// workers are kept active (busy workers finish their current job first) and a
+    /// warning is logged, instead of the OOM killer ending the run mid-proof.
+    #[arg(
+        long,
+        env = "BBR_LOW_MEMORY",
+        default_value = "512MiB",
+        value_parser = parse_mem_budget_bytes
+    )]
+    pub low_memory: u64,
+
+    /// Don't share cores and memory with other WesoForge processes on this host.
+    ///
+    /// By default, processes (CLI or GUI) that together ask for more workers than
+    /// there are cores, or more memory than the machine has, each run a
+    /// proportional share instead of all assuming they own the machine.
+    #[arg(long, env = "BBR_NO_COOPERATIVE", default_value_t = false)]
+    pub no_cooperative: bool,
+
+    /// Only compute jobs with at most this many iterations.
+    ///
+    /// Sent to the backend as a lease filter; jobs above it that are leased anyway
+    /// are released without being computed. For low-power machines that can't
+    /// finish long jobs within a lease.
+    #[arg(long, env = "BBR_MAX_ITERATIONS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_iterations: Option<u64>,
+
+    /// Stop leasing work for the day after submitting this many proofs.
+    ///
+    /// Leased work still finishes; the worker idles until local midnight. Proofs
+    /// submitted earlier today (per the job history) count towards the cap.
+    #[arg(long, env = "BBR_DAILY_PROOFS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub daily_proofs: Option<u64>,
+
+    /// Stop leasing work for the day after this many hours of worker compute
+    /// time (fractions allowed, e.g. `2.5`); a group proof counts once.
+    #[arg(long, env = "BBR_DAILY_CPU_HOURS", value_parser = parse_cpu_hours)]
+    pub daily_cpu_hours: Option<Duration>,
+
+    /// Lease up to this many jobs/groups ahead for workers about to finish, hiding
+    /// the lease round-trip between jobs on high-latency links (0 = off).
+    #[arg(long, env = "BBR_PREFETCH", default_value_t = 0)]
+    pub prefetch: usize,
+
+    /// Renew held leases every this many seconds, for backends that support
+    /// renewal but don't set their own `renew_interval` in lease responses.
+    #[arg(long, env = "BBR_LEASE_RENEW_INTERVAL", value_parser = clap::value_parser!(u64).range(1..))]
+    pub lease_renew_interval: Option<u64>,
+
+    /// Run compute threads in this cgroup v2 (Linux only), relative to
+    /// `/sys/fs/cgroup`. Its parent must delegate the `cpu` and `memory` controllers.
+    #[arg(long, env = "BBR_CGROUP")]
+    pub cgroup: Option<PathBuf>,
+
+    /// `cpu.weight` for compute threads in `--cgroup` (1-10000; kernel default 100).
+    #[arg(
+        long,
+        env = "BBR_CGROUP_CPU_WEIGHT",
+        requires = "cgroup",
+        value_parser = clap::value_parser!(u32).range(1..=10_000)
+    )]
+    pub cgroup_cpu_weight: Option<u32>,
+
+    /// `memory.max` for the client in `--cgroup` (e.g. `4G`, `512M`).
+    #[arg(
+        long,
+        env = "BBR_CGROUP_MEMORY_MAX",
+        requires = "cgroup",
+        value_parser = parse_mem_budget_bytes
+    )]
+    pub cgroup_memory_max: Option<u64>,
+
     /// Run a local benchmark and exit.
     ///
     /// Uses current `--mode` and `--parallel` settings.
     #[arg(long)]
     pub bench: bool,
+
+    /// Run group proofs continuously for this long (e.g. `30m`, `8h`) and report
+    /// throughput drift, variance, max pause and native errors, then exit.
+    ///
+    /// A burn-in test for new hardware or overclocks; uses `--parallel`.
+    #[arg(long, value_name = "DURATION", value_parser = parse_since)]
+    pub bench_soak: Option<Duration>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Summarize the local job history and exit.
+    Stats(StatsArgs),
+    /// Compute proofs for request files dropped into a directory.
+    WatchDir(WatchDirArgs),
+    /// Show the submission signing key's fingerprint, or generate a key.
+    ///
+    /// When a key exists, submissions are signed with it (for backends that
+    /// authenticate submitters).
+    SigningKey(SigningKeyArgs),
+    /// Generate challenge/y/witness test vectors with the prover.
+    GenVectors(GenVectorsArgs),
+    /// Run troubleshooting checks (native self-test, backend, settings, state) and exit.
+    ///
+    /// Exits non-zero when any check fails.
+    Doctor(DoctorArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct DoctorArgs {
+    /// Print the report as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct StatsArgs {
+    /// Only include jobs finished within this window (e.g. `24h`, `7d`).
+    #[arg(long, value_parser = parse_since)]
+    pub since: Option<Duration>,
+
+    /// Print the summary as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct WatchDirArgs {
+    /// Directory to watch for `*.json` proof requests.
+    pub input: PathBuf,
+
+    /// Directory to write `<name>.witness.json` / `<name>.error.json` results to.
+    pub output: PathBuf,
+
+    /// How often to rescan the input directory (e.g. `2s`, `1m`).
+    #[arg(long, default_value = "2s", value_parser = parse_since)]
+    pub poll: Duration,
+
+    /// Process the requests currently in the directory, then exit.
+    #[arg(long)]
+    pub once: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct GenVectorsArgs {
+    /// Iteration counts to generate vectors for (comma-separated, e.g. `1000,100000`).
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub iters: Vec<u64>,
+
+    /// Vectors to generate per iteration count.
+    #[arg(long, default_value_t = 1)]
+    pub count: usize,
+
+    /// File to write the vectors to (JSON).
+    #[arg(long)]
+    pub out: PathBuf,
+
+    /// Seed the challenges are derived from; the same seed yields the same vectors.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// Discriminant size in bits.
+    #[arg(long, default_value_t = 1024)]
+    pub discriminant_bits: usize,
+
+    /// Re-prove each vector with the streaming prover and fail unless it reproduces `y`.
+    #[arg(long)]
+    pub verify: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct SigningKeyArgs {
+    /// Generate a new key.
+    #[arg(long)]
+    pub generate: bool,
+
+    /// With `--generate`, replace an existing key.
+    #[arg(long, requires = "generate")]
+    pub force: bool,
 }
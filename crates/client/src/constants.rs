@@ -1,9 +1,5 @@
-pub const PROGRESS_BAR_STEPS: u64 = 1_000; // Report every 0.1%
 pub const TUI_REFRESH_HZ: u64 = 4;
 pub const TUI_REFRESH_INTERVAL_US: u64 = 1_000_000 / TUI_REFRESH_HZ;
-
-pub fn default_classgroup_element() -> [u8; 100] {
-    let mut el = [0u8; 100];
-    el[0] = 0x08;
-    el
-}
+/// `--tui-inline` repaints the whole dashboard at most this often.
+pub const TUI_INLINE_REDRAW_INTERVAL_MS: u64 = 1_000;
+pub const QUEUE_STATUS_REFRESH_SECS: u64 = 60; // Shared public endpoint; poll gently
@@ -0,0 +1,210 @@
+//! Minimal read-only status page (`--web-dashboard`): `GET /` serves a small
+//! HTML page that polls `GET /api/status` (engine snapshots as JSON).
+//!
+//! No authentication: bind it to a loopback or otherwise trusted address.
+
+use std::net::SocketAddr;
+
+use anyhow::Context as _;
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+
+use bbr_client_engine::StatusSnapshot;
+
+/// Largest request head we read; anything longer is rejected.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// One engine's snapshots, labelled by backend.
+pub struct DashboardSource {
+    pub backend: String,
+    pub snapshots: watch::Receiver<StatusSnapshot>,
+}
+
+/// Bind `addr` and serve the dashboard in the background.
+pub async fn spawn(addr: SocketAddr, sources: Vec<DashboardSource>) -> anyhow::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("bind web dashboard on {addr}"))?;
+    let local_addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let status = status_json(&sources);
+            tokio::spawn(async move {
+                let _ = handle(stream, status).await;
+            });
+        }
+    });
+    Ok(local_addr)
+}
+
+fn status_json(sources: &[DashboardSource]) -> Value {
+    let engines: Vec<Value> = sources
+        .iter()
+        .map(|source| {
+            json!({
+                "backend": source.backend,
+                "snapshot": *source.snapshots.borrow(),
+            })
+        })
+        .collect();
+    json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "engines": engines,
+    })
+}
+
+async fn handle(mut stream: TcpStream, status: Value) -> std::io::Result<()> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buf.len() >= MAX_REQUEST_BYTES {
+            return respond(
+                &mut stream,
+                "431 Request Header Fields Too Large",
+                "text/plain",
+                b"",
+            )
+            .await;
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+
+    if method != "GET" {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", b"").await;
+    }
+    match path {
+        "/" => {
+            respond(
+                &mut stream,
+                "200 OK",
+                "text/html; charset=utf-8",
+                DASHBOARD_HTML.as_bytes(),
+            )
+            .await
+        }
+        "/api/status" => {
+            respond(
+                &mut stream,
+                "200 OK",
+                "application/json",
+                status.to_string().as_bytes(),
+            )
+            .await
+        }
+        _ => respond(&mut stream, "404 Not Found", "text/plain", b"not found").await,
+    }
+}
+
+async fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body).await?;
+    stream.shutdown().await
+}
+
+const DASHBOARD_HTML: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>WesoForge</title>
+<style>
+  body { font: 14px system-ui, sans-serif; margin: 1.5rem; color: #222; }
+  table { border-collapse: collapse; margin-bottom: 1.5rem; }
+  th, td { padding: 0.25rem 0.75rem; text-align: left; border-bottom: 1px solid #ddd; }
+  td.num { text-align: right; font-variant-numeric: tabular-nums; }
+  #error { color: #b00; }
+</style>
+</head>
+<body>
+<h1>WesoForge <span id="version"></span></h1>
+<p id="error"></p>
+<div id="engines"></div>
+<script>
+function cell(row, text, cls) {
+  const td = row.insertCell();
+  td.textContent = text;
+  if (cls) td.className = cls;
+}
+
+function render(status) {
+  document.getElementById('version').textContent = status.version;
+  const root = document.getElementById('engines');
+  root.replaceChildren();
+  for (const engine of status.engines) {
+    const snap = engine.snapshot;
+    const s = snap.session || {};
+    const h = document.createElement('h2');
+    h.textContent = engine.backend + (snap.paused ? ' (paused)' : '') +
+      (snap.stop_requested ? ' (stopping)' : '');
+    const p = document.createElement('p');
+    p.textContent = `jobs finished ${s.jobs_finished ?? 0}, submitted ${s.jobs_submitted ?? 0}, failed ${s.jobs_failed ?? 0}`;
+    const b = snap.budget;
+    if (b) {
+      const parts = [];
+      if (b.max_proofs != null) parts.push(`${b.proofs_today}/${b.max_proofs} proofs`);
+      if (b.max_compute_ms != null) {
+        parts.push(`${(b.compute_ms_today / 3.6e6).toFixed(1)}/${(b.max_compute_ms / 3.6e6).toFixed(1)} CPU-hours`);
+      }
+      p.textContent += `; today ${parts.join(', ')}` + (b.resumes_at ? ' (budget reached, idle until midnight)' : '');
+    }
+    const table = document.createElement('table');
+    const header = table.createTHead().insertRow();
+    for (const name of ['Worker', 'Stage', 'Job', 'Progress', 'it/s']) {
+      const th = document.createElement('th');
+      th.textContent = name;
+      header.appendChild(th);
+    }
+    const body = table.createTBody();
+    for (const w of snap.workers) {
+      const row = body.insertRow();
+      cell(row, String(w.worker_idx + 1));
+      cell(row, w.paused ? 'Paused' : w.stage);
+      cell(row, w.job ? String(w.job.job_id) : '');
+      const pct = w.iters_total > 0 ? (100 * w.iters_done / w.iters_total).toFixed(1) + '%' : '';
+      cell(row, pct, 'num');
+      cell(row, w.iters_per_sec ? w.iters_per_sec.toLocaleString() : '', 'num');
+    }
+    root.append(h, p, table);
+  }
+}
+
+async function refresh() {
+  try {
+    const res = await fetch('/api/status', { cache: 'no-store' });
+    render(await res.json());
+    document.getElementById('error').textContent = '';
+  } catch (e) {
+    document.getElementById('error').textContent = 'Client unreachable: ' + e;
+  }
+}
+
+refresh();
+setInterval(refresh, 2000);
+</script>
+</body>
+</html>
+"#;
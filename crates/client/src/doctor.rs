@@ -0,0 +1,40 @@
+use reqwest::Url;
+
+use bbr_client_engine::{CheckStatus, DoctorReport};
+
+use crate::cli::DoctorArgs;
+
+fn status_label(status: CheckStatus) -> &'static str {
+    match status {
+        CheckStatus::Pass => "ok",
+        CheckStatus::Warn => "warn",
+        CheckStatus::Fail => "FAIL",
+    }
+}
+
+fn print_report(report: &DoctorReport) {
+    for check in &report.checks {
+        println!(
+            "[{:>4}] {}: {}",
+            status_label(check.status),
+            check.title,
+            check.detail
+        );
+        if let Some(fix) = &check.fix {
+            println!("       fix: {fix}");
+        }
+    }
+}
+
+/// Run the troubleshooting checks against `backend_url`; returns `false` when any
+/// check failed.
+pub async fn run_doctor(args: &DoctorArgs, backend_url: &Url) -> anyhow::Result<bool> {
+    let report = bbr_client_engine::run_doctor(backend_url, None).await;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(report.status() != CheckStatus::Fail)
+}
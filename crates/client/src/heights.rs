@@ -0,0 +1,146 @@
+//! TUI summary of where recent work sits in the compaction effort: a histogram
+//! of recently finished block heights, drawn over the backend's remaining range.
+
+use std::collections::VecDeque;
+
+use bbr_client_engine::BackendQueueStatus;
+
+use crate::format::format_number;
+
+/// Finished jobs kept for the histogram.
+const RECENT_HEIGHTS: usize = 200;
+/// Histogram columns.
+const HISTOGRAM_BUCKETS: usize = 24;
+const BAR_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Default)]
+pub(crate) struct HeightSummary {
+    recent: VecDeque<u32>,
+    remaining_blocks: Option<u64>,
+    remaining_range: Option<(u32, u32)>,
+}
+
+impl HeightSummary {
+    pub(crate) fn record(&mut self, height: u32) {
+        if self.recent.len() >= RECENT_HEIGHTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(height);
+    }
+
+    /// Combine the latest status of every backend (block counts add up, ranges
+    /// are merged).
+    pub(crate) fn set_backend_status<'a>(
+        &mut self,
+        statuses: impl IntoIterator<Item = &'a BackendQueueStatus>,
+    ) {
+        self.remaining_blocks = None;
+        self.remaining_range = None;
+        for status in statuses {
+            if let Some(blocks) = status.remaining_uncompact_blocks {
+                *self.remaining_blocks.get_or_insert(0) += blocks;
+            }
+            if let (Some(lo), Some(hi)) = (status.remaining_min_height, status.remaining_max_height)
+            {
+                self.remaining_range = Some(match self.remaining_range {
+                    Some((min, max)) => (min.min(lo), max.max(hi)),
+                    None => (lo.min(hi), lo.max(hi)),
+                });
+            }
+        }
+    }
+
+    /// One-line summary, e.g.
+    /// "Heights: 1,200,000 ▁▁▃█▂▁ 2,400,000 (last 57 jobs) | backend: 12,345 blocks left".
+    pub(crate) fn line(&self) -> String {
+        let mut line = match (self.recent.iter().min(), self.recent.iter().max()) {
+            (Some(&min), Some(&max)) => {
+                let (lo, hi) = match self.remaining_range {
+                    Some((lo, hi)) => (lo.min(min), hi.max(max)),
+                    None => (min, max),
+                };
+                format!(
+                    "Heights: {} {} {} (last {} jobs)",
+                    format_number(lo.into()),
+                    histogram(&self.recent, lo, hi),
+                    format_number(hi.into()),
+                    self.recent.len()
+                )
+            }
+            _ => "Heights: no finished jobs yet".to_string(),
+        };
+
+        let mut backend = Vec::new();
+        if let Some(blocks) = self.remaining_blocks {
+            backend.push(format!("{} blocks left", format_number(blocks)));
+        }
+        if let Some((lo, hi)) = self.remaining_range {
+            backend.push(format!(
+                "range {}–{}",
+                format_number(lo.into()),
+                format_number(hi.into())
+            ));
+        }
+        if !backend.is_empty() {
+            line.push_str(&format!(" | backend: {}", backend.join(", ")));
+        }
+        line
+    }
+}
+
+/// Bar per height bucket over `lo..=hi`, scaled to the fullest bucket; empty
+/// buckets are blank.
+fn histogram(heights: &VecDeque<u32>, lo: u32, hi: u32) -> String {
+    let span = u64::from(hi - lo) + 1;
+    let mut counts = [0usize; HISTOGRAM_BUCKETS];
+    for &height in heights {
+        let offset = u64::from(height.clamp(lo, hi) - lo);
+        let bucket = (offset * HISTOGRAM_BUCKETS as u64 / span) as usize;
+        counts[bucket.min(HISTOGRAM_BUCKETS - 1)] += 1;
+    }
+    let peak = counts.iter().copied().max().unwrap_or(0).max(1);
+    counts
+        .iter()
+        .map(|&count| {
+            if count == 0 {
+                ' '
+            } else {
+                BAR_LEVELS[(count * BAR_LEVELS.len()).div_ceil(peak) - 1]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_places_recent_heights_in_backend_range() {
+        let mut summary = HeightSummary::default();
+        assert_eq!(summary.line(), "Heights: no finished jobs yet");
+
+        for height in [100, 100, 150, 199] {
+            summary.record(height);
+        }
+        summary.set_backend_status([&BackendQueueStatus {
+            remaining_uncompact_blocks: Some(1234),
+            remaining_min_height: Some(0),
+            remaining_max_height: Some(239),
+            ..BackendQueueStatus::default()
+        }]);
+
+        let line = summary.line();
+        assert!(line.starts_with("Heights: 0 "), "{line}");
+        assert!(
+            line.ends_with(" 239 (last 4 jobs) | backend: 1,234 blocks left, range 0–239"),
+            "{line}"
+        );
+        let bars: Vec<char> = histogram(&summary.recent, 0, 239).chars().collect();
+        assert_eq!(bars.len(), HISTOGRAM_BUCKETS);
+        assert_eq!(bars[10], '█');
+        assert_eq!(bars[15], '▄');
+        assert_eq!(bars[19], '▄');
+        assert_eq!(bars[0], ' ');
+    }
+}
@@ -0,0 +1,25 @@
+use bbr_client_core::signing::{generate_signing_key, load_signing_key, signing_key_path};
+
+use crate::cli::SigningKeyArgs;
+
+/// Show (or with `--generate`, create) the submission signing key.
+pub fn run_signing_key(args: &SigningKeyArgs) -> anyhow::Result<()> {
+    let path = signing_key_path()?;
+    let signer = if args.generate {
+        let signer = generate_signing_key(args.force)?;
+        println!("Generated signing key at {}.", path.display());
+        signer
+    } else {
+        match load_signing_key()? {
+            Some(signer) => signer,
+            None => {
+                println!("No signing key; submissions are not signed.");
+                println!("Run `wesoforge signing-key --generate` to create one.");
+                return Ok(());
+            }
+        }
+    };
+    println!("Public key:  {}", signer.public_key_b64());
+    println!("Fingerprint: {}", signer.fingerprint());
+    Ok(())
+}
@@ -0,0 +1,196 @@
+//! Non-TUI output: the classic human-readable lines, or one structured line per
+//! event for container logs (`--log-format`).
+
+use std::io::Write as _;
+
+use serde_json::{Map, Value};
+
+use crate::cli::LogFormat;
+
+/// Severity of a log line. In `text` format `Info` goes to stdout and everything
+/// else to stderr; the structured formats write every line to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Notice,
+    Warn,
+    Error,
+}
+
+impl Level {
+    fn as_str(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Notice => "notice",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+/// One log line: an event name, a human-readable message and extra fields.
+pub struct Record<'a> {
+    level: Level,
+    event: &'a str,
+    message: &'a str,
+    backend: Option<&'a str>,
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(level: Level, event: &'a str, message: &'a str) -> Self {
+        Self {
+            level,
+            event,
+            message,
+            backend: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Backend label, shown as a `[label] ` prefix in `text` format.
+    pub fn backend(mut self, backend: Option<&'a str>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Extra field, omitted in `text` format.
+    pub fn field(mut self, key: &'static str, value: impl Into<Value>) -> Self {
+        self.fields.push((key, value.into()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Logger {
+    format: LogFormat,
+}
+
+impl Logger {
+    pub fn new(format: LogFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn is_structured(&self) -> bool {
+        self.format != LogFormat::Text
+    }
+
+    pub fn info(&self, event: &str, message: &str) {
+        self.log(Record::new(Level::Info, event, message));
+    }
+
+    pub fn notice(&self, event: &str, message: &str) {
+        self.log(Record::new(Level::Notice, event, message));
+    }
+
+    pub fn warn(&self, event: &str, message: &str) {
+        self.log(Record::new(Level::Warn, event, message));
+    }
+
+    pub fn log(&self, record: Record<'_>) {
+        let line = self.render(&record);
+        if self.format == LogFormat::Text && record.level != Level::Info {
+            eprintln!("{line}");
+        } else {
+            // Write the line in one call so concurrent output can't split it.
+            let mut stdout = std::io::stdout().lock();
+            let _ = writeln!(stdout, "{line}");
+        }
+    }
+
+    fn render(&self, record: &Record<'_>) -> String {
+        let timestamp = || {
+            chrono::Utc::now()
+                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                .to_string()
+        };
+        match self.format {
+            LogFormat::Text => match record.backend {
+                Some(backend) => format!("[{backend}] {}", record.message),
+                None => record.message.to_string(),
+            },
+            LogFormat::Compact => {
+                let mut line = format!(
+                    "{} {:<6} {}",
+                    timestamp(),
+                    record.level.as_str().to_ascii_uppercase(),
+                    record.event
+                );
+                if let Some(backend) = record.backend {
+                    line.push_str(&format!(" [{backend}]"));
+                }
+                line.push(' ');
+                line.push_str(&single_line(record.message));
+                line
+            }
+            LogFormat::Logfmt => {
+                let mut pairs = vec![
+                    ("ts", Value::from(timestamp())),
+                    ("level", Value::from(record.level.as_str())),
+                    ("event", Value::from(record.event)),
+                ];
+                if let Some(backend) = record.backend {
+                    pairs.push(("backend", Value::from(backend)));
+                }
+                pairs.push(("msg", Value::from(record.message)));
+                pairs.extend(record.fields.iter().cloned());
+                pairs
+                    .iter()
+                    .map(|(key, value)| format!("{key}={}", logfmt_value(value)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+            LogFormat::Json => {
+                let mut object = Map::new();
+                object.insert("ts".to_string(), Value::from(timestamp()));
+                object.insert("level".to_string(), Value::from(record.level.as_str()));
+                object.insert("event".to_string(), Value::from(record.event));
+                if let Some(backend) = record.backend {
+                    object.insert("backend".to_string(), Value::from(backend));
+                }
+                object.insert("msg".to_string(), Value::from(record.message));
+                for (key, value) in &record.fields {
+                    object.insert((*key).to_string(), value.clone());
+                }
+                Value::Object(object).to_string()
+            }
+        }
+    }
+}
+
+/// Collapse line breaks so every record stays on one line.
+fn single_line(message: &str) -> String {
+    message.lines().collect::<Vec<_>>().join(" | ")
+}
+
+/// logfmt value: bare when it has no spaces, quotes or `=`, otherwise quoted.
+fn logfmt_value(value: &Value) -> String {
+    let raw = match value {
+        Value::String(s) => single_line(s),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    };
+    let bare = !raw.is_empty()
+        && !raw
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '=' || c == '\\');
+    if bare { raw } else { format!("{raw:?}") }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logfmt_values_are_quoted_when_needed() {
+        assert_eq!(logfmt_value(&Value::from(42)), "42");
+        assert_eq!(logfmt_value(&Value::from("accepted")), "accepted");
+        assert_eq!(logfmt_value(&Value::from("two words")), "\"two words\"");
+        assert_eq!(logfmt_value(&Value::from("a=\"b\"")), "\"a=\\\"b\\\"\"");
+        assert_eq!(
+            logfmt_value(&Value::from("line\nbreak")),
+            "\"line | break\""
+        );
+        assert_eq!(logfmt_value(&Value::Null), "\"\"");
+    }
+}
@@ -1,26 +1,51 @@
 mod bench;
 mod cli;
 mod constants;
+mod dashboard;
+mod doctor;
 mod format;
+mod heights;
+mod keys;
+mod log;
+mod selftest;
 mod shutdown;
+mod signals;
+mod stats;
 mod terminal;
 mod ui;
+mod vectors;
+mod watch;
 
 use clap::Parser;
 use std::io::IsTerminal;
 use std::time::Duration;
 
 use bbr_client_chiavdf_fast::{set_bucket_memory_budget_bytes, set_enable_streaming_stats};
+use bbr_client_core::network::load_network_settings;
+use bbr_client_core::redact::Redact as _;
+use bbr_client_core::signing::{SubmissionSigner, load_signing_key};
 use bbr_client_core::submitter::{SubmitterConfig, ensure_submitter_config};
-use bbr_client_engine::{EngineConfig, EngineEvent, start_engine};
+use bbr_client_engine::{
+    BackendQueueStatus, CgroupConfig, DailyBudget, EngineConfig, EngineEvent, TelemetryConfig,
+    WorkerStage, fetch_backend_queue_status, start_engine,
+};
 
-use crate::bench::run_benchmark;
-use crate::cli::{Cli, WorkMode};
-use crate::constants::{PROGRESS_BAR_STEPS, TUI_REFRESH_INTERVAL_US};
-use crate::format::{format_job_done_line, humanize_submit_reason};
+use crate::bench::{run_benchmark, run_soak};
+use crate::cli::{BackendAllocation, Cli, Command, WorkMode};
+use crate::constants::{QUEUE_STATUS_REFRESH_SECS, TUI_REFRESH_INTERVAL_US};
+use crate::dashboard::DashboardSource;
+use crate::doctor::run_doctor;
+use crate::format::{format_duration, format_job_done_line, format_number, humanize_submit_reason};
+use crate::keys::run_signing_key;
+use crate::log::{Level, Logger, Record};
+use crate::selftest::{ensure_prover_runs, run_child as run_selftest_child};
 use crate::shutdown::{ShutdownController, ShutdownEvent, spawn_ctrl_c_handler};
+use crate::signals::{ControlSignal, spawn_control_signal_handler};
+use crate::stats::run_stats;
 use crate::terminal::{TuiInputEvent, TuiTerminal};
 use crate::ui::Ui;
+use crate::vectors::run_gen_vectors;
+use crate::watch::run_watch_dir;
 
 fn format_outcome_status(outcome: &bbr_client_engine::JobOutcome) -> String {
     if let Some(err) = &outcome.error {
@@ -41,13 +66,203 @@ fn format_outcome_status(outcome: &bbr_client_engine::JobOutcome) -> String {
     status
 }
 
+/// Compact session totals for comparing backends, e.g.
+/// "12 submitted, 11 accepted (91.7%), 1 failed".
+fn format_session_brief(session: &bbr_client_engine::SessionStats) -> String {
+    let mut line = format!(
+        "{} submitted, {} accepted",
+        format_number(session.jobs_submitted),
+        format_number(session.jobs_accepted)
+    );
+    if session.jobs_submitted > 0 {
+        let rate = 100.0 * session.jobs_accepted as f64 / session.jobs_submitted as f64;
+        line.push_str(&format!(" ({rate:.1}%)"));
+    }
+    line.push_str(&format!(", {} failed", format_number(session.jobs_failed)));
+    line
+}
+
+fn format_shutdown_report(report: &bbr_client_engine::ShutdownReport) -> Vec<String> {
+    let session = &report.session;
+    let mut lines = vec![format!(
+        "session: {}, {} iterations in {} (compute {})",
+        format_session_brief(session),
+        format_number(session.iterations_submitted),
+        format_duration(Duration::from_millis(session.uptime_ms)),
+        format_duration(Duration::from_millis(session.compute_ms)),
+    )];
+    if session.duplicates_suppressed > 0 {
+        lines.push(format!(
+            "duplicates: {} job(s) reused a witness computed for an identical job",
+            format_number(session.duplicates_suppressed),
+        ));
+    }
+    if report.abandoned_leases > 0 || report.unsubmitted_jobs > 0 || report.resumable_jobs > 0 {
+        lines.push(format!(
+            "left behind: {} abandoned lease(s), {} computed but unsubmitted job(s); {} job(s) saved for resume on next start",
+            report.abandoned_leases, report.unsubmitted_jobs, report.resumable_jobs,
+        ));
+    }
+    if report.expired_jobs > 0 {
+        lines.push(format!(
+            "dropped: {} computed job(s) whose lease expired before submission",
+            report.expired_jobs,
+        ));
+    }
+    lines
+}
+
+/// One-line environment summary for bug reports, e.g.
+/// "environment: wesoforge 0.4.0 (abc1234) linux/x86_64, 16 cpus [avx2,bmi2,adx], prover fast/avx2, parallel 4, mem budget 512 MiB, pin off".
+fn format_environment(env: &bbr_client_engine::EnvironmentInfo) -> String {
+    let mut line = format!("environment: wesoforge {}", env.version);
+    if let Some(hash) = &env.git_hash {
+        line.push_str(&format!(" ({hash})"));
+    }
+    line.push_str(&format!(
+        " {}/{}, {} cpus",
+        env.os, env.arch, env.logical_cpus
+    ));
+    if !env.cpu_features.is_empty() {
+        line.push_str(&format!(" [{}]", env.cpu_features.join(",")));
+    }
+    line.push_str(&format!(", prover {}", env.native_implementation));
+    let parallel = if env.auto_parallel {
+        "auto, up to "
+    } else {
+        ""
+    };
+    line.push_str(&format!(
+        ", parallel {parallel}{}, mem budget {} MiB, pin {}",
+        env.parallel,
+        format_number(env.mem_budget_bytes / (1024 * 1024)),
+        env.pin_mode
+    ));
+    line
+}
+
+/// `message` with a `[backend] ` prefix when running against several backends.
+fn prefixed(backend: Option<&str>, message: &str) -> String {
+    match backend {
+        Some(label) => format!("[{label}] {message}"),
+        None => message.to_string(),
+    }
+}
+
 fn should_log_warning_in_tui(message: &str) -> bool {
-    message.to_ascii_lowercase().contains("lease")
+    let message = message.to_ascii_lowercase();
+    message.contains("lease") || message.contains("maintenance") || message.contains("low memory")
+}
+
+/// Short `host[:port]` label for a backend, used for log prefixes, worker groups
+/// and the per-backend inflight state file.
+fn backend_label(url: &reqwest::Url) -> String {
+    let host = url.host_str().unwrap_or("backend");
+    match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    }
+}
+
+fn backend_namespace(url: &reqwest::Url) -> String {
+    backend_label(url)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Poll a backend's public queue stats for the TUI height summary, tagged with
+/// the engine index. Failures are skipped; the stats endpoint is optional.
+fn poll_queue_status(
+    engine_idx: usize,
+    backend_url: reqwest::Url,
+    network: bbr_client_core::network::NetworkSettings,
+    tx: tokio::sync::mpsc::UnboundedSender<(usize, BackendQueueStatus)>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(QUEUE_STATUS_REFRESH_SECS));
+        loop {
+            interval.tick().await;
+            if let Ok(status) = fetch_backend_queue_status(&backend_url, &network).await
+                && tx.send((engine_idx, status)).is_err()
+            {
+                break;
+            }
+        }
+    });
+}
+
+/// Forward one engine's events into a shared channel, tagged with the engine index.
+fn forward_events(
+    engine_idx: usize,
+    mut events: tokio::sync::broadcast::Receiver<EngineEvent>,
+    tx: tokio::sync::mpsc::UnboundedSender<(usize, EngineEvent)>,
+) {
+    tokio::spawn(async move {
+        loop {
+            let evt = match events.recv().await {
+                Ok(v) => v,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            };
+            if tx.send((engine_idx, evt)).is_err() {
+                break;
+            }
+        }
+    });
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    let logger = Logger::new(cli.log_format);
+
+    if cli.selftest_child {
+        std::process::exit(run_selftest_child());
+    }
+
+    if let Some(Command::Stats(args)) = &cli.command {
+        run_stats(args)?;
+        return Ok(());
+    }
+
+    if let Some(Command::SigningKey(args)) = &cli.command {
+        run_signing_key(args)?;
+        return Ok(());
+    }
+
+    if let Some(Command::Doctor(args)) = &cli.command {
+        let ok = run_doctor(args, &cli.backend_url).await?;
+        if !ok {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::WatchDir(args)) = &cli.command {
+        set_bucket_memory_budget_bytes(cli.mem_budget_bytes);
+        run_watch_dir(args, cli.parallel as usize)?;
+        return Ok(());
+    }
+
+    if let Some(Command::GenVectors(args)) = &cli.command {
+        set_bucket_memory_budget_bytes(cli.mem_budget_bytes);
+        run_gen_vectors(args, cli.parallel as usize)?;
+        return Ok(());
+    }
+
+    if let Some(duration) = cli.bench_soak {
+        set_bucket_memory_budget_bytes(cli.mem_budget_bytes);
+        set_enable_streaming_stats(true);
+        run_soak(duration, cli.parallel as usize)?;
+        return Ok(());
+    }
 
     if cli.bench {
         set_bucket_memory_budget_bytes(cli.mem_budget_bytes);
@@ -56,46 +271,115 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    if !cli.no_selftest {
+        tokio::task::spawn_blocking(ensure_prover_runs).await??;
+    }
+
     let interactive = std::io::stdin().is_terminal();
     let submitter = match ensure_submitter_config(interactive) {
         Ok(Some(cfg)) => cfg,
         Ok(None) => SubmitterConfig::default(),
         Err(err) => {
-            eprintln!("warning: failed to read/write submitter config: {err:#}");
+            logger.warn(
+                "config",
+                &format!("warning: failed to read/write submitter config: {err:#}"),
+            );
             SubmitterConfig::default()
         }
     };
+    let signer = match load_signing_key() {
+        Ok(signer) => signer,
+        Err(err) => {
+            logger.warn(
+                "config",
+                &format!("warning: submissions will not be signed: {err:#}"),
+            );
+            None
+        }
+    };
 
     if cli.parallel == 0 {
         anyhow::bail!("--parallel must be >= 1");
     }
-    let parallel = cli.parallel as usize;
+    let network = load_network_settings()?;
+    let allocations = if cli.backends.is_empty() {
+        vec![BackendAllocation {
+            url: cli.backend_url.clone(),
+            workers: cli.parallel,
+        }]
+    } else {
+        cli.backends.clone()
+    };
+    let multi_backend = allocations.len() > 1;
+    let parallel: usize = allocations.iter().map(|a| a.workers as usize).sum();
 
-    let tui_enabled = !cli.no_tui && std::io::stdout().is_terminal();
+    let tui_enabled = !cli.no_tui && !logger.is_structured() && std::io::stdout().is_terminal();
     let warn_tui_too_many_workers = tui_enabled && parallel > 32;
-    let progress_steps = if tui_enabled { PROGRESS_BAR_STEPS } else { 0 };
 
     let use_groups = cli.mode == WorkMode::Group;
 
-    let engine = start_engine(EngineConfig {
-        backend_url: cli.backend_url.clone(),
-        parallel,
-        use_groups,
-        mem_budget_bytes: cli.mem_budget_bytes,
-        submitter,
-        idle_sleep: Duration::ZERO,
-        progress_steps,
-        progress_tick: Duration::from_micros(TUI_REFRESH_INTERVAL_US),
-        recent_jobs_max: 0,
-        pin_mode: cli.pin.into(),
-    });
-
-    let mut events = engine.subscribe();
+    let (event_tx, mut events) = tokio::sync::mpsc::unbounded_channel::<(usize, EngineEvent)>();
+    let mut engines = Vec::with_capacity(allocations.len());
+    let mut worker_offsets = Vec::with_capacity(allocations.len());
+    let mut backend_labels = Vec::with_capacity(allocations.len());
+    let mut next_offset = 0usize;
+    for (engine_idx, alloc) in allocations.iter().enumerate() {
+        let engine = start_engine(EngineConfig {
+            backend_url: alloc.url.clone(),
+            parallel: alloc.workers as usize,
+            auto_parallel: cli.auto_parallel,
+            low_memory_threshold_bytes: (cli.low_memory > 0).then_some(cli.low_memory),
+            cooperative: !cli.no_cooperative,
+            use_groups,
+            mem_budget_bytes: cli.mem_budget_bytes,
+            submitter: submitter.clone(),
+            signer: signer.clone(),
+            idle_sleep: Duration::ZERO,
+            progress_tick: Duration::from_micros(TUI_REFRESH_INTERVAL_US),
+            recent_jobs_max: 0,
+            pin_mode: cli.pin.into(),
+            cgroup: cli.cgroup.clone().map(|path| CgroupConfig {
+                path,
+                cpu_weight: cli.cgroup_cpu_weight,
+                memory_max_bytes: cli.cgroup_memory_max,
+            }),
+            telemetry: cli.telemetry_url.clone().map(|endpoint| TelemetryConfig {
+                endpoint,
+                interval: TelemetryConfig::DEFAULT_INTERVAL,
+            }),
+            schedule_seed: cli.seed,
+            preempt_at_risk: cli.preempt_at_risk,
+            max_iterations: cli.max_iterations,
+            prefetch: cli.prefetch,
+            lease_renew_interval: cli.lease_renew_interval.map(Duration::from_secs),
+            archive_submissions: cli.archive_submissions,
+            strict_output: cli.strict_output,
+            discriminant_bits: EngineConfig::DEFAULT_DISCRIMINANT_BITS,
+            state_namespace: multi_backend.then(|| backend_namespace(&alloc.url)),
+            engine_threads: cli.engine_threads.map(usize::from),
+            split_groups: cli.split_groups,
+            network: network.clone(),
+            daily_budget: (cli.daily_proofs.is_some() || cli.daily_cpu_hours.is_some()).then_some(
+                DailyBudget {
+                    max_proofs: cli.daily_proofs,
+                    max_compute: cli.daily_cpu_hours,
+                },
+            ),
+        });
+        forward_events(engine_idx, engine.subscribe(), event_tx.clone());
+        engines.push(engine);
+        worker_offsets.push(next_offset);
+        backend_labels.push(backend_label(&alloc.url));
+        next_offset += alloc.workers as usize;
+    }
+    drop(event_tx);
+    let mut engines_stopped = 0usize;
+    let mut shutdown_reports = Vec::new();
 
     let shutdown = std::sync::Arc::new(ShutdownController::new());
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::mpsc::unbounded_channel::<ShutdownEvent>();
     let (input_tx, mut input_rx) = tokio::sync::mpsc::unbounded_channel::<TuiInputEvent>();
-    let tui_terminal = if tui_enabled && std::io::stdin().is_terminal() {
+    let tui_terminal = if tui_enabled && !cli.tui_inline && std::io::stdin().is_terminal() {
         Some(TuiTerminal::enter(
             shutdown.clone(),
             shutdown_tx.clone(),
@@ -108,21 +392,48 @@ async fn main() -> anyhow::Result<()> {
         spawn_ctrl_c_handler(shutdown.clone(), shutdown_tx);
     }
 
-    let startup = format!(
+    let mut startup = format!(
         "wesoforge {} parallel={}",
         env!("CARGO_PKG_VERSION"),
         parallel
     );
+    if multi_backend {
+        let backends: Vec<String> = backend_labels
+            .iter()
+            .zip(&allocations)
+            .map(|(label, alloc)| format!("{label}={}", alloc.workers))
+            .collect();
+        startup.push_str(&format!(" backends={}", backends.join(",")));
+    }
 
     let mut ui = if tui_enabled {
-        Some(Ui::new(parallel)?)
+        Some(Ui::new(parallel, cli.tui_inline)?)
     } else {
         None
     };
     if let Some(ui) = &mut ui {
+        if multi_backend {
+            ui.set_worker_groups(
+                backend_labels
+                    .iter()
+                    .zip(&allocations)
+                    .map(|(label, alloc)| (label.clone(), alloc.workers as usize))
+                    .collect(),
+            );
+        }
         ui.set_status_prefix(&startup);
     } else {
-        println!("{startup}");
+        logger.log(
+            Record::new(Level::Info, "start", &startup)
+                .field("version", env!("CARGO_PKG_VERSION"))
+                .field("parallel", parallel)
+                .field("submitter", submitter.serialize_redacted())
+                .field(
+                    "signing_key",
+                    signer.as_ref().map(SubmissionSigner::fingerprint),
+                )
+                .field("network", network.serialize_redacted()),
+        );
     }
     if warn_tui_too_many_workers {
         let msg = format!(
@@ -130,9 +441,56 @@ async fn main() -> anyhow::Result<()> {
             parallel
         );
         if ui.is_none() {
-            eprintln!("{msg}");
+            logger.warn("config", &msg);
         }
     }
+    if let Some(addr) = cli.web_dashboard {
+        let sources = backend_labels
+            .iter()
+            .zip(&engines)
+            .map(|(label, engine)| DashboardSource {
+                backend: label.clone(),
+                snapshots: engine.watch_snapshot(),
+            })
+            .collect();
+        match dashboard::spawn(addr, sources).await {
+            Ok(local_addr) => {
+                let msg = format!("Web dashboard at http://{local_addr}/");
+                if let Some(ui) = &mut ui {
+                    ui.println(&msg);
+                } else {
+                    logger.log(
+                        Record::new(Level::Info, "dashboard", &msg)
+                            .field("addr", local_addr.to_string()),
+                    );
+                }
+            }
+            Err(err) => {
+                let msg = format!("warning: web dashboard disabled: {err:#}");
+                if let Some(ui) = &mut ui {
+                    ui.println(&msg);
+                } else {
+                    logger.warn("dashboard", &msg);
+                }
+            }
+        }
+    }
+
+    let (queue_tx, mut queue_rx) =
+        tokio::sync::mpsc::unbounded_channel::<(usize, BackendQueueStatus)>();
+    let mut queue_statuses: Vec<Option<BackendQueueStatus>> = vec![None; allocations.len()];
+    if tui_enabled {
+        for (engine_idx, alloc) in allocations.iter().enumerate() {
+            poll_queue_status(
+                engine_idx,
+                alloc.url.clone(),
+                network.clone(),
+                queue_tx.clone(),
+            );
+        }
+    }
+    drop(queue_tx);
+    let mut queue_open = tui_enabled;
 
     let mut worker_busy = vec![false; parallel];
     let mut worker_effective_speed: Vec<u64> = vec![0; parallel];
@@ -140,25 +498,93 @@ async fn main() -> anyhow::Result<()> {
     let mut ticker = tokio::time::interval(Duration::from_micros(TUI_REFRESH_INTERVAL_US));
     ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
+    let (control_tx, mut control_rx) = tokio::sync::mpsc::unbounded_channel::<ControlSignal>();
+    let mut control_open = true;
+    if let Err(err) = spawn_control_signal_handler(control_tx) {
+        let msg = format!("warning: failed to install SIGUSR1/SIGUSR2 handlers: {err}");
+        if let Some(ui) = &mut ui {
+            ui.println(&msg);
+        } else {
+            logger.warn("signals", &msg);
+        }
+    }
+    let mut paused = false;
+
     let mut immediate_exit = false;
 
     loop {
         tokio::select! {
+            control_opt = control_rx.recv(), if control_open => {
+                match control_opt {
+                    Some(ControlSignal::TogglePause) => {
+                        paused = !paused;
+                        for engine in &engines {
+                            if paused {
+                                engine.pause();
+                            } else {
+                                engine.resume();
+                            }
+                        }
+                        let msg = if paused {
+                            "Paused (SIGUSR1) — finishing current work, not leasing new jobs."
+                        } else {
+                            "Resumed (SIGUSR1)."
+                        };
+                        if let Some(ui) = &mut ui {
+                            ui.println(msg);
+                        } else {
+                            logger.notice(if paused { "paused" } else { "resumed" }, msg);
+                        }
+                    }
+                    Some(ControlSignal::DumpStatus) => {
+                        let snapshots: Vec<_> = backend_labels
+                            .iter()
+                            .cloned()
+                            .zip(engines.iter().map(|engine| engine.snapshot()))
+                            .collect();
+                        if let Some(ui) = &mut ui {
+                            match ui.export_logs(&snapshots) {
+                                Ok(path) => ui.println(&format!("status dumped to {}", path.display())),
+                                Err(err) => ui.println(&format!("status dump failed: {err:#}")),
+                            }
+                        } else {
+                            for (label, snapshot) in &snapshots {
+                                match serde_json::to_value(snapshot) {
+                                    Ok(json) if logger.is_structured() => logger.log(
+                                        Record::new(Level::Info, "status", "status snapshot")
+                                            .backend(Some(label))
+                                            .field("snapshot", json),
+                                    ),
+                                    Ok(json) => logger.info("status", &format!("status {label}: {json}")),
+                                    Err(err) => logger.log(Record::new(
+                                        Level::Error,
+                                        "status",
+                                        &format!("status dump failed: {err:#}"),
+                                    )),
+                                }
+                            }
+                        }
+                    }
+                    None => control_open = false,
+                }
+            }
             ev_opt = shutdown_rx.recv() => {
                 match ev_opt {
                     Some(ShutdownEvent::Graceful) => {
                         if let Some(ui) = &mut ui {
                             ui.set_stop_message("Stop requested — finishing current work before exiting (press CTRL+C again to exit immediately).");
                         } else {
-                            eprintln!("Stop requested — finishing current work before exiting (press CTRL+C again to exit immediately).");
+                            logger.notice("stop_requested", "Stop requested — finishing current work before exiting (press CTRL+C again to exit immediately).");
+                        }
+                        for engine in &engines {
+                            engine.request_stop();
                         }
-                        engine.request_stop();
                     }
                     Some(ShutdownEvent::Immediate) => {
                         if let Some(ui) = &mut ui {
                             ui.set_stop_message("Stop requested again — exiting immediately.");
                         } else {
-                            eprintln!("Stop requested again — exiting immediately.");
+                            logger.notice("exit", "Stop requested again — exiting immediately.");
                         }
                         immediate_exit = true;
                         break;
@@ -171,23 +597,70 @@ async fn main() -> anyhow::Result<()> {
                     let busy = worker_busy.iter().filter(|v| **v).count();
                     let speed: u64 = worker_effective_speed.iter().sum();
                     ui.tick_global(speed, busy, parallel);
+                    if multi_backend {
+                        ui.set_worker_group_stats(
+                            engines
+                                .iter()
+                                .map(|engine| format_session_brief(&engine.snapshot().session))
+                                .collect(),
+                        );
+                    }
+                }
+            }
+            status_opt = queue_rx.recv(), if queue_open => {
+                match status_opt {
+                    Some((engine_idx, status)) => {
+                        queue_statuses[engine_idx] = Some(status);
+                        if let Some(ui) = &mut ui {
+                            ui.set_backend_status(queue_statuses.iter().flatten());
+                        }
+                    }
+                    None => queue_open = false,
                 }
             }
             input_opt = input_rx.recv(), if tui_enabled => {
                 if let (Some(ui), Some(input)) = (&mut ui, input_opt) {
-                    ui.handle_input(input);
+                    if input == TuiInputEvent::ExportLogs {
+                        let snapshots: Vec<_> = backend_labels
+                            .iter()
+                            .cloned()
+                            .zip(engines.iter().map(|engine| engine.snapshot()))
+                            .collect();
+                        match ui.export_logs(&snapshots) {
+                            Ok(path) => ui.println(&format!("logs exported to {}", path.display())),
+                            Err(err) => ui.println(&format!("log export failed: {err:#}")),
+                        }
+                    } else {
+                        ui.handle_input(input);
+                    }
                 }
             }
             evt = events.recv() => {
-                let evt = match evt {
-                    Ok(v) => v,
-                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
-                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                let Some((engine_idx, evt)) = evt else {
+                    break;
                 };
+                let worker_offset = worker_offsets[engine_idx];
+                let backend = multi_backend.then(|| backend_labels[engine_idx].as_str());
 
                 match evt {
                     EngineEvent::Started | EngineEvent::StopRequested => {}
+                    EngineEvent::Environment { environment } => {
+                        let line = format_environment(&environment);
+                        if let Some(ui) = &mut ui {
+                            ui.println(&prefixed(backend, &line));
+                        } else {
+                            logger.log(
+                                Record::new(Level::Info, "environment", &line)
+                                    .backend(backend)
+                                    .field("version", environment.version.as_str())
+                                    .field("git_hash", environment.git_hash.as_deref())
+                                    .field("native_implementation", environment.native_implementation.as_str())
+                                    .field("backend_url", environment.backend_url.as_str()),
+                            );
+                        }
+                    }
                     EngineEvent::WorkerJobStarted { worker_idx, job } => {
+                        let worker_idx = worker_offset + worker_idx;
                         if let Some(slot) = worker_busy.get_mut(worker_idx) {
                             *slot = true;
                         }
@@ -195,7 +668,8 @@ async fn main() -> anyhow::Result<()> {
                             ui.set_worker_job(worker_idx, &job);
                         }
                     }
-                    EngineEvent::WorkerProgress { worker_idx, iters_done, iters_per_sec, effective_iters_per_sec, .. } => {
+                    EngineEvent::WorkerProgress { worker_idx, iters_done, iters_per_sec, effective_iters_per_sec, finalize_ms_estimate, .. } => {
+                        let worker_idx = worker_offset + worker_idx;
                         if let Some(slot) = worker_effective_speed.get_mut(worker_idx) {
                             *slot = effective_iters_per_sec;
                         }
@@ -205,12 +679,29 @@ async fn main() -> anyhow::Result<()> {
                                 iters_done,
                                 iters_per_sec,
                                 effective_iters_per_sec,
+                                finalize_ms_estimate,
+                            );
+                        }
+                    }
+                    EngineEvent::WorkerStage { worker_idx, stage: WorkerStage::Finalizing } => {
+                        let worker_idx = worker_offset + worker_idx;
+                        if let Some(ui) = &mut ui {
+                            ui.set_worker_finalizing(worker_idx);
+                        } else {
+                            logger.log(
+                                Record::new(
+                                    Level::Info,
+                                    "worker_finalizing",
+                                    &format!("W{}: Finalizing proof…", worker_idx + 1),
+                                )
+                                .backend(backend)
+                                .field("worker", worker_idx + 1),
                             );
                         }
                     }
                     EngineEvent::WorkerStage { .. } => {}
                     EngineEvent::JobFinished { outcome } => {
-                        let worker_idx = outcome.worker_idx;
+                        let worker_idx = worker_offset + outcome.worker_idx;
                         if let Some(slot) = worker_busy.get_mut(worker_idx) {
                             *slot = false;
                         }
@@ -219,6 +710,9 @@ async fn main() -> anyhow::Result<()> {
                         }
                         if let Some(ui) = &mut ui {
                             ui.set_worker_idle(worker_idx);
+                            if outcome.error.is_none() {
+                                ui.record_job_height(outcome.job.height);
+                            }
                         }
 
                         let status = format_outcome_status(&outcome);
@@ -232,28 +726,74 @@ async fn main() -> anyhow::Result<()> {
                         );
 
                         if let Some(ui) = &mut ui {
-                            ui.println(&line);
+                            ui.println(&prefixed(backend, &line));
                         } else {
-                            println!("{line}");
+                            logger.log(
+                                Record::new(Level::Info, "job_finished", &line)
+                                    .backend(backend)
+                                    .field("worker", worker_idx + 1)
+                                    .field("job_id", outcome.job.job_id)
+                                    .field("height", outcome.job.height)
+                                    .field("field_vdf", outcome.job.field_vdf)
+                                    .field("iterations", outcome.job.number_of_iterations)
+                                    .field("group_proofs", outcome.job.group_proofs)
+                                    .field("status", status.as_str())
+                                    .field("submit_reason", outcome.submit_reason.as_deref())
+                                    .field("error", outcome.error.as_deref())
+                                    .field("duration_ms", outcome.total_ms),
+                            );
+                        }
+                    }
+                    EngineEvent::WorkerRestarted { worker_idx, restarts, reason } => {
+                        let worker_idx = worker_offset + worker_idx;
+                        if let Some(slot) = worker_busy.get_mut(worker_idx) {
+                            *slot = false;
+                        }
+                        if let Some(slot) = worker_effective_speed.get_mut(worker_idx) {
+                            *slot = 0;
+                        }
+                        let line = format!(
+                            "W{}: worker crashed, restarting (restart {restarts}): {reason}",
+                            worker_idx + 1
+                        );
+                        if let Some(ui) = &mut ui {
+                            ui.set_worker_idle(worker_idx);
+                            ui.println(&prefixed(backend, &line));
+                        } else {
+                            logger.log(
+                                Record::new(Level::Warn, "worker_restarted", &line)
+                                    .backend(backend)
+                                    .field("worker", worker_idx + 1)
+                                    .field("restarts", restarts)
+                                    .field("reason", reason.as_str()),
+                            );
                         }
                     }
                     EngineEvent::Warning { message } => {
                         if let Some(ui) = &mut ui {
                             if should_log_warning_in_tui(&message) {
-                                ui.println(&message);
+                                ui.println(&prefixed(backend, &message));
                             }
                         } else {
-                            eprintln!("{message}");
+                            logger.log(Record::new(Level::Warn, "warning", &message).backend(backend));
                         }
                     }
-                    EngineEvent::Error { message } => {
+                    EngineEvent::Error { message, .. } => {
                         if let Some(ui) = &mut ui {
-                            ui.println(&message);
+                            ui.println(&prefixed(backend, &message));
                         } else {
-                            eprintln!("{message}");
+                            logger.log(Record::new(Level::Error, "error", &message).backend(backend));
+                        }
+                    }
+                    EngineEvent::ShutdownReport { report } => {
+                        shutdown_reports.push((backend.map(str::to_string), report));
+                    }
+                    EngineEvent::Stopped => {
+                        engines_stopped += 1;
+                        if engines_stopped == engines.len() {
+                            break;
                         }
                     }
-                    EngineEvent::Stopped => break,
                 }
             }
         }
@@ -268,6 +808,18 @@ async fn main() -> anyhow::Result<()> {
         std::process::exit(130);
     }
 
-    engine.wait().await?;
+    for engine in engines {
+        engine.wait().await?;
+    }
+
+    // Print after leaving the TUI so the summary stays on screen.
+    drop(tui_terminal);
+    for (backend, report) in &shutdown_reports {
+        for line in format_shutdown_report(report) {
+            logger.log(
+                Record::new(Level::Info, "shutdown_report", &line).backend(backend.as_deref()),
+            );
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,86 @@
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use bbr_client_chiavdf_fast::{ClassgroupElement, prove_one_weso_fast};
+
+const SELFTEST_CHALLENGE: [u8; 32] = *b"wesoforge-startup-selftest-chal!";
+const SELFTEST_DISCRIMINANT_BITS: usize = 1024;
+const SELFTEST_ITERATIONS: u64 = 10_000;
+
+/// A self-test child still running after this is treated as hung.
+const SELFTEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Body of the hidden `--selftest-child` mode: prove once and exit. A prover the
+/// CPU can't run kills this process (SIGILL, SIGSEGV) instead of the client.
+pub fn run_child() -> i32 {
+    let x_s = ClassgroupElement::generator().to_bytes();
+    match prove_one_weso_fast(
+        &SELFTEST_CHALLENGE,
+        &x_s,
+        SELFTEST_DISCRIMINANT_BITS,
+        SELFTEST_ITERATIONS,
+    ) {
+        Ok(_) => 0,
+        Err(err) => {
+            eprintln!("self-test failed: {err}");
+            1
+        }
+    }
+}
+
+/// Run the prover self-test in a child process and fail when it crashes, hangs
+/// or returns an error, so a broken prover stops the client before it takes work.
+pub fn ensure_prover_runs() -> anyhow::Result<()> {
+    run_selftest_child()
+        .map_err(|failure| anyhow::anyhow!("native prover self-test {failure}; cannot continue"))
+}
+
+fn run_selftest_child() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|err| format!("could not start ({err})"))?;
+    let mut child = Command::new(exe)
+        .arg("--selftest-child")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("could not start ({err})"))?;
+
+    let started_at = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => return Err(describe_exit(status)),
+            Ok(None) if started_at.elapsed() >= SELFTEST_TIMEOUT => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("hung for {}s", SELFTEST_TIMEOUT.as_secs()));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(err) => return Err(format!("could not be waited for ({err})")),
+        }
+    }
+}
+
+fn describe_exit(status: ExitStatus) -> String {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt as _;
+
+        if let Some(signal) = status.signal() {
+            let name = match signal {
+                4 => "SIGILL",
+                6 => "SIGABRT",
+                8 => "SIGFPE",
+                11 => "SIGSEGV",
+                _ => return format!("crashed (signal {signal})"),
+            };
+            return format!("crashed ({name})");
+        }
+    }
+    match status.code() {
+        // STATUS_ILLEGAL_INSTRUCTION, Windows' SIGILL.
+        Some(code) if code as u32 == 0xC000_001D => "crashed (illegal instruction)".to_string(),
+        Some(code) => format!("failed (exit code {code})"),
+        None => "failed".to_string(),
+    }
+}
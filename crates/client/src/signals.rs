@@ -0,0 +1,44 @@
+use tokio::sync::mpsc;
+
+/// Runtime controls delivered via Unix signals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlSignal {
+    /// `SIGUSR1`: toggle pause/resume of new work leasing.
+    TogglePause,
+    /// `SIGUSR2`: dump a status snapshot to the log.
+    DumpStatus,
+}
+
+/// Forward `SIGUSR1`/`SIGUSR2` as [`ControlSignal`]s.
+#[cfg(unix)]
+pub fn spawn_control_signal_handler(
+    control_tx: mpsc::UnboundedSender<ControlSignal>,
+) -> std::io::Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut usr1 = signal(SignalKind::user_defined1())?;
+    let mut usr2 = signal(SignalKind::user_defined2())?;
+    tokio::spawn(async move {
+        loop {
+            let signal = tokio::select! {
+                v = usr1.recv() => v.map(|_| ControlSignal::TogglePause),
+                v = usr2.recv() => v.map(|_| ControlSignal::DumpStatus),
+            };
+            let Some(signal) = signal else {
+                return;
+            };
+            if control_tx.send(signal).is_err() {
+                return;
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn spawn_control_signal_handler(
+    control_tx: mpsc::UnboundedSender<ControlSignal>,
+) -> std::io::Result<()> {
+    drop(control_tx);
+    Ok(())
+}
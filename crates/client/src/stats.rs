@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use bbr_client_engine::{JobHistoryEntry, job_history_path, load_job_history};
+
+use crate::cli::StatsArgs;
+use crate::format::{format_duration, format_number, humanize_submit_reason};
+
+const SECS_PER_DAY: f64 = 86_400.0;
+
+/// Upper bounds (seconds) of the lease headroom histogram buckets; one more
+/// bucket collects everything above.
+const HEADROOM_BUCKETS_SECS: [u64; 6] = [60, 300, 600, 1_800, 3_600, 7_200];
+
+/// Submits with less lease time left than this count as close calls.
+const NEAR_EXPIRY_SECS: u64 = 300;
+
+/// How much of the lease was left when submissions were answered, and how
+/// many jobs lost their lease before they could be submitted.
+#[derive(Debug, Default, Serialize)]
+struct LeaseHeadroomStats {
+    submits: u64,
+    min_secs: u64,
+    p10_secs: u64,
+    p50_secs: u64,
+    near_expiry: u64,
+    expired: u64,
+    buckets: Vec<HeadroomBucket>,
+}
+
+#[derive(Debug, Serialize)]
+struct HeadroomBucket {
+    /// Upper bound in seconds (inclusive); `None` for the overflow bucket.
+    le_secs: Option<u64>,
+    count: u64,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct HistoryStats {
+    jobs: u64,
+    accepted: u64,
+    failed: u64,
+    acceptance_rate: f64,
+    proofs_per_day: f64,
+    avg_compute_ms: u64,
+    avg_submit_ms: u64,
+    avg_total_ms: u64,
+    reasons: BTreeMap<String, u64>,
+    lease_headroom: LeaseHeadroomStats,
+}
+
+#[derive(Debug, Serialize)]
+struct StatsReport {
+    since: Option<i64>,
+    until: i64,
+    #[serde(flatten)]
+    total: HistoryStats,
+    backends: BTreeMap<String, HistoryStats>,
+}
+
+fn is_accepted(entry: &JobHistoryEntry) -> bool {
+    entry.outcome.error.is_none() && entry.outcome.submit_reason.as_deref() == Some("accepted")
+}
+
+/// `p`-th percentile (0..=100) of `sorted`.
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied().unwrap_or(0)
+}
+
+fn summarize_headroom(entries: &[&JobHistoryEntry]) -> LeaseHeadroomStats {
+    let mut headroom: Vec<u64> = entries
+        .iter()
+        .filter_map(|entry| entry.outcome.lease_headroom_secs)
+        .collect();
+    headroom.sort_unstable();
+
+    let mut buckets = [0u64; HEADROOM_BUCKETS_SECS.len() + 1];
+    for &secs in &headroom {
+        let bucket = HEADROOM_BUCKETS_SECS
+            .iter()
+            .position(|&le| secs <= le)
+            .unwrap_or(HEADROOM_BUCKETS_SECS.len());
+        buckets[bucket] += 1;
+    }
+
+    LeaseHeadroomStats {
+        submits: headroom.len() as u64,
+        min_secs: headroom.first().copied().unwrap_or(0),
+        p10_secs: percentile(&headroom, 10),
+        p50_secs: percentile(&headroom, 50),
+        near_expiry: headroom
+            .iter()
+            .filter(|&&secs| secs < NEAR_EXPIRY_SECS)
+            .count() as u64,
+        expired: entries
+            .iter()
+            .filter(|entry| entry.outcome.lease_expired)
+            .count() as u64,
+        buckets: buckets
+            .iter()
+            .enumerate()
+            .map(|(idx, &count)| HeadroomBucket {
+                le_secs: HEADROOM_BUCKETS_SECS.get(idx).copied(),
+                count,
+            })
+            .collect(),
+    }
+}
+
+fn summarize(entries: &[&JobHistoryEntry], window_secs: i64) -> HistoryStats {
+    let mut stats = HistoryStats::default();
+    let mut compute_ms = 0u64;
+    let mut submit_ms = 0u64;
+    let mut total_ms = 0u64;
+
+    for entry in entries {
+        let outcome = &entry.outcome;
+        stats.jobs += 1;
+        if is_accepted(entry) {
+            stats.accepted += 1;
+        }
+        let reason = if outcome.error.is_some() {
+            stats.failed += 1;
+            "error"
+        } else {
+            outcome.submit_reason.as_deref().unwrap_or("unknown")
+        };
+        *stats.reasons.entry(reason.to_string()).or_default() += 1;
+
+        compute_ms = compute_ms.saturating_add(outcome.compute_ms);
+        submit_ms = submit_ms.saturating_add(outcome.submit_ms);
+        total_ms = total_ms.saturating_add(outcome.total_ms);
+    }
+
+    if stats.jobs > 0 {
+        stats.acceptance_rate = stats.accepted as f64 / stats.jobs as f64;
+        stats.avg_compute_ms = compute_ms / stats.jobs;
+        stats.avg_submit_ms = submit_ms / stats.jobs;
+        stats.avg_total_ms = total_ms / stats.jobs;
+    }
+    if window_secs > 0 {
+        stats.proofs_per_day = stats.accepted as f64 * SECS_PER_DAY / window_secs as f64;
+    }
+    stats.lease_headroom = summarize_headroom(entries);
+    stats
+}
+
+fn print_stats(stats: &HistoryStats, indent: &str) {
+    println!(
+        "{indent}Jobs: {} (accepted: {}, failed: {}, acceptance: {:.1}%)",
+        format_number(stats.jobs),
+        format_number(stats.accepted),
+        format_number(stats.failed),
+        stats.acceptance_rate * 100.0
+    );
+    println!("{indent}Proofs/day: {:.1}", stats.proofs_per_day);
+    println!(
+        "{indent}Avg duration: {} (compute: {}, submit: {})",
+        format_duration(Duration::from_millis(stats.avg_total_ms)),
+        format_duration(Duration::from_millis(stats.avg_compute_ms)),
+        format_duration(Duration::from_millis(stats.avg_submit_ms))
+    );
+    for (reason, count) in &stats.reasons {
+        println!(
+            "{indent}  {}: {}",
+            humanize_submit_reason(reason),
+            format_number(*count)
+        );
+    }
+    print_headroom(&stats.lease_headroom, indent);
+}
+
+/// Lease time left at submit; a low p10 or any expiries mean jobs are cutting
+/// it close and a smaller group size or fewer workers would be safer.
+fn print_headroom(headroom: &LeaseHeadroomStats, indent: &str) {
+    if headroom.submits == 0 && headroom.expired == 0 {
+        return;
+    }
+    let secs = |secs: u64| format_duration(Duration::from_secs(secs));
+    if headroom.submits > 0 {
+        println!(
+            "{indent}Lease left at submit: p10 {}, median {}, min {} ({} of {} within {})",
+            secs(headroom.p10_secs),
+            secs(headroom.p50_secs),
+            secs(headroom.min_secs),
+            format_number(headroom.near_expiry),
+            format_number(headroom.submits),
+            secs(NEAR_EXPIRY_SECS)
+        );
+        let buckets: Vec<String> = headroom
+            .buckets
+            .iter()
+            .map(|bucket| match bucket.le_secs {
+                Some(le) => format!("<={}: {}", secs(le), format_number(bucket.count)),
+                None => format!(
+                    ">{}: {}",
+                    secs(HEADROOM_BUCKETS_SECS[HEADROOM_BUCKETS_SECS.len() - 1]),
+                    format_number(bucket.count)
+                ),
+            })
+            .collect();
+        println!("{indent}  {}", buckets.join(", "));
+    }
+    if headroom.expired > 0 {
+        println!(
+            "{indent}  Lease expired before submit: {}",
+            format_number(headroom.expired)
+        );
+    }
+}
+
+/// One row per backend so pools can be compared side by side.
+fn print_backend_table(backends: &BTreeMap<String, HistoryStats>) {
+    let width = backends
+        .keys()
+        .map(String::len)
+        .chain(std::iter::once("Backend".len()))
+        .max()
+        .unwrap_or(0);
+    println!(
+        "{:<width$}  {:>8}  {:>8}  {:>10}  {:>10}  {:>12}  {:>12}",
+        "Backend", "Jobs", "Accepted", "Acceptance", "Proofs/day", "Avg compute", "Lease p10"
+    );
+    for (url, stats) in backends {
+        let lease_p10 = if stats.lease_headroom.submits > 0 {
+            format_duration(Duration::from_secs(stats.lease_headroom.p10_secs))
+        } else {
+            "-".to_string()
+        };
+        println!(
+            "{url:<width$}  {:>8}  {:>8}  {:>9.1}%  {:>10.1}  {:>12}  {:>12}",
+            format_number(stats.jobs),
+            format_number(stats.accepted),
+            stats.acceptance_rate * 100.0,
+            stats.proofs_per_day,
+            format_duration(Duration::from_millis(stats.avg_compute_ms)),
+            lease_p10
+        );
+    }
+}
+
+pub fn run_stats(args: &StatsArgs) -> anyhow::Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let since = args
+        .since
+        .map(|window| now.saturating_sub(window.as_secs() as i64));
+
+    let entries = load_job_history(since)?;
+
+    // Without `--since`, the window spans from the oldest recorded job to now.
+    let window_start = since.or_else(|| entries.iter().map(|e| e.finished_at).min());
+    let window_secs = window_start.map(|start| now - start).unwrap_or(0);
+
+    let all: Vec<&JobHistoryEntry> = entries.iter().collect();
+    let mut by_backend: BTreeMap<&str, Vec<&JobHistoryEntry>> = BTreeMap::new();
+    for entry in &entries {
+        by_backend
+            .entry(entry.backend_url.as_str())
+            .or_default()
+            .push(entry);
+    }
+
+    let report = StatsReport {
+        since,
+        until: now,
+        total: summarize(&all, window_secs),
+        backends: by_backend
+            .into_iter()
+            .map(|(url, entries)| (url.to_string(), summarize(&entries, window_secs)))
+            .collect(),
+    };
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if report.total.jobs == 0 {
+        println!("No jobs recorded in {}", job_history_path()?.display());
+        return Ok(());
+    }
+
+    match args.since {
+        Some(window) => println!("Last {}:", format_duration(window)),
+        None => println!("All recorded jobs:"),
+    }
+    print_stats(&report.total, "");
+    if report.backends.len() > 1 {
+        println!();
+        print_backend_table(&report.backends);
+        for (url, stats) in &report.backends {
+            println!();
+            println!("Backend {url}:");
+            print_stats(stats, "  ");
+        }
+    }
+    Ok(())
+}
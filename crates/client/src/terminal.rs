@@ -20,6 +20,7 @@ pub enum TuiInputEvent {
     LogPageDown,
     LogHome,
     LogEnd,
+    ExportLogs,
 }
 
 #[cfg(unix)]
@@ -109,6 +110,13 @@ impl TuiTerminal {
                                     None
                                 }
                             }
+                            KeyCode::Char(ch) if ch.eq_ignore_ascii_case(&'e') => {
+                                if key.kind == KeyEventKind::Press {
+                                    Some(TuiInputEvent::ExportLogs)
+                                } else {
+                                    None
+                                }
+                            }
                             _ => None,
                         };
                         if let Some(ev) = mapped {
@@ -1,19 +1,25 @@
 use std::collections::VecDeque;
-use std::io::Stdout;
-use std::time::Instant;
+use std::io::{Stdout, Write as _};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use bbr_client_engine::JobSummary;
-use ratatui::Terminal;
+use bbr_client_core::format::format_eta;
+use bbr_client_engine::{BackendQueueStatus, JobSummary, StatusSnapshot};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::prelude::{Color, CrosstermBackend, Line, Modifier, Span, Style};
 use ratatui::widgets::{Cell, Clear, List, ListItem, Paragraph, Row, Table};
+use ratatui::{Terminal, TerminalOptions, Viewport};
 
+use crate::constants::TUI_INLINE_REDRAW_INTERVAL_MS;
 use crate::format::{field_vdf_label, format_number};
+use crate::heights::HeightSummary;
 use crate::terminal::TuiInputEvent;
 
 const DETAILED_PROGRESS_BAR_WIDTH: usize = 20;
 const MAX_LOG_LINES: usize = 200;
-const LOG_SHORTCUTS: &str = "Logs: Up/Down PgUp/PgDn Home/End | h = hide logs";
+/// Lines kept for log export (`e`), beyond what the log pane can scroll back to.
+const MAX_LOG_HISTORY_LINES: usize = 50_000;
+const LOG_SHORTCUTS: &str = "Logs: Up/Down PgUp/PgDn Home/End | e = export | h = hide logs";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TopViewMode {
@@ -36,6 +42,10 @@ struct WorkerUiState {
     display_iters_done: u64,
     squaring_iters_per_sec: u64,
     effective_iters_per_sec: u64,
+    /// Expected finalization time after squaring, from the engine (0 = unknown).
+    finalize_ms_estimate: u64,
+    /// Set once squaring is done and the proof is being finalized.
+    finalizing_since: Option<Instant>,
     last_reported_at: Instant,
     label: String,
 }
@@ -44,22 +54,40 @@ pub(crate) struct Ui {
     terminal: Terminal<CrosstermBackend<Stdout>>,
     worker_states: Vec<WorkerUiState>,
     worker_prefix_width: usize,
+    worker_groups: Vec<(String, usize)>,
+    worker_group_stats: Vec<String>,
     top_mode: TopViewMode,
     global_message: String,
+    heights: HeightSummary,
     status_prefix: String,
     stop_message: String,
     logs: VecDeque<String>,
+    log_history: VecDeque<String>,
     logs_visible: bool,
     log_scroll_from_bottom: usize,
     log_viewport_height: usize,
     needs_redraw_clear: bool,
     dirty: bool,
+    /// Drawn in the normal screen without key input (`--tui-inline`).
+    inline: bool,
+    last_full_redraw: Instant,
 }
 
 impl Ui {
-    pub(crate) fn new(worker_count: usize) -> anyhow::Result<Self> {
+    pub(crate) fn new(worker_count: usize, inline: bool) -> anyhow::Result<Self> {
         let backend = CrosstermBackend::new(std::io::stdout());
-        let terminal = Terminal::new(backend)?;
+        let terminal = if inline {
+            // A screen-high region below the prompt; earlier output scrolls up.
+            let (_, rows) = crossterm::terminal::size()?;
+            Terminal::with_options(
+                backend,
+                TerminalOptions {
+                    viewport: Viewport::Inline(rows),
+                },
+            )?
+        } else {
+            Terminal::new(backend)?
+        };
         let mut worker_states = Vec::with_capacity(worker_count);
         for _ in 0..worker_count {
             worker_states.push(WorkerUiState {
@@ -68,6 +96,8 @@ impl Ui {
                 display_iters_done: 0,
                 squaring_iters_per_sec: 0,
                 effective_iters_per_sec: 0,
+                finalize_ms_estimate: 0,
+                finalizing_since: None,
                 last_reported_at: Instant::now(),
                 label: "Idle".to_string(),
             });
@@ -77,16 +107,22 @@ impl Ui {
             terminal,
             worker_states,
             worker_prefix_width: worker_count.max(1).ilog10() as usize + 1,
+            worker_groups: Vec::new(),
+            worker_group_stats: Vec::new(),
             top_mode: TopViewMode::Detailed,
             global_message: "Global: 0 it/s".to_string(),
+            heights: HeightSummary::default(),
             status_prefix: String::new(),
             stop_message: String::new(),
             logs: VecDeque::new(),
+            log_history: VecDeque::new(),
             logs_visible: true,
             log_scroll_from_bottom: 0,
             log_viewport_height: 1,
             needs_redraw_clear: true,
             dirty: true,
+            inline,
+            last_full_redraw: Instant::now(),
         };
         ui.redraw();
         Ok(ui)
@@ -103,6 +139,10 @@ impl Ui {
             self.log_scroll_from_bottom = self.log_scroll_from_bottom.saturating_add(1);
         }
         self.logs.push_back(msg.to_string());
+        if self.log_history.len() >= MAX_LOG_HISTORY_LINES {
+            self.log_history.pop_front();
+        }
+        self.log_history.push_back(msg.to_string());
         self.clamp_log_scroll();
         self.dirty = true;
         self.redraw();
@@ -125,6 +165,8 @@ impl Ui {
             TuiInputEvent::LogPageDown => self.scroll_logs_down(self.log_viewport_height.max(1)),
             TuiInputEvent::LogHome => self.log_scroll_from_bottom = self.max_log_scroll(),
             TuiInputEvent::LogEnd => self.log_scroll_from_bottom = 0,
+            // Handled by the caller, which owns the engine snapshots.
+            TuiInputEvent::ExportLogs => {}
         }
         self.clamp_log_scroll();
         self.dirty = true;
@@ -140,6 +182,8 @@ impl Ui {
         state.display_iters_done = 0;
         state.squaring_iters_per_sec = 0;
         state.effective_iters_per_sec = 0;
+        state.finalize_ms_estimate = 0;
+        state.finalizing_since = None;
         state.last_reported_at = Instant::now();
         state.label = msg;
         self.dirty = true;
@@ -170,6 +214,7 @@ impl Ui {
         iters_done: u64,
         squaring_iters_per_sec: u64,
         effective_iters_per_sec: u64,
+        finalize_ms_estimate: u64,
     ) {
         let Some(state) = self.worker_states.get_mut(worker_idx) else {
             return;
@@ -181,10 +226,26 @@ impl Ui {
         state.display_iters_done = state.display_iters_done.max(state.reported_iters_done);
         state.squaring_iters_per_sec = squaring_iters_per_sec;
         state.effective_iters_per_sec = effective_iters_per_sec;
+        state.finalize_ms_estimate = finalize_ms_estimate;
         state.last_reported_at = Instant::now();
         self.dirty = true;
     }
 
+    /// Squaring finished; show the finalize stage instead of a full, silent bar.
+    pub(crate) fn set_worker_finalizing(&mut self, worker_idx: usize) {
+        let Some(state) = self.worker_states.get_mut(worker_idx) else {
+            return;
+        };
+        if state.total_iters == 0 {
+            return;
+        }
+        state.reported_iters_done = state.total_iters;
+        state.display_iters_done = state.total_iters;
+        state.finalizing_since = Some(Instant::now());
+        self.dirty = true;
+        self.redraw();
+    }
+
     pub(crate) fn set_worker_idle(&mut self, worker_idx: usize) {
         let Some(state) = self.worker_states.get_mut(worker_idx) else {
             return;
@@ -194,12 +255,59 @@ impl Ui {
         state.display_iters_done = 0;
         state.squaring_iters_per_sec = 0;
         state.effective_iters_per_sec = 0;
+        state.finalize_ms_estimate = 0;
+        state.finalizing_since = None;
         state.last_reported_at = Instant::now();
         state.label = "Idle".to_string();
         self.dirty = true;
         self.redraw();
     }
 
+    /// Add a finished job's block height to the height summary.
+    pub(crate) fn record_job_height(&mut self, height: u32) {
+        self.heights.record(height);
+        self.dirty = true;
+    }
+
+    /// Latest queue status of each backend, for the height summary.
+    pub(crate) fn set_backend_status<'a>(
+        &mut self,
+        statuses: impl IntoIterator<Item = &'a BackendQueueStatus>,
+    ) {
+        self.heights.set_backend_status(statuses);
+        self.dirty = true;
+    }
+
+    /// Write the full log history plus the given engine snapshots to a
+    /// timestamped file in the current directory.
+    pub(crate) fn export_logs(
+        &self,
+        snapshots: &[(String, StatusSnapshot)],
+    ) -> anyhow::Result<PathBuf> {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = PathBuf::from(format!("wesoforge-log-{stamp}.txt"));
+        let mut out = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        writeln!(out, "{}", self.status_line().trim())?;
+        writeln!(out, "{}", self.global_message)?;
+        writeln!(out, "{}", self.heights.line())?;
+        writeln!(out)?;
+        writeln!(out, "== log ({} lines)", self.log_history.len())?;
+        for line in &self.log_history {
+            writeln!(out, "{line}")?;
+        }
+        for (label, snapshot) in snapshots {
+            writeln!(out)?;
+            writeln!(out, "== snapshot {label}")?;
+            serde_json::to_writer_pretty(&mut out, snapshot)?;
+            writeln!(out)?;
+        }
+        out.flush()?;
+        Ok(path)
+    }
+
     pub(crate) fn set_stop_message(&mut self, msg: &str) {
         self.stop_message = msg.to_string();
         self.dirty = true;
@@ -212,6 +320,22 @@ impl Ui {
         self.redraw();
     }
 
+    /// Group consecutive workers under labelled headers (e.g. one group per
+    /// backend). Group sizes must add up to the worker count.
+    pub(crate) fn set_worker_groups(&mut self, groups: Vec<(String, usize)>) {
+        self.worker_groups = groups;
+        self.needs_redraw_clear = true;
+        self.redraw();
+    }
+
+    /// Per-group stats shown next to each group header (same order as the groups).
+    pub(crate) fn set_worker_group_stats(&mut self, stats: Vec<String>) {
+        if self.worker_group_stats != stats {
+            self.worker_group_stats = stats;
+            self.dirty = true;
+        }
+    }
+
     pub(crate) fn tick_global(&mut self, speed: u64, busy: usize, total: usize) {
         self.global_message = format!(
             "Global: {} it/s (running {busy}/{total})",
@@ -241,21 +365,45 @@ impl Ui {
 
     pub(crate) fn freeze(&mut self) {
         self.dirty = true;
+        if self.inline {
+            self.needs_redraw_clear = true;
+        }
         self.redraw();
+        if self.inline {
+            // Leave the final frame in place and continue output below it.
+            let bottom = self.terminal.get_frame().area().bottom();
+            let _ = self.terminal.set_cursor_position((0, bottom));
+            let _ = self.terminal.show_cursor();
+            println!();
+        }
     }
 
     fn redraw(&mut self) {
         if !self.dirty && !self.needs_redraw_clear {
             return;
         }
+        if self.inline {
+            // Multiplexers garble partial updates of the normal screen, so
+            // repaint everything, but only now and then.
+            let interval = Duration::from_millis(TUI_INLINE_REDRAW_INTERVAL_MS);
+            if !self.needs_redraw_clear && self.last_full_redraw.elapsed() < interval {
+                return;
+            }
+            if self.terminal.clear().is_err() {
+                return;
+            }
+            self.last_full_redraw = Instant::now();
+        }
         self.update_log_viewport_hint();
         self.clamp_log_scroll();
 
         let needs_redraw_clear = self.needs_redraw_clear;
         let global_message = self.global_message.clone();
+        let heights_line = self.heights.line();
         let status_message = self.status_line();
         let top_mode = self.top_mode;
         let logs_visible = self.logs_visible;
+        let show_shortcuts = !self.inline;
         let grid_column_widths = self
             .terminal
             .size()
@@ -267,19 +415,45 @@ impl Ui {
                 compute_equal_column_widths(top_content_area.width as usize, 4, 1)
             })
             .unwrap_or_else(|| vec![0; 4]);
-        let detailed_worker_lines: Vec<Line<'static>> = self
-            .worker_states
-            .iter()
-            .enumerate()
-            .map(|(idx, state)| self.worker_line_detailed(idx, state))
-            .collect();
-        let grid_worker_cells: Vec<Vec<Line<'static>>> = self
-            .worker_states
-            .chunks(4)
-            .enumerate()
-            .map(|(row_idx, chunk)| {
+        let show_group_headers = self.worker_groups.len() > 1;
+        let groups = if show_group_headers {
+            self.worker_groups.clone()
+        } else {
+            vec![(String::new(), self.worker_states.len())]
+        };
+        let mut detailed_worker_lines: Vec<Line<'static>> =
+            Vec::with_capacity(self.worker_states.len() + groups.len());
+        let mut grid_worker_cells: Vec<Vec<Line<'static>>> = Vec::new();
+        let mut group_start = 0usize;
+        for (group_idx, (label, count)) in groups.iter().enumerate() {
+            let group_end = (group_start + count).min(self.worker_states.len());
+            if show_group_headers {
+                let mut spans = vec![Span::styled(
+                    label.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )];
+                if let Some(stats) = self.worker_group_stats.get(group_idx) {
+                    spans.push(Span::styled(
+                        format!("  {stats}"),
+                        Style::default().add_modifier(Modifier::DIM),
+                    ));
+                }
+                let header = Line::from(spans);
+                detailed_worker_lines.push(header.clone());
+                let mut cells = vec![header];
+                cells.resize(4, Line::from(""));
+                grid_worker_cells.push(cells);
+            }
+            for idx in group_start..group_end {
+                detailed_worker_lines
+                    .push(self.worker_line_detailed(idx, &self.worker_states[idx]));
+            }
+            for (row_idx, chunk) in self.worker_states[group_start..group_end]
+                .chunks(4)
+                .enumerate()
+            {
                 let mut cells = Vec::with_capacity(4);
-                let base_idx = row_idx * 4;
+                let base_idx = group_start + row_idx * 4;
                 for (col_idx, state) in chunk.iter().enumerate() {
                     let cell_width = grid_column_widths.get(col_idx).copied().unwrap_or(0);
                     cells.push(self.worker_cell_grid(base_idx + col_idx, state, cell_width));
@@ -287,9 +461,10 @@ impl Ui {
                 while cells.len() < 4 {
                     cells.push(Line::from(""));
                 }
-                cells
-            })
-            .collect();
+                grid_worker_cells.push(cells);
+            }
+            group_start = group_end;
+        }
         let visible_logs = self.visible_logs();
 
         let draw_result = self.terminal.draw(|frame| {
@@ -304,8 +479,13 @@ impl Ui {
                 frame.render_widget(Clear, stop_area);
             }
 
-            let global = Paragraph::new(global_message.as_str())
-                .style(Style::default().add_modifier(Modifier::BOLD));
+            let global = Paragraph::new(vec![
+                Line::styled(
+                    global_message.as_str(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Line::styled(heights_line.as_str(), Style::default().fg(Color::DarkGray)),
+            ]);
             frame.render_widget(global, global_area);
 
             match top_mode {
@@ -353,11 +533,13 @@ impl Ui {
                 TopViewMode::Grid4 if logs_visible => "Top: Tab = detailed view | h = hide logs",
                 TopViewMode::Grid4 => "Top: Tab = detailed view | h = show logs",
             };
-            frame.render_widget(
-                Paragraph::new(top_shortcuts).style(Style::default().fg(Color::DarkGray)),
-                top_footer_area,
-            );
-            if logs_visible {
+            if show_shortcuts {
+                frame.render_widget(
+                    Paragraph::new(top_shortcuts).style(Style::default().fg(Color::DarkGray)),
+                    top_footer_area,
+                );
+            }
+            if logs_visible && show_shortcuts {
                 frame.render_widget(
                     Paragraph::new(LOG_SHORTCUTS).style(Style::default().fg(Color::DarkGray)),
                     log_footer_area,
@@ -390,11 +572,7 @@ impl Ui {
         let percent = (iters_done.saturating_mul(100) / total_iters).min(100);
         let filled = (iters_done.saturating_mul(DETAILED_PROGRESS_BAR_WIDTH as u64) / total_iters)
             .min(DETAILED_PROGRESS_BAR_WIDTH as u64) as usize;
-        let eta = if state.squaring_iters_per_sec == 0 || iters_done >= total_iters {
-            "--:--:--".to_string()
-        } else {
-            format_eta((total_iters - iters_done) / state.squaring_iters_per_sec)
-        };
+        let eta = worker_eta(state);
 
         let before_bar = format!("{prefix}  {percent:>3}%[");
         let bar_filled = "#".repeat(filled);
@@ -403,7 +581,11 @@ impl Ui {
             "] {eta} ETA  {:>12} it/s  {:>11} it   {}",
             format_number(state.effective_iters_per_sec),
             format_number(iters_done),
-            state.label
+            if state.finalizing_since.is_some() {
+                format!("Finalizing proof… {}", state.label)
+            } else {
+                state.label.clone()
+            }
         );
 
         Line::from(vec![
@@ -426,18 +608,7 @@ impl Ui {
             width = self.worker_prefix_width
         );
         let left = format!("{prefix} [");
-        let idle_or_eta = if state.total_iters == 0 {
-            "--:--:--".to_string()
-        } else {
-            let total_iters = state.total_iters.max(1);
-            let iters_done = state.display_iters_done.min(total_iters);
-            if state.squaring_iters_per_sec == 0 || iters_done >= total_iters {
-                "--:--:--".to_string()
-            } else {
-                format_eta((total_iters - iters_done) / state.squaring_iters_per_sec)
-            }
-        };
-        let right = format!("] {idle_or_eta}");
+        let right = format!("] {}", worker_eta(state));
         let bar_width = cell_width.saturating_sub(left.len() + right.len()).max(1);
 
         if state.total_iters == 0 {
@@ -449,9 +620,14 @@ impl Ui {
         let filled = (iters_done.saturating_mul(bar_width as u64) / total_iters)
             .min(bar_width as u64) as usize;
 
+        let filled_color = if state.finalizing_since.is_some() {
+            Color::Yellow
+        } else {
+            Color::Cyan
+        };
         Line::from(vec![
             Span::raw(left),
-            Span::styled("#".repeat(filled), Style::default().fg(Color::Cyan)),
+            Span::styled("#".repeat(filled), Style::default().fg(filled_color)),
             Span::styled(
                 "-".repeat(bar_width.saturating_sub(filled)),
                 Style::default().fg(Color::Blue),
@@ -538,7 +714,7 @@ fn compute_layout(area: Rect, logs_visible: bool) -> (Rect, Rect, Rect, Rect, Re
     let outer = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1),
+            Constraint::Length(2),
             Constraint::Min(1),
             Constraint::Length(1),
         ])
@@ -578,9 +754,23 @@ fn compute_equal_column_widths(total_width: usize, columns: usize, spacing: usiz
     widths
 }
 
-fn format_eta(total_seconds: u64) -> String {
-    let hours = total_seconds / 3600;
-    let minutes = (total_seconds % 3600) / 60;
-    let seconds = total_seconds % 60;
-    format!("{hours:02}:{minutes:02}:{seconds:02}")
+/// Time left for a worker's job, including the expected finalize phase.
+fn worker_eta(state: &WorkerUiState) -> String {
+    const UNKNOWN: &str = "--:--:--";
+    if state.total_iters == 0 {
+        return UNKNOWN.to_string();
+    }
+    let finalize_secs = state.finalize_ms_estimate / 1000;
+    if let Some(since) = state.finalizing_since {
+        if state.finalize_ms_estimate == 0 {
+            return UNKNOWN.to_string();
+        }
+        return format_eta(finalize_secs.saturating_sub(since.elapsed().as_secs()));
+    }
+    let total_iters = state.total_iters.max(1);
+    let iters_done = state.display_iters_done.min(total_iters);
+    if state.squaring_iters_per_sec == 0 || iters_done >= total_iters {
+        return UNKNOWN.to_string();
+    }
+    format_eta((total_iters - iters_done) / state.squaring_iters_per_sec + finalize_secs)
 }
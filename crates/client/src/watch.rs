@@ -0,0 +1,197 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Instant;
+
+use anyhow::Context;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as B64;
+use serde::{Deserialize, Serialize};
+
+use bbr_client_chiavdf_fast::{
+    ClassgroupElement, compact_vdf_info_and_proof, prove_one_weso_fast,
+    prove_one_weso_fast_streaming_getblock_opt,
+};
+
+use crate::cli::WatchDirArgs;
+use crate::format::format_duration;
+
+const DEFAULT_DISCRIMINANT_BITS: usize = 1024;
+const WITNESS_SUFFIX: &str = ".witness.json";
+const ERROR_SUFFIX: &str = ".error.json";
+
+/// A proof request file (`<name>.json` in the input directory).
+#[derive(Debug, Deserialize)]
+struct ProofRequest {
+    challenge_b64: String,
+    number_of_iterations: u64,
+    /// Expected `y`; when present the faster streaming prover is used.
+    #[serde(default)]
+    output_b64: Option<String>,
+    #[serde(default)]
+    discriminant_size_bits: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct ProofResult {
+    number_of_iterations: u64,
+    y_b64: String,
+    witness_b64: String,
+    /// Chia `VDFProof` (compact, normalized to identity), streamable-serialized.
+    vdf_proof_b64: String,
+    /// Set when `output_b64` was given and the computed `y` differs from it.
+    output_mismatch: bool,
+    compute_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct ProofError {
+    error: String,
+}
+
+fn result_path(output_dir: &Path, stem: &str, suffix: &str) -> PathBuf {
+    output_dir.join(format!("{stem}{suffix}"))
+}
+
+/// Request files in `input_dir` that don't have a result in `output_dir` yet.
+fn pending_requests(input_dir: &Path, output_dir: &Path) -> anyhow::Result<Vec<(String, PathBuf)>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(input_dir)
+        .with_context(|| format!("read input dir {}", input_dir.display()))?
+    {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if result_path(output_dir, stem, WITNESS_SUFFIX).exists()
+            || result_path(output_dir, stem, ERROR_SUFFIX).exists()
+        {
+            continue;
+        }
+        out.push((stem.to_string(), path));
+    }
+    out.sort();
+    Ok(out)
+}
+
+fn prove_request(path: &Path) -> anyhow::Result<ProofResult> {
+    let raw = std::fs::read(path).context("read request")?;
+    let req: ProofRequest = serde_json::from_slice(&raw).context("parse request")?;
+    let challenge = B64
+        .decode(req.challenge_b64.as_bytes())
+        .context("decode challenge_b64")?;
+    let expected = req
+        .output_b64
+        .as_deref()
+        .map(|s| B64.decode(s.as_bytes()).context("decode output_b64"))
+        .transpose()?;
+    let bits = req
+        .discriminant_size_bits
+        .unwrap_or(DEFAULT_DISCRIMINANT_BITS);
+    let x = ClassgroupElement::generator();
+
+    let started_at = Instant::now();
+    let out = match &expected {
+        Some(y_ref) => prove_one_weso_fast_streaming_getblock_opt(
+            &challenge,
+            x.as_bytes(),
+            y_ref,
+            bits,
+            req.number_of_iterations,
+        ),
+        None => prove_one_weso_fast(&challenge, x.as_bytes(), bits, req.number_of_iterations),
+    }
+    .context("compute proof")?;
+    let compute_ms = started_at.elapsed().as_millis() as u64;
+
+    let vdf_proof = compact_vdf_info_and_proof(&challenge, req.number_of_iterations, &out, true)
+        .map(|(_, proof)| proof.to_bytes())
+        .context("serialize VDFProof")?;
+
+    Ok(ProofResult {
+        number_of_iterations: req.number_of_iterations,
+        y_b64: B64.encode(&out.y),
+        witness_b64: B64.encode(&out.witness),
+        vdf_proof_b64: B64.encode(vdf_proof),
+        output_mismatch: expected.is_some_and(|e| e != out.y),
+        compute_ms,
+    })
+}
+
+/// Write `value` as JSON via a temp file + rename so readers never see a partial file.
+fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn process_request(output_dir: &Path, stem: &str, path: &Path) -> anyhow::Result<()> {
+    match prove_request(path) {
+        Ok(result) => {
+            let line = format!(
+                "{stem}: done in {}{}",
+                format_duration(std::time::Duration::from_millis(result.compute_ms)),
+                if result.output_mismatch {
+                    " (output mismatch)"
+                } else {
+                    ""
+                }
+            );
+            write_json_atomic(&result_path(output_dir, stem, WITNESS_SUFFIX), &result)?;
+            println!("{line}");
+        }
+        Err(err) => {
+            let error = format!("{err:#}");
+            write_json_atomic(
+                &result_path(output_dir, stem, ERROR_SUFFIX),
+                &ProofError {
+                    error: error.clone(),
+                },
+            )?;
+            eprintln!("{stem}: {error}");
+        }
+    }
+    Ok(())
+}
+
+pub fn run_watch_dir(args: &WatchDirArgs, parallel: usize) -> anyhow::Result<()> {
+    let parallel = parallel.max(1);
+    std::fs::create_dir_all(&args.output)
+        .with_context(|| format!("create output dir {}", args.output.display()))?;
+    println!(
+        "watching {} -> {} (parallel={parallel})",
+        args.input.display(),
+        args.output.display()
+    );
+
+    loop {
+        let pending = pending_requests(&args.input, &args.output)?;
+        if !pending.is_empty() {
+            let next = AtomicUsize::new(0);
+            thread::scope(|scope| {
+                for _ in 0..parallel.min(pending.len()) {
+                    scope.spawn(|| {
+                        loop {
+                            let idx = next.fetch_add(1, Ordering::Relaxed);
+                            let Some((stem, path)) = pending.get(idx) else {
+                                break;
+                            };
+                            if let Err(err) = process_request(&args.output, stem, path) {
+                                eprintln!("{stem}: failed to write result: {err:#}");
+                            }
+                        }
+                    });
+                }
+            });
+        }
+
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(args.poll);
+    }
+}
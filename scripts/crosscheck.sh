@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+# Cross-validate the fast chiavdf engine against the portable fallback prover.
+# Run after bumping the chiavdf submodule.
+set -euo pipefail
+
+ROOT="$(cd -- "$(dirname -- "${BASH_SOURCE[0]}")/.." && pwd)"
+cd "$ROOT"
+
+VECTORS="${BBR_CROSSCHECK_VECTORS:-$ROOT/target/crosscheck-vectors.txt}"
+FALLBACK_TARGET_DIR="$ROOT/target/crosscheck-fallback"
+mkdir -p "$(dirname -- "$VECTORS")"
+
+# BBR_CROSSCHECK_REFERENCE=rust records with the pure-Rust prover instead.
+if [[ "${BBR_CROSSCHECK_REFERENCE:-}" == "rust" ]]; then
+  echo "recording reference vectors with the pure-Rust prover -> $VECTORS"
+  BBR_CROSSCHECK_RECORD=1 BBR_CROSSCHECK_VECTORS="$VECTORS" \
+    cargo test --release -p bbr-client-chiavdf-fast --target-dir "$FALLBACK_TARGET_DIR" \
+    --features rust-fallback --test cross_validation -- --nocapture
+else
+  echo "recording reference vectors with the portable fallback -> $VECTORS"
+  BBR_FORCE_PORTABLE_FALLBACK=1 BBR_CROSSCHECK_RECORD=1 BBR_CROSSCHECK_VECTORS="$VECTORS" \
+    cargo test --release -p bbr-client-chiavdf-fast --target-dir "$FALLBACK_TARGET_DIR" \
+    --test cross_validation -- --nocapture
+fi
+
+echo "comparing the fast engine against $VECTORS"
+BBR_CROSSCHECK_VECTORS="$VECTORS" \
+  cargo test --release -p bbr-client-chiavdf-fast --test cross_validation -- --nocapture